@@ -1,13 +1,202 @@
-use crate::autodiffable::{AutoDiffable, ForwardDiffable};
+use crate::autodiffable::{AutoDiffable, ForwardDiffable, HessianDiffable, HvpDiffable, VjpDiffable};
 use crate::diffable::Diffable;
 use crate::gradienttype::GradientType;
+use crate::reverse::{ReverseDiffable, Tape, Var};
+use crate::graphviz::{DotAnnotate, DotGraph, ToDot};
+use crate::tryautodiff::{check_all, check_contraction, check_dot, check_value, ADEvaluated, AutoDiffError, CheckValue, TryAutoDiffable};
+use crate::symbolic::{Expr, Symbolic, SymbolicConstant};
+use std::fmt;
 use std::ops::Add;
 use ndarray::linalg::Dot;
 use crate::ad_ndarray::traits::{TensorDot, TensorContraction};
+use crate::forward::Outer;
+use crate::traits::{Conjugate, InstZero};
+use ndarray::{Array, ArrayD, Axis, DimAdd, Dimension, LinalgScalar, Zip};
+use std::marker::PhantomData;
 
 use crate as autodiff;
 use autodiff_derive::*;
 
+
+// the contracted axes of an operand, as written in the symbolic form of a contraction
+fn axes(axes: &[usize]) -> Expr {
+    Expr::Const(format!("{:?}", axes))
+}
+
+// the axes of an operand of a tensor contraction which are not contracted over, in ascending order
+fn free_axes(ndim: usize, contracted: &[usize]) -> Vec<usize> {
+    (0..ndim).filter(|i| !contracted.contains(i)).collect()
+}
+
+// the position of each contracted axis among the contracted axes sorted in ascending order, which is the
+// order they are left in by a contraction of the other operand over its free axes
+fn contracted_order(axes: &[usize]) -> Vec<usize> {
+    axes.iter().map(|a| axes.iter().filter(|b| *b < a).count()).collect()
+}
+
+// for h = f contracted with g over (f_axes, g_axes), whose axes are the free axes of f followed by the free axes
+// of g, the cotangent of f is c contracted with g over the free axes of g. this leaves the free axes of f
+// followed by the contracted axes of g, which are then put back in the order of the axes of f
+fn contraction_vjp_lhs<T, DC, DG, DF>(cotangent: &Array<T, DC>, g: &Array<T, DG>, axes: (&[usize], &[usize])) -> Array<T, DF>
+where
+    T: LinalgScalar,
+    DC: Dimension,
+    DG: Dimension,
+    DF: Dimension,
+{
+    let (f_axes, g_axes) = axes;
+    let n_free = cotangent.ndim() + g_axes.len() - g.ndim();
+
+    let c_free = (n_free..cotangent.ndim()).map(Axis).collect::<Vec<_>>();
+    let g_free = free_axes(g.ndim(), g_axes).into_iter().map(Axis).collect::<Vec<_>>();
+    let vf = ndarray_einsum_beta::tensordot(cotangent, g, &c_free, &g_free);
+
+    let order = contracted_order(g_axes);
+    let mut free = 0..n_free;
+    let permutation = (0..n_free + f_axes.len()).map(|a| match f_axes.iter().position(|b| *b == a) {
+        Some(k) => n_free + order[k],
+        None => free.next().unwrap(),
+    }).collect::<Vec<_>>();
+
+    vf.permuted_axes(permutation).into_dimensionality::<DF>().expect(
+        "the cotangent of a contraction must have the free axes of both operands")
+}
+
+// for h = f contracted with g over (f_axes, g_axes), the cotangent of g is f contracted with c over the free axes
+// of f. this leaves the contracted axes of f followed by the free axes of g, which are then put back in the order
+// of the axes of g
+fn contraction_vjp_rhs<T, DF, DC, DG>(f: &Array<T, DF>, cotangent: &Array<T, DC>, axes: (&[usize], &[usize])) -> Array<T, DG>
+where
+    T: LinalgScalar,
+    DF: Dimension,
+    DC: Dimension,
+    DG: Dimension,
+{
+    let (f_axes, g_axes) = axes;
+    let n_contracted = f_axes.len();
+    let n_free = f.ndim() - n_contracted;
+
+    let f_free = free_axes(f.ndim(), f_axes).into_iter().map(Axis).collect::<Vec<_>>();
+    let c_free = (0..n_free).map(Axis).collect::<Vec<_>>();
+    let vg = ndarray_einsum_beta::tensordot(f, cotangent, &f_free, &c_free);
+
+    let order = contracted_order(f_axes);
+    let mut free = n_contracted..;
+    let permutation = (0..cotangent.ndim() - n_free + n_contracted).map(|b| match g_axes.iter().position(|a| *a == b) {
+        Some(k) => order[k],
+        None => free.next().unwrap(),
+    }).collect::<Vec<_>>();
+
+    vg.permuted_axes(permutation).into_dimensionality::<DG>().expect(
+        "the cotangent of a contraction must have the free axes of both operands")
+}
+
+// the axes of an operand shifted past the n leading axes of its gradient or Hessian
+fn shifted_axes(axes: &[usize], n: usize) -> Vec<Axis> {
+    axes.iter().map(|a| Axis(a + n)).collect()
+}
+
+// the second derivatives d²f[k...]/dx[i...]dx[j...] of an array valued f, with shape (x.shape, x.shape, f.shape).
+// the Hessian of f, which has the type of the outer product of two gradients and shape (x.shape, f.shape, x.shape,
+// f.shape), holds them on the diagonal of the axes of f, since different elements of f are never differentiated
+// together
+fn hessian_diagonal<T, DH>(hessian: &Array<T, DH>, nx: usize) -> ArrayD<T>
+where
+    T: Clone,
+    DH: Dimension,
+{
+    let hessian = hessian.view().into_dyn();
+    let nf = hessian.ndim() / 2 - nx;
+    let (xs, fs) = (&hessian.shape()[..nx], &hessian.shape()[nx..nx + nf]);
+    let dim = xs.iter().chain(xs).chain(fs).copied().collect::<Vec<_>>();
+
+    ArrayD::from_shape_fn(dim, |idx| {
+        let (i, j, k) = (&idx.slice()[..nx], &idx.slice()[nx..2 * nx], &idx.slice()[2 * nx..]);
+        hessian[i.iter().chain(k).chain(j).chain(k).copied().collect::<Vec<_>>().as_slice()].clone()
+    })
+}
+
+// the Hessian with shape (x.shape, f.shape, x.shape, f.shape) holding the second derivatives of f on the diagonal of
+// the axes of f, the inverse of hessian_diagonal
+fn hessian_from_diagonal<T, DH>(diagonal: &ArrayD<T>, nx: usize) -> Array<T, DH>
+where
+    T: LinalgScalar,
+    DH: Dimension,
+{
+    let (xs, fs) = (&diagonal.shape()[..nx], &diagonal.shape()[2 * nx..]);
+    let dim = xs.iter().chain(fs).chain(xs).chain(fs).copied().collect::<Vec<_>>();
+
+    let mut hessian = ArrayD::zeros(dim);
+    for (idx, d) in diagonal.indexed_iter() {
+        let (i, j, k) = (&idx.slice()[..nx], &idx.slice()[nx..2 * nx], &idx.slice()[2 * nx..]);
+        hessian[i.iter().chain(k).chain(j).chain(k).copied().collect::<Vec<_>>().as_slice()] = *d;
+    }
+
+    hessian.into_dimensionality::<DH>().expect("the Hessian must have the axes of the input and output twice")
+}
+
+// an operand of a contraction, with its gradient and Hessian if it is a function of x rather than a constant
+type HessianOperand<T> = (ArrayD<T>, Option<(ArrayD<T>, ArrayD<T>)>);
+
+// the value, gradient and Hessian of h = f contracted with g over (f_axes, g_axes), whose axes are the free axes of f
+// followed by the free axes of g. with the axes of x first in the gradients, by the product rule
+//
+// dh = df . g + f . dg
+// d²h = d²f . g + f . d²g + df . dg + dg . df
+//
+// where every term is put back in the order (x.shape, h.shape) or (x.shape, x.shape, h.shape)
+fn contraction_hessian<T, DO, DG, DH>(f: HessianOperand<T>, g: HessianOperand<T>, axes: (&[usize], &[usize])) -> (Array<T, DO>, Array<T, DG>, Array<T, DH>)
+where
+    T: LinalgScalar,
+    DO: Dimension,
+    DG: Dimension,
+    DH: Dimension,
+{
+    let (f, fd) = f;
+    let (g, gd) = g;
+    let (f_axes, g_axes) = axes;
+    let nf = f.ndim() - f_axes.len();
+    let nx = match (&fd, &gd) {
+        (Some((df, _)), _) => df.ndim() - f.ndim(),
+        (_, Some((dg, _))) => dg.ndim() - g.ndim(),
+        (None, None) => panic!("a contraction of two constants has no derivatives"),
+    };
+
+    let h = ndarray_einsum_beta::tensordot(&f, &g, &shifted_axes(f_axes, 0), &shifted_axes(g_axes, 0));
+
+    let mut grads = Vec::new();
+    let mut hessians = Vec::new();
+    if let Some((df, d2f)) = &fd {
+        grads.push(ndarray_einsum_beta::tensordot(df, &g, &shifted_axes(f_axes, nx), &shifted_axes(g_axes, 0)));
+        let d2f = hessian_diagonal(d2f, nx);
+        hessians.push(ndarray_einsum_beta::tensordot(&d2f, &g, &shifted_axes(f_axes, 2 * nx), &shifted_axes(g_axes, 0)));
+    }
+    if let Some((dg, d2g)) = &gd {
+        // f . dg and f . d²g leave the free axes of f before the axes of x
+        let fdg = ndarray_einsum_beta::tensordot(&f, dg, &shifted_axes(f_axes, 0), &shifted_axes(g_axes, nx));
+        grads.push(fdg.permuted_axes((nf..nf + nx).chain(0..nf).chain(nf + nx..h.ndim() + nx).collect::<Vec<_>>()));
+        let d2g = hessian_diagonal(d2g, nx);
+        let fd2g = ndarray_einsum_beta::tensordot(&f, &d2g, &shifted_axes(f_axes, 0), &shifted_axes(g_axes, 2 * nx));
+        hessians.push(fd2g.permuted_axes((nf..nf + 2 * nx).chain(0..nf).chain(nf + 2 * nx..h.ndim() + 2 * nx).collect::<Vec<_>>()));
+    }
+    if let (Some((df, _)), Some((dg, _))) = (&fd, &gd) {
+        // df . dg has the axes (x, free f, x, free g), with the first x from df for df . dg and from dg for dg . df
+        let dfdg = ndarray_einsum_beta::tensordot(df, dg, &shifted_axes(f_axes, nx), &shifted_axes(g_axes, nx));
+        let free_g = 2 * nx + nf..h.ndim() + 2 * nx;
+        hessians.push(dfdg.clone().permuted_axes((0..nx).chain(nx + nf..2 * nx + nf).chain(nx..nx + nf).chain(free_g.clone()).collect::<Vec<_>>()));
+        hessians.push(dfdg.permuted_axes((nx + nf..2 * nx + nf).chain(0..nx).chain(nx..nx + nf).chain(free_g).collect::<Vec<_>>()));
+    }
+
+    let dh = grads.into_iter().reduce(|a, b| a + b).unwrap();
+    let d2h = hessians.into_iter().reduce(|a, b| a + b).unwrap();
+
+    (
+        h.into_dimensionality::<DO>().expect("the contraction must have the free axes of both operands"),
+        dh.into_dimensionality::<DG>().expect("the gradient must have the axes of the input and output"),
+        hessian_from_diagonal(&d2h, nx),
+    )
+}
+
 #[derive(FuncCompose, Debug, Clone, Copy)]
 pub struct ADDot<A, B>(pub A, pub B);
 
@@ -19,6 +208,38 @@ where
     type Output = <A::Output as Dot<B::Output>>::Output;
 }
 
+impl<A, B> Symbolic for ADDot<A, B>
+where
+    A: Symbolic,
+    B: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::call("dot", vec![self.0.symbolic(x), self.1.symbolic(x)])
+    }
+}
+
+impl<A, B> fmt::Display for ADDot<A, B>
+where
+    Self: Symbolic,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbolic(&Expr::var("x")))
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADDot<A, B>
+where
+    A: ToDot<StaticArgs>,
+    B: ToDot<StaticArgs, Input = A::Input>,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let g = self.1.dot_node(graph, at);
+        graph.op("dot", self.annotate_at(at), &[(f, Some("lhs")), (g, Some("rhs"))])
+    }
+}
+
 impl<StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, AGradB, ABGrad, Output, Grad, A, B> AutoDiffable<StaticArgs> for ADDot<A, B>
 where
     A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
@@ -80,6 +301,59 @@ where
 
         df.dot(&g).add(f.dot(&dg))
     }
+
+    fn eval_all(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            Grad,
+            Grad
+        )
+    {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+        let (g, dg, dgdconjz) = self.1.eval_all(x, static_args);
+
+        (f.dot(&g), df.dot(&g).add(f.dot(&dg)), dfdconjz.dot(&g).add(f.dot(&dgdconjz)))
+    }
+}
+
+impl<StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs> for ADDot<A, B>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    B: TryAutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    Input: GradientType<BOutput, GradientType = BGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f, g and f.dot(g) can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    BOutput: CheckValue,
+    BGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    // ensure f.dot(g) can be evaluated from the checked results of f and g
+    ADDot<ADEvaluated<Input, AOutput, AGrad>, ADEvaluated<Input, BOutput, BGrad>>: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError>
+    {
+        let f = self.0.try_eval(x, static_args).map_err(|e| e.within("ADDot", 0))?;
+        let g = self.1.try_eval(x, static_args).map_err(|e| e.within("ADDot", 1))?;
+        check_dot("ADDot", &f, &g)?;
+
+        let h = ADDot(ADEvaluated::value(f), ADEvaluated::value(g));
+        check_value("ADDot", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(&self, x: &Input, static_args: &StaticArgs) -> Result<(Output, Grad, Grad), AutoDiffError>
+    {
+        let f = self.0.try_eval_all(x, static_args).map_err(|e| e.within("ADDot", 0))?;
+        let g = self.1.try_eval_all(x, static_args).map_err(|e| e.within("ADDot", 1))?;
+        check_dot("ADDot", &f.0, &g.0)?;
+
+        let h = ADDot(ADEvaluated::all(f), ADEvaluated::all(g));
+        check_all("ADDot", h.eval_all(x, static_args))
+    }
 }
 
 impl<StaticArgs, Input, AOutput, BOutput, Output, A, B> ForwardDiffable<StaticArgs> for ADDot<A, B>
@@ -144,6 +418,152 @@ where
     }
 }
 
+// array valued operands are not scalars on the tape, so the whole operation is recorded as a
+// single leaf, which pulls the adjoints back through its vjp without forming the jacobians of the
+// operands
+impl<StaticArgs, Input, Output, A, B> ReverseDiffable<StaticArgs> for ADDot<A, B>
+where
+    Self: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure the gradient has the type of the input, so the adjoints are pulled back by the vjp
+    Input: GradientType<Output, GradientType = Input> + InstZero + Conjugate<Output = Input>,
+    Output: InstZero + Conjugate<Output = Output>,
+{
+    fn eval_reverse<'a>(&'a self, x: &'a <Self as Diffable<StaticArgs>>::Input, tape: &mut Tape<'a, Input, Output>,
+                    static_args: &'a StaticArgs) -> Var
+    {
+        tape.record_vjp(self, x, static_args)
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, A, B> VjpDiffable<StaticArgs> for ADDot<A, B>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    B: VjpDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Array<T, DA>: Dot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+    Input: Add<Input, Output = Input>,
+{
+    fn eval_primal(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                   static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        self.0.eval_primal(x, static_args).dot(&self.1.eval_primal(x, static_args))
+    }
+
+    fn eval_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let f = self.0.eval_primal(x, static_args);
+        let g = self.1.eval_primal(x, static_args);
+        let (f_axes, g_axes) = ([f.ndim() - 1], [0]);
+
+        // c^T d(f . g) = (c . g^T)^T df + (f^T . c)^T dg, where the transposes contract over the free axes of
+        // g and f respectively
+        let vf = self.0.vjp(x, &contraction_vjp_lhs(cotangent, &g, (&f_axes[..], &g_axes[..])), static_args);
+        let vg = self.1.vjp(x, &contraction_vjp_rhs(&f, cotangent, (&f_axes[..], &g_axes[..])), static_args);
+
+        (f.dot(&g), vf.add(vg))
+    }
+
+    fn eval_conj_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                     static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let f = self.0.eval_primal(x, static_args);
+        let g = self.1.eval_primal(x, static_args);
+        let (f_axes, g_axes) = ([f.ndim() - 1], [0]);
+
+        let vf = self.0.conj_vjp(x, &contraction_vjp_lhs(cotangent, &g, (&f_axes[..], &g_axes[..])), static_args);
+        let vg = self.1.conj_vjp(x, &contraction_vjp_rhs(&f, cotangent, (&f_axes[..], &g_axes[..])), static_args);
+
+        (f.dot(&g), vf.add(vg))
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, DAG, DBG, DG, DAH, DBH, DH, A, B> HessianDiffable<StaticArgs> for ADDot<A, B>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    B: HessianDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Input: GradientType<Array<T, DA>, GradientType = Array<T, DAG>>
+        + GradientType<Array<T, DB>, GradientType = Array<T, DBG>>
+        + GradientType<Array<T, DO>, GradientType = Array<T, DG>>,
+    Array<T, DAG>: Outer<Array<T, DAG>, Output = Array<T, DAH>>,
+    Array<T, DBG>: Outer<Array<T, DBG>, Output = Array<T, DBH>>,
+    Array<T, DG>: Outer<Array<T, DG>, Output = Array<T, DH>>,
+    Array<T, DA>: Dot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+    DAG: Dimension,
+    DBG: Dimension,
+    DG: Dimension,
+    DAH: Dimension,
+    DBH: Dimension,
+    DH: Dimension,
+{
+    fn eval_hessian(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                    static_args: &StaticArgs) -> (Array<T, DO>, Array<T, DG>, Array<T, DH>)
+    {
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
+        let (g, dg, d2g) = self.1.eval_hessian(x, static_args);
+        let (f_axes, g_axes) = ([f.ndim() - 1], [0]);
+
+        contraction_hessian(
+            (f.into_dyn(), Some((df.into_dyn(), d2f.into_dyn()))),
+            (g.into_dyn(), Some((dg.into_dyn(), d2g.into_dyn()))),
+            (&f_axes[..], &g_axes[..]),
+        )
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, A, B> HvpDiffable<StaticArgs> for ADDot<A, B>
+where
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Array<T, DA>> + ForwardDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    B: HvpDiffable<StaticArgs, Input = Input, Output = Array<T, DB>> + ForwardDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Array<T, DA>: Dot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+    Input: Add<Input, Output = Input>,
+{
+    fn eval_hvp(&self, x: &<Self as Diffable<StaticArgs>>::Input, v: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output, dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f, df) = self.0.eval_forward_grad(x, v, static_args);
+        let (g, dg) = self.1.eval_forward_grad(x, v, static_args);
+        let (f_axes, g_axes) = ([f.ndim() - 1], [0]);
+        let axes = (&f_axes[..], &g_axes[..]);
+
+        // the cotangents c . g^T and f^T . c are bilinear, so their derivatives along v follow from the product rule
+        let cf = contraction_vjp_lhs(cotangent, &g, axes);
+        let dcf = contraction_vjp_lhs::<_, _, _, DA>(dcotangent, &g, axes) + contraction_vjp_lhs::<_, _, _, DA>(cotangent, &dg, axes);
+        let cg = contraction_vjp_rhs(&f, cotangent, axes);
+        let dcg = contraction_vjp_rhs::<_, _, _, DB>(&df, cotangent, axes) + contraction_vjp_rhs::<_, _, _, DB>(&f, dcotangent, axes);
+
+        let (_, vf, dvf) = self.0.eval_hvp(x, v, &cf, &dcf, static_args);
+        let (_, vg, dvg) = self.1.eval_hvp(x, v, &cg, &dcg, static_args);
+
+        (f.dot(&g), vf.add(vg), dvf.add(dvg))
+    }
+}
+
 #[derive(FuncCompose, Debug, Clone, Copy)]
 pub struct ADTensorDot<A, B>(pub A, pub B);
 
@@ -155,6 +575,38 @@ where
     type Output = <A::Output as TensorDot<B::Output>>::Output;
 }
 
+impl<A, B> Symbolic for ADTensorDot<A, B>
+where
+    A: Symbolic,
+    B: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::call("tensordot", vec![self.0.symbolic(x), self.1.symbolic(x)])
+    }
+}
+
+impl<A, B> fmt::Display for ADTensorDot<A, B>
+where
+    Self: Symbolic,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbolic(&Expr::var("x")))
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADTensorDot<A, B>
+where
+    A: ToDot<StaticArgs>,
+    B: ToDot<StaticArgs, Input = A::Input>,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let g = self.1.dot_node(graph, at);
+        graph.op("tensordot", self.annotate_at(at), &[(f, Some("lhs")), (g, Some("rhs"))])
+    }
+}
+
 impl<StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, AGradB, ABGrad, Output, Grad, A, B> AutoDiffable<StaticArgs> for ADTensorDot<A, B>
 where
     A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
@@ -216,6 +668,59 @@ where
 
         df.tensordot(&g).add(f.tensordot(&dg))
     }
+
+    fn eval_all(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            Grad,
+            Grad
+        )
+    {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+        let (g, dg, dgdconjz) = self.1.eval_all(x, static_args);
+
+        (f.tensordot(&g), df.tensordot(&g).add(f.tensordot(&dg)), dfdconjz.tensordot(&g).add(f.tensordot(&dgdconjz)))
+    }
+}
+
+impl<StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs> for ADTensorDot<A, B>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    B: TryAutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    Input: GradientType<BOutput, GradientType = BGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f, g and f.tensordot(g) can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    BOutput: CheckValue,
+    BGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    // ensure f.tensordot(g) can be evaluated from the checked results of f and g
+    ADTensorDot<ADEvaluated<Input, AOutput, AGrad>, ADEvaluated<Input, BOutput, BGrad>>: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError>
+    {
+        let f = self.0.try_eval(x, static_args).map_err(|e| e.within("ADTensorDot", 0))?;
+        let g = self.1.try_eval(x, static_args).map_err(|e| e.within("ADTensorDot", 1))?;
+        check_dot("ADTensorDot", &f, &g)?;
+
+        let h = ADTensorDot(ADEvaluated::value(f), ADEvaluated::value(g));
+        check_value("ADTensorDot", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(&self, x: &Input, static_args: &StaticArgs) -> Result<(Output, Grad, Grad), AutoDiffError>
+    {
+        let f = self.0.try_eval_all(x, static_args).map_err(|e| e.within("ADTensorDot", 0))?;
+        let g = self.1.try_eval_all(x, static_args).map_err(|e| e.within("ADTensorDot", 1))?;
+        check_dot("ADTensorDot", &f.0, &g.0)?;
+
+        let h = ADTensorDot(ADEvaluated::all(f), ADEvaluated::all(g));
+        check_all("ADTensorDot", h.eval_all(x, static_args))
+    }
 }
 
 impl<StaticArgs, Input, AOutput, BOutput, Output, A, B> ForwardDiffable<StaticArgs> for ADTensorDot<A, B>
@@ -280,6 +785,149 @@ where
     }
 }
 
+impl<StaticArgs, Input, Output, A, B> ReverseDiffable<StaticArgs> for ADTensorDot<A, B>
+where
+    Self: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure the gradient has the type of the input, so the adjoints are pulled back by the vjp
+    Input: GradientType<Output, GradientType = Input> + InstZero + Conjugate<Output = Input>,
+    Output: InstZero + Conjugate<Output = Output>,
+{
+    fn eval_reverse<'a>(&'a self, x: &'a <Self as Diffable<StaticArgs>>::Input, tape: &mut Tape<'a, Input, Output>,
+                    static_args: &'a StaticArgs) -> Var
+    {
+        tape.record_vjp(self, x, static_args)
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, A, B> VjpDiffable<StaticArgs> for ADTensorDot<A, B>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    B: VjpDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Array<T, DA>: TensorDot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+    Input: Add<Input, Output = Input>,
+{
+    fn eval_primal(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                   static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        self.0.eval_primal(x, static_args).tensordot(&self.1.eval_primal(x, static_args))
+    }
+
+    fn eval_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let f = self.0.eval_primal(x, static_args);
+        let g = self.1.eval_primal(x, static_args);
+        let (f_axes, g_axes) = ([f.ndim() - 1], [0]);
+
+        // c^T d(f . g) = (c . g^T)^T df + (f^T . c)^T dg, where the transposes contract over the free axes of
+        // g and f respectively
+        let vf = self.0.vjp(x, &contraction_vjp_lhs(cotangent, &g, (&f_axes[..], &g_axes[..])), static_args);
+        let vg = self.1.vjp(x, &contraction_vjp_rhs(&f, cotangent, (&f_axes[..], &g_axes[..])), static_args);
+
+        (f.tensordot(&g), vf.add(vg))
+    }
+
+    fn eval_conj_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                     static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let f = self.0.eval_primal(x, static_args);
+        let g = self.1.eval_primal(x, static_args);
+        let (f_axes, g_axes) = ([f.ndim() - 1], [0]);
+
+        let vf = self.0.conj_vjp(x, &contraction_vjp_lhs(cotangent, &g, (&f_axes[..], &g_axes[..])), static_args);
+        let vg = self.1.conj_vjp(x, &contraction_vjp_rhs(&f, cotangent, (&f_axes[..], &g_axes[..])), static_args);
+
+        (f.tensordot(&g), vf.add(vg))
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, DAG, DBG, DG, DAH, DBH, DH, A, B> HessianDiffable<StaticArgs> for ADTensorDot<A, B>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    B: HessianDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Input: GradientType<Array<T, DA>, GradientType = Array<T, DAG>>
+        + GradientType<Array<T, DB>, GradientType = Array<T, DBG>>
+        + GradientType<Array<T, DO>, GradientType = Array<T, DG>>,
+    Array<T, DAG>: Outer<Array<T, DAG>, Output = Array<T, DAH>>,
+    Array<T, DBG>: Outer<Array<T, DBG>, Output = Array<T, DBH>>,
+    Array<T, DG>: Outer<Array<T, DG>, Output = Array<T, DH>>,
+    Array<T, DA>: TensorDot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+    DAG: Dimension,
+    DBG: Dimension,
+    DG: Dimension,
+    DAH: Dimension,
+    DBH: Dimension,
+    DH: Dimension,
+{
+    fn eval_hessian(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                    static_args: &StaticArgs) -> (Array<T, DO>, Array<T, DG>, Array<T, DH>)
+    {
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
+        let (g, dg, d2g) = self.1.eval_hessian(x, static_args);
+        let (f_axes, g_axes) = ([f.ndim() - 1], [0]);
+
+        contraction_hessian(
+            (f.into_dyn(), Some((df.into_dyn(), d2f.into_dyn()))),
+            (g.into_dyn(), Some((dg.into_dyn(), d2g.into_dyn()))),
+            (&f_axes[..], &g_axes[..]),
+        )
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, A, B> HvpDiffable<StaticArgs> for ADTensorDot<A, B>
+where
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Array<T, DA>> + ForwardDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    B: HvpDiffable<StaticArgs, Input = Input, Output = Array<T, DB>> + ForwardDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Array<T, DA>: TensorDot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+    Input: Add<Input, Output = Input>,
+{
+    fn eval_hvp(&self, x: &<Self as Diffable<StaticArgs>>::Input, v: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output, dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f, df) = self.0.eval_forward_grad(x, v, static_args);
+        let (g, dg) = self.1.eval_forward_grad(x, v, static_args);
+        let (f_axes, g_axes) = ([f.ndim() - 1], [0]);
+        let axes = (&f_axes[..], &g_axes[..]);
+
+        // the cotangents c . g^T and f^T . c are bilinear, so their derivatives along v follow from the product rule
+        let cf = contraction_vjp_lhs(cotangent, &g, axes);
+        let dcf = contraction_vjp_lhs::<_, _, _, DA>(dcotangent, &g, axes) + contraction_vjp_lhs::<_, _, _, DA>(cotangent, &dg, axes);
+        let cg = contraction_vjp_rhs(&f, cotangent, axes);
+        let dcg = contraction_vjp_rhs::<_, _, _, DB>(&df, cotangent, axes) + contraction_vjp_rhs::<_, _, _, DB>(&f, dcotangent, axes);
+
+        let (_, vf, dvf) = self.0.eval_hvp(x, v, &cf, &dcf, static_args);
+        let (_, vg, dvg) = self.1.eval_hvp(x, v, &cg, &dcg, static_args);
+
+        (f.tensordot(&g), vf.add(vg), dvf.add(dvg))
+    }
+}
+
 #[derive(FuncCompose, Debug, Clone, Copy)]
 pub struct ADTensorContraction<A, B, const N: usize>(pub A, pub B, pub ([usize; N], [usize; N]));
 // N is the number of dimensions contracted over
@@ -293,6 +941,38 @@ where
     type Output = <A::Output as TensorContraction<N, B::Output>>::Output;
 }
 
+impl<const N: usize, A, B> Symbolic for ADTensorContraction<A, B, N>
+where
+    A: Symbolic,
+    B: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::call("contract", vec![self.0.symbolic(x), self.1.symbolic(x), axes(&self.2.0), axes(&self.2.1)])
+    }
+}
+
+impl<const N: usize, A, B> fmt::Display for ADTensorContraction<A, B, N>
+where
+    Self: Symbolic,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbolic(&Expr::var("x")))
+    }
+}
+
+impl<const N: usize, StaticArgs, A, B> ToDot<StaticArgs> for ADTensorContraction<A, B, N>
+where
+    A: ToDot<StaticArgs>,
+    B: ToDot<StaticArgs, Input = A::Input>,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let g = self.1.dot_node(graph, at);
+        graph.op(&format!("contract {:?} {:?}", self.2.0, self.2.1), self.annotate_at(at), &[(f, Some("lhs")), (g, Some("rhs"))])
+    }
+}
+
 impl<const N: usize, StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, AGradB, ABGrad, Output, Grad, A, B> AutoDiffable<StaticArgs> for ADTensorContraction<A, B, N>
 where
     A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
@@ -354,6 +1034,64 @@ where
 
         df.contract(&g, (&self.2.0, &self.2.1)).add(f.contract(&dg, (&self.2.0, &self.2.1)))
     }
+
+    fn eval_all(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            Grad,
+            Grad
+        )
+    {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+        let (g, dg, dgdconjz) = self.1.eval_all(x, static_args);
+        let axes = (&self.2.0, &self.2.1);
+
+        (
+            f.contract(&g, axes),
+            df.contract(&g, axes).add(f.contract(&dg, axes)),
+            dfdconjz.contract(&g, axes).add(f.contract(&dgdconjz, axes))
+        )
+    }
+}
+
+impl<const N: usize, StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs> for ADTensorContraction<A, B, N>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    B: TryAutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    Input: GradientType<BOutput, GradientType = BGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f, g and f.contract(g) can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    BOutput: CheckValue,
+    BGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    // ensure f.contract(g) can be evaluated from the checked results of f and g
+    ADTensorContraction<ADEvaluated<Input, AOutput, AGrad>, ADEvaluated<Input, BOutput, BGrad>, N>: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError>
+    {
+        let f = self.0.try_eval(x, static_args).map_err(|e| e.within("ADTensorContraction", 0))?;
+        let g = self.1.try_eval(x, static_args).map_err(|e| e.within("ADTensorContraction", 1))?;
+        check_contraction("ADTensorContraction", &f, &g, &self.2.0, &self.2.1)?;
+
+        let h = ADTensorContraction(ADEvaluated::value(f), ADEvaluated::value(g), self.2);
+        check_value("ADTensorContraction", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(&self, x: &Input, static_args: &StaticArgs) -> Result<(Output, Grad, Grad), AutoDiffError>
+    {
+        let f = self.0.try_eval_all(x, static_args).map_err(|e| e.within("ADTensorContraction", 0))?;
+        let g = self.1.try_eval_all(x, static_args).map_err(|e| e.within("ADTensorContraction", 1))?;
+        check_contraction("ADTensorContraction", &f.0, &g.0, &self.2.0, &self.2.1)?;
+
+        let h = ADTensorContraction(ADEvaluated::all(f), ADEvaluated::all(g), self.2);
+        check_all("ADTensorContraction", h.eval_all(x, static_args))
+    }
 }
 
 impl<const N: usize, StaticArgs, Input, AOutput, BOutput, Output, A, B> ForwardDiffable<StaticArgs> for ADTensorContraction<A, B, N>
@@ -418,24 +1156,199 @@ where
     }
 }
 
-// operations with constants
-#[derive(FuncCompose, Debug, Clone, Copy)]
-pub struct ADConstantDot<A, B>(pub A, pub B);
-
-impl<A: Diffable<StaticArgs>, B, StaticArgs> Diffable<StaticArgs> for ADConstantDot<A, B>
+impl<const N: usize, StaticArgs, Input, Output, A, B> ReverseDiffable<StaticArgs> for ADTensorContraction<A, B, N>
 where
-    A::Output: Dot<B>,
+    Self: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure the gradient has the type of the input, so the adjoints are pulled back by the vjp
+    Input: GradientType<Output, GradientType = Input> + InstZero + Conjugate<Output = Input>,
+    Output: InstZero + Conjugate<Output = Output>,
 {
-    type Input = A::Input;
-    type Output = <A::Output as Dot<B>>::Output;
+    fn eval_reverse<'a>(&'a self, x: &'a <Self as Diffable<StaticArgs>>::Input, tape: &mut Tape<'a, Input, Output>,
+                    static_args: &'a StaticArgs) -> Var
+    {
+        tape.record_vjp(self, x, static_args)
+    }
 }
 
-impl<StaticArgs, Input, Output, Grad, AOutput, AGrad, A, B> AutoDiffable<StaticArgs> for ADConstantDot<A, B>
+impl<const N: usize, StaticArgs, Input, T, DA, DB, DO, A, B> VjpDiffable<StaticArgs> for ADTensorContraction<A, B, N>
 where
-    A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
-    Input: GradientType<AOutput, GradientType = AGrad>,
-    Input: GradientType<Output, GradientType = Grad>,
-    // ensure A.dot(B) is defined and returns type Output
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    B: VjpDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Array<T, DA>: TensorContraction<N, Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+    Input: Add<Input, Output = Input>,
+{
+    fn eval_primal(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                   static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        self.0.eval_primal(x, static_args).contract(&self.1.eval_primal(x, static_args), (&self.2.0, &self.2.1))
+    }
+
+    fn eval_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let f = self.0.eval_primal(x, static_args);
+        let g = self.1.eval_primal(x, static_args);
+        let (f_axes, g_axes) = &self.2;
+
+        // c^T d(f . g) = (c . g^T)^T df + (f^T . c)^T dg, where the transposes contract over the free axes of
+        // g and f respectively
+        let vf = self.0.vjp(x, &contraction_vjp_lhs(cotangent, &g, (&f_axes[..], &g_axes[..])), static_args);
+        let vg = self.1.vjp(x, &contraction_vjp_rhs(&f, cotangent, (&f_axes[..], &g_axes[..])), static_args);
+
+        (f.contract(&g, (&self.2.0, &self.2.1)), vf.add(vg))
+    }
+
+    fn eval_conj_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                     static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let f = self.0.eval_primal(x, static_args);
+        let g = self.1.eval_primal(x, static_args);
+        let (f_axes, g_axes) = &self.2;
+
+        let vf = self.0.conj_vjp(x, &contraction_vjp_lhs(cotangent, &g, (&f_axes[..], &g_axes[..])), static_args);
+        let vg = self.1.conj_vjp(x, &contraction_vjp_rhs(&f, cotangent, (&f_axes[..], &g_axes[..])), static_args);
+
+        (f.contract(&g, (&self.2.0, &self.2.1)), vf.add(vg))
+    }
+}
+
+impl<const N: usize, StaticArgs, Input, T, DA, DB, DO, DAG, DBG, DG, DAH, DBH, DH, A, B> HessianDiffable<StaticArgs> for ADTensorContraction<A, B, N>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    B: HessianDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Input: GradientType<Array<T, DA>, GradientType = Array<T, DAG>>
+        + GradientType<Array<T, DB>, GradientType = Array<T, DBG>>
+        + GradientType<Array<T, DO>, GradientType = Array<T, DG>>,
+    Array<T, DAG>: Outer<Array<T, DAG>, Output = Array<T, DAH>>,
+    Array<T, DBG>: Outer<Array<T, DBG>, Output = Array<T, DBH>>,
+    Array<T, DG>: Outer<Array<T, DG>, Output = Array<T, DH>>,
+    Array<T, DA>: TensorContraction<N, Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+    DAG: Dimension,
+    DBG: Dimension,
+    DG: Dimension,
+    DAH: Dimension,
+    DBH: Dimension,
+    DH: Dimension,
+{
+    fn eval_hessian(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                    static_args: &StaticArgs) -> (Array<T, DO>, Array<T, DG>, Array<T, DH>)
+    {
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
+        let (g, dg, d2g) = self.1.eval_hessian(x, static_args);
+        let (f_axes, g_axes) = &self.2;
+
+        contraction_hessian(
+            (f.into_dyn(), Some((df.into_dyn(), d2f.into_dyn()))),
+            (g.into_dyn(), Some((dg.into_dyn(), d2g.into_dyn()))),
+            (&f_axes[..], &g_axes[..]),
+        )
+    }
+}
+
+impl<const N: usize, StaticArgs, Input, T, DA, DB, DO, A, B> HvpDiffable<StaticArgs> for ADTensorContraction<A, B, N>
+where
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Array<T, DA>> + ForwardDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    B: HvpDiffable<StaticArgs, Input = Input, Output = Array<T, DB>> + ForwardDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Array<T, DA>: TensorContraction<N, Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+    Input: Add<Input, Output = Input>,
+{
+    fn eval_hvp(&self, x: &<Self as Diffable<StaticArgs>>::Input, v: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output, dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f, df) = self.0.eval_forward_grad(x, v, static_args);
+        let (g, dg) = self.1.eval_forward_grad(x, v, static_args);
+        let (f_axes, g_axes) = &self.2;
+        let axes = (&f_axes[..], &g_axes[..]);
+
+        // the cotangents c . g^T and f^T . c are bilinear, so their derivatives along v follow from the product rule
+        let cf = contraction_vjp_lhs(cotangent, &g, axes);
+        let dcf = contraction_vjp_lhs::<_, _, _, DA>(dcotangent, &g, axes) + contraction_vjp_lhs::<_, _, _, DA>(cotangent, &dg, axes);
+        let cg = contraction_vjp_rhs(&f, cotangent, axes);
+        let dcg = contraction_vjp_rhs::<_, _, _, DB>(&df, cotangent, axes) + contraction_vjp_rhs::<_, _, _, DB>(&f, dcotangent, axes);
+
+        let (_, vf, dvf) = self.0.eval_hvp(x, v, &cf, &dcf, static_args);
+        let (_, vg, dvg) = self.1.eval_hvp(x, v, &cg, &dcg, static_args);
+
+        (f.contract(&g, (&self.2.0, &self.2.1)), vf.add(vg), dvf.add(dvg))
+    }
+}
+
+// operations with constants
+#[derive(FuncCompose, Debug, Clone, Copy)]
+pub struct ADConstantDot<A, B>(pub A, pub B);
+
+impl<A: Diffable<StaticArgs>, B, StaticArgs> Diffable<StaticArgs> for ADConstantDot<A, B>
+where
+    A::Output: Dot<B>,
+{
+    type Input = A::Input;
+    type Output = <A::Output as Dot<B>>::Output;
+}
+
+impl<A, B> Symbolic for ADConstantDot<A, B>
+where
+    A: Symbolic,
+    B: SymbolicConstant,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::call("dot", vec![self.0.symbolic(x), self.1.symbolic_constant()])
+    }
+}
+
+impl<A, B> fmt::Display for ADConstantDot<A, B>
+where
+    Self: Symbolic,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbolic(&Expr::var("x")))
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADConstantDot<A, B>
+where
+    A: ToDot<StaticArgs>,
+    B: SymbolicConstant,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let c = graph.constant(&self.1.symbolic_constant().to_string());
+        graph.op("dot", self.annotate_at(at), &[(f, Some("lhs")), (c, Some("rhs"))])
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, AOutput, AGrad, A, B> AutoDiffable<StaticArgs> for ADConstantDot<A, B>
+where
+    A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure A.dot(B) is defined and returns type Output
     AOutput: Dot<B, Output = Output>,
     AGrad: Dot<B, Output = Grad>,
 {
@@ -484,6 +1397,53 @@ where
 
         df.dot(&self.1)
     }
+
+    fn eval_all(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            Grad,
+            Grad
+        )
+    {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+
+        (f.dot(&self.1), df.dot(&self.1), dfdconjz.dot(&self.1))
+    }
+}
+
+impl<StaticArgs, Input, AOutput, AGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs> for ADConstantDot<A, B>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f and f.dot(b), and the constant, can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    B: Clone + CheckValue,
+    // ensure f.dot(b) can be evaluated from the checked results of f
+    ADConstantDot<ADEvaluated<Input, AOutput, AGrad>, B>: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError>
+    {
+        let f = self.0.try_eval(x, static_args).map_err(|e| e.within("ADConstantDot", 0))?;
+        check_dot("ADConstantDot", &f, &self.1)?;
+
+        let h = ADConstantDot(ADEvaluated::value(f), self.1.clone());
+        check_value("ADConstantDot", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(&self, x: &Input, static_args: &StaticArgs) -> Result<(Output, Grad, Grad), AutoDiffError>
+    {
+        let f = self.0.try_eval_all(x, static_args).map_err(|e| e.within("ADConstantDot", 0))?;
+        check_dot("ADConstantDot", &f.0, &self.1)?;
+
+        let h = ADConstantDot(ADEvaluated::all(f), self.1.clone());
+        check_all("ADConstantDot", h.eval_all(x, static_args))
+    }
 }
 
 impl<StaticArgs, Input, Output, AOutput, A, B> ForwardDiffable<StaticArgs> for ADConstantDot<A, B>
@@ -541,6 +1501,126 @@ where
     }
 }
 
+impl<StaticArgs, Input, Output, A, B> ReverseDiffable<StaticArgs> for ADConstantDot<A, B>
+where
+    Self: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure the gradient has the type of the input, so the adjoints are pulled back by the vjp
+    Input: GradientType<Output, GradientType = Input> + InstZero + Conjugate<Output = Input>,
+    Output: InstZero + Conjugate<Output = Output>,
+{
+    fn eval_reverse<'a>(&'a self, x: &'a <Self as Diffable<StaticArgs>>::Input, tape: &mut Tape<'a, Input, Output>,
+                    static_args: &'a StaticArgs) -> Var
+    {
+        tape.record_vjp(self, x, static_args)
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, A> VjpDiffable<StaticArgs> for ADConstantDot<A, Array<T, DB>>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    Array<T, DA>: Dot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+{
+    fn eval_primal(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                   static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        self.0.eval_primal(x, static_args).dot(&self.1)
+    }
+
+    fn eval_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = ([cotangent.ndim() - self.1.ndim() + 1], [0]);
+
+        // c^T d(f . b) = (c . b^T)^T df, where the transpose contracts over the free axes of b
+        let (f, vf) = self.0.eval_vjp(x, &contraction_vjp_lhs(cotangent, &self.1, (&f_axes[..], &g_axes[..])), static_args);
+
+        (f.dot(&self.1), vf)
+    }
+
+    fn eval_conj_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                     static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = ([cotangent.ndim() - self.1.ndim() + 1], [0]);
+
+        let (f, vf) = self.0.eval_conj_vjp(x, &contraction_vjp_lhs(cotangent, &self.1, (&f_axes[..], &g_axes[..])), static_args);
+
+        (f.dot(&self.1), vf)
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, DAG, DG, DAH, DH, A> HessianDiffable<StaticArgs> for ADConstantDot<A, Array<T, DB>>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    Input: GradientType<Array<T, DA>, GradientType = Array<T, DAG>>
+        + GradientType<Array<T, DO>, GradientType = Array<T, DG>>,
+    Array<T, DAG>: Outer<Array<T, DAG>, Output = Array<T, DAH>>,
+    Array<T, DG>: Outer<Array<T, DG>, Output = Array<T, DH>>,
+    Array<T, DA>: Dot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+    DAG: Dimension,
+    DG: Dimension,
+    DAH: Dimension,
+    DH: Dimension,
+{
+    fn eval_hessian(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                    static_args: &StaticArgs) -> (Array<T, DO>, Array<T, DG>, Array<T, DH>)
+    {
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
+        let (f_axes, g_axes) = ([f.ndim() - 1], [0]);
+
+        contraction_hessian(
+            (f.into_dyn(), Some((df.into_dyn(), d2f.into_dyn()))),
+            (self.1.clone().into_dyn(), None),
+            (&f_axes[..], &g_axes[..]),
+        )
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, A> HvpDiffable<StaticArgs> for ADConstantDot<A, Array<T, DB>>
+where
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    Array<T, DA>: Dot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+{
+    fn eval_hvp(&self, x: &<Self as Diffable<StaticArgs>>::Input, v: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output, dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = ([cotangent.ndim() - self.1.ndim() + 1], [0]);
+        let axes = (&f_axes[..], &g_axes[..]);
+
+        // the cotangent c . b^T is linear in c
+        let cf = contraction_vjp_lhs(cotangent, &self.1, axes);
+        let dcf = contraction_vjp_lhs(dcotangent, &self.1, axes);
+        let (f, vf, dvf) = self.0.eval_hvp(x, v, &cf, &dcf, static_args);
+
+        (f.dot(&self.1), vf, dvf)
+    }
+}
+
 #[derive(FuncCompose, Debug, Clone, Copy)]
 pub struct ADConstantLeftDot<A, B>(pub A, pub B);
 // dot product by constant from the left
@@ -553,6 +1633,38 @@ where
     type Output = <A as Dot<B::Output>>::Output;
 }
 
+impl<A, B> Symbolic for ADConstantLeftDot<A, B>
+where
+    A: SymbolicConstant,
+    B: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::call("dot", vec![self.0.symbolic_constant(), self.1.symbolic(x)])
+    }
+}
+
+impl<A, B> fmt::Display for ADConstantLeftDot<A, B>
+where
+    Self: Symbolic,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbolic(&Expr::var("x")))
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADConstantLeftDot<A, B>
+where
+    A: SymbolicConstant,
+    B: ToDot<StaticArgs>,
+    Self: DotAnnotate<StaticArgs, Input = B::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&B::Input, &StaticArgs)>) -> usize {
+        let c = graph.constant(&self.0.symbolic_constant().to_string());
+        let g = self.1.dot_node(graph, at);
+        graph.op("dot", self.annotate_at(at), &[(c, Some("lhs")), (g, Some("rhs"))])
+    }
+}
+
 impl<StaticArgs, Input, Output, Grad, BOutput, BGrad, A, B> AutoDiffable<StaticArgs> for ADConstantLeftDot<A, B>
 where
     A: Dot<BOutput, Output = Output>,
@@ -602,6 +1714,53 @@ where
     {
         self.0.dot(&self.1.conj_grad(x, static_args))
     }
+
+    fn eval_all(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            Grad,
+            Grad
+        )
+    {
+        let (g, dg, dgdconjz) = self.1.eval_all(x, static_args);
+
+        (self.0.dot(&g), self.0.dot(&dg), self.0.dot(&dgdconjz))
+    }
+}
+
+impl<StaticArgs, Input, BOutput, BGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs> for ADConstantLeftDot<A, B>
+where
+    B: TryAutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    Input: GradientType<BOutput, GradientType = BGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of g and a.dot(g), and the constant, can be checked
+    BOutput: CheckValue,
+    BGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    A: Clone + CheckValue,
+    // ensure a.dot(g) can be evaluated from the checked results of g
+    ADConstantLeftDot<A, ADEvaluated<Input, BOutput, BGrad>>: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError>
+    {
+        let g = self.1.try_eval(x, static_args).map_err(|e| e.within("ADConstantLeftDot", 1))?;
+        check_dot("ADConstantLeftDot", &self.0, &g)?;
+
+        let h = ADConstantLeftDot(self.0.clone(), ADEvaluated::value(g));
+        check_value("ADConstantLeftDot", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(&self, x: &Input, static_args: &StaticArgs) -> Result<(Output, Grad, Grad), AutoDiffError>
+    {
+        let g = self.1.try_eval_all(x, static_args).map_err(|e| e.within("ADConstantLeftDot", 1))?;
+        check_dot("ADConstantLeftDot", &self.0, &g.0)?;
+
+        let h = ADConstantLeftDot(self.0.clone(), ADEvaluated::all(g));
+        check_all("ADConstantLeftDot", h.eval_all(x, static_args))
+    }
 }
 
 impl<StaticArgs, Input, Output, BOutput, A, B> ForwardDiffable<StaticArgs> for ADConstantLeftDot<A, B>
@@ -653,6 +1812,126 @@ where
     }
 }
 
+impl<StaticArgs, Input, Output, A, B> ReverseDiffable<StaticArgs> for ADConstantLeftDot<A, B>
+where
+    Self: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure the gradient has the type of the input, so the adjoints are pulled back by the vjp
+    Input: GradientType<Output, GradientType = Input> + InstZero + Conjugate<Output = Input>,
+    Output: InstZero + Conjugate<Output = Output>,
+{
+    fn eval_reverse<'a>(&'a self, x: &'a <Self as Diffable<StaticArgs>>::Input, tape: &mut Tape<'a, Input, Output>,
+                    static_args: &'a StaticArgs) -> Var
+    {
+        tape.record_vjp(self, x, static_args)
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, B> VjpDiffable<StaticArgs> for ADConstantLeftDot<Array<T, DA>, B>
+where
+    B: VjpDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Array<T, DA>: Dot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+{
+    fn eval_primal(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                   static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        self.0.dot(&self.1.eval_primal(x, static_args))
+    }
+
+    fn eval_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = ([self.0.ndim() - 1], [0]);
+
+        // c^T d(a . g) = (a^T . c)^T dg, where the transpose contracts over the free axes of a
+        let (g, vg) = self.1.eval_vjp(x, &contraction_vjp_rhs(&self.0, cotangent, (&f_axes[..], &g_axes[..])), static_args);
+
+        (self.0.dot(&g), vg)
+    }
+
+    fn eval_conj_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                     static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = ([self.0.ndim() - 1], [0]);
+
+        let (g, vg) = self.1.eval_conj_vjp(x, &contraction_vjp_rhs(&self.0, cotangent, (&f_axes[..], &g_axes[..])), static_args);
+
+        (self.0.dot(&g), vg)
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, DBG, DG, DBH, DH, B> HessianDiffable<StaticArgs> for ADConstantLeftDot<Array<T, DA>, B>
+where
+    B: HessianDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Input: GradientType<Array<T, DB>, GradientType = Array<T, DBG>>
+        + GradientType<Array<T, DO>, GradientType = Array<T, DG>>,
+    Array<T, DBG>: Outer<Array<T, DBG>, Output = Array<T, DBH>>,
+    Array<T, DG>: Outer<Array<T, DG>, Output = Array<T, DH>>,
+    Array<T, DA>: Dot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+    DBG: Dimension,
+    DG: Dimension,
+    DBH: Dimension,
+    DH: Dimension,
+{
+    fn eval_hessian(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                    static_args: &StaticArgs) -> (Array<T, DO>, Array<T, DG>, Array<T, DH>)
+    {
+        let (g, dg, d2g) = self.1.eval_hessian(x, static_args);
+        let (f_axes, g_axes) = ([self.0.ndim() - 1], [0]);
+
+        contraction_hessian(
+            (self.0.clone().into_dyn(), None),
+            (g.into_dyn(), Some((dg.into_dyn(), d2g.into_dyn()))),
+            (&f_axes[..], &g_axes[..]),
+        )
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, B> HvpDiffable<StaticArgs> for ADConstantLeftDot<Array<T, DA>, B>
+where
+    B: HvpDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Array<T, DA>: Dot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+{
+    fn eval_hvp(&self, x: &<Self as Diffable<StaticArgs>>::Input, v: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output, dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = ([self.0.ndim() - 1], [0]);
+        let axes = (&f_axes[..], &g_axes[..]);
+
+        // the cotangent a^T . c is linear in c
+        let cg = contraction_vjp_rhs(&self.0, cotangent, axes);
+        let dcg = contraction_vjp_rhs(&self.0, dcotangent, axes);
+        let (g, vg, dvg) = self.1.eval_hvp(x, v, &cg, &dcg, static_args);
+
+        (self.0.dot(&g), vg, dvg)
+    }
+}
+
 #[derive(FuncCompose, Debug, Clone, Copy)]
 pub struct ADConstantTensorDot<A, B>(pub A, pub B);
 
@@ -664,6 +1943,38 @@ where
     type Output = <A::Output as TensorDot<B>>::Output;
 }
 
+impl<A, B> Symbolic for ADConstantTensorDot<A, B>
+where
+    A: Symbolic,
+    B: SymbolicConstant,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::call("tensordot", vec![self.0.symbolic(x), self.1.symbolic_constant()])
+    }
+}
+
+impl<A, B> fmt::Display for ADConstantTensorDot<A, B>
+where
+    Self: Symbolic,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbolic(&Expr::var("x")))
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADConstantTensorDot<A, B>
+where
+    A: ToDot<StaticArgs>,
+    B: SymbolicConstant,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let c = graph.constant(&self.1.symbolic_constant().to_string());
+        graph.op("tensordot", self.annotate_at(at), &[(f, Some("lhs")), (c, Some("rhs"))])
+    }
+}
+
 impl<StaticArgs, Input, Output, Grad, AOutput, AGrad, A, B> AutoDiffable<StaticArgs> for ADConstantTensorDot<A, B>
 where
     A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
@@ -714,24 +2025,71 @@ where
     {
         self.0.conj_grad(x, static_args).tensordot(&self.1)
     }
-}
-
-impl<StaticArgs, Input, Output, AOutput, A, B> ForwardDiffable<StaticArgs> for ADConstantTensorDot<A, B>
-where
-    A: ForwardDiffable<StaticArgs, Input = Input, Output = AOutput>,
-    AOutput: TensorDot<B, Output = Output>,
-{
-    fn eval_forward(&self, x: &<Self as Diffable<StaticArgs>>::Input,
-                    static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
-    {
-        self.0.eval_forward(x, static_args).tensordot(&self.1)
-    }
 
-    fn eval_forward_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, dx: &<Self as Diffable<StaticArgs>>::Input,
-                         static_args: &StaticArgs) ->
+    fn eval_all(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs) ->
         (
             <Self as Diffable<StaticArgs>>::Output,
-            <Self as Diffable<StaticArgs>>::Output
+            Grad,
+            Grad
+        )
+    {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+
+        (f.tensordot(&self.1), df.tensordot(&self.1), dfdconjz.tensordot(&self.1))
+    }
+}
+
+impl<StaticArgs, Input, AOutput, AGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs> for ADConstantTensorDot<A, B>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f and f.tensordot(b), and the constant, can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    B: Clone + CheckValue,
+    // ensure f.tensordot(b) can be evaluated from the checked results of f
+    ADConstantTensorDot<ADEvaluated<Input, AOutput, AGrad>, B>: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError>
+    {
+        let f = self.0.try_eval(x, static_args).map_err(|e| e.within("ADConstantTensorDot", 0))?;
+        check_dot("ADConstantTensorDot", &f, &self.1)?;
+
+        let h = ADConstantTensorDot(ADEvaluated::value(f), self.1.clone());
+        check_value("ADConstantTensorDot", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(&self, x: &Input, static_args: &StaticArgs) -> Result<(Output, Grad, Grad), AutoDiffError>
+    {
+        let f = self.0.try_eval_all(x, static_args).map_err(|e| e.within("ADConstantTensorDot", 0))?;
+        check_dot("ADConstantTensorDot", &f.0, &self.1)?;
+
+        let h = ADConstantTensorDot(ADEvaluated::all(f), self.1.clone());
+        check_all("ADConstantTensorDot", h.eval_all(x, static_args))
+    }
+}
+
+impl<StaticArgs, Input, Output, AOutput, A, B> ForwardDiffable<StaticArgs> for ADConstantTensorDot<A, B>
+where
+    A: ForwardDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    AOutput: TensorDot<B, Output = Output>,
+{
+    fn eval_forward(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                    static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        self.0.eval_forward(x, static_args).tensordot(&self.1)
+    }
+
+    fn eval_forward_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, dx: &<Self as Diffable<StaticArgs>>::Input,
+                         static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Output
         )
     {
         let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
@@ -764,6 +2122,126 @@ where
     }
 }
 
+impl<StaticArgs, Input, Output, A, B> ReverseDiffable<StaticArgs> for ADConstantTensorDot<A, B>
+where
+    Self: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure the gradient has the type of the input, so the adjoints are pulled back by the vjp
+    Input: GradientType<Output, GradientType = Input> + InstZero + Conjugate<Output = Input>,
+    Output: InstZero + Conjugate<Output = Output>,
+{
+    fn eval_reverse<'a>(&'a self, x: &'a <Self as Diffable<StaticArgs>>::Input, tape: &mut Tape<'a, Input, Output>,
+                    static_args: &'a StaticArgs) -> Var
+    {
+        tape.record_vjp(self, x, static_args)
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, A> VjpDiffable<StaticArgs> for ADConstantTensorDot<A, Array<T, DB>>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    Array<T, DA>: TensorDot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+{
+    fn eval_primal(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                   static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        self.0.eval_primal(x, static_args).tensordot(&self.1)
+    }
+
+    fn eval_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = ([cotangent.ndim() - self.1.ndim() + 1], [0]);
+
+        // c^T d(f . b) = (c . b^T)^T df, where the transpose contracts over the free axes of b
+        let (f, vf) = self.0.eval_vjp(x, &contraction_vjp_lhs(cotangent, &self.1, (&f_axes[..], &g_axes[..])), static_args);
+
+        (f.tensordot(&self.1), vf)
+    }
+
+    fn eval_conj_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                     static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = ([cotangent.ndim() - self.1.ndim() + 1], [0]);
+
+        let (f, vf) = self.0.eval_conj_vjp(x, &contraction_vjp_lhs(cotangent, &self.1, (&f_axes[..], &g_axes[..])), static_args);
+
+        (f.tensordot(&self.1), vf)
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, DAG, DG, DAH, DH, A> HessianDiffable<StaticArgs> for ADConstantTensorDot<A, Array<T, DB>>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    Input: GradientType<Array<T, DA>, GradientType = Array<T, DAG>>
+        + GradientType<Array<T, DO>, GradientType = Array<T, DG>>,
+    Array<T, DAG>: Outer<Array<T, DAG>, Output = Array<T, DAH>>,
+    Array<T, DG>: Outer<Array<T, DG>, Output = Array<T, DH>>,
+    Array<T, DA>: TensorDot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+    DAG: Dimension,
+    DG: Dimension,
+    DAH: Dimension,
+    DH: Dimension,
+{
+    fn eval_hessian(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                    static_args: &StaticArgs) -> (Array<T, DO>, Array<T, DG>, Array<T, DH>)
+    {
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
+        let (f_axes, g_axes) = ([f.ndim() - 1], [0]);
+
+        contraction_hessian(
+            (f.into_dyn(), Some((df.into_dyn(), d2f.into_dyn()))),
+            (self.1.clone().into_dyn(), None),
+            (&f_axes[..], &g_axes[..]),
+        )
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, A> HvpDiffable<StaticArgs> for ADConstantTensorDot<A, Array<T, DB>>
+where
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    Array<T, DA>: TensorDot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+{
+    fn eval_hvp(&self, x: &<Self as Diffable<StaticArgs>>::Input, v: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output, dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = ([cotangent.ndim() - self.1.ndim() + 1], [0]);
+        let axes = (&f_axes[..], &g_axes[..]);
+
+        // the cotangent c . b^T is linear in c
+        let cf = contraction_vjp_lhs(cotangent, &self.1, axes);
+        let dcf = contraction_vjp_lhs(dcotangent, &self.1, axes);
+        let (f, vf, dvf) = self.0.eval_hvp(x, v, &cf, &dcf, static_args);
+
+        (f.tensordot(&self.1), vf, dvf)
+    }
+}
+
 #[derive(FuncCompose, Debug, Clone, Copy)]
 pub struct ADConstantLeftTensorDot<A, B>(pub A, pub B);
 
@@ -775,6 +2253,38 @@ where
     type Output = <A as TensorDot<B::Output>>::Output;
 }
 
+impl<A, B> Symbolic for ADConstantLeftTensorDot<A, B>
+where
+    A: SymbolicConstant,
+    B: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::call("tensordot", vec![self.0.symbolic_constant(), self.1.symbolic(x)])
+    }
+}
+
+impl<A, B> fmt::Display for ADConstantLeftTensorDot<A, B>
+where
+    Self: Symbolic,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbolic(&Expr::var("x")))
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADConstantLeftTensorDot<A, B>
+where
+    A: SymbolicConstant,
+    B: ToDot<StaticArgs>,
+    Self: DotAnnotate<StaticArgs, Input = B::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&B::Input, &StaticArgs)>) -> usize {
+        let c = graph.constant(&self.0.symbolic_constant().to_string());
+        let g = self.1.dot_node(graph, at);
+        graph.op("tensordot", self.annotate_at(at), &[(c, Some("lhs")), (g, Some("rhs"))])
+    }
+}
+
 impl<StaticArgs, Input, Output, Grad, BOutput, BGrad, A, B> AutoDiffable<StaticArgs> for ADConstantLeftTensorDot<A, B>
 where
     A: TensorDot<BOutput, Output = Output>,
@@ -824,6 +2334,173 @@ where
     {
         self.0.tensordot(&self.1.conj_grad(x, static_args))
     }
+
+    fn eval_all(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            Grad,
+            Grad
+        )
+    {
+        let (g, dg, dgdconjz) = self.1.eval_all(x, static_args);
+
+        (self.0.tensordot(&g), self.0.tensordot(&dg), self.0.tensordot(&dgdconjz))
+    }
+}
+
+impl<StaticArgs, Input, BOutput, BGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs> for ADConstantLeftTensorDot<A, B>
+where
+    B: TryAutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    Input: GradientType<BOutput, GradientType = BGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of g and a.tensordot(g), and the constant, can be checked
+    BOutput: CheckValue,
+    BGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    A: Clone + CheckValue,
+    // ensure a.tensordot(g) can be evaluated from the checked results of g
+    ADConstantLeftTensorDot<A, ADEvaluated<Input, BOutput, BGrad>>: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError>
+    {
+        let g = self.1.try_eval(x, static_args).map_err(|e| e.within("ADConstantLeftTensorDot", 1))?;
+        check_dot("ADConstantLeftTensorDot", &self.0, &g)?;
+
+        let h = ADConstantLeftTensorDot(self.0.clone(), ADEvaluated::value(g));
+        check_value("ADConstantLeftTensorDot", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(&self, x: &Input, static_args: &StaticArgs) -> Result<(Output, Grad, Grad), AutoDiffError>
+    {
+        let g = self.1.try_eval_all(x, static_args).map_err(|e| e.within("ADConstantLeftTensorDot", 1))?;
+        check_dot("ADConstantLeftTensorDot", &self.0, &g.0)?;
+
+        let h = ADConstantLeftTensorDot(self.0.clone(), ADEvaluated::all(g));
+        check_all("ADConstantLeftTensorDot", h.eval_all(x, static_args))
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B> ReverseDiffable<StaticArgs> for ADConstantLeftTensorDot<A, B>
+where
+    Self: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure the gradient has the type of the input, so the adjoints are pulled back by the vjp
+    Input: GradientType<Output, GradientType = Input> + InstZero + Conjugate<Output = Input>,
+    Output: InstZero + Conjugate<Output = Output>,
+{
+    fn eval_reverse<'a>(&'a self, x: &'a <Self as Diffable<StaticArgs>>::Input, tape: &mut Tape<'a, Input, Output>,
+                    static_args: &'a StaticArgs) -> Var
+    {
+        tape.record_vjp(self, x, static_args)
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, B> VjpDiffable<StaticArgs> for ADConstantLeftTensorDot<Array<T, DA>, B>
+where
+    B: VjpDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Array<T, DA>: TensorDot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+{
+    fn eval_primal(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                   static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        self.0.tensordot(&self.1.eval_primal(x, static_args))
+    }
+
+    fn eval_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = ([self.0.ndim() - 1], [0]);
+
+        // c^T d(a . g) = (a^T . c)^T dg, where the transpose contracts over the free axes of a
+        let (g, vg) = self.1.eval_vjp(x, &contraction_vjp_rhs(&self.0, cotangent, (&f_axes[..], &g_axes[..])), static_args);
+
+        (self.0.tensordot(&g), vg)
+    }
+
+    fn eval_conj_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                     static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = ([self.0.ndim() - 1], [0]);
+
+        let (g, vg) = self.1.eval_conj_vjp(x, &contraction_vjp_rhs(&self.0, cotangent, (&f_axes[..], &g_axes[..])), static_args);
+
+        (self.0.tensordot(&g), vg)
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, DBG, DG, DBH, DH, B> HessianDiffable<StaticArgs> for ADConstantLeftTensorDot<Array<T, DA>, B>
+where
+    B: HessianDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Input: GradientType<Array<T, DB>, GradientType = Array<T, DBG>>
+        + GradientType<Array<T, DO>, GradientType = Array<T, DG>>,
+    Array<T, DBG>: Outer<Array<T, DBG>, Output = Array<T, DBH>>,
+    Array<T, DG>: Outer<Array<T, DG>, Output = Array<T, DH>>,
+    Array<T, DA>: TensorDot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+    DBG: Dimension,
+    DG: Dimension,
+    DBH: Dimension,
+    DH: Dimension,
+{
+    fn eval_hessian(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                    static_args: &StaticArgs) -> (Array<T, DO>, Array<T, DG>, Array<T, DH>)
+    {
+        let (g, dg, d2g) = self.1.eval_hessian(x, static_args);
+        let (f_axes, g_axes) = ([self.0.ndim() - 1], [0]);
+
+        contraction_hessian(
+            (self.0.clone().into_dyn(), None),
+            (g.into_dyn(), Some((dg.into_dyn(), d2g.into_dyn()))),
+            (&f_axes[..], &g_axes[..]),
+        )
+    }
+}
+
+impl<StaticArgs, Input, T, DA, DB, DO, B> HvpDiffable<StaticArgs> for ADConstantLeftTensorDot<Array<T, DA>, B>
+where
+    B: HvpDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Array<T, DA>: TensorDot<Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+{
+    fn eval_hvp(&self, x: &<Self as Diffable<StaticArgs>>::Input, v: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output, dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = ([self.0.ndim() - 1], [0]);
+        let axes = (&f_axes[..], &g_axes[..]);
+
+        // the cotangent a^T . c is linear in c
+        let cg = contraction_vjp_rhs(&self.0, cotangent, axes);
+        let dcg = contraction_vjp_rhs(&self.0, dcotangent, axes);
+        let (g, vg, dvg) = self.1.eval_hvp(x, v, &cg, &dcg, static_args);
+
+        (self.0.tensordot(&g), vg, dvg)
+    }
 }
 
 #[derive(FuncCompose, Debug, Clone, Copy)]
@@ -837,6 +2514,38 @@ where
     type Output = <A::Output as TensorContraction<N, B>>::Output;
 }
 
+impl<const N: usize, A, B> Symbolic for ADConstantTensorContraction<A, B, N>
+where
+    A: Symbolic,
+    B: SymbolicConstant,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::call("contract", vec![self.0.symbolic(x), self.1.symbolic_constant(), axes(&self.2.0), axes(&self.2.1)])
+    }
+}
+
+impl<const N: usize, A, B> fmt::Display for ADConstantTensorContraction<A, B, N>
+where
+    Self: Symbolic,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbolic(&Expr::var("x")))
+    }
+}
+
+impl<const N: usize, StaticArgs, A, B> ToDot<StaticArgs> for ADConstantTensorContraction<A, B, N>
+where
+    A: ToDot<StaticArgs>,
+    B: SymbolicConstant,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let c = graph.constant(&self.1.symbolic_constant().to_string());
+        graph.op(&format!("contract {:?} {:?}", self.2.0, self.2.1), self.annotate_at(at), &[(f, Some("lhs")), (c, Some("rhs"))])
+    }
+}
+
 impl<const N: usize, StaticArgs, Input, Output, Grad, AOutput, AGrad, A, B> AutoDiffable<StaticArgs> for ADConstantTensorContraction<A, B, N>
 where
     A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
@@ -886,6 +2595,54 @@ where
     {
         self.0.conj_grad(x, static_args).contract(&self.1, (&self.2.0, &self.2.1))
     }
+
+    fn eval_all(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            Grad,
+            Grad
+        )
+    {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+        let axes = (&self.2.0, &self.2.1);
+
+        (f.contract(&self.1, axes), df.contract(&self.1, axes), dfdconjz.contract(&self.1, axes))
+    }
+}
+
+impl<const N: usize, StaticArgs, Input, AOutput, AGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs> for ADConstantTensorContraction<A, B, N>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f and f.contract(b), and the constant, can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    B: Clone + CheckValue,
+    // ensure f.contract(b) can be evaluated from the checked results of f
+    ADConstantTensorContraction<ADEvaluated<Input, AOutput, AGrad>, B, N>: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError>
+    {
+        let f = self.0.try_eval(x, static_args).map_err(|e| e.within("ADConstantTensorContraction", 0))?;
+        check_contraction("ADConstantTensorContraction", &f, &self.1, &self.2.0, &self.2.1)?;
+
+        let h = ADConstantTensorContraction(ADEvaluated::value(f), self.1.clone(), self.2);
+        check_value("ADConstantTensorContraction", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(&self, x: &Input, static_args: &StaticArgs) -> Result<(Output, Grad, Grad), AutoDiffError>
+    {
+        let f = self.0.try_eval_all(x, static_args).map_err(|e| e.within("ADConstantTensorContraction", 0))?;
+        check_contraction("ADConstantTensorContraction", &f.0, &self.1, &self.2.0, &self.2.1)?;
+
+        let h = ADConstantTensorContraction(ADEvaluated::all(f), self.1.clone(), self.2);
+        check_all("ADConstantTensorContraction", h.eval_all(x, static_args))
+    }
 }
 
 impl<const N: usize, StaticArgs, Input, Output, AOutput, A, B> ForwardDiffable<StaticArgs> for ADConstantTensorContraction<A, B, N>
@@ -937,85 +2694,285 @@ where
 
 }
 
-#[derive(FuncCompose, Debug, Clone, Copy)]
-pub struct ADConstantLeftTensorContraction<A, B, const N: usize>(pub A, pub B, pub ([usize; N], [usize; N]));
-
-impl<const N: usize, A, B: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs> for ADConstantLeftTensorContraction<A, B, N>
+impl<const N: usize, StaticArgs, Input, Output, A, B> ReverseDiffable<StaticArgs> for ADConstantTensorContraction<A, B, N>
 where
-    A: TensorContraction<N, B::Output>,
+    Self: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure the gradient has the type of the input, so the adjoints are pulled back by the vjp
+    Input: GradientType<Output, GradientType = Input> + InstZero + Conjugate<Output = Input>,
+    Output: InstZero + Conjugate<Output = Output>,
 {
-    type Input = B::Input;
-    type Output = <A as TensorContraction<N, B::Output>>::Output;
+    fn eval_reverse<'a>(&'a self, x: &'a <Self as Diffable<StaticArgs>>::Input, tape: &mut Tape<'a, Input, Output>,
+                    static_args: &'a StaticArgs) -> Var
+    {
+        tape.record_vjp(self, x, static_args)
+    }
 }
 
-impl<const N: usize, StaticArgs, Input, Output, Grad, BOutput, BGrad, A, B> AutoDiffable<StaticArgs> for ADConstantLeftTensorContraction<A, B, N>
+impl<const N: usize, StaticArgs, Input, T, DA, DB, DO, A> VjpDiffable<StaticArgs> for ADConstantTensorContraction<A, Array<T, DB>, N>
 where
-    A: TensorContraction<N, BOutput, Output = Output>,
-    A: TensorContraction<N, BGrad, Output = Grad>,
-    B: AutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
-    Input: GradientType<BOutput, GradientType = BGrad>,
-    Input: GradientType<Output, GradientType = Grad>,
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    Array<T, DA>: TensorContraction<N, Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
 {
-    fn eval(&self, x: &<Self as Diffable<StaticArgs>>::Input,
-            static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    fn eval_primal(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                   static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
     {
-        self.0.contract(&self.1.eval(x, static_args), (&self.2.0, &self.2.1))
+        self.0.eval_primal(x, static_args).contract(&self.1, (&self.2.0, &self.2.1))
     }
 
-    fn eval_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input,
-                 static_args: &StaticArgs) ->
+    fn eval_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
         (
             <Self as Diffable<StaticArgs>>::Output,
-            Grad
+            <Self as Diffable<StaticArgs>>::Input
         )
     {
-        let (g, dg) = self.1.eval_grad(x, static_args);
+        let (f_axes, g_axes) = &self.2;
 
-        (self.0.contract(&g, (&self.2.0, &self.2.1)), self.0.contract(&dg, (&self.2.0, &self.2.1)))
+        // c^T d(f . b) = (c . b^T)^T df, where the transpose contracts over the free axes of b
+        let (f, vf) = self.0.eval_vjp(x, &contraction_vjp_lhs(cotangent, &self.1, (&f_axes[..], &g_axes[..])), static_args);
+
+        (f.contract(&self.1, (&self.2.0, &self.2.1)), vf)
     }
 
-    fn eval_conj_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input,
-                      static_args: &StaticArgs) ->
+    fn eval_conj_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                     static_args: &StaticArgs) ->
         (
             <Self as Diffable<StaticArgs>>::Output,
-            Grad
+            <Self as Diffable<StaticArgs>>::Input
         )
     {
-        let (g, dg) = self.1.eval_conj_grad(x, static_args);
-
-        (self.0.contract(&g, (&self.2.0, &self.2.1)), self.0.contract(&dg, (&self.2.0, &self.2.1)))
-    }
+        let (f_axes, g_axes) = &self.2;
 
-    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input,
-            static_args: &StaticArgs) -> Grad
-    {
-        self.0.contract(&self.1.grad(x, static_args), (&self.2.0, &self.2.1))
-    }
+        let (f, vf) = self.0.eval_conj_vjp(x, &contraction_vjp_lhs(cotangent, &self.1, (&f_axes[..], &g_axes[..])), static_args);
 
-    fn conj_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input,
-                 static_args: &StaticArgs) -> Grad
-    {
-        self.0.contract(&self.1.conj_grad(x, static_args), (&self.2.0, &self.2.1))
+        (f.contract(&self.1, (&self.2.0, &self.2.1)), vf)
     }
 }
 
-impl<const N: usize, StaticArgs, Input, Output, BOutput, A, B> ForwardDiffable<StaticArgs> for ADConstantLeftTensorContraction<A, B, N>
+impl<const N: usize, StaticArgs, Input, T, DA, DB, DO, DAG, DG, DAH, DH, A> HessianDiffable<StaticArgs> for ADConstantTensorContraction<A, Array<T, DB>, N>
 where
-    A: TensorContraction<N, BOutput, Output = Output>,
-    Output: Add<Output, Output = Output>,
-    B: ForwardDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    Input: GradientType<Array<T, DA>, GradientType = Array<T, DAG>>
+        + GradientType<Array<T, DO>, GradientType = Array<T, DG>>,
+    Array<T, DAG>: Outer<Array<T, DAG>, Output = Array<T, DAH>>,
+    Array<T, DG>: Outer<Array<T, DG>, Output = Array<T, DH>>,
+    Array<T, DA>: TensorContraction<N, Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+    DAG: Dimension,
+    DG: Dimension,
+    DAH: Dimension,
+    DH: Dimension,
 {
-    fn eval_forward(&self, x: &<Self as Diffable<StaticArgs>>::Input,
-                    static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    fn eval_hessian(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                    static_args: &StaticArgs) -> (Array<T, DO>, Array<T, DG>, Array<T, DH>)
     {
-        self.0.contract(&self.1.eval_forward(x, static_args), (&self.2.0, &self.2.1))
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
+        let (f_axes, g_axes) = &self.2;
+
+        contraction_hessian(
+            (f.into_dyn(), Some((df.into_dyn(), d2f.into_dyn()))),
+            (self.1.clone().into_dyn(), None),
+            (&f_axes[..], &g_axes[..]),
+        )
     }
+}
 
-    fn eval_forward_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, dx: &<Self as Diffable<StaticArgs>>::Input,
-                         static_args: &StaticArgs) ->
+impl<const N: usize, StaticArgs, Input, T, DA, DB, DO, A> HvpDiffable<StaticArgs> for ADConstantTensorContraction<A, Array<T, DB>, N>
+where
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Array<T, DA>>,
+    Array<T, DA>: TensorContraction<N, Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+{
+    fn eval_hvp(&self, x: &<Self as Diffable<StaticArgs>>::Input, v: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output, dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
         (
             <Self as Diffable<StaticArgs>>::Output,
-            <Self as Diffable<StaticArgs>>::Output
+            <Self as Diffable<StaticArgs>>::Input,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = &self.2;
+        let axes = (&f_axes[..], &g_axes[..]);
+
+        // the cotangent c . b^T is linear in c
+        let cf = contraction_vjp_lhs(cotangent, &self.1, axes);
+        let dcf = contraction_vjp_lhs(dcotangent, &self.1, axes);
+        let (f, vf, dvf) = self.0.eval_hvp(x, v, &cf, &dcf, static_args);
+
+        (f.contract(&self.1, (&self.2.0, &self.2.1)), vf, dvf)
+    }
+}
+
+#[derive(FuncCompose, Debug, Clone, Copy)]
+pub struct ADConstantLeftTensorContraction<A, B, const N: usize>(pub A, pub B, pub ([usize; N], [usize; N]));
+
+impl<const N: usize, A, B: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs> for ADConstantLeftTensorContraction<A, B, N>
+where
+    A: TensorContraction<N, B::Output>,
+{
+    type Input = B::Input;
+    type Output = <A as TensorContraction<N, B::Output>>::Output;
+}
+
+impl<const N: usize, A, B> Symbolic for ADConstantLeftTensorContraction<A, B, N>
+where
+    A: SymbolicConstant,
+    B: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::call("contract", vec![self.0.symbolic_constant(), self.1.symbolic(x), axes(&self.2.0), axes(&self.2.1)])
+    }
+}
+
+impl<const N: usize, A, B> fmt::Display for ADConstantLeftTensorContraction<A, B, N>
+where
+    Self: Symbolic,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbolic(&Expr::var("x")))
+    }
+}
+
+impl<const N: usize, StaticArgs, A, B> ToDot<StaticArgs> for ADConstantLeftTensorContraction<A, B, N>
+where
+    A: SymbolicConstant,
+    B: ToDot<StaticArgs>,
+    Self: DotAnnotate<StaticArgs, Input = B::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&B::Input, &StaticArgs)>) -> usize {
+        let c = graph.constant(&self.0.symbolic_constant().to_string());
+        let g = self.1.dot_node(graph, at);
+        graph.op(&format!("contract {:?} {:?}", self.2.0, self.2.1), self.annotate_at(at), &[(c, Some("lhs")), (g, Some("rhs"))])
+    }
+}
+
+impl<const N: usize, StaticArgs, Input, Output, Grad, BOutput, BGrad, A, B> AutoDiffable<StaticArgs> for ADConstantLeftTensorContraction<A, B, N>
+where
+    A: TensorContraction<N, BOutput, Output = Output>,
+    A: TensorContraction<N, BGrad, Output = Grad>,
+    B: AutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    Input: GradientType<BOutput, GradientType = BGrad>,
+    Input: GradientType<Output, GradientType = Grad>,
+{
+    fn eval(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+            static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        self.0.contract(&self.1.eval(x, static_args), (&self.2.0, &self.2.1))
+    }
+
+    fn eval_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                 static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            Grad
+        )
+    {
+        let (g, dg) = self.1.eval_grad(x, static_args);
+
+        (self.0.contract(&g, (&self.2.0, &self.2.1)), self.0.contract(&dg, (&self.2.0, &self.2.1)))
+    }
+
+    fn eval_conj_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                      static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            Grad
+        )
+    {
+        let (g, dg) = self.1.eval_conj_grad(x, static_args);
+
+        (self.0.contract(&g, (&self.2.0, &self.2.1)), self.0.contract(&dg, (&self.2.0, &self.2.1)))
+    }
+
+    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+            static_args: &StaticArgs) -> Grad
+    {
+        self.0.contract(&self.1.grad(x, static_args), (&self.2.0, &self.2.1))
+    }
+
+    fn conj_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                 static_args: &StaticArgs) -> Grad
+    {
+        self.0.contract(&self.1.conj_grad(x, static_args), (&self.2.0, &self.2.1))
+    }
+
+    fn eval_all(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            Grad,
+            Grad
+        )
+    {
+        let (g, dg, dgdconjz) = self.1.eval_all(x, static_args);
+        let axes = (&self.2.0, &self.2.1);
+
+        (self.0.contract(&g, axes), self.0.contract(&dg, axes), self.0.contract(&dgdconjz, axes))
+    }
+}
+
+impl<const N: usize, StaticArgs, Input, BOutput, BGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs> for ADConstantLeftTensorContraction<A, B, N>
+where
+    B: TryAutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    Input: GradientType<BOutput, GradientType = BGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of g and a.contract(g), and the constant, can be checked
+    BOutput: CheckValue,
+    BGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    A: Clone + CheckValue,
+    // ensure a.contract(g) can be evaluated from the checked results of g
+    ADConstantLeftTensorContraction<A, ADEvaluated<Input, BOutput, BGrad>, N>: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError>
+    {
+        let g = self.1.try_eval(x, static_args).map_err(|e| e.within("ADConstantLeftTensorContraction", 1))?;
+        check_contraction("ADConstantLeftTensorContraction", &self.0, &g, &self.2.0, &self.2.1)?;
+
+        let h = ADConstantLeftTensorContraction(self.0.clone(), ADEvaluated::value(g), self.2);
+        check_value("ADConstantLeftTensorContraction", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(&self, x: &Input, static_args: &StaticArgs) -> Result<(Output, Grad, Grad), AutoDiffError>
+    {
+        let g = self.1.try_eval_all(x, static_args).map_err(|e| e.within("ADConstantLeftTensorContraction", 1))?;
+        check_contraction("ADConstantLeftTensorContraction", &self.0, &g.0, &self.2.0, &self.2.1)?;
+
+        let h = ADConstantLeftTensorContraction(self.0.clone(), ADEvaluated::all(g), self.2);
+        check_all("ADConstantLeftTensorContraction", h.eval_all(x, static_args))
+    }
+}
+
+impl<const N: usize, StaticArgs, Input, Output, BOutput, A, B> ForwardDiffable<StaticArgs> for ADConstantLeftTensorContraction<A, B, N>
+where
+    A: TensorContraction<N, BOutput, Output = Output>,
+    Output: Add<Output, Output = Output>,
+    B: ForwardDiffable<StaticArgs, Input = Input, Output = BOutput>,
+{
+    fn eval_forward(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                    static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        self.0.contract(&self.1.eval_forward(x, static_args), (&self.2.0, &self.2.1))
+    }
+
+    fn eval_forward_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, dx: &<Self as Diffable<StaticArgs>>::Input,
+                         static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Output
         )
     {
         let (g, dg) = self.1.eval_forward_grad(x, dx, static_args);
@@ -1048,3 +3005,970 @@ where
     }
 }
 
+impl<const N: usize, StaticArgs, Input, Output, A, B> ReverseDiffable<StaticArgs> for ADConstantLeftTensorContraction<A, B, N>
+where
+    Self: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure the gradient has the type of the input, so the adjoints are pulled back by the vjp
+    Input: GradientType<Output, GradientType = Input> + InstZero + Conjugate<Output = Input>,
+    Output: InstZero + Conjugate<Output = Output>,
+{
+    fn eval_reverse<'a>(&'a self, x: &'a <Self as Diffable<StaticArgs>>::Input, tape: &mut Tape<'a, Input, Output>,
+                    static_args: &'a StaticArgs) -> Var
+    {
+        tape.record_vjp(self, x, static_args)
+    }
+}
+
+impl<const N: usize, StaticArgs, Input, T, DA, DB, DO, B> VjpDiffable<StaticArgs> for ADConstantLeftTensorContraction<Array<T, DA>, B, N>
+where
+    B: VjpDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Array<T, DA>: TensorContraction<N, Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+{
+    fn eval_primal(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                   static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        self.0.contract(&self.1.eval_primal(x, static_args), (&self.2.0, &self.2.1))
+    }
+
+    fn eval_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = &self.2;
+
+        // c^T d(a . g) = (a^T . c)^T dg, where the transpose contracts over the free axes of a
+        let (g, vg) = self.1.eval_vjp(x, &contraction_vjp_rhs(&self.0, cotangent, (&f_axes[..], &g_axes[..])), static_args);
+
+        (self.0.contract(&g, (&self.2.0, &self.2.1)), vg)
+    }
+
+    fn eval_conj_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                     static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = &self.2;
+
+        let (g, vg) = self.1.eval_conj_vjp(x, &contraction_vjp_rhs(&self.0, cotangent, (&f_axes[..], &g_axes[..])), static_args);
+
+        (self.0.contract(&g, (&self.2.0, &self.2.1)), vg)
+    }
+}
+
+impl<const N: usize, StaticArgs, Input, T, DA, DB, DO, DBG, DG, DBH, DH, B> HessianDiffable<StaticArgs> for ADConstantLeftTensorContraction<Array<T, DA>, B, N>
+where
+    B: HessianDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Input: GradientType<Array<T, DB>, GradientType = Array<T, DBG>>
+        + GradientType<Array<T, DO>, GradientType = Array<T, DG>>,
+    Array<T, DBG>: Outer<Array<T, DBG>, Output = Array<T, DBH>>,
+    Array<T, DG>: Outer<Array<T, DG>, Output = Array<T, DH>>,
+    Array<T, DA>: TensorContraction<N, Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+    DBG: Dimension,
+    DG: Dimension,
+    DBH: Dimension,
+    DH: Dimension,
+{
+    fn eval_hessian(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                    static_args: &StaticArgs) -> (Array<T, DO>, Array<T, DG>, Array<T, DH>)
+    {
+        let (g, dg, d2g) = self.1.eval_hessian(x, static_args);
+        let (f_axes, g_axes) = &self.2;
+
+        contraction_hessian(
+            (self.0.clone().into_dyn(), None),
+            (g.into_dyn(), Some((dg.into_dyn(), d2g.into_dyn()))),
+            (&f_axes[..], &g_axes[..]),
+        )
+    }
+}
+
+impl<const N: usize, StaticArgs, Input, T, DA, DB, DO, B> HvpDiffable<StaticArgs> for ADConstantLeftTensorContraction<Array<T, DA>, B, N>
+where
+    B: HvpDiffable<StaticArgs, Input = Input, Output = Array<T, DB>>,
+    Array<T, DA>: TensorContraction<N, Array<T, DB>, Output = Array<T, DO>>,
+    T: LinalgScalar,
+    DA: Dimension,
+    DB: Dimension,
+    DO: Dimension,
+{
+    fn eval_hvp(&self, x: &<Self as Diffable<StaticArgs>>::Input, v: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output, dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        let (f_axes, g_axes) = &self.2;
+        let axes = (&f_axes[..], &g_axes[..]);
+
+        // the cotangent a^T . c is linear in c
+        let cg = contraction_vjp_rhs(&self.0, cotangent, axes);
+        let dcg = contraction_vjp_rhs(&self.0, dcotangent, axes);
+        let (g, vg, dvg) = self.1.eval_hvp(x, v, &cg, &dcg, static_args);
+
+        (self.0.contract(&g, (&self.2.0, &self.2.1)), vg, dvg)
+    }
+}
+
+
+/// Applies a scalar function `F` to every element of an array. `D` is the dimension of the array,
+/// usually inferred from how the result is used.
+#[derive(FuncCompose, Debug, Clone, Copy)]
+pub struct ADElementwise<F, D>(pub F, pub PhantomData<D>);
+
+impl<F, D, T, StaticArgs> Diffable<StaticArgs> for ADElementwise<F, D>
+where
+    F: Diffable<StaticArgs, Input = T, Output = T>,
+    D: Dimension,
+{
+    type Input = Array<T, D>;
+    type Output = Array<T, D>;
+}
+
+impl<F, D> Symbolic for ADElementwise<F, D>
+where
+    F: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        // the function is written as applied to x, where it is applied to every element of x
+        self.0.symbolic(x)
+    }
+}
+
+impl<F, D> fmt::Display for ADElementwise<F, D>
+where
+    Self: Symbolic,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbolic(&Expr::var("x")))
+    }
+}
+
+impl<StaticArgs, F, D> ToDot<StaticArgs> for ADElementwise<F, D>
+where
+    F: ToDot<StaticArgs>,
+    Self: DotAnnotate<StaticArgs>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&Self::Input, &StaticArgs)>) -> usize {
+        // F acts on the elements rather than on the array, so it is drawn without annotations
+        let f = self.0.dot_node(graph, None);
+        graph.op("elementwise", self.annotate_at(at), &[(f, None)])
+    }
+}
+
+// builds the (shape..., shape...) jacobian of an elementwise map from its diagonal, which is given in
+// the logical order of the input elements. the flat index of [idx..., idx...] is i * n + i
+fn diagonal_jacobian<G, DG>(shape: &[usize], diagonal: Vec<G>) -> Array<G, DG>
+where
+    G: Clone + InstZero,
+    DG: Dimension,
+{
+    let n = diagonal.len();
+
+    let mut dim = DG::zeros(2 * shape.len());
+    for (d, s) in dim.slice_mut().iter_mut().zip(shape.iter().chain(shape.iter())) {
+        *d = *s;
+    }
+
+    let mut data = match diagonal.first() {
+        Some(g) => vec![g.zero(); n * n],
+        None => vec![],
+    };
+    for (i, g) in diagonal.into_iter().enumerate() {
+        data[i * n + i] = g;
+    }
+
+    Array::from_shape_vec(dim, data).unwrap()
+}
+
+// builds the (shape..., shape..., shape..., shape...) Hessian of an elementwise map from the second derivatives of each
+// element wrt itself, which are its only non-zero entries. the flat index of [idx..., idx..., idx..., idx...] is
+// i * (n³ + n² + n + 1)
+fn diagonal_hessian<H, DH>(shape: &[usize], diagonal: Vec<H>) -> Array<H, DH>
+where
+    H: Clone + InstZero,
+    DH: Dimension,
+{
+    let n = diagonal.len();
+
+    let mut dim = DH::zeros(4 * shape.len());
+    for (d, s) in dim.slice_mut().iter_mut().zip(shape.iter().cycle()) {
+        *d = *s;
+    }
+
+    let mut data = match diagonal.first() {
+        Some(h) => vec![h.zero(); n * n * n * n],
+        None => vec![],
+    };
+    for (i, h) in diagonal.into_iter().enumerate() {
+        data[i * (n * n * n + n * n + n + 1)] = h;
+    }
+
+    Array::from_shape_vec(dim, data).unwrap()
+}
+
+// the tangents and cotangents of an elementwise map are zipped with its input element by element, so like Zip they
+// must have the same shape rather than being silently truncated
+fn assert_elementwise_shape<A, B, D>(x: &Array<A, D>, other: &Array<B, D>)
+where
+    D: Dimension,
+{
+    assert_eq!(x.shape(), other.shape(), "the arrays of an elementwise map must have the shape of its input");
+}
+
+// splits the (value, gradient) pairs of every element, in logical order, into a pair of arrays
+fn unzip_elementwise<T, D>(dim: D, pairs: Vec<(T, T)>) -> (Array<T, D>, Array<T, D>)
+where
+    D: Dimension,
+{
+    let (f, df): (Vec<T>, Vec<T>) = pairs.into_iter().unzip();
+
+    (Array::from_shape_vec(dim.clone(), f).unwrap(), Array::from_shape_vec(dim, df).unwrap())
+}
+
+// splits the (value, vector-Jacobian product, derivative) triples of every element, in logical order, into three arrays
+fn unzip3_elementwise<T, D>(dim: D, triples: Vec<(T, T, T)>) -> (Array<T, D>, Array<T, D>, Array<T, D>)
+where
+    D: Dimension,
+{
+    let (f, vdv): (Vec<T>, Vec<(T, T)>) = triples.into_iter().map(|(f, v, dv)| (f, (v, dv))).unzip();
+    let (v, dv) = unzip_elementwise(dim.clone(), vdv);
+
+    (Array::from_shape_vec(dim, f).unwrap(), v, dv)
+}
+
+impl<StaticArgs, F, D, DG, T, TG> AutoDiffable<StaticArgs> for ADElementwise<F, D>
+where
+    F: AutoDiffable<StaticArgs, Input = T, Output = T>,
+    T: GradientType<T, GradientType = TG>,
+    TG: Clone + InstZero,
+    D: Dimension + DimAdd<D, Output = DG>,
+    DG: Dimension,
+{
+    fn eval(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+            static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        x.map(|xi| self.0.eval(xi, static_args))
+    }
+
+    fn eval_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                 static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            Array<TG, DG>
+        )
+    {
+        // the jacobian of an elementwise map is diagonal, so only the scalar derivatives are computed
+        let (f, df): (Vec<T>, Vec<TG>) = x.iter().map(|xi| self.0.eval_grad(xi, static_args)).unzip();
+
+        (Array::from_shape_vec(x.raw_dim(), f).unwrap(), diagonal_jacobian(x.shape(), df))
+    }
+
+    fn eval_conj_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                      static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            Array<TG, DG>
+        )
+    {
+        let (f, df): (Vec<T>, Vec<TG>) = x.iter().map(|xi| self.0.eval_conj_grad(xi, static_args)).unzip();
+
+        (Array::from_shape_vec(x.raw_dim(), f).unwrap(), diagonal_jacobian(x.shape(), df))
+    }
+
+    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+            static_args: &StaticArgs) -> Array<TG, DG>
+    {
+        diagonal_jacobian(x.shape(), x.iter().map(|xi| self.0.grad(xi, static_args)).collect())
+    }
+
+    fn conj_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                 static_args: &StaticArgs) -> Array<TG, DG>
+    {
+        diagonal_jacobian(x.shape(), x.iter().map(|xi| self.0.conj_grad(xi, static_args)).collect())
+    }
+
+    fn eval_all(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            Array<TG, DG>,
+            Array<TG, DG>
+        )
+    {
+        let mut f = Vec::with_capacity(x.len());
+        let mut df = Vec::with_capacity(x.len());
+        let mut dfdconjz = Vec::with_capacity(x.len());
+        for xi in x.iter() {
+            let (fi, dfi, dfdconjzi) = self.0.eval_all(xi, static_args);
+            f.push(fi);
+            df.push(dfi);
+            dfdconjz.push(dfdconjzi);
+        }
+
+        (Array::from_shape_vec(x.raw_dim(), f).unwrap(), diagonal_jacobian(x.shape(), df), diagonal_jacobian(x.shape(), dfdconjz))
+    }
+}
+
+// F is checked as a whole on the array, since the elements have no nodes of their own
+impl<StaticArgs, F, D, Input, Output, Grad> TryAutoDiffable<StaticArgs> for ADElementwise<F, D>
+where
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the value and gradients can be checked
+    Output: CheckValue,
+    Grad: CheckValue,
+{
+}
+
+impl<StaticArgs, F, D, T> ForwardDiffable<StaticArgs> for ADElementwise<F, D>
+where
+    F: ForwardDiffable<StaticArgs, Input = T, Output = T>,
+    D: Dimension,
+{
+    fn eval_forward(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                    static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        x.map(|xi| self.0.eval_forward(xi, static_args))
+    }
+
+    // each output element only depends on the matching input element, so the forward gradient is
+    // the elementwise scalar forward gradient, no dense jacobian is needed
+    fn eval_forward_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, dx: &<Self as Diffable<StaticArgs>>::Input,
+                         static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Output
+        )
+    {
+        assert_elementwise_shape(x, dx);
+        let pairs = x.iter().zip(dx.iter()).map(|(xi, dxi)| self.0.eval_forward_grad(xi, dxi, static_args)).collect();
+
+        unzip_elementwise(x.raw_dim(), pairs)
+    }
+
+    fn eval_forward_conj_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, dx: &<Self as Diffable<StaticArgs>>::Input,
+                              static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Output
+        )
+    {
+        assert_elementwise_shape(x, dx);
+        let pairs = x.iter().zip(dx.iter()).map(|(xi, dxi)| self.0.eval_forward_conj_grad(xi, dxi, static_args)).collect();
+
+        unzip_elementwise(x.raw_dim(), pairs)
+    }
+
+    fn forward_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, dx: &<Self as Diffable<StaticArgs>>::Input,
+                    static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        Zip::from(x).and(dx).map_collect(|xi, dxi| self.0.forward_grad(xi, dxi, static_args))
+    }
+
+    fn forward_conj_grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, dx: &<Self as Diffable<StaticArgs>>::Input,
+                         static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        Zip::from(x).and(dx).map_collect(|xi, dxi| self.0.forward_conj_grad(xi, dxi, static_args))
+    }
+}
+
+impl<StaticArgs, Input, Output, F, D> ReverseDiffable<StaticArgs> for ADElementwise<F, D>
+where
+    Self: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure the gradient has the type of the input, so the adjoints are pulled back by the vjp
+    Input: GradientType<Output, GradientType = Input> + InstZero + Conjugate<Output = Input>,
+    Output: InstZero + Conjugate<Output = Output>,
+{
+    fn eval_reverse<'a>(&'a self, x: &'a <Self as Diffable<StaticArgs>>::Input, tape: &mut Tape<'a, Input, Output>,
+                    static_args: &'a StaticArgs) -> Var
+    {
+        tape.record_vjp(self, x, static_args)
+    }
+}
+
+impl<StaticArgs, F, D, T> VjpDiffable<StaticArgs> for ADElementwise<F, D>
+where
+    F: VjpDiffable<StaticArgs, Input = T, Output = T>,
+    D: Dimension,
+{
+    fn eval_primal(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                   static_args: &StaticArgs) -> <Self as Diffable<StaticArgs>>::Output
+    {
+        x.map(|xi| self.0.eval_primal(xi, static_args))
+    }
+
+    fn eval_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        // the jacobian is diagonal, so each element of the cotangent is only pulled back through the matching
+        // element of the input
+        assert_elementwise_shape(x, cotangent);
+        let pairs = x.iter().zip(cotangent.iter()).map(|(xi, ci)| self.0.eval_vjp(xi, ci, static_args)).collect();
+
+        unzip_elementwise(x.raw_dim(), pairs)
+    }
+
+    fn eval_conj_vjp(&self, x: &<Self as Diffable<StaticArgs>>::Input, cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                     static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        assert_elementwise_shape(x, cotangent);
+        let pairs = x.iter().zip(cotangent.iter()).map(|(xi, ci)| self.0.eval_conj_vjp(xi, ci, static_args)).collect();
+
+        unzip_elementwise(x.raw_dim(), pairs)
+    }
+}
+
+impl<StaticArgs, F, D, DG, DH, T, TG, TH> HessianDiffable<StaticArgs> for ADElementwise<F, D>
+where
+    F: HessianDiffable<StaticArgs, Input = T, Output = T>,
+    T: GradientType<T, GradientType = TG>,
+    TG: Clone + InstZero + Outer<TG, Output = TH>,
+    TH: Clone + InstZero,
+    D: Dimension + DimAdd<D, Output = DG>,
+    DG: Dimension + DimAdd<DG, Output = DH>,
+    DH: Dimension,
+    Array<TG, DG>: Outer<Array<TG, DG>, Output = Array<TH, DH>>,
+{
+    fn eval_hessian(&self, x: &<Self as Diffable<StaticArgs>>::Input,
+                    static_args: &StaticArgs) -> (Array<T, D>, Array<TG, DG>, Array<TH, DH>)
+    {
+        // every element of the output depends only on the same element of the input, so only the scalar second
+        // derivatives are computed
+        let mut f = Vec::with_capacity(x.len());
+        let mut df = Vec::with_capacity(x.len());
+        let mut d2f = Vec::with_capacity(x.len());
+        for xi in x.iter() {
+            let (fi, dfi, d2fi) = self.0.eval_hessian(xi, static_args);
+            f.push(fi);
+            df.push(dfi);
+            d2f.push(d2fi);
+        }
+
+        (Array::from_shape_vec(x.raw_dim(), f).unwrap(), diagonal_jacobian(x.shape(), df), diagonal_hessian(x.shape(), d2f))
+    }
+}
+
+impl<StaticArgs, F, D, T> HvpDiffable<StaticArgs> for ADElementwise<F, D>
+where
+    F: HvpDiffable<StaticArgs, Input = T, Output = T>,
+    D: Dimension,
+{
+    fn eval_hvp(&self, x: &<Self as Diffable<StaticArgs>>::Input, v: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output, dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs) ->
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <Self as Diffable<StaticArgs>>::Input,
+            <Self as Diffable<StaticArgs>>::Input
+        )
+    {
+        // the hessian is diagonal as well, so each element only depends on the matching elements of v and the
+        // cotangents
+        assert_elementwise_shape(x, v);
+        assert_elementwise_shape(x, cotangent);
+        assert_elementwise_shape(x, dcotangent);
+        let triples = x.iter().zip(v.iter()).zip(cotangent.iter().zip(dcotangent.iter()))
+            .map(|((xi, vi), (ci, dci))| self.0.eval_hvp(xi, vi, ci, dci, static_args)).collect();
+
+        unzip3_elementwise(x.raw_dim(), triples)
+    }
+}
+
+#[test]
+fn test_elementwise() {
+    use crate::ad_ndarray::func_traits::Dot;
+    use crate::autodiff::AutoDiff;
+    use crate::funcs::Identity;
+    use crate::traits::Sin;
+    use ndarray::{arr1, arr2, Array1, Array2, Array4, ShapeBuilder};
+
+    // f(x) = sin(x) on scalars, f'(x) = cos(x)
+    let i = AutoDiff::new(Identity::new());
+    let f = i.sin().elementwise();
+    let df = |x: f64| x.cos();
+
+    let x = arr1(&[-1.0, 0.5, 2.0]);
+    let dx = arr1(&[1.0, 2.0, 3.0]);
+
+    let (f_x, df_dx): (Array1<f64>, Array2<f64>) = f.eval_grad(&x, &());
+    assert_eq!(f_x, x.mapv(|x| x.sin()));
+    assert_eq!(df_dx, Array2::from_diag(&x.mapv(df)));
+    assert_eq!(f.conj_grad(&x, &()), Array2::<f64>::zeros((3, 3)));
+
+    let (f_x, df_x): (Array1<f64>, Array1<f64>) = f.eval_forward_grad(&x, &dx, &());
+    assert_eq!(f_x, x.mapv(|x| x.sin()));
+    assert_eq!(df_x, x.mapv(df) * &dx);
+
+    // rank 2 arrays have a rank 4 jacobian, with d f[i, j] / d x[k, l] = f'(x[i, j]) if i == k && j == l
+    let f = i.sin().elementwise();
+    let x = arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let df_dx: Array4<f64> = f.grad(&x, &());
+    assert_eq!(df_dx.shape(), &[2, 3, 2, 3]);
+    for ((a, b, c, d), g) in df_dx.indexed_iter() {
+        if a == c && b == d {
+            assert_eq!(*g, df(x[[a, b]]));
+        } else {
+            assert_eq!(*g, 0.0);
+        }
+    }
+
+    // the elementwise gradient of a column major array is in the logical order of the elements
+    let mut xt = Array2::zeros((2, 3).f());
+    xt.assign(&x);
+    assert!(!xt.is_standard_layout());
+    let dxt = xt.mapv(|x| x * 0.5);
+    let (f_xt, df_xt): (Array2<f64>, Array2<f64>) = f.eval_forward_grad(&xt, &dxt, &());
+    assert_eq!(f_xt, x.mapv(|x| x.sin()));
+    assert_eq!(df_xt, x.mapv(|x| df(x) * (x * 0.5)));
+
+    // eval_all agrees with eval, grad and conj_grad, for elementwise functions and dot products of them
+    let f = i.sin().elementwise();
+    let x = arr1(&[-1.0, 0.5, 2.0]);
+    let (f_x, df_dx, df_dconjx): (Array1<f64>, Array2<f64>, Array2<f64>) = f.eval_all(&x, &());
+    assert_eq!((f_x, df_dx, df_dconjx), (f.eval(&x, &()), f.grad(&x, &()), f.conj_grad(&x, &())));
+    let g = f.dot(&arr2(&[[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]));
+    let (g_x, dg_dx, dg_dconjx): (Array1<f64>, Array2<f64>, Array2<f64>) = g.eval_all(&x, &());
+    assert_eq!((g_x, dg_dx, dg_dconjx), (g.eval(&x, &()), g.grad(&x, &()), g.conj_grad(&x, &())));
+}
+
+#[test]
+#[should_panic(expected = "the arrays of an elementwise map must have the shape of its input")]
+fn test_elementwise_forward_shape() {
+    use crate::autodiff::AutoDiff;
+    use crate::funcs::Identity;
+    use crate::traits::Sin;
+    use ndarray::arr1;
+
+    // a tangent with fewer elements than the input is not truncated to fit, as in forward_grad
+    let f = AutoDiff::new(Identity::new()).sin().elementwise();
+    f.eval_forward_grad(&arr1(&[-1.0, 0.5, 2.0]), &arr1(&[1.0, 2.0]), &());
+}
+
+#[test]
+#[should_panic(expected = "the arrays of an elementwise map must have the shape of its input")]
+fn test_elementwise_vjp_shape() {
+    use crate::autodiff::AutoDiff;
+    use crate::funcs::Identity;
+    use crate::traits::Sin;
+    use ndarray::arr2;
+
+    // a cotangent with the elements of the input in another shape is not pulled back
+    let f = AutoDiff::new(Identity::new()).sin().elementwise();
+    f.eval_vjp(&arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]), &arr2(&[[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]), &());
+}
+
+#[test]
+#[should_panic(expected = "the arrays of an elementwise map must have the shape of its input")]
+fn test_elementwise_hvp_shape() {
+    use crate::autodiff::AutoDiff;
+    use crate::funcs::Identity;
+    use crate::traits::Sin;
+    use ndarray::arr1;
+
+    // the derivative of the cotangent must match the input as well
+    let f = AutoDiff::new(Identity::new()).sin().elementwise();
+    let x = arr1(&[-1.0, 0.5, 2.0]);
+    f.eval_hvp(&x, &x, &x, &arr1(&[1.0, 2.0]), &());
+}
+
+// sum of squares of an array, as an example of a custom function of many inputs on the tape
+#[cfg(test)]
+#[derive(FuncCompose, Debug, Clone, Copy)]
+struct SumSquares;
+
+#[cfg(test)]
+impl Diffable<()> for SumSquares {
+    type Input = ndarray::Array1<f64>;
+    type Output = ndarray::Array0<f64>;
+}
+
+#[cfg(test)]
+impl AutoDiffable<()> for SumSquares {
+    fn eval_grad(&self, x: &ndarray::Array1<f64>, _: &()) -> (ndarray::Array0<f64>, ndarray::Array1<f64>) {
+        (ndarray::arr0(x.iter().map(|x| x * x).sum()), x.mapv(|x| 2.0 * x))
+    }
+
+    fn eval_conj_grad(&self, x: &ndarray::Array1<f64>, _: &()) -> (ndarray::Array0<f64>, ndarray::Array1<f64>) {
+        (ndarray::arr0(x.iter().map(|x| x * x).sum()), x.mapv(|_| 0.0))
+    }
+}
+
+#[cfg(test)]
+impl HessianDiffable<()> for SumSquares {
+    fn eval_hessian(&self, x: &ndarray::Array1<f64>, _: &()) -> (ndarray::Array0<f64>, ndarray::Array1<f64>, ndarray::Array2<f64>) {
+        (ndarray::arr0(x.iter().map(|x| x * x).sum()), x.mapv(|x| 2.0 * x), ndarray::Array2::eye(x.len()) * 2.0)
+    }
+}
+
+#[cfg(test)]
+impl ForwardDiffable<()> for SumSquares {
+    fn eval_forward_grad(&self, x: &ndarray::Array1<f64>, dx: &ndarray::Array1<f64>, _: &()) -> (ndarray::Array0<f64>, ndarray::Array0<f64>) {
+        (ndarray::arr0(x.iter().map(|x| x * x).sum()), ndarray::arr0(2.0 * x.dot(dx)))
+    }
+
+    fn eval_forward_conj_grad(&self, x: &ndarray::Array1<f64>, _: &ndarray::Array1<f64>, _: &()) -> (ndarray::Array0<f64>, ndarray::Array0<f64>) {
+        (ndarray::arr0(x.iter().map(|x| x * x).sum()), ndarray::arr0(0.0))
+    }
+}
+
+#[cfg(test)]
+impl HvpDiffable<()> for SumSquares {
+    fn eval_hvp(&self, x: &ndarray::Array1<f64>, v: &ndarray::Array1<f64>, cotangent: &ndarray::Array0<f64>,
+                dcotangent: &ndarray::Array0<f64>, _: &()) -> (ndarray::Array0<f64>, ndarray::Array1<f64>, ndarray::Array1<f64>)
+    {
+        let (c, dc) = (cotangent[()], dcotangent[()]);
+        (ndarray::arr0(x.iter().map(|x| x * x).sum()), x.mapv(|x| 2.0 * c * x), x.mapv(|x| 2.0 * dc * x) + v.mapv(|v| 2.0 * c * v))
+    }
+}
+
+#[cfg(test)]
+impl ReverseDiffable<()> for SumSquares {
+    fn eval_reverse<'a>(&'a self, x: &'a ndarray::Array1<f64>, tape: &mut Tape<'a, ndarray::Array1<f64>, ndarray::Array0<f64>>,
+                    static_args: &'a ()) -> Var
+    {
+        tape.record(self, x, static_args)
+    }
+}
+
+#[test]
+fn test_reverse() {
+    use crate::autodiff::AutoDiff;
+    use crate::traits::{Exp, Sin};
+    use ndarray::{arr0, arr1, Array0, Array1};
+
+    // a scalar loss of many inputs, f(x) = sin(s(x)) + e^(-s(x)) * s(x) with s(x) = x.x
+    let s = AutoDiff::new(SumSquares);
+    let f = s.sin() + (-s).exp() * s;
+
+    let x = arr1(&[1.0, 0.5, -0.25, 0.75]);
+    let (f_x, df_dx): (Array0<f64>, Array1<f64>) = f.eval_grad(&x, &());
+
+    let xx: f64 = x.iter().map(|x| x * x).sum();
+    assert_eq!(f_x, arr0(xx.sin() + (-xx).exp() * xx));
+    let expected = x.mapv(|x| 2.0 * x * (xx.cos() + (-xx).exp() * (1.0 - xx)));
+    assert!((&df_dx - &expected).iter().all(|d| d.abs() < 1e-12));
+
+    let mut tape = Tape::new(&x);
+    let y = f.eval_reverse(&x, &mut tape, &());
+    assert_eq!(*tape.value(y), f_x);
+    let (dfr_dx, dfr_dconjx) = tape.backward(y);
+    assert!((&dfr_dx - &df_dx).iter().all(|d| d.abs() < 1e-12));
+    assert_eq!(dfr_dconjx, Array1::<f64>::zeros(4));
+
+    // a constant output has a zero gradient with the shape of the input
+    let c = tape.constant(arr0(3.0));
+    let zeros = Array1::<f64>::zeros(4);
+    assert_eq!(tape.backward(c), (zeros.clone(), zeros));
+
+    // a contraction is recorded as a leaf pulling the adjoints back through its vjp,
+    // f(x) = sin(h(x)) + h(x)^2 with h(x) = sin(x).e^x
+    let i = AutoDiff::new(crate::funcs::Identity::new());
+    let h = i.sin().elementwise().contract(&i.exp().elementwise(), (&[0], &[0]));
+    let f = h.sin() + h * h;
+
+    let hx: f64 = x.iter().map(|x| x.sin() * x.exp()).sum();
+    let df_dx = x.mapv(|x| (hx.cos() + 2.0 * hx) * (x.cos() + x.sin()) * x.exp());
+
+    let mut tape = Tape::new(&x);
+    let y = f.eval_reverse(&x, &mut tape, &());
+    assert!((tape.value(y)[()] - (hx.sin() + hx * hx)).abs() < 1e-12);
+    let (dfr_dx, dfr_dconjx) = tape.backward(y);
+    assert!((&dfr_dx - &df_dx).iter().all(|d| d.abs() < 1e-12));
+    assert_eq!(dfr_dconjx, Array1::<f64>::zeros(4));
+}
+
+#[test]
+fn test_vjp() {
+    use crate::autodiff::AutoDiff;
+    use crate::funcs::Identity;
+    use crate::traits::{Exp, Sin};
+    use ndarray::{Array2, Array3, Array4};
+
+    fn assert_close<D: Dimension>(left: &Array<f64, D>, right: &Array<f64, D>) {
+        assert_eq!(left.shape(), right.shape());
+        assert!(left.iter().zip(right.iter()).all(|(l, r)| (l - r).abs() < 1e-12), "{} != {}", left, right);
+    }
+
+    // the vjp is the adjoint of the forward gradient, so (c^T J)[k] = c . (J e_k)
+    fn adjoint<DC: Dimension>(x: &Array3<f64>, c: &Array<f64, DC>, jvp: impl Fn(&Array3<f64>) -> Array<f64, DC>) -> Array3<f64> {
+        Array3::from_shape_fn(x.raw_dim(), |k| {
+            let mut e = Array3::zeros(x.raw_dim());
+            e[k] = 1.0;
+            (c * &jvp(&e)).sum()
+        })
+    }
+
+    let i = AutoDiff::new(Identity::new());
+    let f = i.sin().elementwise();
+    let g = i.exp().elementwise();
+
+    let x = Array3::from_shape_fn((3, 2, 3), |(a, b, c)| 0.1 * (a as f64) - 0.2 * (b as f64) + 0.3 * (c as f64));
+
+    // the vjp of an elementwise function is the cotangent times the derivative
+    let c = x.mapv(|x| 1.5 - x);
+    let (f_x, vf): (Array3<f64>, Array3<f64>) = f.eval_vjp(&x, &c, &());
+    assert_close(&f_x, &x.mapv(|x| x.sin()));
+    assert_close(&vf, &(&c * &x.mapv(|x| x.cos())));
+
+    // contraction over axes in a different order in each operand, so the cotangents must be permuted back
+    let h = f.contract(&g, (&[2, 0], &[0, 2]));
+    let c = Array2::from_shape_fn((2, 2), |(a, b)| (a as f64) - 0.5 * (b as f64));
+    let (h_x, vh): (Array2<f64>, Array3<f64>) = h.eval_vjp(&x, &c, &());
+    assert_close(&h_x, &h.eval(&x, &()));
+    assert_close(&vh, &adjoint(&x, &c, |dx| h.forward_grad(&x, dx, &())));
+
+    // contractions with constants on either side
+    let b = Array3::from_shape_fn((3, 3, 5), |(a, b, c)| 0.25 * (a as f64) - 0.5 * (b as f64) + (c as f64));
+
+    let h = f.contract(&b, (&[0, 2], &[1, 0]));
+    let c = Array2::from_shape_fn((2, 5), |(a, b)| 1.0 - (a as f64) * (b as f64));
+    let (h_x, vh): (Array2<f64>, Array3<f64>) = h.eval_vjp(&x, &c, &());
+    assert_close(&h_x, &h.eval(&x, &()));
+    assert_close(&vh, &adjoint(&x, &c, |dx| h.forward_grad(&x, dx, &())));
+
+    let b = Array3::from_shape_fn((4, 3, 5), |(a, b, c)| 0.25 * (a as f64) - 0.5 * (b as f64) + (c as f64));
+    let h = b.contract(&g, (&[1], &[0]));
+    let c = Array4::from_shape_fn((4, 5, 2, 3), |(a, b, d, e)| (a as f64) + 0.5 * (b as f64) - (d as f64) * (e as f64));
+    let vh: Array3<f64> = h.vjp(&x, &c, &());
+    assert_close(&vh, &adjoint(&x, &c, |dx| h.forward_grad(&x, dx, &())));
+
+    // tensordot contracts the last axis of f with the first axis of g
+    let b = Array2::from_shape_fn((3, 2), |(a, b)| (a as f64) * 0.5 - (b as f64));
+    let h = f.tensordot(&b);
+    let c = Array3::from_shape_fn((3, 2, 2), |(a, b, d)| (a as f64) - (b as f64) + 2.0 * (d as f64));
+    let (h_x, vh): (Array3<f64>, Array3<f64>) = h.eval_vjp(&x, &c, &());
+    assert_close(&h_x, &h.eval_forward(&x, &()));
+    assert_close(&vh, &adjoint(&x, &c, |dx| h.forward_grad(&x, dx, &())));
+}
+
+#[test]
+fn test_hessian() {
+    use crate::autodiff::AutoDiff;
+    use crate::funcs::Identity;
+    use crate::traits::{Exp, Sin};
+    use ndarray::{arr0, arr1, Array0, Array1, Array2, Array4};
+
+    // f(x) = sin(s(x)) + e^(-s(x)) * s(x) with s(x) = x.x, so the Hessian of f of a rank 1 array is rank 2
+    let s = AutoDiff::new(SumSquares);
+    let f = s.sin() + (-s).exp() * s;
+
+    let x = arr1(&[1.0, 0.5, -0.25, 0.75]);
+    let (f_x, df_dx, d2f_dx2): (Array0<f64>, Array1<f64>, Array2<f64>) = f.eval_hessian(&x, &());
+
+    // with f = phi(s), d²f/dx² = phi''(s) * 4 x x^T + phi'(s) * 2 I
+    let xx: f64 = x.iter().map(|x| x * x).sum();
+    let dphi = xx.cos() + (-xx).exp() * (1.0 - xx);
+    let d2phi = -xx.sin() + (-xx).exp() * (xx - 2.0);
+    let expected = Array2::from_shape_fn((4, 4), |(i, j)| d2phi * 4.0 * x[i] * x[j] + if i == j { 2.0 * dphi } else { 0.0 });
+
+    assert_eq!(f_x, arr0(xx.sin() + (-xx).exp() * xx));
+    assert!((&df_dx - &f.grad(&x, &())).iter().all(|d| d.abs() < 1e-12));
+    assert!((&d2f_dx2 - &expected).iter().all(|d| d.abs() < 1e-12));
+
+    // the gradient as its own function has the Hessian as its gradient
+    let (g_x, dg_dx): (Array1<f64>, Array2<f64>) = f.grad_fn().eval_grad(&x, &());
+    assert_eq!(g_x, df_dx);
+    assert_eq!(dg_dx, d2f_dx2);
+
+    fn assert_close<D: Dimension>(left: &Array<f64, D>, right: &Array<f64, D>) {
+        assert_eq!(left.shape(), right.shape());
+        assert!(left.iter().zip(right.iter()).all(|(l, r)| (l - r).abs() < 1e-12), "{} != {}", left, right);
+    }
+
+    // the Hessian of an array valued function holds the second derivatives of each element of the output on the
+    // diagonal of its axes, here h(x) = sin(x) . w with d²h[k]/dx[i]dx[j] = -sin(x[i]) w[i, k] if i == j
+    let i = AutoDiff::new(Identity::new());
+    let w = Array2::from_shape_fn((4, 3), |(a, b)| 0.5 * (a as f64) - (b as f64));
+    let h = i.sin().elementwise().dot(&w);
+    let (h_x, dh_dx, d2h_dx2): (Array1<f64>, Array2<f64>, Array4<f64>) = h.eval_hessian(&x, &());
+    assert_close(&h_x, &x.mapv(|x| x.sin()).dot(&w));
+    assert_close(&dh_dx, &h.grad(&x, &()));
+    let expected = Array4::from_shape_fn((4, 3, 4, 3), |(i, k, j, l)| if i == j && k == l { -x[i].sin() * w[[i, k]] } else { 0.0 });
+    assert_close(&d2h_dx2, &expected);
+
+    // and with the constant on the left, w^T . sin(x)
+    let h = w.t().to_owned().dot(&i.sin().elementwise());
+    let d2h_dx2: Array4<f64> = h.hessian(&x, &());
+    assert_close(&d2h_dx2, &expected);
+
+    // g(x) = sin(x) . e^x contracts two functions, so its Hessian has the cross terms of both gradients, and composes
+    // with the Hessian of sin
+    let g = i.sin().elementwise().contract(&i.exp().elementwise(), (&[0], &[0]));
+    let f = g.sin();
+    let (f_x, df_dx, d2f_dx2): (Array0<f64>, Array1<f64>, Array2<f64>) = f.eval_hessian(&x, &());
+
+    let gx: f64 = x.iter().map(|x| x.sin() * x.exp()).sum();
+    let dg = x.mapv(|x| (x.cos() + x.sin()) * x.exp());
+    let expected = Array2::from_shape_fn((4, 4), |(i, j)| {
+        -gx.sin() * dg[i] * dg[j] + if i == j { gx.cos() * 2.0 * x[i].cos() * x[i].exp() } else { 0.0 }
+    });
+    assert_close(&f_x, &arr0(gx.sin()));
+    assert_close(&df_dx, &(&dg * gx.cos()));
+    assert_close(&d2f_dx2, &expected);
+    let v = arr1(&[0.5, -1.0, 2.0, 0.25]);
+    assert_close(&f.hessian(&x, &()).dot(&v), &f.hvp(&x, &v, &()));
+
+    // tensordot contracts the last axis of sin(x) with the first axis of e^x, as the contraction above
+    let t = i.sin().elementwise().tensordot(&i.exp().elementwise());
+    assert_close(&t.hessian(&x, &()), &g.hessian(&x, &()));
+
+    // contracting axes in a different order in each operand, g(x) = sum_ab sin(x[a, b]) e^x[b, a]
+    let y = Array2::from_shape_fn((2, 2), |(a, b)| 0.3 * (a as f64) - 0.7 * (b as f64) + 0.1);
+    let g = i.sin().elementwise().contract(&i.exp().elementwise(), (&[0, 1], &[1, 0]));
+    let (_, dg_dy, d2g_dy2): (Array0<f64>, Array2<f64>, Array4<f64>) = g.eval_hessian(&y, &());
+
+    // dg/dy[c, d] = cos(y[c, d]) e^y[d, c] + sin(y[d, c]) e^y[c, d]
+    let expected = Array2::from_shape_fn((2, 2), |(c, d)| y[[c, d]].cos() * y[[d, c]].exp() + y[[d, c]].sin() * y[[c, d]].exp());
+    assert_close(&dg_dy, &expected);
+    let expected = Array4::from_shape_fn((2, 2, 2, 2), |(c, d, e, f)| {
+        let (same, swapped) = ((e, f) == (c, d), (e, f) == (d, c));
+        let mut d2g = 0.0;
+        if same {
+            d2g += -y[[c, d]].sin() * y[[d, c]].exp() + y[[d, c]].sin() * y[[c, d]].exp();
+        }
+        if swapped {
+            d2g += y[[c, d]].cos() * y[[d, c]].exp() + y[[d, c]].cos() * y[[c, d]].exp();
+        }
+        d2g
+    });
+    assert_close(&d2g_dy2, &expected);
+}
+
+#[test]
+fn test_hvp() {
+    use crate::autodiff::AutoDiff;
+    use crate::funcs::Identity;
+    use crate::traits::{Exp, Sin};
+    use ndarray::{arr1, Array0, Array2};
+
+    fn assert_close<D: Dimension>(left: &Array<f64, D>, right: &Array<f64, D>) {
+        assert_eq!(left.shape(), right.shape());
+        assert!(left.iter().zip(right.iter()).all(|(l, r)| (l - r).abs() < 1e-12), "{} != {}", left, right);
+    }
+
+    // the Hessian-vector product of a function of a rank 1 array agrees with the rank 2 Hessian times v
+    let s = AutoDiff::new(SumSquares);
+    let f = s.sin() + (-s).exp() * s;
+
+    let x = arr1(&[1.0, 0.5, -0.25, 0.75]);
+    let v = arr1(&[0.5, -1.0, 2.0, 0.25]);
+    let d2f_dx2: Array2<f64> = f.hessian(&x, &());
+    assert_close(&f.hvp(&x, &v, &()), &d2f_dx2.dot(&v));
+    assert_close(&f.grad_fn().forward_grad(&x, &v, &()), &d2f_dx2.dot(&v));
+
+    // h(x) = sum(sin(x) * e^x) is a contraction of two elementwise maps over every axis, so its Hessian is
+    // diagonal with d²(sin(x) * e^x)/dx² = 2 cos(x) e^x
+    let i = AutoDiff::new(Identity::new());
+    let h = i.sin().elementwise().contract(&i.exp().elementwise(), (&[0, 1], &[0, 1]));
+
+    let x = Array2::from_shape_fn((2, 3), |(a, b)| 0.3 * (a as f64) - 0.2 * (b as f64));
+    let v = Array2::from_shape_fn((2, 3), |(a, b)| 1.0 - (a as f64) + 0.5 * (b as f64));
+    let hv: Array2<f64> = h.hvp(&x, &v, &());
+    assert_close(&hv, &(x.mapv(|x| 2.0 * x.cos() * x.exp()) * &v));
+
+    // with constants on either side, d²(sum(b * sin(x)))/dx² = -b sin(x), and products of contractions
+    let b = Array2::from_shape_fn((2, 3), |(a, b)| 0.5 * (a as f64) + (b as f64));
+    let k = i.sin().elementwise().contract(&b, (&[0, 1], &[0, 1]));
+    let kv: Array2<f64> = k.hvp(&x, &v, &());
+    assert_close(&kv, &(-&b * &x.mapv(|x| x.sin()) * &v));
+    let kl = b.contract(&i.sin().elementwise(), (&[0, 1], &[0, 1]));
+    let klv: Array2<f64> = kl.hvp(&x, &v, &());
+    assert_close(&klv, &kv);
+
+    // d²(h k) v = (dh dk^T + dk dh^T) v + h d²k v + k d²h v
+    let hk = h * k.clone();
+    let (h_x, k_x): (Array0<f64>, Array0<f64>) = (h.eval_forward(&x, &()), k.eval_forward(&x, &()));
+    let dh = x.mapv(|x| (x.cos() + x.sin()) * x.exp());
+    let dk = &b * &x.mapv(|x| x.cos());
+    let expected = &dh * (&dk * &v).sum() + &dk * (&dh * &v).sum() + &kv * h_x[()] + &hv * k_x[()];
+    let hkv: Array2<f64> = hk.hvp(&x, &v, &());
+    assert_close(&hkv, &expected);
+}
+
+#[test]
+fn test_symbolic() {
+    use crate::ad_ndarray::func_traits::{Dot, TensorContraction};
+    use crate::autodiff::AutoDiff;
+    use crate::funcs::Identity;
+    use crate::symbolic::Named;
+    use crate::traits::Sin;
+    use ndarray::{arr2, Array2, Ix2};
+
+    // constant arrays are written by value, or by name when wrapped in Named
+    let i: AutoDiff<(), Identity<(), Array2<f64>>> = AutoDiff::new(Identity::new());
+    let a = arr2(&[[1.0, 2.0], [3.0, 4.0]]);
+    let f = i.dot(&a);
+    assert_eq!(f.to_string(), "dot(x, [[1, 2], [3, 4]])");
+    assert_eq!(f.dot(&f).to_string(), "dot(dot(x, [[1, 2], [3, 4]]), dot(x, [[1, 2], [3, 4]]))");
+    let g = i.contract(&Named::new("A", a), (&[1], &[0]));
+    assert_eq!(g.to_string(), "contract(x, A, [1], [0])");
+    assert_eq!(g.to_latex(), "\\operatorname{contract}\\left(x, A, [1], [0]\\right)");
+
+    // an elementwise function is written as applied to x
+    let s: AutoDiff<(), Identity<(), f64>> = AutoDiff::new(Identity::new());
+    assert_eq!(s.sin().elementwise::<Ix2>().to_string(), "sin(x)");
+}
+
+#[test]
+fn test_to_dot() {
+    use crate::ad_ndarray::func_traits::TensorContraction;
+    use crate::autodiff::AutoDiff;
+    use crate::funcs::Identity;
+    use ndarray::{arr2, Array2};
+
+    // the axes of a contraction are part of its label, and constant arrays are boxes
+    let i: AutoDiff<(), Identity<(), Array2<f64>>> = AutoDiff::new(Identity::new());
+    let f = i.contract(&arr2(&[[1.0, 2.0], [3.0, 4.0]]), (&[1], &[0]));
+    let dot = f.to_dot();
+    assert!(dot.contains("n0 [label=\"x\"];"));
+    assert!(dot.contains("n1 [label=\"[[1, 2], [3, 4]]\", shape=box];"));
+    assert!(dot.contains("n2 [label=\"contract [1] [0]\"];"));
+    assert!(dot.contains("n0 -> n2 [label=\"lhs\"];"));
+    assert!(dot.contains("n1 -> n2 [label=\"rhs\"];"));
+}
+
+#[test]
+fn test_try_autodiff() {
+    use crate::ad_ndarray::func_traits::Dot;
+    use crate::autodiff::AutoDiff;
+    use crate::funcs::Identity;
+    use crate::tryautodiff::{AutoDiffError, TryAutoDiffable};
+    use ndarray::{arr1, arr2, Array1};
+
+    let i: AutoDiff<(), Identity<(), Array1<f64>>> = AutoDiff::new(Identity::new());
+    let x = arr1(&[1.0, 2.0]);
+
+    // arrays of matching shapes evaluate as usual
+    let f = i.dot(&arr2(&[[1.0, 2.0], [3.0, 4.0]]));
+    assert_eq!(f.try_eval_grad(&x, &()), Ok(f.eval_grad(&x, &())));
+
+    // mismatched shapes are an error rather than a panic, for dot products and elementwise operations
+    let g = i.dot(&arr2(&[[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]));
+    assert_eq!(g.try_eval(&x, &()), Err(AutoDiffError::shape_mismatch("ADConstantDot", vec![2], vec![3, 2])));
+    let h = i + i.dot(&arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]));
+    assert_eq!(h.try_eval_all(&x, &()), Err(AutoDiffError::shape_mismatch("ADAdd", vec![2], vec![3])));
+}
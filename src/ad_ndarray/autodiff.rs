@@ -136,3 +136,12 @@ where
         AutoDiff(ADConstantLeftTensorContraction((*self).clone(), _other.0.clone(), (*axes.0, *axes.1)), PhantomData)
     }
 }
+
+/// Elementwise application of a scalar AutoDiff to arrays
+impl<StaticArgs, T> AutoDiff<StaticArgs, T> {
+    /// Apply this scalar function to every element of an array with dimension D. The gradient
+    /// is the diagonal jacobian and the forward gradient is computed elementwise.
+    pub fn elementwise<D: Dimension>(self) -> AutoDiff<StaticArgs, ADElementwise<T, D>> {
+        AutoDiff(ADElementwise(self.0, PhantomData), PhantomData)
+    }
+}
@@ -1,18 +1,36 @@
 use crate::ad_ndarray::dimabssub::DimAbsSub;
 use crate::autotuple::AutoTuple;
-use crate::forward::ForwardMul;
+use crate::finitediff::{CentralDifference, Components, FiniteDiffInput};
+use crate::forward::{ForwardMul, Outer};
 use crate::gradienttype::GradientType;
+use crate::symbolic::{Expr, SymbolicConstant};
 use crate::traits::{
-    Abs, AbsSqr, Arg, Conjugate, GradientIdentity, InstOne, InstZero, PossiblyComplex, Signum,
+    Abs, AbsSqr, Acos, Acosh, Arg, Asin, Asinh, Atan, Atanh, Conjugate, Cos, Cosh, Elu, Erf, Erfc,
+    Exp, Gelu, GradientIdentity, GradientZero, Heaviside, InstOne, InstZero, LeakyRelu, Ln, Log,
+    Log10, Log2, NormCdf, NormPdf, PossiblyComplex, Relu, Sigmoid, Signum, Sin, Sinh, Softplus,
+    Sqrt, Tan, Tanh,
 };
+use crate::tryautodiff::CheckValue;
 use ndarray::{
-    ArrayBase, Axis, DataOwned, DimAdd, DimMax, Dimension, IxDyn, LinalgScalar, OwnedRepr,
+    ArrayBase, Axis, Data, DataOwned, DimAdd, DimMax, Dimension, IxDyn, LinalgScalar, OwnedRepr,
     RawDataClone,
 };
 use ndarray_einsum_beta;
-use num::traits::{One, Zero};
+use num::traits::{Float, One, Zero};
 use std::ops::{Add, Mul};
 
+#[cfg(test)]
+use crate::ad_ndarray::func_traits::Dot;
+#[cfg(test)]
+use crate::autodiff::AutoDiff;
+#[cfg(test)]
+use crate::autodiffable::{AutoDiffable, ForwardDiffable};
+#[cfg(test)]
+use crate::compose::AutoCompose;
+#[cfg(test)]
+use crate::finitediff::{check_grad, ADFiniteDiff};
+#[cfg(test)]
+use crate::funcs::Identity;
 #[cfg(test)]
 use ndarray::{arr1, arr2, Array0, Array1, Array2, Dim};
 
@@ -86,6 +104,29 @@ where
     }
 }
 
+impl<AI, DI, AO, DO, AG, DG> GradientZero<ArrayBase<OwnedRepr<AO>, DO>>
+    for ArrayBase<OwnedRepr<AI>, DI>
+where
+    DI: Dimension + DimAdd<DO, Output = DG>,
+    DO: Dimension,
+    DG: Dimension,
+    AI: GradientType<AO, GradientType = AG>,
+    AG: Clone + Zero,
+{
+    fn grad_zero(&self, output: &ArrayBase<OwnedRepr<AO>, DO>) -> ArrayBase<OwnedRepr<AG>, DG> {
+        // the gradient has the axes of the input followed by the axes of the output
+        let grad_shape = self
+            .shape()
+            .iter()
+            .chain(output.shape())
+            .copied()
+            .collect::<Vec<_>>();
+        ArrayBase::<OwnedRepr<AG>, IxDyn>::zeros(grad_shape)
+            .into_dimensionality::<DG>()
+            .unwrap()
+    }
+}
+
 // implement PossiblyComplex for ArrayBase<S, D>
 impl<A, S, D> PossiblyComplex for ArrayBase<S, D>
 where
@@ -98,6 +139,30 @@ where
     }
 }
 
+// implement CheckValue for ArrayBase<S, D>, whose shape is checked by TryAutoDiffable
+impl<A, S, D> CheckValue for ArrayBase<S, D>
+where
+    A: CheckValue,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn has_nan(&self) -> bool {
+        self.iter().any(|x| x.has_nan())
+    }
+
+    fn has_infinite(&self) -> bool {
+        self.iter().any(|x| x.has_infinite())
+    }
+
+    fn has_zero(&self) -> bool {
+        self.iter().any(|x| x.has_zero())
+    }
+
+    fn value_shape(&self) -> Option<Vec<usize>> {
+        Some(self.shape().to_vec())
+    }
+}
+
 // implement Conjugate for ArrayBase<OwnedRepr<_>, _>
 impl<A, D> Conjugate for ArrayBase<OwnedRepr<A>, D>
 where
@@ -158,6 +223,357 @@ where
     }
 }
 
+// implement Exp for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Exp for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Exp,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn exp(self) -> Self::Output {
+        self.mapv(|x| x.exp())
+    }
+}
+
+// implement Ln for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Ln for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Ln,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn ln(self) -> Self::Output {
+        self.mapv(|x| x.ln())
+    }
+}
+
+// implement Log for ArrayBase<OwnedRepr<_>, _> with a scalar base
+impl<A, D, B> Log<B> for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Log<B>,
+    B: Clone,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn log(self, base: B) -> Self::Output {
+        self.mapv(|x| x.log(base.clone()))
+    }
+}
+
+// implement Log2 for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Log2 for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Log2,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn log2(self) -> Self::Output {
+        self.mapv(|x| x.log2())
+    }
+}
+
+// implement Log10 for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Log10 for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Log10,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn log10(self) -> Self::Output {
+        self.mapv(|x| x.log10())
+    }
+}
+
+// implement Sqrt for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Sqrt for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Sqrt,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn sqrt(self) -> Self::Output {
+        self.mapv(|x| x.sqrt())
+    }
+}
+
+// implement Sin for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Sin for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Sin,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn sin(self) -> Self::Output {
+        self.mapv(|x| x.sin())
+    }
+}
+
+// implement Cos for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Cos for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Cos,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn cos(self) -> Self::Output {
+        self.mapv(|x| x.cos())
+    }
+}
+
+// implement Tan for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Tan for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Tan,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn tan(self) -> Self::Output {
+        self.mapv(|x| x.tan())
+    }
+}
+
+// implement Asin for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Asin for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Asin,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn asin(self) -> Self::Output {
+        self.mapv(|x| x.asin())
+    }
+}
+
+// implement Acos for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Acos for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Acos,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn acos(self) -> Self::Output {
+        self.mapv(|x| x.acos())
+    }
+}
+
+// implement Atan for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Atan for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Atan,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn atan(self) -> Self::Output {
+        self.mapv(|x| x.atan())
+    }
+}
+
+// implement Sinh for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Sinh for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Sinh,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn sinh(self) -> Self::Output {
+        self.mapv(|x| x.sinh())
+    }
+}
+
+// implement Cosh for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Cosh for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Cosh,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn cosh(self) -> Self::Output {
+        self.mapv(|x| x.cosh())
+    }
+}
+
+// implement Tanh for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Tanh for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Tanh,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn tanh(self) -> Self::Output {
+        self.mapv(|x| x.tanh())
+    }
+}
+
+// implement Asinh for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Asinh for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Asinh,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn asinh(self) -> Self::Output {
+        self.mapv(|x| x.asinh())
+    }
+}
+
+// implement Acosh for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Acosh for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Acosh,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn acosh(self) -> Self::Output {
+        self.mapv(|x| x.acosh())
+    }
+}
+
+// implement Atanh for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Atanh for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Atanh,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn atanh(self) -> Self::Output {
+        self.mapv(|x| x.atanh())
+    }
+}
+
+// implement Erf for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Erf for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Erf,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn erf(self) -> Self::Output {
+        self.mapv(|x| x.erf())
+    }
+}
+
+// implement Erfc for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Erfc for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Erfc,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn erfc(self) -> Self::Output {
+        self.mapv(|x| x.erfc())
+    }
+}
+
+// implement Heaviside for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Heaviside for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Heaviside,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn heaviside(self) -> Self::Output {
+        self.mapv(|x| x.heaviside())
+    }
+}
+
+// implement NormCdf for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> NormCdf for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + NormCdf,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn norm_cdf(self) -> Self::Output {
+        self.mapv(|x| x.norm_cdf())
+    }
+}
+
+// implement NormPdf for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> NormPdf for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + NormPdf,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn norm_pdf(self) -> Self::Output {
+        self.mapv(|x| x.norm_pdf())
+    }
+}
+
+// implement Relu for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Relu for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Relu,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn relu(self) -> Self::Output {
+        self.mapv(|x| x.relu())
+    }
+}
+
+// implement Sigmoid for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Sigmoid for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Sigmoid,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn sigmoid(self) -> Self::Output {
+        self.mapv(|x| x.sigmoid())
+    }
+}
+
+// implement Softplus for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Softplus for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Softplus,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn softplus(self) -> Self::Output {
+        self.mapv(|x| x.softplus())
+    }
+}
+
+// implement Gelu for ArrayBase<OwnedRepr<_>, _>
+impl<A, D> Gelu for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Gelu,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn gelu(self) -> Self::Output {
+        self.mapv(|x| x.gelu())
+    }
+}
+
+// implement LeakyRelu for ArrayBase<OwnedRepr<_>, _> with a scalar alpha
+impl<A, D, B> LeakyRelu<B> for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + LeakyRelu<B>,
+    B: Clone,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn leaky_relu(self, alpha: B) -> Self::Output {
+        self.mapv(|x| x.leaky_relu(alpha.clone()))
+    }
+}
+
+// implement Elu for ArrayBase<OwnedRepr<_>, _> with a scalar alpha
+impl<A, D, B> Elu<B> for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension,
+    A: Clone + Elu<B>,
+    B: Clone,
+{
+    type Output = ArrayBase<OwnedRepr<A::Output>, D>;
+    fn elu(self, alpha: B) -> Self::Output {
+        self.mapv(|x| x.elu(alpha.clone()))
+    }
+}
+
 // implement From<ArrayBase<S, D>> for AutoTuple<(ArrayBase<S, D>,)>
 impl<A, S, D> From<ArrayBase<S, D>> for AutoTuple<(ArrayBase<S, D>,)>
 where
@@ -237,12 +653,31 @@ where
 {
     type ResultGrad = ArrayBase<OwnedRepr<AS>, DR>;
     fn forward_mul(&self, other: &ArrayBase<OwnedRepr<AS>, DG>) -> Self::ResultGrad {
+        <Self as ForwardMul<ArrayBase<OwnedRepr<AI>, DI>, _>>::try_forward_mul(self, other)
+            .unwrap_or_else(|| {
+                panic!(
+                    "forward_mul of gradients with mismatched shapes {:?} and {:?}",
+                    self.shape(),
+                    other.shape()
+                )
+            })
+    }
+
+    fn try_forward_mul(&self, other: &ArrayBase<OwnedRepr<AS>, DG>) -> Option<Self::ResultGrad> {
         // better implementation using tensordot
         // df/dx * dg/dx requires the summation over the first DI::NDIM dimensions of df/dx and the last DG::NDIM dimensions of dg/dx
         // this is because the array df/dx[i,j,k,..., a, b, c, ...] is df[a, b, c, ...] / dx[i, j, k, ...]
 
         let sum_idxs: usize = DI::NDIM.unwrap().try_into().unwrap();
 
+        // the summed axes must exist, and have the same lengths, in both arrays
+        if sum_idxs > self.ndim()
+            || sum_idxs > other.ndim()
+            || self.shape()[..sum_idxs] != other.shape()[other.ndim() - sum_idxs..]
+        {
+            return None;
+        }
+
         let lhs = (0usize..sum_idxs).map(|x| Axis(x)).collect::<Vec<_>>();
         let rhs = (other.ndim() - sum_idxs..other.ndim())
             .map(|x| Axis(x))
@@ -251,10 +686,8 @@ where
         let res_dyn: ArrayBase<OwnedRepr<AS>, IxDyn> =
             ndarray_einsum_beta::tensordot(self, other, lhs.as_slice(), rhs.as_slice());
 
-        // convert to static dimension
-        let res: ArrayBase<OwnedRepr<AS>, DR> = res_dyn.into_dimensionality::<DR>().unwrap();
-
-        res
+        // convert to static dimension, which fails if the result has the wrong number of axes
+        res_dyn.into_dimensionality::<DR>().ok()
     }
 }
 
@@ -274,3 +707,254 @@ fn test_forward_mul() {
     >>::forward_mul(&b, &a);
     assert_eq!(res, c1);
 }
+
+#[test]
+fn test_try_forward_mul() {
+    let a = arr1(&[1.0, 2.0]);
+    let b = arr2(&[[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+
+    // the first axis of b has length 3 but a has length 2, so they cannot be summed over
+    let res = <ArrayBase<OwnedRepr<f64>, Dim<[usize; 2]>> as ForwardMul<
+        ArrayBase<OwnedRepr<f64>, Dim<[usize; 1]>>,
+        ArrayBase<OwnedRepr<f64>, Dim<[usize; 1]>>,
+    >>::try_forward_mul(&b, &a);
+    assert_eq!(res, None);
+}
+
+// outer product of two arrays, used for the second order terms of the Hessian:
+//
+// (a ⊗ b)[i, j, ..., k, l, ...] = a[i, j, ...] * b[k, l, ...]
+//
+// for a function with an Array0 output, the gradient has the shape of the input, so the Hessian
+// has shape (x.shape, x.shape)
+impl<A, D, E, F> Outer<ArrayBase<OwnedRepr<A>, E>> for ArrayBase<OwnedRepr<A>, D>
+where
+    D: Dimension + DimAdd<E, Output = F>,
+    E: Dimension,
+    F: Dimension,
+    A: LinalgScalar,
+{
+    type Output = ArrayBase<OwnedRepr<A>, F>;
+    fn outer(&self, other: &ArrayBase<OwnedRepr<A>, E>) -> Self::Output {
+        let res_dyn: ArrayBase<OwnedRepr<A>, IxDyn> =
+            ndarray_einsum_beta::tensordot(self, other, &[], &[]);
+
+        // convert to static dimension
+        res_dyn.into_dimensionality::<F>().unwrap()
+    }
+}
+
+#[test]
+fn test_outer() {
+    let a = arr1(&[1.0, 2.0]);
+    let b = arr1(&[3.0, 4.0, 5.0]);
+
+    let res: Array2<f64> = a.outer(&b);
+    assert_eq!(res, arr2(&[[3.0, 4.0, 5.0], [6.0, 8.0, 10.0]]));
+}
+
+// finite differences wrt each element of the input, whose derivatives of the output fill the
+// gradient, with shape (input.shape, output.shape) as in the layout of forward_mul:
+//
+// grad[i, j, ..., k, l, ...] = d output[k, l, ...] / d input[i, j, ...]
+impl<R, A, DI, AO, DO, AG, DG> FiniteDiffInput<ArrayBase<OwnedRepr<AO>, DO>>
+    for ArrayBase<OwnedRepr<A>, DI>
+where
+    R: Float,
+    DI: Dimension + DimAdd<DO, Output = DG>,
+    DO: Dimension,
+    DG: Dimension,
+    A: Clone
+        + InstZero
+        + FiniteDiffInput<ArrayBase<OwnedRepr<AO>, DO>, Real = R>
+        + GradientType<ArrayBase<OwnedRepr<AO>, DO>, GradientType = ArrayBase<OwnedRepr<AG>, DO>>
+        + GradientType<AO, GradientType = AG>,
+    AG: Clone + Zero,
+{
+    type Real = R;
+
+    fn finite_diff_grad(
+        &self,
+        f: impl Fn(&Self) -> ArrayBase<OwnedRepr<AO>, DO>,
+        scheme: &CentralDifference<R>,
+    ) -> (ArrayBase<OwnedRepr<AG>, DG>, ArrayBase<OwnedRepr<AG>, DG>) {
+        let mut grad_shape: Option<Vec<usize>> = None;
+        let mut columns = Vec::with_capacity(self.len());
+
+        for (idx, a) in self.view().into_dyn().indexed_iter() {
+            let (g, c) = a.finite_diff_grad(
+                |y| {
+                    let mut x = self.clone().into_dyn();
+                    x[idx.clone()] = y.clone();
+                    f(&x.into_dimensionality::<DI>().unwrap())
+                },
+                scheme,
+            );
+            grad_shape
+                .get_or_insert_with(|| self.shape().iter().chain(g.shape()).copied().collect());
+            columns.push((idx, g, c));
+        }
+
+        // an empty input has an empty gradient, whose output shape is that of f(x)
+        let grad_shape = grad_shape.unwrap_or_else(|| {
+            self.shape()
+                .iter()
+                .chain(f(self).shape())
+                .copied()
+                .collect()
+        });
+        let mut grad = ArrayBase::<OwnedRepr<AG>, IxDyn>::zeros(grad_shape.clone());
+        let mut conj_grad = ArrayBase::<OwnedRepr<AG>, IxDyn>::zeros(grad_shape);
+        for (idx, g, c) in columns {
+            for ((out_idx, g), c) in g.into_dyn().indexed_iter().zip(c.iter()) {
+                let full_idx: Vec<usize> =
+                    idx.slice().iter().chain(out_idx.slice()).copied().collect();
+                grad[full_idx.as_slice()] = g.clone();
+                conj_grad[full_idx.as_slice()] = c.clone();
+            }
+        }
+
+        // convert to static dimension
+        (
+            grad.into_dimensionality::<DG>().unwrap(),
+            conj_grad.into_dimensionality::<DG>().unwrap(),
+        )
+    }
+
+    fn basis(&self) -> Vec<Self> {
+        let zero = self.mapv(|a| a.zero()).into_dyn();
+        let mut basis = Vec::new();
+
+        for (idx, a) in self.view().into_dyn().indexed_iter() {
+            for b in a.basis() {
+                let mut x = zero.clone();
+                x[idx.clone()] = b;
+                basis.push(x.into_dimensionality::<DI>().unwrap());
+            }
+        }
+        basis
+    }
+}
+
+impl<A, S, D> Components for ArrayBase<S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+    A: Components,
+{
+    fn components(&self) -> Vec<num::complex::Complex<f64>> {
+        self.iter().flat_map(|a| a.components()).collect()
+    }
+}
+
+// arrays are written by value on a single line, e.g. [[1, 2], [3, 4]]
+impl<A, S, D> SymbolicConstant for ArrayBase<S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+    A: std::fmt::Display,
+{
+    fn symbolic_constant(&self) -> Expr {
+        Expr::Const(self.to_string().replace('\n', ""))
+    }
+}
+
+#[test]
+fn test_finite_diff_array() {
+    // f(x) = x * sum(x), so df_i/dx_j = delta_ij sum(x) + x_i
+    let x = arr1(&[1.0, -2.0, 0.5]);
+    let f = AutoDiff::new(ADFiniteDiff::new(|x: &Array1<f64>, _: &()| x * x.sum()));
+    let df_dx: Array2<f64> = f.grad(&x, &());
+    // df/dx[j, i] = df_i/dx_j, input axes first
+    let expected = Array2::from_diag(&arr1(&[-0.5, -0.5, -0.5])) + x.clone().insert_axis(Axis(0));
+    assert!((df_dx - expected).iter().all(|d| d.abs() < 1e-8));
+
+    // with an Array0 output, the gradient has the shape of the input
+    let g = AutoDiff::new(
+        ADFiniteDiff::new(|x: &Array1<f64>, _: &()| x.mapv(|x| x * x).sum_axis(Axis(0)))
+            .with_richardson(),
+    );
+    let dg_dx: Array1<f64> = g.grad(&x, &());
+    assert!((dg_dx - &x * 2.0).iter().all(|d| d.abs() < 1e-8));
+
+    // a non-square gradient composes with the gradient of the outer function:
+    // h(x) = w . u(x), u(x) = (x0 x1, x1 + x2), so dh/dx_j = w_i du_i/dx_j
+    let u = AutoDiff::new(ADFiniteDiff::new(|x: &Array1<f64>, _: &()| {
+        arr1(&[x[0] * x[1], x[1] + x[2]])
+    }));
+    let du_dx: Array2<f64> = u.grad(&x, &());
+    assert_eq!(du_dx.shape(), &[3, 2]);
+    let w = arr1(&[2.0, -1.0]);
+    let h = AutoDiff::new(ADFiniteDiff::new(move |u: &Array1<f64>, _: &()| {
+        Array0::from_elem((), w.dot(u))
+    }))
+    .compose(u);
+    let dh_dx: Array1<f64> = h.grad(&x, &());
+    let expected = arr1(&[2.0 * x[1], 2.0 * x[0] - 1.0, -1.0]);
+    assert!((dh_dx - expected).iter().all(|d| d.abs() < 1e-8));
+
+    // check_grad compares every element of the (3, 3) gradient
+    let i = AutoDiff::new(Identity::new());
+    let check = check_grad(&i.sin(), &x, &(), 1e-6);
+    assert!(check.is_ok(), "{}", check);
+    let check = check_grad(&i.sin().exp(), &x, &(), 1e-6);
+    assert!(check.is_ok(), "{}", check);
+
+    // and the (3, 2) gradient of x . A, whose layout matters as A is neither square nor symmetric
+    let i: AutoDiff<(), Identity<(), Array1<f64>>> = AutoDiff::new(Identity::new());
+    let check = check_grad(
+        &i.dot(&arr2(&[[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]])),
+        &x,
+        &(),
+        1e-6,
+    );
+    assert!(check.is_ok(), "{}", check);
+    let check = check_grad(
+        &i.dot(&arr2(&[[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]])).sin(),
+        &x,
+        &(),
+        1e-6,
+    );
+    assert!(check.is_ok(), "{}", check);
+}
+
+#[test]
+fn test_activations() {
+    // activations act elementwise, so the gradient of act(x) is diagonal
+    let x = arr1(&[-1.5, 0.0, 2.0]);
+    let dx = arr1(&[1.0, 2.0, 3.0]);
+    let i = AutoDiff::new(Identity::new());
+
+    let relu = i.relu();
+    let (r_x, dr_x): (Array1<f64>, Array1<f64>) = relu.eval_forward_grad(&x, &dx, &());
+    assert_eq!(r_x, arr1(&[0.0, 0.0, 2.0]));
+    assert_eq!(dr_x, arr1(&[0.0, 0.0, 3.0]));
+    let dr_dx: Array2<f64> = relu.grad(&x, &());
+    assert_eq!(dr_dx, Array2::from_diag(&arr1(&[0.0, 0.0, 1.0])));
+
+    let leaky = i.leaky_relu(0.1);
+    let (l_x, dl_x): (Array1<f64>, Array1<f64>) = leaky.eval_forward_grad(&x, &dx, &());
+    assert_eq!(l_x, arr1(&[-0.15000000000000002, 0.0, 2.0]));
+    assert_eq!(dl_x, arr1(&[0.1, 0.2, 3.0]));
+
+    let sigmoid = i.sigmoid();
+    let s_x: Array1<f64> = sigmoid.eval(&x, &());
+    let ds_dx: Array2<f64> = sigmoid.grad(&x, &());
+    assert_eq!(s_x, x.clone().mapv(|x| x.sigmoid()));
+    assert_eq!(ds_dx, Array2::from_diag(&x.mapv(|x| x.sigmoid() * (-x).sigmoid())));
+
+    let softplus = i.softplus();
+    let dsp_x: Array1<f64> = softplus.forward_grad(&x, &dx, &());
+    assert_eq!(dsp_x, s_x * &dx);
+
+    let gelu = i.gelu();
+    let (g_x, dg_x): (Array1<f64>, Array1<f64>) = gelu.eval_forward_grad(&x, &dx, &());
+    assert_eq!(g_x, x.clone().mapv(|x| x * x.norm_cdf()));
+    assert_eq!(dg_x, x.mapv(|x| x.norm_cdf() + x * x.norm_pdf()) * &dx);
+
+    let elu = i.elu(1.0);
+    let (e_x, de_x): (Array1<f64>, Array1<f64>) = elu.eval_forward_grad(&x, &dx, &());
+    assert_eq!(e_x, arr1(&[(-1.5_f64).exp_m1(), 0.0, 2.0]));
+    // alpha * e^x is computed as elu(x) + alpha
+    assert_eq!(de_x, arr1(&[(-1.5_f64).exp_m1() + 1.0, 2.0, 3.0]));
+}
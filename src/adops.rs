@@ -1,9 +1,25 @@
-use crate::autodiffable::{AutoDiffable, ForwardDiffable};
+use crate::autodiffable::{
+    AutoDiffable, ForwardDiffable, HessianDiffable, HvpDiffable, VjpDiffable,
+};
 use crate::diffable::Diffable;
-use crate::forward::ForwardMul;
+use crate::forward::{ForwardMul, Outer};
+use crate::funcs::Identity;
 use crate::gradienttype::GradientType;
-use crate::traits::{Abs, AbsSqr, Conjugate, InstOne, InstZero, PossiblyComplex, Signum}; //, Arg};
-use num::traits::Pow;
+use crate::graphviz::{DotAnnotate, DotGraph, ToDot};
+use crate::reverse::{ReverseDiffable, Tape, Var};
+use crate::simplify::{DynExpr, DynExprScalar, ToDynExpr};
+use crate::symbolic::{Expr, Symbolic, SymbolicConstant};
+use crate::taylor::{Taylor, TaylorCoefficient, TaylorDiffable};
+use crate::traits::{
+    Abs, AbsSqr, Acos, Acosh, Asin, Asinh, Atan, Atan2, Atanh, Conjugate, Cos, Cosh, Digamma, Elu,
+    Erf, Erfc, Exp, Gamma, Gelu, Heaviside, Hypot, InstOne, InstZero, LeakyRelu, Lgamma, Ln, Log,
+    Log10, Log2, NormCdf, NormPdf, PossiblyComplex, Relu, Sigmoid, Signum, Sin, Sinh, Softplus,
+    Sqrt, Tan, Tanh, Trigamma,
+}; //, Arg};
+use crate::tryautodiff::{
+    check_all, check_shapes, check_value, ADEvaluated, AutoDiffError, CheckValue, TryAutoDiffable,
+};
+use num::traits::{FloatConst, Pow};
 use std::marker::PhantomData;
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
@@ -20,6 +36,34 @@ impl<A: Diffable<StaticArgs>, NewInput, NewOutput, StaticArgs> Diffable<StaticAr
     type Output = NewOutput;
 }
 
+impl<A, NewInput, NewOutput> Symbolic for ADCoerce<A, NewInput, NewOutput>
+where
+    A: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        self.0.symbolic(x)
+    }
+}
+
+impl<StaticArgs, A, NewInput, NewOutput> ToDot<StaticArgs> for ADCoerce<A, NewInput, NewOutput>
+where
+    A: ToDot<StaticArgs>,
+    Self: DotAnnotate<StaticArgs, Input = NewInput>,
+    // ensure the input of A can be annotated
+    NewInput: Clone,
+    A::Input: From<NewInput>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&NewInput, &StaticArgs)>) -> usize {
+        let inner_at: Option<(A::Input, &StaticArgs)> =
+            at.map(|(x, static_args)| (x.clone().into(), static_args));
+        let f = self.0.dot_node(
+            graph,
+            inner_at.as_ref().map(|(x, static_args)| (x, *static_args)),
+        );
+        graph.op("coerce", self.annotate_at(at), &[(f, None)])
+    }
+}
+
 // coerce A's input and output to match NewInput and NewOutput
 impl<StaticArgs, Input, Output, Grad, NewInput, NewOutput, NewGradient, A> AutoDiffable<StaticArgs>
     for ADCoerce<A, NewInput, NewOutput>
@@ -74,6 +118,55 @@ where
     ) -> NewGradient {
         self.0.conj_grad(&x.clone().into(), static_args).into()
     }
+
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        NewGradient,
+        NewGradient,
+    ) {
+        let (f, df, dcf) = self.0.eval_all(&x.clone().into(), static_args);
+        (f.into(), df.into(), dcf.into())
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, NewInput, NewOutput, NewGradient, A>
+    TryAutoDiffable<StaticArgs> for ADCoerce<A, NewInput, NewOutput>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    NewInput: Clone + GradientType<NewOutput, GradientType = NewGradient>,
+    Input: From<NewInput>,
+    NewOutput: From<Output>,
+    NewGradient: From<Grad>,
+    // ensure the results of A, and their coerced versions, can be checked
+    Output: CheckValue,
+    Grad: CheckValue,
+    NewOutput: CheckValue,
+    NewGradient: CheckValue,
+{
+    fn try_eval(&self, x: &NewInput, static_args: &StaticArgs) -> Result<NewOutput, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(&x.clone().into(), static_args)
+            .map_err(|e| e.within("ADCoerce", 0))?;
+        check_value("ADCoerce", f.into())
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &NewInput,
+        static_args: &StaticArgs,
+    ) -> Result<(NewOutput, NewGradient, NewGradient), AutoDiffError> {
+        let (f, df, dfdconjz) = self
+            .0
+            .try_eval_all(&x.clone().into(), static_args)
+            .map_err(|e| e.within("ADCoerce", 0))?;
+        check_all("ADCoerce", (f.into(), df.into(), dfdconjz.into()))
+    }
 }
 
 // impl ForwardDiffable for ADCoerce<A, NewInput, NewOutput>
@@ -146,6 +239,75 @@ where
     }
 }
 
+impl<StaticArgs, NewInput, NewOutput, A> ReverseDiffable<StaticArgs>
+    for ADCoerce<A, NewInput, NewOutput>
+where
+    Self: VjpDiffable<StaticArgs, Input = NewInput, Output = NewOutput>,
+    // ensure the gradient has the type of the input, so the adjoints are pulled back by the vjp
+    NewInput:
+        GradientType<NewOutput, GradientType = NewInput> + InstZero + Conjugate<Output = NewInput>,
+    NewOutput: InstZero + Conjugate<Output = NewOutput>,
+{
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, NewInput, NewOutput>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        // the tape of A has different types, so the coerced function is recorded as a single leaf,
+        // which pulls the adjoints back through the vjp of A
+        tape.record_vjp(self, x, static_args)
+    }
+}
+
+impl<StaticArgs, Input, Output, NewInput, NewOutput, A> VjpDiffable<StaticArgs>
+    for ADCoerce<A, NewInput, NewOutput>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    NewInput: Clone + From<Input>,
+    NewOutput: Clone + From<Output>,
+    Input: From<NewInput>,
+    Output: From<NewOutput>,
+{
+    fn eval_primal(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval_primal(&x.clone().into(), static_args).into()
+    }
+
+    fn eval_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, vjp) = self
+            .0
+            .eval_vjp(&x.clone().into(), &cotangent.clone().into(), static_args);
+        (f.into(), vjp.into())
+    }
+
+    fn eval_conj_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, vjp) =
+            self.0
+                .eval_conj_vjp(&x.clone().into(), &cotangent.clone().into(), static_args);
+        (f.into(), vjp.into())
+    }
+}
+
 #[derive(FuncCompose, Debug, Clone, Copy)]
 pub struct ADAppendStaticArgs<A, NewStaticArgs>(pub A, pub PhantomData<NewStaticArgs>);
 
@@ -156,6 +318,38 @@ impl<A: Diffable<StaticArgs>, StaticArgs, NewStaticArgs> Diffable<(StaticArgs, N
     type Output = A::Output;
 }
 
+impl<A, NewStaticArgs> Symbolic for ADAppendStaticArgs<A, NewStaticArgs>
+where
+    A: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        self.0.symbolic(x)
+    }
+}
+
+impl<StaticArgs, NewStaticArgs, A> ToDot<(StaticArgs, NewStaticArgs)>
+    for ADAppendStaticArgs<A, NewStaticArgs>
+where
+    A: ToDot<StaticArgs>,
+    Self: DotAnnotate<(StaticArgs, NewStaticArgs), Input = A::Input>,
+{
+    fn dot_node(
+        &self,
+        graph: &mut DotGraph,
+        at: Option<(&A::Input, &(StaticArgs, NewStaticArgs))>,
+    ) -> usize {
+        // A only sees its part of the static args
+        let f = self
+            .0
+            .dot_node(graph, at.map(|(x, static_args)| (x, &static_args.0)));
+        graph.op(
+            "append_static_args",
+            self.annotate_at(at),
+            &[(f, Some("static_args.0"))],
+        )
+    }
+}
+
 impl<StaticArgs, NewStaticArgs, Input, Output, Gradient, A>
     AutoDiffable<(StaticArgs, NewStaticArgs)> for ADAppendStaticArgs<A, NewStaticArgs>
 where
@@ -189,6 +383,45 @@ where
     fn conj_grad(&self, x: &Self::Input, static_args: &(StaticArgs, NewStaticArgs)) -> Gradient {
         self.0.conj_grad(x, &static_args.0)
     }
+
+    fn eval_all(
+        &self,
+        x: &Self::Input,
+        static_args: &(StaticArgs, NewStaticArgs),
+    ) -> (Self::Output, Gradient, Gradient) {
+        self.0.eval_all(x, &static_args.0)
+    }
+}
+
+// A only sees its part of the static args
+impl<StaticArgs, NewStaticArgs, Input, Output, Gradient, A>
+    TryAutoDiffable<(StaticArgs, NewStaticArgs)> for ADAppendStaticArgs<A, NewStaticArgs>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Gradient>,
+    // ensure the results of A can be checked
+    Output: CheckValue,
+    Gradient: CheckValue,
+{
+    fn try_eval(
+        &self,
+        x: &Input,
+        static_args: &(StaticArgs, NewStaticArgs),
+    ) -> Result<Output, AutoDiffError> {
+        self.0
+            .try_eval(x, &static_args.0)
+            .map_err(|e| e.within("ADAppendStaticArgs", 0))
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &(StaticArgs, NewStaticArgs),
+    ) -> Result<(Output, Gradient, Gradient), AutoDiffError> {
+        self.0
+            .try_eval_all(x, &static_args.0)
+            .map_err(|e| e.within("ADAppendStaticArgs", 0))
+    }
 }
 
 impl<StaticArgs, NewStaticArgs, Input, Output, A> ForwardDiffable<(StaticArgs, NewStaticArgs)>
@@ -238,6 +471,101 @@ where
     }
 }
 
+impl<StaticArgs, NewStaticArgs, Input, Output, Gradient, A>
+    ReverseDiffable<(StaticArgs, NewStaticArgs)> for ADAppendStaticArgs<A, NewStaticArgs>
+where
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Gradient>,
+{
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a Self::Input,
+        tape: &mut Tape<'a, Gradient, Output>,
+        static_args: &'a (StaticArgs, NewStaticArgs),
+    ) -> Var {
+        self.0.eval_reverse(x, tape, &static_args.0)
+    }
+}
+
+impl<StaticArgs, NewStaticArgs, Input, Output, A> VjpDiffable<(StaticArgs, NewStaticArgs)>
+    for ADAppendStaticArgs<A, NewStaticArgs>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn eval_primal(
+        &self,
+        x: &Self::Input,
+        static_args: &(StaticArgs, NewStaticArgs),
+    ) -> Self::Output {
+        self.0.eval_primal(x, &static_args.0)
+    }
+
+    fn eval_vjp(
+        &self,
+        x: &Self::Input,
+        cotangent: &Self::Output,
+        static_args: &(StaticArgs, NewStaticArgs),
+    ) -> (Self::Output, Self::Input) {
+        self.0.eval_vjp(x, cotangent, &static_args.0)
+    }
+
+    fn eval_conj_vjp(
+        &self,
+        x: &Self::Input,
+        cotangent: &Self::Output,
+        static_args: &(StaticArgs, NewStaticArgs),
+    ) -> (Self::Output, Self::Input) {
+        self.0.eval_conj_vjp(x, cotangent, &static_args.0)
+    }
+}
+
+impl<StaticArgs, NewStaticArgs, Input, Output, Grad, Hess, A>
+    HessianDiffable<(StaticArgs, NewStaticArgs)> for ADAppendStaticArgs<A, NewStaticArgs>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    Grad: Outer<Grad, Output = Hess>,
+{
+    fn eval_hessian(
+        &self,
+        x: &Self::Input,
+        static_args: &(StaticArgs, NewStaticArgs),
+    ) -> (Self::Output, Grad, Hess) {
+        self.0.eval_hessian(x, &static_args.0)
+    }
+}
+
+impl<StaticArgs, NewStaticArgs, A> HvpDiffable<(StaticArgs, NewStaticArgs)>
+    for ADAppendStaticArgs<A, NewStaticArgs>
+where
+    A: HvpDiffable<StaticArgs>,
+{
+    fn eval_hvp(
+        &self,
+        x: &Self::Input,
+        v: &Self::Input,
+        cotangent: &Self::Output,
+        dcotangent: &Self::Output,
+        static_args: &(StaticArgs, NewStaticArgs),
+    ) -> (Self::Output, Self::Input, Self::Input) {
+        self.0.eval_hvp(x, v, cotangent, dcotangent, &static_args.0)
+    }
+}
+
+impl<StaticArgs, NewStaticArgs, A, const K: usize> TaylorDiffable<(StaticArgs, NewStaticArgs), K>
+    for ADAppendStaticArgs<A, NewStaticArgs>
+where
+    A: TaylorDiffable<StaticArgs, K>,
+{
+    fn eval_taylor_series(
+        &self,
+        x: &Taylor<Self::Input, K>,
+        static_args: &(StaticArgs, NewStaticArgs),
+    ) -> Taylor<Self::Output, K> {
+        self.0.eval_taylor_series(x, &static_args.0)
+    }
+}
+
 #[derive(FuncCompose, Debug, Clone, Copy)]
 pub struct ADPrependStaticArgs<A, NewStaticArgs>(pub A, pub PhantomData<NewStaticArgs>);
 
@@ -248,6 +576,38 @@ impl<A: Diffable<StaticArgs>, StaticArgs, NewStaticArgs> Diffable<(NewStaticArgs
     type Output = A::Output;
 }
 
+impl<A, NewStaticArgs> Symbolic for ADPrependStaticArgs<A, NewStaticArgs>
+where
+    A: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        self.0.symbolic(x)
+    }
+}
+
+impl<StaticArgs, NewStaticArgs, A> ToDot<(NewStaticArgs, StaticArgs)>
+    for ADPrependStaticArgs<A, NewStaticArgs>
+where
+    A: ToDot<StaticArgs>,
+    Self: DotAnnotate<(NewStaticArgs, StaticArgs), Input = A::Input>,
+{
+    fn dot_node(
+        &self,
+        graph: &mut DotGraph,
+        at: Option<(&A::Input, &(NewStaticArgs, StaticArgs))>,
+    ) -> usize {
+        // A only sees its part of the static args
+        let f = self
+            .0
+            .dot_node(graph, at.map(|(x, static_args)| (x, &static_args.1)));
+        graph.op(
+            "prepend_static_args",
+            self.annotate_at(at),
+            &[(f, Some("static_args.1"))],
+        )
+    }
+}
+
 impl<StaticArgs, NewStaticArgs, Input, Output, Gradient, A>
     AutoDiffable<(NewStaticArgs, StaticArgs)> for ADPrependStaticArgs<A, NewStaticArgs>
 where
@@ -281,6 +641,45 @@ where
     fn conj_grad(&self, x: &Self::Input, static_args: &(NewStaticArgs, StaticArgs)) -> Gradient {
         self.0.conj_grad(x, &static_args.1)
     }
+
+    fn eval_all(
+        &self,
+        x: &Self::Input,
+        static_args: &(NewStaticArgs, StaticArgs),
+    ) -> (Self::Output, Gradient, Gradient) {
+        self.0.eval_all(x, &static_args.1)
+    }
+}
+
+// A only sees its part of the static args
+impl<StaticArgs, NewStaticArgs, Input, Output, Gradient, A>
+    TryAutoDiffable<(NewStaticArgs, StaticArgs)> for ADPrependStaticArgs<A, NewStaticArgs>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Gradient>,
+    // ensure the results of A can be checked
+    Output: CheckValue,
+    Gradient: CheckValue,
+{
+    fn try_eval(
+        &self,
+        x: &Input,
+        static_args: &(NewStaticArgs, StaticArgs),
+    ) -> Result<Output, AutoDiffError> {
+        self.0
+            .try_eval(x, &static_args.1)
+            .map_err(|e| e.within("ADPrependStaticArgs", 0))
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &(NewStaticArgs, StaticArgs),
+    ) -> Result<(Output, Gradient, Gradient), AutoDiffError> {
+        self.0
+            .try_eval_all(x, &static_args.1)
+            .map_err(|e| e.within("ADPrependStaticArgs", 0))
+    }
 }
 
 impl<StaticArgs, NewStaticArgs, Input, Output, A> ForwardDiffable<(NewStaticArgs, StaticArgs)>
@@ -330,73 +729,271 @@ where
     }
 }
 
-#[derive(FuncCompose, Debug, Clone, Copy)]
-pub struct ADAdd<A, B>(pub A, pub B);
-
-impl<A: Diffable<StaticArgs>, B: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs>
-    for ADAdd<A, B>
+impl<StaticArgs, NewStaticArgs, Input, Output, Gradient, A>
+    ReverseDiffable<(NewStaticArgs, StaticArgs)> for ADPrependStaticArgs<A, NewStaticArgs>
 where
-    A::Output: Add<B::Output>,
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Gradient>,
 {
-    type Input = A::Input;
-    type Output = <A::Output as Add<B::Output>>::Output;
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a Self::Input,
+        tape: &mut Tape<'a, Gradient, Output>,
+        static_args: &'a (NewStaticArgs, StaticArgs),
+    ) -> Var {
+        self.0.eval_reverse(x, tape, &static_args.1)
+    }
 }
 
-impl<StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, Output, Grad, A, B> AutoDiffable<StaticArgs>
-    for ADAdd<A, B>
+impl<StaticArgs, NewStaticArgs, Input, Output, A> VjpDiffable<(NewStaticArgs, StaticArgs)>
+    for ADPrependStaticArgs<A, NewStaticArgs>
 where
-    A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
-    Input: GradientType<AOutput, GradientType = AGrad>,
-    B: AutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
-    Input: GradientType<BOutput, GradientType = BGrad>,
-    AOutput: Add<BOutput, Output = Output>,
-    AGrad: Add<BGrad, Output = Grad>,
-    Input: GradientType<Output, GradientType = Grad>,
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
 {
-    fn eval(
+    fn eval_primal(
         &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
-        // use .add instead of + to allow for newtypes which implement Deref
-        self.0.eval(x, static_args).add(self.1.eval(x, static_args))
+        x: &Self::Input,
+        static_args: &(NewStaticArgs, StaticArgs),
+    ) -> Self::Output {
+        self.0.eval_primal(x, &static_args.1)
     }
 
-    fn eval_grad(
+    fn eval_vjp(
         &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        let (f, df) = self.0.eval_grad(x, static_args);
-        let (g, dg) = self.1.eval_grad(x, static_args);
-
-        (f.add(g), df.add(dg))
+        x: &Self::Input,
+        cotangent: &Self::Output,
+        static_args: &(NewStaticArgs, StaticArgs),
+    ) -> (Self::Output, Self::Input) {
+        self.0.eval_vjp(x, cotangent, &static_args.1)
     }
 
-    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
-        self.0.grad(x, static_args).add(self.1.grad(x, static_args))
+    fn eval_conj_vjp(
+        &self,
+        x: &Self::Input,
+        cotangent: &Self::Output,
+        static_args: &(NewStaticArgs, StaticArgs),
+    ) -> (Self::Output, Self::Input) {
+        self.0.eval_conj_vjp(x, cotangent, &static_args.1)
     }
+}
 
-    fn eval_conj_grad(
+impl<StaticArgs, NewStaticArgs, Input, Output, Grad, Hess, A>
+    HessianDiffable<(NewStaticArgs, StaticArgs)> for ADPrependStaticArgs<A, NewStaticArgs>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    Grad: Outer<Grad, Output = Hess>,
+{
+    fn eval_hessian(
         &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        let (f, df) = self.0.eval_conj_grad(x, static_args);
-        let (g, dg) = self.1.eval_conj_grad(x, static_args);
-
-        (f.add(g), df.add(dg))
+        x: &Self::Input,
+        static_args: &(NewStaticArgs, StaticArgs),
+    ) -> (Self::Output, Grad, Hess) {
+        self.0.eval_hessian(x, &static_args.1)
     }
+}
 
-    fn conj_grad(
+impl<StaticArgs, NewStaticArgs, A> HvpDiffable<(NewStaticArgs, StaticArgs)>
+    for ADPrependStaticArgs<A, NewStaticArgs>
+where
+    A: HvpDiffable<StaticArgs>,
+{
+    fn eval_hvp(
         &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
+        x: &Self::Input,
+        v: &Self::Input,
+        cotangent: &Self::Output,
+        dcotangent: &Self::Output,
+        static_args: &(NewStaticArgs, StaticArgs),
+    ) -> (Self::Output, Self::Input, Self::Input) {
+        self.0.eval_hvp(x, v, cotangent, dcotangent, &static_args.1)
+    }
+}
+
+impl<StaticArgs, NewStaticArgs, A, const K: usize> TaylorDiffable<(NewStaticArgs, StaticArgs), K>
+    for ADPrependStaticArgs<A, NewStaticArgs>
+where
+    A: TaylorDiffable<StaticArgs, K>,
+{
+    fn eval_taylor_series(
+        &self,
+        x: &Taylor<Self::Input, K>,
+        static_args: &(NewStaticArgs, StaticArgs),
+    ) -> Taylor<Self::Output, K> {
+        self.0.eval_taylor_series(x, &static_args.1)
+    }
+}
+
+#[derive(FuncCompose, Debug, Clone, Copy)]
+pub struct ADAdd<A, B>(pub A, pub B);
+
+impl<A: Diffable<StaticArgs>, B: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs>
+    for ADAdd<A, B>
+where
+    A::Output: Add<B::Output>,
+{
+    type Input = A::Input;
+    type Output = <A::Output as Add<B::Output>>::Output;
+}
+
+impl<A, B> Symbolic for ADAdd<A, B>
+where
+    A: Symbolic,
+    B: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::Add(Box::new(self.0.symbolic(x)), Box::new(self.1.symbolic(x)))
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADAdd<A, B>
+where
+    A: ToDot<StaticArgs>,
+    B: ToDot<StaticArgs, Input = A::Input>,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let g = self.1.dot_node(graph, at);
+        graph.op("+", self.annotate_at(at), &[(f, None), (g, None)])
+    }
+}
+
+impl<StaticArgs, T, A, B> ToDynExpr<StaticArgs, T> for ADAdd<A, B>
+where
+    A: ToDynExpr<StaticArgs, T>,
+    B: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::Add(
+            Box::new(self.0.to_dyn_expr()),
+            Box::new(self.1.to_dyn_expr()),
+        )
+    }
+}
+
+impl<StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, Output, Grad, A, B> AutoDiffable<StaticArgs>
+    for ADAdd<A, B>
+where
+    A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    B: AutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    Input: GradientType<BOutput, GradientType = BGrad>,
+    AOutput: Add<BOutput, Output = Output>,
+    AGrad: Add<BGrad, Output = Grad>,
+    Input: GradientType<Output, GradientType = Grad>,
+{
+    fn eval(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        // use .add instead of + to allow for newtypes which implement Deref
+        self.0.eval(x, static_args).add(self.1.eval(x, static_args))
+    }
+
+    fn eval_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        let (f, df) = self.0.eval_grad(x, static_args);
+        let (g, dg) = self.1.eval_grad(x, static_args);
+
+        (f.add(g), df.add(dg))
+    }
+
+    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
+        self.0.grad(x, static_args).add(self.1.grad(x, static_args))
+    }
+
+    fn eval_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        let (f, df) = self.0.eval_conj_grad(x, static_args);
+        let (g, dg) = self.1.eval_conj_grad(x, static_args);
+
+        (f.add(g), df.add(dg))
+    }
+
+    fn conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> Grad {
         self.0
             .conj_grad(x, static_args)
             .add(self.1.conj_grad(x, static_args))
     }
+
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+        let (g, dg, dgdconjz) = self.1.eval_all(x, static_args);
+
+        (f.add(g), df.add(dg), dfdconjz.add(dgdconjz))
+    }
+}
+
+impl<StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, Output, Grad, A, B>
+    TryAutoDiffable<StaticArgs> for ADAdd<A, B>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    B: TryAutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    Input: GradientType<BOutput, GradientType = BGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f, g and f + g can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    BOutput: CheckValue,
+    BGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    // ensure f + g can be evaluated from the checked results of f and g
+    ADAdd<ADEvaluated<Input, AOutput, AGrad>, ADEvaluated<Input, BOutput, BGrad>>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADAdd", 0))?;
+        let g = self
+            .1
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADAdd", 1))?;
+        check_shapes("ADAdd", &f, &g)?;
+
+        let h = ADAdd(ADEvaluated::value(f), ADEvaluated::value(g));
+        check_value("ADAdd", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADAdd", 0))?;
+        let g = self
+            .1
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADAdd", 1))?;
+        check_shapes("ADAdd", &f.0, &g.0)?;
+
+        let h = ADAdd(ADEvaluated::all(f), ADEvaluated::all(g));
+        check_all("ADAdd", h.eval_all(x, static_args))
+    }
 }
 
 impl<StaticArgs, Input, AOutput, BOutput, Output, A, B> ForwardDiffable<StaticArgs> for ADAdd<A, B>
@@ -469,6 +1066,152 @@ where
     }
 }
 
+impl<StaticArgs, Input, Output, Grad, A, B> ReverseDiffable<StaticArgs> for ADAdd<A, B>
+where
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f + g is defined and is Output, and the partials 1 and 0 can be made
+    Output: Clone + InstZero + InstOne,
+{
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let v = self.1.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
+        let g = tape.value(v).clone();
+
+        // d(f + g)/df = 1, d(f + g)/dg = 1
+        let (one, zero) = (f.one(), f.zero());
+
+        tape.binary(f.add(g), (u, one.clone(), zero.clone()), (v, one, zero))
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B> VjpDiffable<StaticArgs> for ADAdd<A, B>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f + g is defined and is Output
+    Output: Add<Output, Output = Output>,
+    // ensure the vector-Jacobian products of f and g can be combined
+    Input: Add<Input, Output = Input>,
+{
+    fn eval_primal(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0
+            .eval_primal(x, static_args)
+            .add(self.1.eval_primal(x, static_args))
+    }
+
+    fn eval_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        // c^T d(f + g) = c^T df + c^T dg
+
+        let (f, vf) = self.0.eval_vjp(x, cotangent, static_args);
+        let (g, vg) = self.1.eval_vjp(x, cotangent, static_args);
+
+        (f.add(g), vf.add(vg))
+    }
+
+    fn eval_conj_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, vf) = self.0.eval_conj_vjp(x, cotangent, static_args);
+        let (g, vg) = self.1.eval_conj_vjp(x, cotangent, static_args);
+
+        (f.add(g), vf.add(vg))
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, Hess, A, B> HessianDiffable<StaticArgs> for ADAdd<A, B>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f + g, df + dg and d²f + d²g are defined
+    Output: Add<Output, Output = Output>,
+    Grad: Add<Grad, Output = Grad> + Outer<Grad, Output = Hess>,
+    Hess: Add<Hess, Output = Hess>,
+{
+    fn eval_hessian(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Hess) {
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
+        let (g, dg, d2g) = self.1.eval_hessian(x, static_args);
+
+        (f.add(g), df.add(dg), d2f.add(d2g))
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B> HvpDiffable<StaticArgs> for ADAdd<A, B>
+where
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: HvpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f + g and the vector-Jacobian products of f + g are defined
+    Output: Add<Output, Output = Output>,
+    Input: Add<Input, Output = Input>,
+{
+    fn eval_hvp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        v: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, vf, dvf) = self.0.eval_hvp(x, v, cotangent, dcotangent, static_args);
+        let (g, vg, dvg) = self.1.eval_hvp(x, v, cotangent, dcotangent, static_args);
+
+        (f.add(g), vf.add(vg), dvf.add(dvg))
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B, const K: usize> TaylorDiffable<StaticArgs, K> for ADAdd<A, B>
+where
+    A: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    B: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    // ensure f + g is defined on the series of f and g
+    Output: TaylorCoefficient,
+{
+    fn eval_taylor_series(
+        &self,
+        x: &Taylor<<Self as Diffable<StaticArgs>>::Input, K>,
+        static_args: &StaticArgs,
+    ) -> Taylor<<Self as Diffable<StaticArgs>>::Output, K> {
+        let f = self.0.eval_taylor_series(x, static_args);
+        let g = self.1.eval_taylor_series(x, static_args);
+
+        f.add(g)
+    }
+}
+
 #[derive(FuncCompose, Debug, Clone, Copy)]
 pub struct ADSub<A, B>(pub A, pub B);
 
@@ -481,6 +1224,47 @@ where
     type Output = <A::Output as Sub<B::Output>>::Output;
 }
 
+impl<A, B> Symbolic for ADSub<A, B>
+where
+    A: Symbolic,
+    B: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::Sub(Box::new(self.0.symbolic(x)), Box::new(self.1.symbolic(x)))
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADSub<A, B>
+where
+    A: ToDot<StaticArgs>,
+    B: ToDot<StaticArgs, Input = A::Input>,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let g = self.1.dot_node(graph, at);
+        graph.op(
+            "-",
+            self.annotate_at(at),
+            &[(f, Some("lhs")), (g, Some("rhs"))],
+        )
+    }
+}
+
+impl<StaticArgs, T, A, B> ToDynExpr<StaticArgs, T> for ADSub<A, B>
+where
+    A: ToDynExpr<StaticArgs, T>,
+    B: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::Sub(
+            Box::new(self.0.to_dyn_expr()),
+            Box::new(self.1.to_dyn_expr()),
+        )
+    }
+}
+
 impl<StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, Output, Grad, A, B> AutoDiffable<StaticArgs>
     for ADSub<A, B>
 where
@@ -536,6 +1320,72 @@ where
             .conj_grad(x, static_args)
             .sub(self.1.conj_grad(x, static_args))
     }
+
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+        let (g, dg, dgdconjz) = self.1.eval_all(x, static_args);
+
+        (f.sub(g), df.sub(dg), dfdconjz.sub(dgdconjz))
+    }
+}
+
+impl<StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, Output, Grad, A, B>
+    TryAutoDiffable<StaticArgs> for ADSub<A, B>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    B: TryAutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    Input: GradientType<BOutput, GradientType = BGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f, g and f - g can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    BOutput: CheckValue,
+    BGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    // ensure f - g can be evaluated from the checked results of f and g
+    ADSub<ADEvaluated<Input, AOutput, AGrad>, ADEvaluated<Input, BOutput, BGrad>>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADSub", 0))?;
+        let g = self
+            .1
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADSub", 1))?;
+        check_shapes("ADSub", &f, &g)?;
+
+        let h = ADSub(ADEvaluated::value(f), ADEvaluated::value(g));
+        check_value("ADSub", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADSub", 0))?;
+        let g = self
+            .1
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADSub", 1))?;
+        check_shapes("ADSub", &f.0, &g.0)?;
+
+        let h = ADSub(ADEvaluated::all(f), ADEvaluated::all(g));
+        check_all("ADSub", h.eval_all(x, static_args))
+    }
 }
 
 impl<StaticArgs, Input, AOutput, BOutput, Output, A, B> ForwardDiffable<StaticArgs> for ADSub<A, B>
@@ -607,53 +1457,240 @@ where
     }
 }
 
-#[derive(FuncCompose, Debug, Clone, Copy)]
-pub struct ADMul<A, B>(pub A, pub B);
-
-impl<A: Diffable<StaticArgs>, B: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs>
-    for ADMul<A, B>
+impl<StaticArgs, Input, Output, Grad, A, B> ReverseDiffable<StaticArgs> for ADSub<A, B>
 where
-    A::Output: Mul<B::Output>,
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f - g is defined and is Output, and the partials 1, -1 and 0 can be made
+    Output: Clone + InstZero + InstOne + Sub<Output, Output = Output> + Neg<Output = Output>,
 {
-    type Input = A::Input;
-    type Output = <A::Output as Mul<B::Output>>::Output;
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let v = self.1.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
+        let g = tape.value(v).clone();
+
+        // d(f - g)/df = 1, d(f - g)/dg = -1
+        let (one, zero) = (f.one(), f.zero());
+
+        tape.binary(
+            f.sub(g),
+            (u, one.clone(), zero.clone()),
+            (v, one.neg(), zero),
+        )
+    }
 }
 
-impl<StaticArgs, Input, Output, Grad, AOutput, BOutput, AGrad, BGrad, DAB, ADB, A, B>
-    AutoDiffable<StaticArgs> for ADMul<A, B>
+impl<StaticArgs, Input, Output, A, B> VjpDiffable<StaticArgs> for ADSub<A, B>
 where
-    A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
-    Input: GradientType<AOutput, GradientType = AGrad>,
-    B: AutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
-    Input: GradientType<BOutput, GradientType = BGrad>,
-    // make sure A and B are both Clone
-    AOutput: Clone,
-    BOutput: Clone,
-    // make sure A * B is defined and Output = Output
-    AOutput: Mul<BOutput, Output = Output>,
-    // make sure dA * B is defined and Output = DAB
-    AGrad: Mul<BOutput, Output = DAB>,
-    // make sure A * dB is defined and Output = ADB
-    AOutput: Mul<BGrad, Output = ADB>,
-    // make sure DAB + ADB is defined and Output = Grad
-    DAB: Add<ADB, Output = Grad>,
-    // assign gradient type
-    Input: GradientType<Output, GradientType = Grad>,
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f - g is defined and is Output
+    Output: Sub<Output, Output = Output>,
+    // ensure the vector-Jacobian products of f and g can be combined
+    Input: Sub<Input, Output = Input>,
 {
-    fn eval(
+    fn eval_primal(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        // use .mul instead of * to allow for newtypes which implement Deref
-        self.0.eval(x, static_args).mul(self.1.eval(x, static_args))
+        self.0
+            .eval_primal(x, static_args)
+            .sub(self.1.eval_primal(x, static_args))
     }
 
-    fn eval_grad(
+    fn eval_vjp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
-    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        // c^T d(f - g) = c^T df - c^T dg
+
+        let (f, vf) = self.0.eval_vjp(x, cotangent, static_args);
+        let (g, vg) = self.1.eval_vjp(x, cotangent, static_args);
+
+        (f.sub(g), vf.sub(vg))
+    }
+
+    fn eval_conj_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, vf) = self.0.eval_conj_vjp(x, cotangent, static_args);
+        let (g, vg) = self.1.eval_conj_vjp(x, cotangent, static_args);
+
+        (f.sub(g), vf.sub(vg))
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, Hess, A, B> HessianDiffable<StaticArgs> for ADSub<A, B>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f - g, df - dg and d²f - d²g are defined
+    Output: Sub<Output, Output = Output>,
+    Grad: Sub<Grad, Output = Grad> + Outer<Grad, Output = Hess>,
+    Hess: Sub<Hess, Output = Hess>,
+{
+    fn eval_hessian(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Hess) {
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
+        let (g, dg, d2g) = self.1.eval_hessian(x, static_args);
+
+        (f.sub(g), df.sub(dg), d2f.sub(d2g))
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B> HvpDiffable<StaticArgs> for ADSub<A, B>
+where
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: HvpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f - g and the vector-Jacobian products of f - g are defined
+    Output: Sub<Output, Output = Output>,
+    Input: Sub<Input, Output = Input>,
+{
+    fn eval_hvp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        v: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, vf, dvf) = self.0.eval_hvp(x, v, cotangent, dcotangent, static_args);
+        let (g, vg, dvg) = self.1.eval_hvp(x, v, cotangent, dcotangent, static_args);
+
+        (f.sub(g), vf.sub(vg), dvf.sub(dvg))
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B, const K: usize> TaylorDiffable<StaticArgs, K> for ADSub<A, B>
+where
+    A: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    B: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    // ensure f - g is defined on the series of f and g
+    Output: TaylorCoefficient,
+{
+    fn eval_taylor_series(
+        &self,
+        x: &Taylor<<Self as Diffable<StaticArgs>>::Input, K>,
+        static_args: &StaticArgs,
+    ) -> Taylor<<Self as Diffable<StaticArgs>>::Output, K> {
+        let f = self.0.eval_taylor_series(x, static_args);
+        let g = self.1.eval_taylor_series(x, static_args);
+
+        f.sub(g)
+    }
+}
+
+#[derive(FuncCompose, Debug, Clone, Copy)]
+pub struct ADMul<A, B>(pub A, pub B);
+
+impl<A: Diffable<StaticArgs>, B: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs>
+    for ADMul<A, B>
+where
+    A::Output: Mul<B::Output>,
+{
+    type Input = A::Input;
+    type Output = <A::Output as Mul<B::Output>>::Output;
+}
+
+impl<A, B> Symbolic for ADMul<A, B>
+where
+    A: Symbolic,
+    B: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::Mul(Box::new(self.0.symbolic(x)), Box::new(self.1.symbolic(x)))
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADMul<A, B>
+where
+    A: ToDot<StaticArgs>,
+    B: ToDot<StaticArgs, Input = A::Input>,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let g = self.1.dot_node(graph, at);
+        graph.op("*", self.annotate_at(at), &[(f, None), (g, None)])
+    }
+}
+
+impl<StaticArgs, T, A, B> ToDynExpr<StaticArgs, T> for ADMul<A, B>
+where
+    A: ToDynExpr<StaticArgs, T>,
+    B: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::Mul(
+            Box::new(self.0.to_dyn_expr()),
+            Box::new(self.1.to_dyn_expr()),
+        )
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, AOutput, BOutput, AGrad, BGrad, DAB, ADB, A, B>
+    AutoDiffable<StaticArgs> for ADMul<A, B>
+where
+    A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    B: AutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    Input: GradientType<BOutput, GradientType = BGrad>,
+    // make sure A and B are both Clone
+    AOutput: Clone,
+    BOutput: Clone,
+    // make sure A * B is defined and Output = Output
+    AOutput: Mul<BOutput, Output = Output>,
+    // make sure dA * B is defined and Output = DAB
+    AGrad: Mul<BOutput, Output = DAB>,
+    // make sure A * dB is defined and Output = ADB
+    AOutput: Mul<BGrad, Output = ADB>,
+    // make sure DAB + ADB is defined and Output = Grad
+    DAB: Add<ADB, Output = Grad>,
+    // assign gradient type
+    Input: GradientType<Output, GradientType = Grad>,
+{
+    fn eval(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        // use .mul instead of * to allow for newtypes which implement Deref
+        self.0.eval(x, static_args).mul(self.1.eval(x, static_args))
+    }
+
+    fn eval_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
         let (f, df) = self.0.eval_grad(x, static_args);
         let (g, dg) = self.1.eval_grad(x, static_args);
 
@@ -667,10 +1704,8 @@ where
     }
 
     fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
-        let f = self.0.eval(x, static_args);
-        let g = self.1.eval(x, static_args);
-        let df = self.0.grad(x, static_args);
-        let dg = self.1.grad(x, static_args);
+        let (f, df) = self.0.eval_grad(x, static_args);
+        let (g, dg) = self.1.eval_grad(x, static_args);
 
         df.mul(g).add(f.mul(dg))
     }
@@ -691,13 +1726,81 @@ where
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> Grad {
-        let f = self.0.eval(x, static_args);
-        let g = self.1.eval(x, static_args);
-        let df = self.0.conj_grad(x, static_args);
-        let dg = self.1.conj_grad(x, static_args);
+        let (f, df) = self.0.eval_conj_grad(x, static_args);
+        let (g, dg) = self.1.eval_conj_grad(x, static_args);
 
         df.mul(g).add(f.mul(dg))
     }
+
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+        let (g, dg, dgdconjz) = self.1.eval_all(x, static_args);
+
+        (
+            f.clone().mul(g.clone()),
+            df.mul(g.clone()).add(f.clone().mul(dg)),
+            dfdconjz.mul(g).add(f.mul(dgdconjz)),
+        )
+    }
+}
+
+impl<StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, Output, Grad, A, B>
+    TryAutoDiffable<StaticArgs> for ADMul<A, B>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    B: TryAutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    Input: GradientType<BOutput, GradientType = BGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f, g and f * g can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    BOutput: CheckValue,
+    BGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    // ensure f * g can be evaluated from the checked results of f and g
+    ADMul<ADEvaluated<Input, AOutput, AGrad>, ADEvaluated<Input, BOutput, BGrad>>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADMul", 0))?;
+        let g = self
+            .1
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADMul", 1))?;
+        check_shapes("ADMul", &f, &g)?;
+
+        let h = ADMul(ADEvaluated::value(f), ADEvaluated::value(g));
+        check_value("ADMul", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADMul", 0))?;
+        let g = self
+            .1
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADMul", 1))?;
+        check_shapes("ADMul", &f.0, &g.0)?;
+
+        let h = ADMul(ADEvaluated::all(f), ADEvaluated::all(g));
+        check_all("ADMul", h.eval_all(x, static_args))
+    }
 }
 
 impl<StaticArgs, Input, Output, AOutput, BOutput, A, B> ForwardDiffable<StaticArgs> for ADMul<A, B>
@@ -789,6 +1892,194 @@ where
     }
 }
 
+impl<StaticArgs, Input, Output, Grad, A, B> ReverseDiffable<StaticArgs> for ADMul<A, B>
+where
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f * g is defined and is Output
+    Output: Clone + InstZero + InstOne,
+{
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let v = self.1.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
+        let g = tape.value(v).clone();
+
+        // d(f * g)/df = g, d(f * g)/dg = f
+        let zero = f.zero();
+
+        tape.binary(f.clone().mul(g.clone()), (u, g, zero.clone()), (v, f, zero))
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B> VjpDiffable<StaticArgs> for ADMul<A, B>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f * g and the cotangents c * g and c * f are defined and are Output
+    Output: Clone + Mul<Output, Output = Output>,
+    // ensure the vector-Jacobian products of f and g can be summed
+    Input: Add<Input, Output = Input>,
+{
+    fn eval_primal(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0
+            .eval_primal(x, static_args)
+            .mul(self.1.eval_primal(x, static_args))
+    }
+
+    fn eval_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let f = self.0.eval_primal(x, static_args);
+        let g = self.1.eval_primal(x, static_args);
+
+        // c^T d(f * g) = (c * g)^T df + (c * f)^T dg
+
+        let vf = self
+            .0
+            .vjp(x, &cotangent.clone().mul(g.clone()), static_args);
+        let vg = self
+            .1
+            .vjp(x, &cotangent.clone().mul(f.clone()), static_args);
+
+        (f.mul(g), vf.add(vg))
+    }
+
+    fn eval_conj_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let f = self.0.eval_primal(x, static_args);
+        let g = self.1.eval_primal(x, static_args);
+
+        let vf = self
+            .0
+            .conj_vjp(x, &cotangent.clone().mul(g.clone()), static_args);
+        let vg = self
+            .1
+            .conj_vjp(x, &cotangent.clone().mul(f.clone()), static_args);
+
+        (f.mul(g), vf.add(vg))
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, Hess, A, B> HessianDiffable<StaticArgs> for ADMul<A, B>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f * g, df * g and d²f * g are defined
+    Output: Clone + Mul<Output, Output = Output>,
+    Grad:
+        Clone + Add<Grad, Output = Grad> + Mul<Output, Output = Grad> + Outer<Grad, Output = Hess>,
+    Hess: Add<Hess, Output = Hess> + Mul<Output, Output = Hess>,
+{
+    fn eval_hessian(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Hess) {
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
+        let (g, dg, d2g) = self.1.eval_hessian(x, static_args);
+
+        // d²(f * g) = d²f * g + f * d²g + df ⊗ dg + dg ⊗ df
+
+        let d2fg = d2f
+            .mul(g.clone())
+            .add(d2g.mul(f.clone()))
+            .add(df.outer(&dg))
+            .add(dg.outer(&df));
+
+        (f.clone().mul(g.clone()), df.mul(g).add(dg.mul(f)), d2fg)
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B> HvpDiffable<StaticArgs> for ADMul<A, B>
+where
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Output>
+        + ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: HvpDiffable<StaticArgs, Input = Input, Output = Output>
+        + ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f * g and the cotangents c * g, c * f and their derivatives are defined
+    Output: Clone + Add<Output, Output = Output> + Mul<Output, Output = Output>,
+    Input: Add<Input, Output = Input>,
+{
+    fn eval_hvp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        v: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, df) = self.0.eval_forward_grad(x, v, static_args);
+        let (g, dg) = self.1.eval_forward_grad(x, v, static_args);
+
+        // the cotangents of f and g are c * g and c * f, so along v
+        // d(c * g) = dc * g + c * dg and d(c * f) = dc * f + c * df
+
+        let cf = cotangent.clone().mul(g.clone());
+        let dcf = dcotangent
+            .clone()
+            .mul(g.clone())
+            .add(cotangent.clone().mul(dg));
+        let cg = cotangent.clone().mul(f.clone());
+        let dcg = dcotangent
+            .clone()
+            .mul(f.clone())
+            .add(cotangent.clone().mul(df));
+
+        let (_, vf, dvf) = self.0.eval_hvp(x, v, &cf, &dcf, static_args);
+        let (_, vg, dvg) = self.1.eval_hvp(x, v, &cg, &dcg, static_args);
+
+        (f.mul(g), vf.add(vg), dvf.add(dvg))
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B, const K: usize> TaylorDiffable<StaticArgs, K> for ADMul<A, B>
+where
+    A: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    B: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    // ensure f * g is defined on the series of f and g
+    Output: TaylorCoefficient,
+{
+    fn eval_taylor_series(
+        &self,
+        x: &Taylor<<Self as Diffable<StaticArgs>>::Input, K>,
+        static_args: &StaticArgs,
+    ) -> Taylor<<Self as Diffable<StaticArgs>>::Output, K> {
+        let f = self.0.eval_taylor_series(x, static_args);
+        let g = self.1.eval_taylor_series(x, static_args);
+
+        f.mul(g)
+    }
+}
+
 #[derive(FuncCompose, Debug, Clone, Copy)]
 pub struct ADDiv<A, B>(pub A, pub B);
 
@@ -801,6 +2092,47 @@ where
     type Output = <A::Output as Div<B::Output>>::Output;
 }
 
+impl<A, B> Symbolic for ADDiv<A, B>
+where
+    A: Symbolic,
+    B: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::Div(Box::new(self.0.symbolic(x)), Box::new(self.1.symbolic(x)))
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADDiv<A, B>
+where
+    A: ToDot<StaticArgs>,
+    B: ToDot<StaticArgs, Input = A::Input>,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let g = self.1.dot_node(graph, at);
+        graph.op(
+            "/",
+            self.annotate_at(at),
+            &[(f, Some("lhs")), (g, Some("rhs"))],
+        )
+    }
+}
+
+impl<StaticArgs, T, A, B> ToDynExpr<StaticArgs, T> for ADDiv<A, B>
+where
+    A: ToDynExpr<StaticArgs, T>,
+    B: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::Div(
+            Box::new(self.0.to_dyn_expr()),
+            Box::new(self.1.to_dyn_expr()),
+        )
+    }
+}
+
 impl<
         StaticArgs,
         Input,
@@ -900,33 +2232,114 @@ where
 
         df.div(g.clone()).sub(f.mul(dg).div(g.clone().mul(g)))
     }
-}
 
-impl<StaticArgs, Input, Output, AOutput, BOutput, BB, AB, ABOVBB, A, B> ForwardDiffable<StaticArgs>
-    for ADDiv<A, B>
-where
-    A: ForwardDiffable<StaticArgs, Input = Input, Output = AOutput>,
-    B: ForwardDiffable<StaticArgs, Input = Input, Output = BOutput>,
-    // ensure f and g are both Clone
-    AOutput: Clone,
-    BOutput: Clone,
-    // ensure A/B is defined and Output = Output
-    // ensure dA/B is defined (df/g)
-    AOutput: Div<BOutput, Output = Output>,
-    // ensure B^2 is defined
-    BOutput: Mul<BOutput, Output = BB>,
-    // ensure A*dB is defined (f * dg)
-    AOutput: Mul<BOutput, Output = AB>,
-    // ensure AdB/B^2 is defined (f * dg/g^2)
-    AB: Div<BB, Output = ABOVBB>,
-    // ensure dA/B - AdB/B^2 is defined (df/g - f * dg/g^2)
-    Output: Sub<ABOVBB, Output = Output>,
-{
-    fn eval_forward(
+    fn eval_all(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+        let (g, dg, dgdconjz) = self.1.eval_all(x, static_args);
+
+        // d(f/g) = df/g - f*dg/g^2 for both derivatives
+
+        (
+            f.clone().div(g.clone()),
+            df.div(g.clone())
+                .sub(f.clone().mul(dg).div(g.clone().mul(g.clone()))),
+            dfdconjz
+                .div(g.clone())
+                .sub(f.mul(dgdconjz).div(g.clone().mul(g))),
+        )
+    }
+}
+
+impl<StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, Output, Grad, A, B>
+    TryAutoDiffable<StaticArgs> for ADDiv<A, B>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    B: TryAutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    Input: GradientType<BOutput, GradientType = BGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f, g and f / g can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    BOutput: CheckValue,
+    BGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    // ensure f / g can be evaluated from the checked results of f and g
+    ADDiv<ADEvaluated<Input, AOutput, AGrad>, ADEvaluated<Input, BOutput, BGrad>>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADDiv", 0))?;
+        let g = self
+            .1
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADDiv", 1))?;
+        check_shapes("ADDiv", &f, &g)?;
+        if g.has_zero() {
+            return Err(AutoDiffError::domain("ADDiv", "division by zero"));
+        }
+
+        let h = ADDiv(ADEvaluated::value(f), ADEvaluated::value(g));
+        check_value("ADDiv", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADDiv", 0))?;
+        let g = self
+            .1
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADDiv", 1))?;
+        check_shapes("ADDiv", &f.0, &g.0)?;
+        if g.0.has_zero() {
+            return Err(AutoDiffError::domain("ADDiv", "division by zero"));
+        }
+
+        let h = ADDiv(ADEvaluated::all(f), ADEvaluated::all(g));
+        check_all("ADDiv", h.eval_all(x, static_args))
+    }
+}
+
+impl<StaticArgs, Input, Output, AOutput, BOutput, BB, AB, ABOVBB, A, B> ForwardDiffable<StaticArgs>
+    for ADDiv<A, B>
+where
+    A: ForwardDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    B: ForwardDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    // ensure f and g are both Clone
+    AOutput: Clone,
+    BOutput: Clone,
+    // ensure A/B is defined and Output = Output
+    // ensure dA/B is defined (df/g)
+    AOutput: Div<BOutput, Output = Output>,
+    // ensure B^2 is defined
+    BOutput: Mul<BOutput, Output = BB>,
+    // ensure A*dB is defined (f * dg)
+    AOutput: Mul<BOutput, Output = AB>,
+    // ensure AdB/B^2 is defined (f * dg/g^2)
+    AB: Div<BB, Output = ABOVBB>,
+    // ensure dA/B - AdB/B^2 is defined (df/g - f * dg/g^2)
+    Output: Sub<ABOVBB, Output = Output>,
+{
+    fn eval_forward(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
         let f = self.0.eval_forward(x, static_args);
         let g = self.1.eval_forward(x, static_args);
 
@@ -999,6 +2412,227 @@ where
     }
 }
 
+impl<StaticArgs, Input, Output, Grad, A, B> ReverseDiffable<StaticArgs> for ADDiv<A, B>
+where
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f / g is defined and is Output
+    Output: Clone + InstZero + InstOne + Div<Output, Output = Output> + Neg<Output = Output>,
+{
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let v = self.1.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
+        let g = tape.value(v).clone();
+
+        // d(f / g)/df = 1 / g, d(f / g)/dg = -(f / g) / g
+        let zero = f.zero();
+        let fg = f.clone().div(g.clone());
+
+        tape.binary(
+            fg.clone(),
+            (u, f.one().div(g.clone()), zero.clone()),
+            (v, fg.div(g).neg(), zero),
+        )
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B> VjpDiffable<StaticArgs> for ADDiv<A, B>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f / g and the cotangents c / g and c * (f / g) / g are defined and are Output
+    Output: Clone + Div<Output, Output = Output> + Mul<Output, Output = Output>,
+    // ensure the vector-Jacobian products of f and g can be subtracted
+    Input: Sub<Input, Output = Input>,
+{
+    fn eval_primal(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0
+            .eval_primal(x, static_args)
+            .div(self.1.eval_primal(x, static_args))
+    }
+
+    fn eval_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let f = self.0.eval_primal(x, static_args);
+        let g = self.1.eval_primal(x, static_args);
+        let fg = f.div(g.clone());
+
+        // c^T d(f / g) = (c / g)^T df - (c * (f / g) / g)^T dg
+
+        let vf = self
+            .0
+            .vjp(x, &cotangent.clone().div(g.clone()), static_args);
+        let vg = self
+            .1
+            .vjp(x, &cotangent.clone().mul(fg.clone()).div(g), static_args);
+
+        (fg, vf.sub(vg))
+    }
+
+    fn eval_conj_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let f = self.0.eval_primal(x, static_args);
+        let g = self.1.eval_primal(x, static_args);
+        let fg = f.div(g.clone());
+
+        let vf = self
+            .0
+            .conj_vjp(x, &cotangent.clone().div(g.clone()), static_args);
+        let vg = self
+            .1
+            .conj_vjp(x, &cotangent.clone().mul(fg.clone()).div(g), static_args);
+
+        (fg, vf.sub(vg))
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, Hess, A, B> HessianDiffable<StaticArgs> for ADDiv<A, B>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f / g, 1 / g and the products of the gradients with them are defined
+    Output: Clone
+        + InstOne
+        + Add<Output, Output = Output>
+        + Mul<Output, Output = Output>
+        + Div<Output, Output = Output>,
+    Grad:
+        Clone + Sub<Grad, Output = Grad> + Mul<Output, Output = Grad> + Outer<Grad, Output = Hess>,
+    Hess: Add<Hess, Output = Hess> + Sub<Hess, Output = Hess> + Mul<Output, Output = Hess>,
+{
+    fn eval_hessian(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Hess) {
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
+        let (g, dg, d2g) = self.1.eval_hessian(x, static_args);
+
+        // with h = f / g,
+        //
+        // d(f / g) = df / g - dg * h / g
+        // d²(f / g) = d²f / g - (df ⊗ dg + dg ⊗ df) / g^2 - d²g * h / g + dg ⊗ dg * 2h / g^2
+
+        let inv_g = g.one().div(g);
+        let inv_g2 = inv_g.clone().mul(inv_g.clone());
+        let h = f.mul(inv_g.clone());
+        let h_g = h.clone().mul(inv_g.clone());
+        let h2_g2 = h.clone().add(h.clone()).mul(inv_g2.clone());
+
+        let d2h = d2f
+            .mul(inv_g.clone())
+            .sub(df.outer(&dg).add(dg.outer(&df)).mul(inv_g2))
+            .sub(d2g.mul(h_g.clone()))
+            .add(dg.outer(&dg).mul(h2_g2));
+
+        (h, df.mul(inv_g).sub(dg.mul(h_g)), d2h)
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B> HvpDiffable<StaticArgs> for ADDiv<A, B>
+where
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Output>
+        + ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: HvpDiffable<StaticArgs, Input = Input, Output = Output>
+        + ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f / g, 1 / g and the cotangents and their derivatives are defined
+    Output: Clone
+        + InstOne
+        + Neg<Output = Output>
+        + Add<Output, Output = Output>
+        + Mul<Output, Output = Output>
+        + Div<Output, Output = Output>,
+    Input: Add<Input, Output = Input>,
+{
+    fn eval_hvp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        v: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, df) = self.0.eval_forward_grad(x, v, static_args);
+        let (g, dg) = self.1.eval_forward_grad(x, v, static_args);
+
+        // with r = 1 / g and h = f * r, along v
+        // dr = -dg * r^2 and dh = df * r + f * dr
+
+        let r = g.one().div(g);
+        let dr = dg.mul(r.clone()).mul(r.clone()).neg();
+        let dh = df.mul(r.clone()).add(f.clone().mul(dr.clone()));
+        let h = f.mul(r.clone());
+
+        // the cotangents of f and g are c * r and -c * h * r
+
+        let hr = h.clone().mul(r.clone());
+        let dhr = dh.mul(r.clone()).add(h.clone().mul(dr.clone()));
+
+        let cf = cotangent.clone().mul(r.clone());
+        let dcf = dcotangent.clone().mul(r).add(cotangent.clone().mul(dr));
+        let cg = cotangent.clone().mul(hr.clone()).neg();
+        let dcg = dcotangent
+            .clone()
+            .mul(hr)
+            .add(cotangent.clone().mul(dhr))
+            .neg();
+
+        let (_, vf, dvf) = self.0.eval_hvp(x, v, &cf, &dcf, static_args);
+        let (_, vg, dvg) = self.1.eval_hvp(x, v, &cg, &dcg, static_args);
+
+        (h, vf.add(vg), dvf.add(dvg))
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B, const K: usize> TaylorDiffable<StaticArgs, K> for ADDiv<A, B>
+where
+    A: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    B: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    // ensure f / g is defined on the series of f and g
+    Output: TaylorCoefficient,
+{
+    fn eval_taylor_series(
+        &self,
+        x: &Taylor<<Self as Diffable<StaticArgs>>::Input, K>,
+        static_args: &StaticArgs,
+    ) -> Taylor<<Self as Diffable<StaticArgs>>::Output, K> {
+        let f = self.0.eval_taylor_series(x, static_args);
+        let g = self.1.eval_taylor_series(x, static_args);
+
+        f.div(g)
+    }
+}
+
 #[derive(FuncCompose, Debug, Clone, Copy)]
 pub struct ADNeg<A>(pub A);
 
@@ -1010,6 +2644,36 @@ where
     type Output = <A::Output as Neg>::Output;
 }
 
+impl<A> Symbolic for ADNeg<A>
+where
+    A: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::Neg(Box::new(self.0.symbolic(x)))
+    }
+}
+
+impl<StaticArgs, A> ToDot<StaticArgs> for ADNeg<A>
+where
+    A: ToDot<StaticArgs>,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        graph.op("neg", self.annotate_at(at), &[(f, None)])
+    }
+}
+
+impl<StaticArgs, T, A> ToDynExpr<StaticArgs, T> for ADNeg<A>
+where
+    A: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::Neg(Box::new(self.0.to_dyn_expr()))
+    }
+}
+
 impl<StaticArgs, Input, Output, Grad, AOutput, AGrad, A> AutoDiffable<StaticArgs> for ADNeg<A>
 where
     // ensure A has Neg
@@ -1060,6 +2724,52 @@ where
     ) -> Grad {
         self.0.conj_grad(x, static_args).neg()
     }
+
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+
+        (f.neg(), df.neg(), dfdconjz.neg())
+    }
+}
+
+impl<StaticArgs, Input, AOutput, AGrad, Output, Grad, A> TryAutoDiffable<StaticArgs> for ADNeg<A>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f and -f can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    // ensure -f can be evaluated from the checked results of f
+    ADNeg<ADEvaluated<Input, AOutput, AGrad>>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADNeg", 0))?;
+        check_value("ADNeg", ADNeg(ADEvaluated::value(f)).eval(x, static_args))
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADNeg", 0))?;
+        check_all("ADNeg", ADNeg(ADEvaluated::all(f)).eval_all(x, static_args))
+    }
 }
 
 impl<StaticArgs, Input, Output, AOutput, A> ForwardDiffable<StaticArgs> for ADNeg<A>
@@ -1124,120 +2834,265 @@ where
     }
 }
 
-#[derive(FuncCompose, Debug, Clone, Copy)]
-pub struct ADCompose<Outer, Inner>(pub Outer, pub Inner);
-
-impl<Outer, Inner, StaticArgs> Diffable<StaticArgs> for ADCompose<Outer, Inner>
+impl<StaticArgs, Input, Output, Grad, A> ReverseDiffable<StaticArgs> for ADNeg<A>
 where
-    Outer: Diffable<StaticArgs>,
-    Inner: Diffable<StaticArgs>,
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure -f is defined and is Output
+    Output: Clone + InstZero + InstOne + Neg<Output = Output>,
 {
-    type Input = Inner::Input;
-    type Output = Outer::Output;
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
+
+        // d(-f)/df = -1
+        let (one, zero) = (f.one(), f.zero());
+
+        tape.unary(f.neg(), (u, one.neg(), zero))
+    }
 }
 
-impl<
-        StaticArgs,
-        InnerInput,
-        InnerOutput,
-        InnerGrad,
-        OuterInput,
-        OuterOutput,
-        OuterGrad,
-        Grad,
-        Outer,
-        Inner,
-    > AutoDiffable<StaticArgs> for ADCompose<Outer, Inner>
+impl<StaticArgs, Input, Output, A> VjpDiffable<StaticArgs> for ADNeg<A>
 where
-    Outer: AutoDiffable<StaticArgs, Input = OuterInput, Output = OuterOutput>,
-    Inner: AutoDiffable<StaticArgs, Input = InnerInput, Output = InnerOutput>,
-    OuterInput: From<InnerOutput> + GradientType<OuterOutput, GradientType = OuterGrad>,
-    InnerInput: GradientType<InnerOutput, GradientType = InnerGrad>
-        + GradientType<OuterOutput, GradientType = Grad>,
-    OuterGrad: ForwardMul<OuterInput, InnerGrad, ResultGrad = Grad>,
-    InnerInput: PossiblyComplex,
-    InnerOutput: Clone,
-    OuterInput: PossiblyComplex,
-    InnerGrad: Conjugate<Output = InnerGrad>,
-    OuterGrad: Conjugate<Output = OuterGrad>,
-    Grad: Add<Output = Grad>,
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure -f and the cotangent -c are defined and are Output
+    Output: Clone + Neg<Output = Output>,
 {
-    fn eval(
+    fn eval_primal(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0
-            .eval(&self.1.eval(x, static_args).into(), static_args)
+        self.0.eval_primal(x, static_args).neg()
     }
 
-    fn eval_grad(
+    fn eval_vjp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
-    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        if InnerInput::is_always_real() && OuterInput::is_always_real() {
-            let (g, dg) = self.1.eval_grad(x, static_args);
-            let (f, df) = self.0.eval_grad(&g.into(), static_args);
-            (f, df.forward_mul(&dg))
-        } else {
-            // in the Wirtinger calculus we have
-            //
-            // d/dz (f(g(z))) = df/dz(g(z)) * dg/dz + df/dconjz(g(z)) * dconjg/dz
-            // and dconjg/dz = conj(dg/dconjz)
-
-            let (g, dg) = self.1.eval_grad(x, static_args);
-            let dconjg = self.1.conj_grad(x, static_args).conj();
-            let (f, df) = self.0.eval_grad(&g.clone().into(), static_args);
-            let dfdconjg = self.0.conj_grad(&g.into(), static_args);
-
-            (f, df.forward_mul(&dg).add(dfdconjg.forward_mul(&dconjg)))
-        }
-    }
-
-    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
-        if InnerInput::is_always_real() && OuterInput::is_always_real() {
-            let (g, dg) = self.1.eval_grad(x, static_args);
-            let df = self.0.grad(&g.into(), static_args);
-            df.forward_mul(&dg)
-        } else {
-            // in the Wirtinger calculus we have
-            //
-            // d/dz (f(g(z))) = df/dz(g(z)) * dg/dz + df/dconjz(g(z)) * dconjg/dz
-            // and dconjg/dz = conj(dg/dconjz)
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        // c^T d(-f) = (-c)^T df
 
-            let (g, dg) = self.1.eval_grad(x, static_args);
-            let dconjg = self.1.conj_grad(x, static_args).conj();
-            let df = self.0.grad(&g.clone().into(), static_args);
-            let dfdconjg = self.0.conj_grad(&g.into(), static_args);
+        let (f, vf) = self.0.eval_vjp(x, &cotangent.clone().neg(), static_args);
 
-            df.forward_mul(&dg).add(dfdconjg.forward_mul(&dconjg))
-        }
+        (f.neg(), vf)
     }
 
-    fn eval_conj_grad(
+    fn eval_conj_vjp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
-    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        if InnerInput::is_always_real() && OuterInput::is_always_real() {
-            self.eval_grad(x, static_args)
-        } else {
-            // in the Wirtinger calculus we have
-            //
-            // d/dconjz (f(g(z))) = df/dz(g(z)) * dg/dconjz + df/dconjz(g(z)) * dgconj/dconjz
-            // and dgconj/dconjz = conj(dg/dz)
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, vf) = self
+            .0
+            .eval_conj_vjp(x, &cotangent.clone().neg(), static_args);
 
-            let (g, dgdconjz) = self.1.eval_conj_grad(x, static_args);
-            let dconjgdconjz = self.1.grad(x, static_args).conj();
-            let (f, df) = self.0.eval_grad(&g.clone().into(), static_args);
-            let dfdconjg = self.0.conj_grad(&g.into(), static_args);
+        (f.neg(), vf)
+    }
+}
 
-            (
-                f,
-                df.forward_mul(&dgdconjz)
-                    .add(dfdconjg.forward_mul(&dconjgdconjz)),
-            )
+impl<StaticArgs, Input, Output, Grad, Hess, A> HessianDiffable<StaticArgs> for ADNeg<A>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure -f, -df and -d²f are defined
+    Output: Neg<Output = Output>,
+    Grad: Neg<Output = Grad> + Outer<Grad, Output = Hess>,
+    Hess: Neg<Output = Hess>,
+{
+    fn eval_hessian(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Hess) {
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
+
+        (f.neg(), df.neg(), d2f.neg())
+    }
+}
+
+impl<StaticArgs, Input, Output, A> HvpDiffable<StaticArgs> for ADNeg<A>
+where
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure -f and the negated vector-Jacobian products are defined
+    Output: Neg<Output = Output>,
+    Input: Neg<Output = Input>,
+{
+    fn eval_hvp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        v: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, vf, dvf) = self.0.eval_hvp(x, v, cotangent, dcotangent, static_args);
+
+        (f.neg(), vf.neg(), dvf.neg())
+    }
+}
+
+impl<StaticArgs, Input, Output, A, const K: usize> TaylorDiffable<StaticArgs, K> for ADNeg<A>
+where
+    A: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    // ensure -f is defined on the series of f
+    Output: TaylorCoefficient,
+{
+    fn eval_taylor_series(
+        &self,
+        x: &Taylor<<Self as Diffable<StaticArgs>>::Input, K>,
+        static_args: &StaticArgs,
+    ) -> Taylor<<Self as Diffable<StaticArgs>>::Output, K> {
+        self.0.eval_taylor_series(x, static_args).neg()
+    }
+}
+
+#[derive(FuncCompose, Debug, Clone, Copy)]
+pub struct ADCompose<Outer, Inner>(pub Outer, pub Inner);
+
+impl<Outer, Inner, StaticArgs> Diffable<StaticArgs> for ADCompose<Outer, Inner>
+where
+    Outer: Diffable<StaticArgs>,
+    Inner: Diffable<StaticArgs>,
+{
+    type Input = Inner::Input;
+    type Output = Outer::Output;
+}
+
+impl<Outer, Inner> Symbolic for ADCompose<Outer, Inner>
+where
+    Outer: Symbolic,
+    Inner: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        self.0.symbolic(&self.1.symbolic(x))
+    }
+}
+
+impl<StaticArgs, Outer, Inner> ToDot<StaticArgs> for ADCompose<Outer, Inner>
+where
+    Inner: ToDot<StaticArgs>,
+    Outer: ToDot<StaticArgs, Input = Inner::Output>,
+    Self: DotAnnotate<StaticArgs, Input = Inner::Input>,
+    // ensure the inner function can be evaluated for the annotations of the outer one
+    Inner: AutoDiffable<StaticArgs>,
+    Inner::Input: GradientType<Inner::Output>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&Inner::Input, &StaticArgs)>) -> usize {
+        let g = self.1.dot_node(graph, at);
+        // the outer function is annotated at the value of the inner one
+        let outer_at = at.map(|(x, static_args)| (self.1.eval(x, static_args), static_args));
+        let f = self.0.dot_node(
+            graph,
+            outer_at.as_ref().map(|(y, static_args)| (y, *static_args)),
+        );
+        graph.op(
+            "compose",
+            self.annotate_at(at),
+            &[(f, Some("outer")), (g, Some("inner"))],
+        )
+    }
+}
+
+impl<StaticArgs, T, Outer, Inner> ToDynExpr<StaticArgs, T> for ADCompose<Outer, Inner>
+where
+    Outer: ToDynExpr<StaticArgs, T>,
+    Inner: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        self.0.to_dyn_expr().substitute(&self.1.to_dyn_expr())
+    }
+}
+
+impl<
+        StaticArgs,
+        InnerInput,
+        InnerOutput,
+        InnerGrad,
+        OuterInput,
+        OuterOutput,
+        OuterGrad,
+        Grad,
+        Outer,
+        Inner,
+    > AutoDiffable<StaticArgs> for ADCompose<Outer, Inner>
+where
+    Outer: AutoDiffable<StaticArgs, Input = OuterInput, Output = OuterOutput>,
+    Inner: AutoDiffable<StaticArgs, Input = InnerInput, Output = InnerOutput>,
+    OuterInput: From<InnerOutput> + GradientType<OuterOutput, GradientType = OuterGrad>,
+    InnerInput: GradientType<InnerOutput, GradientType = InnerGrad>
+        + GradientType<OuterOutput, GradientType = Grad>,
+    OuterGrad: ForwardMul<OuterInput, InnerGrad, ResultGrad = Grad>,
+    InnerInput: PossiblyComplex,
+    InnerOutput: Clone,
+    OuterInput: PossiblyComplex,
+    InnerGrad: Conjugate<Output = InnerGrad>,
+    OuterGrad: Conjugate<Output = OuterGrad>,
+    Grad: Add<Output = Grad>,
+{
+    fn eval(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0
+            .eval(&self.1.eval(x, static_args).into(), static_args)
+    }
+
+    fn eval_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        if InnerInput::is_always_real() && OuterInput::is_always_real() {
+            let (g, dg) = self.1.eval_grad(x, static_args);
+            let (f, df) = self.0.eval_grad(&g.into(), static_args);
+            (f, df.forward_mul(&dg))
+        } else {
+            // both Wirtinger derivatives of f and g are needed, see eval_all
+            let (f, df, _) = self.eval_all(x, static_args);
+            (f, df)
+        }
+    }
+
+    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
+        if InnerInput::is_always_real() && OuterInput::is_always_real() {
+            let (g, dg) = self.1.eval_grad(x, static_args);
+            let df = self.0.grad(&g.into(), static_args);
+            df.forward_mul(&dg)
+        } else {
+            self.eval_all(x, static_args).1
+        }
+    }
+
+    fn eval_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        if InnerInput::is_always_real() && OuterInput::is_always_real() {
+            self.eval_grad(x, static_args)
+        } else {
+            let (f, _, dfdconjz) = self.eval_all(x, static_args);
+            (f, dfdconjz)
         }
     }
 
@@ -1248,20 +3103,118 @@ where
     ) -> Grad {
         if InnerInput::is_always_real() && OuterInput::is_always_real() {
             self.grad(x, static_args)
+        } else {
+            self.eval_all(x, static_args).2
+        }
+    }
+
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        if InnerInput::is_always_real() && OuterInput::is_always_real() {
+            let (g, dg) = self.1.eval_grad(x, static_args);
+            let (f, df) = self.0.eval_grad(&g.into(), static_args);
+            (f, df.forward_mul(&dg), df.forward_mul(&dg))
         } else {
             // in the Wirtinger calculus we have
             //
-            // d/dconjz (f(g(z))) = df/dz(g(z)) * dg/dconjz + df/dconjz(g(z)) * dgconj/dconjz
-            // and dgconj/dconjz = conj(dg/dz)
+            // d/dz (f(g(z))) = df/dz(g(z)) * dg/dz + df/dconjz(g(z)) * dconjg/dz
+            // d/dconjz (f(g(z))) = df/dz(g(z)) * dg/dconjz + df/dconjz(g(z)) * dconjg/dconjz
+            // and dconjg/dz = conj(dg/dconjz), dconjg/dconjz = conj(dg/dz)
+            //
+            // so f and g are each evaluated once, with both of their derivatives
+
+            let (g, dgdz, dgdconjz) = self.1.eval_all(x, static_args);
+            let (f, dfdg, dfdconjg) = self.0.eval_all(&g.into(), static_args);
+
+            let dconjgdz = dgdconjz.conj();
+            let dconjgdconjz = dgdz.conj();
+
+            (
+                f,
+                dfdg.forward_mul(&dgdz).add(dfdconjg.forward_mul(&dconjgdz)),
+                dfdg.forward_mul(&dgdconjz)
+                    .add(dfdconjg.forward_mul(&dconjgdconjz)),
+            )
+        }
+    }
+}
 
-            let (g, dgdconjz) = self.1.eval_conj_grad(x, static_args);
-            let dconjgdconjz = self.1.grad(x, static_args).conj();
-            let df = self.0.grad(&g.clone().into(), static_args);
-            let dfdconjg = self.0.conj_grad(&g.into(), static_args);
+impl<
+        StaticArgs,
+        InnerInput,
+        InnerOutput,
+        InnerGrad,
+        OuterInput,
+        OuterOutput,
+        OuterGrad,
+        Grad,
+        Outer,
+        Inner,
+    > TryAutoDiffable<StaticArgs> for ADCompose<Outer, Inner>
+where
+    Outer: TryAutoDiffable<StaticArgs, Input = OuterInput, Output = OuterOutput>,
+    Inner: TryAutoDiffable<StaticArgs, Input = InnerInput, Output = InnerOutput>,
+    OuterInput: From<InnerOutput> + GradientType<OuterOutput, GradientType = OuterGrad>,
+    InnerInput: GradientType<InnerOutput, GradientType = InnerGrad>
+        + GradientType<OuterOutput, GradientType = Grad>,
+    Self: AutoDiffable<StaticArgs, Input = InnerInput, Output = OuterOutput>,
+    // ensure the shapes of df/dg and dg/dx can be checked before the chain rule multiplies them
+    OuterGrad: ForwardMul<OuterInput, InnerGrad, ResultGrad = Grad>,
+    InnerOutput: Clone,
+    // ensure the results of f, g and f(g) can be checked
+    InnerOutput: CheckValue,
+    InnerGrad: CheckValue,
+    OuterOutput: CheckValue,
+    OuterGrad: CheckValue,
+    Grad: CheckValue,
+    // ensure f(g) can be evaluated from the checked results of f and g
+    ADCompose<
+        ADEvaluated<OuterInput, OuterOutput, OuterGrad>,
+        ADEvaluated<InnerInput, InnerOutput, InnerGrad>,
+    >: AutoDiffable<StaticArgs, Input = InnerInput, Output = OuterOutput>,
+{
+    fn try_eval(
+        &self,
+        x: &InnerInput,
+        static_args: &StaticArgs,
+    ) -> Result<OuterOutput, AutoDiffError> {
+        let g = self
+            .1
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADCompose", 1))?;
+        self.0
+            .try_eval(&g.into(), static_args)
+            .map_err(|e| e.within("ADCompose", 0))
+    }
 
-            df.forward_mul(&dgdconjz)
-                .add(dfdconjg.forward_mul(&dconjgdconjz))
+    fn try_eval_all(
+        &self,
+        x: &InnerInput,
+        static_args: &StaticArgs,
+    ) -> Result<(OuterOutput, Grad, Grad), AutoDiffError> {
+        let g = self
+            .1
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADCompose", 1))?;
+        let f = self
+            .0
+            .try_eval_all(&g.0.clone().into(), static_args)
+            .map_err(|e| e.within("ADCompose", 0))?;
+
+        // forward_mul panics if the axes of df/dg and dg/dx don't match
+        if f.1.try_forward_mul(&g.1).is_none() {
+            return Err(AutoDiffError::shape_mismatch(
+                "ADCompose",
+                f.1.value_shape().unwrap_or_default(),
+                g.1.value_shape().unwrap_or_default(),
+            ));
         }
+
+        let h = ADCompose(ADEvaluated::all(f), ADEvaluated::all(g));
+        check_all("ADCompose", h.eval_all(x, static_args))
     }
 }
 
@@ -1320,6 +3273,7 @@ where
         }
     }
 
+    #[allow(clippy::let_and_return)]
     fn forward_grad(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
@@ -1370,14 +3324,14 @@ where
             // d/dconjz (f(g(z))) = df/dz(g(z)) * dg/dconjz + df/dconjz(g(z)) * dgconj/dconjz
             // and dgconj/dconjz = conj(dg/dz)
 
-            // g and dg/dz * dz
+            // g and dg/dconj(z) * dconj(z)
             let (g, dg) = self.1.eval_forward_conj_grad(x, dx, static_args);
-            // conj(dg/dconj(z) * dconj(z)) = dconj(g)/dz * dz
+            // conj(dg/dz * dz) = dconj(g)/dconj(z) * dconj(z)
             let dgdconjz = self.1.forward_grad(x, dx, static_args);
             // f and df/dg * dg
             let (f, df) = self
                 .0
-                .eval_forward_conj_grad(&g.clone().into(), &dg.into(), static_args);
+                .eval_forward_grad(&g.clone().into(), &dg.into(), static_args);
             // df/dconjg * dconjg
             let dfdconjg = self
                 .0
@@ -1401,14 +3355,14 @@ where
             // d/dconjz (f(g(z))) = df/dz(g(z)) * dg/dconjz + df/dconjz(g(z)) * dgconj/dconjz
             // and dgconj/dconjz = conj(dg/dz)
 
-            // g and dg/dz * dz
+            // g and dg/dconj(z) * dconj(z)
             let (g, dg) = self.1.eval_forward_conj_grad(x, dx, static_args);
-            // conj(dg/dconj(z) * dconj(z)) = dconj(g)/dz * dz
+            // conj(dg/dz * dz) = dconj(g)/dconj(z) * dconj(z)
             let dgdconjz = self.1.forward_grad(x, dx, static_args);
-            // f and df/dg * dg
+            // df/dg * dg
             let df = self
                 .0
-                .forward_conj_grad(&g.clone().into(), &dg.into(), static_args);
+                .forward_grad(&g.clone().into(), &dg.into(), static_args);
             // df/dconjg * dconjg
             let dfdconjg = self
                 .0
@@ -1419,155 +3373,294 @@ where
     }
 }
 
-#[derive(FuncCompose, Debug, Clone, Copy)]
-pub struct ADConstantAdd<A, B>(pub A, pub B);
-
-impl<A: Diffable<StaticArgs>, B, StaticArgs> Diffable<StaticArgs> for ADConstantAdd<A, B>
+impl<StaticArgs, InnerInput, OuterInput, Output, Grad, Outer, Inner> ReverseDiffable<StaticArgs>
+    for ADCompose<Outer, Inner>
 where
-    A::Output: Add<B>,
-    B: Clone,
+    Outer: AutoDiffable<StaticArgs, Input = OuterInput, Output = Output>,
+    Inner: ReverseDiffable<StaticArgs, Input = InnerInput, Output = Output>,
+    InnerInput: GradientType<Output, GradientType = Grad>,
+    // ensure the outer function is a scalar function of the inner one, so its derivatives are
+    // the local partials
+    OuterInput: From<Output> + GradientType<Output, GradientType = Output>,
+    Output: Clone,
 {
-    type Input = A::Input;
-    type Output = <A::Output as Add<B>>::Output;
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        // the outer function only sees the single value g, so its gradient and conjugate gradient
+        // at g are cheap to compute directly
+
+        let u = self.1.eval_reverse(x, tape, static_args);
+        let g = tape.value(u).clone();
+        let (f, dfdg) = self.0.eval_grad(&g.clone().into(), static_args);
+        let dfdconjg = self.0.conj_grad(&g.into(), static_args);
+
+        tape.unary(f, (u, dfdg, dfdconjg))
+    }
 }
 
-impl<StaticArgs, Input, Output, Grad, AOutput, AGrad, A, B> AutoDiffable<StaticArgs>
-    for ADConstantAdd<A, B>
+impl<StaticArgs, InnerInput, InnerOutput, OuterInput, OuterOutput, Outer, Inner>
+    VjpDiffable<StaticArgs> for ADCompose<Outer, Inner>
 where
-    A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
-    Input: GradientType<AOutput, GradientType = AGrad>,
-    // ensure A + B is defined and Output = Output
-    AOutput: Add<B, Output = Output>,
-    AGrad: Add<B, Output = Grad>,
-    // ensure B is Clone and B.zero is defined
-    B: Clone + InstZero,
-    // assign gradient type
-    Input: GradientType<Output, GradientType = Grad>,
+    Outer: VjpDiffable<StaticArgs, Input = OuterInput, Output = OuterOutput>,
+    Inner: VjpDiffable<StaticArgs, Input = InnerInput, Output = InnerOutput>,
+    // ensure values can be passed to the outer function, and its vector-Jacobian products can be
+    // passed back to the inner function as cotangents
+    OuterInput: From<InnerOutput> + PossiblyComplex,
+    InnerOutput: Clone + From<OuterInput> + Conjugate<Output = InnerOutput>,
+    InnerInput:
+        PossiblyComplex + Add<InnerInput, Output = InnerInput> + Conjugate<Output = InnerInput>,
 {
-    fn eval(
+    fn eval_primal(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval(x, static_args).add(self.1.clone())
+        self.0
+            .eval_primal(&self.1.eval_primal(x, static_args).into(), static_args)
     }
 
-    fn eval_grad(
+    fn eval_vjp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
-    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        let (f, df) = self.0.eval_grad(x, static_args);
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        if InnerInput::is_always_real() && OuterInput::is_always_real() {
+            let g = self.1.eval_primal(x, static_args);
+            let (f, vf) = self.0.eval_vjp(&g.into(), cotangent, static_args);
 
-        (f.add(self.1.clone()), df.add(self.1.zero()))
-    }
+            (f, self.1.vjp(x, &vf.into(), static_args))
+        } else {
+            // in the Wirtinger calculus we have
+            //
+            // c^T d/dz (f(g(z))) = c^T df/dz(g(z)) * dg/dz + c^T df/dconjz(g(z)) * dconjg/dz
+            // and dconjg/dz = conj(dg/dconjz), so the second term is
+            // conj(conj(c^T df/dconjz(g(z)))^T dg/dconjz)
 
-    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
-        self.0.grad(x, static_args).add(self.1.zero())
-    }
+            let g = self.1.eval_primal(x, static_args);
+            let (f, vf) = self.0.eval_vjp(&g.clone().into(), cotangent, static_args);
+            let vfconj = InnerOutput::from(self.0.conj_vjp(&g.into(), cotangent, static_args));
 
-    fn eval_conj_grad(
-        &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        let (f, df) = self.0.eval_conj_grad(x, static_args);
+            let vg = self.1.vjp(x, &vf.into(), static_args);
+            let vgconj = self.1.conj_vjp(x, &vfconj.conj(), static_args).conj();
 
-        (f.add(self.1.clone()), df.add(self.1.zero()))
+            (f, vg.add(vgconj))
+        }
     }
 
-    fn conj_grad(
+    fn eval_conj_vjp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
-    ) -> Grad {
-        self.0.conj_grad(x, static_args).add(self.1.zero())
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        if InnerInput::is_always_real() && OuterInput::is_always_real() {
+            self.eval_vjp(x, cotangent, static_args)
+        } else {
+            // in the Wirtinger calculus we have
+            //
+            // c^T d/dconjz (f(g(z))) = c^T df/dz(g(z)) * dg/dconjz + c^T df/dconjz(g(z)) * dgconj/dconjz
+            // and dgconj/dconjz = conj(dg/dz), so the second term is
+            // conj(conj(c^T df/dconjz(g(z)))^T dg/dz)
+
+            let g = self.1.eval_primal(x, static_args);
+            let (f, vf) = self.0.eval_vjp(&g.clone().into(), cotangent, static_args);
+            let vfconj = InnerOutput::from(self.0.conj_vjp(&g.into(), cotangent, static_args));
+
+            let vg = self.1.conj_vjp(x, &vf.into(), static_args);
+            let vgconj = self.1.vjp(x, &vfconj.conj(), static_args).conj();
+
+            (f, vg.add(vgconj))
+        }
     }
 }
 
-impl<StaticArgs, Input, Output, AOutput, A, B> ForwardDiffable<StaticArgs> for ADConstantAdd<A, B>
-where
-    A: ForwardDiffable<StaticArgs, Input = Input, Output = AOutput>,
-    // ensure A + B is defined and Output = Output
-    AOutput: Add<B, Output = Output>,
-    B: Clone + InstZero,
+impl<
+        StaticArgs,
+        OuterInput,
+        OuterOutput,
+        OuterGrad,
+        OuterHess,
+        InnerInput,
+        InnerOutput,
+        InnerGrad,
+        InnerHess,
+        Grad,
+        Hess,
+        Outer,
+        Inner,
+    > HessianDiffable<StaticArgs> for ADCompose<Outer, Inner>
+where
+    Outer: HessianDiffable<StaticArgs, Input = OuterInput, Output = OuterOutput>,
+    Inner: HessianDiffable<StaticArgs, Input = InnerInput, Output = InnerOutput>,
+    // the trait is spelled out in full as the Outer type parameter shadows it
+    OuterInput: From<InnerOutput> + GradientType<OuterOutput, GradientType = OuterGrad>,
+    OuterGrad: Clone + crate::forward::Outer<OuterGrad, Output = OuterHess>,
+    InnerInput: GradientType<InnerOutput, GradientType = InnerGrad>
+        + GradientType<OuterOutput, GradientType = Grad>,
+    // ensure the chain rule products are defined and are Grad and Hess
+    InnerGrad: Clone
+        + crate::forward::Outer<InnerGrad, Output = InnerHess>
+        + Mul<OuterGrad, Output = Grad>,
+    InnerHess: Mul<OuterHess, Output = Hess> + Mul<OuterGrad, Output = Hess>,
+    Grad: crate::forward::Outer<Grad, Output = Hess>,
+    Hess: Add<Hess, Output = Hess>,
 {
-    fn eval_forward(
+    fn eval_hessian(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval_forward(x, static_args).add(self.1.clone())
-    }
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Hess) {
+        let (f, df, d2f) = self.1.eval_hessian(x, static_args);
+        let (g, dg, d2g) = self.0.eval_hessian(&f.into(), static_args);
 
-    fn eval_forward_grad(
-        &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> (
-        <Self as Diffable<StaticArgs>>::Output,
-        <Self as Diffable<StaticArgs>>::Output,
-    ) {
-        let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+        // the chain rule for a scalar inner function f
+        //
+        // d²g(f) = d²g/df² * df ⊗ df + dg/df * d²f
 
-        (f.add(self.1.clone()), df.add(self.1.zero()))
+        let d2gf = df.outer(&df).mul(d2g).add(d2f.mul(dg.clone()));
+
+        (g, df.mul(dg), d2gf)
     }
-    fn eval_forward_conj_grad(
+}
+
+impl<StaticArgs, OuterInput, OuterOutput, InnerInput, InnerOutput, Outer, Inner>
+    HvpDiffable<StaticArgs> for ADCompose<Outer, Inner>
+where
+    Outer: HvpDiffable<StaticArgs, Input = OuterInput, Output = OuterOutput>,
+    Inner: HvpDiffable<StaticArgs, Input = InnerInput, Output = InnerOutput>
+        + ForwardDiffable<StaticArgs, Input = InnerInput, Output = InnerOutput>,
+    // ensure values and tangents can be passed to the outer function, and its vector-Jacobian
+    // products can be passed back to the inner function as cotangents
+    OuterInput: From<InnerOutput>,
+    InnerOutput: From<OuterInput>,
+{
+    fn eval_hvp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
+        v: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        dcotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
     ) -> (
         <Self as Diffable<StaticArgs>>::Output,
-        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+        <Self as Diffable<StaticArgs>>::Input,
     ) {
-        let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+        // the outer function is evaluated along the tangent of the inner function, and its vector-Jacobian
+        // product and derivative are the cotangent and its derivative for the inner function
 
-        (f.add(self.1.clone()), df.add(self.1.zero()))
-    }
+        let (f, df) = self.1.eval_forward_grad(x, v, static_args);
+        let (g, vg, dvg) =
+            self.0
+                .eval_hvp(&f.into(), &df.into(), cotangent, dcotangent, static_args);
+        let (_, vf, dvf) = self.1.eval_hvp(x, v, &vg.into(), &dvg.into(), static_args);
 
-    fn forward_grad(
-        &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.forward_grad(x, dx, static_args).add(self.1.zero())
+        (g, vf, dvf)
     }
+}
 
-    fn forward_conj_grad(
+impl<
+        StaticArgs,
+        OuterInput,
+        OuterOutput,
+        InnerInput,
+        InnerOutput,
+        Outer,
+        Inner,
+        const K: usize,
+    > TaylorDiffable<StaticArgs, K> for ADCompose<Outer, Inner>
+where
+    Outer: TaylorDiffable<StaticArgs, K, Input = OuterInput, Output = OuterOutput>,
+    Inner: TaylorDiffable<StaticArgs, K, Input = InnerInput, Output = InnerOutput>,
+    // ensure the coefficients of the inner series can be passed to the outer function
+    OuterInput: From<InnerOutput>,
+{
+    fn eval_taylor_series(
         &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
+        x: &Taylor<<Self as Diffable<StaticArgs>>::Input, K>,
         static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0
-            .forward_conj_grad(x, dx, static_args)
-            .add(self.1.zero())
+    ) -> Taylor<<Self as Diffable<StaticArgs>>::Output, K> {
+        // the series of the inner function is the argument of the outer function, and composing
+        // truncated series only needs the coefficients up to the same order
+
+        let f = self.1.eval_taylor_series(x, static_args);
+        self.0.eval_taylor_series(&f.map(Into::into), static_args)
     }
 }
 
 #[derive(FuncCompose, Debug, Clone, Copy)]
-pub struct ADConstantSub<A, B>(pub A, pub B);
+pub struct ADConstantAdd<A, B>(pub A, pub B);
 
-impl<A: Diffable<StaticArgs>, B, StaticArgs> Diffable<StaticArgs> for ADConstantSub<A, B>
+impl<A: Diffable<StaticArgs>, B, StaticArgs> Diffable<StaticArgs> for ADConstantAdd<A, B>
 where
-    A::Output: Sub<B>,
+    A::Output: Add<B>,
     B: Clone,
 {
     type Input = A::Input;
-    type Output = <A::Output as Sub<B>>::Output;
+    type Output = <A::Output as Add<B>>::Output;
+}
+
+impl<A, B> Symbolic for ADConstantAdd<A, B>
+where
+    A: Symbolic,
+    B: SymbolicConstant,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::Add(
+            Box::new(self.0.symbolic(x)),
+            Box::new(self.1.symbolic_constant()),
+        )
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADConstantAdd<A, B>
+where
+    A: ToDot<StaticArgs>,
+    B: SymbolicConstant,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let c = graph.constant(&self.1.symbolic_constant().to_string());
+        graph.op("+", self.annotate_at(at), &[(f, None), (c, None)])
+    }
+}
+
+impl<StaticArgs, T, A, B> ToDynExpr<StaticArgs, T> for ADConstantAdd<A, B>
+where
+    A: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+    // ensure the constant can be written as a scalar, e.g. the value of a named constant
+    B: Clone,
+    T: Add<B, Output = T>,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::Add(
+            Box::new(self.0.to_dyn_expr()),
+            Box::new(DynExpr::Const(T::from_constant(&self.1))),
+        )
+    }
 }
 
 impl<StaticArgs, Input, Output, Grad, AOutput, AGrad, A, B> AutoDiffable<StaticArgs>
-    for ADConstantSub<A, B>
+    for ADConstantAdd<A, B>
 where
     A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
     Input: GradientType<AOutput, GradientType = AGrad>,
     // ensure A + B is defined and Output = Output
-    AOutput: Sub<B, Output = Output>,
-    AGrad: Sub<B, Output = Grad>,
+    AOutput: Add<B, Output = Output>,
+    AGrad: Add<B, Output = Grad>,
     // ensure B is Clone and B.zero is defined
     B: Clone + InstZero,
     // assign gradient type
@@ -1578,7 +3671,7 @@ where
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval(x, static_args).sub(self.1.clone())
+        self.0.eval(x, static_args).add(self.1.clone())
     }
 
     fn eval_grad(
@@ -1588,11 +3681,11 @@ where
     ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
         let (f, df) = self.0.eval_grad(x, static_args);
 
-        (f.sub(self.1.clone()), df.sub(self.1.zero()))
+        (f.add(self.1.clone()), df.add(self.1.zero()))
     }
 
     fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
-        self.0.grad(x, static_args).sub(self.1.zero())
+        self.0.grad(x, static_args).add(self.1.zero())
     }
 
     fn eval_conj_grad(
@@ -1602,7 +3695,7 @@ where
     ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
         let (f, df) = self.0.eval_conj_grad(x, static_args);
 
-        (f.sub(self.1.clone()), df.sub(self.1.zero()))
+        (f.add(self.1.clone()), df.add(self.1.zero()))
     }
 
     fn conj_grad(
@@ -1610,15 +3703,73 @@ where
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> Grad {
-        self.0.conj_grad(x, static_args).sub(self.1.zero())
+        self.0.conj_grad(x, static_args).add(self.1.zero())
+    }
+
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+
+        (
+            f.add(self.1.clone()),
+            df.add(self.1.zero()),
+            dfdconjz.add(self.1.zero()),
+        )
     }
 }
 
-impl<StaticArgs, Input, Output, AOutput, A, B> ForwardDiffable<StaticArgs> for ADConstantSub<A, B>
+impl<StaticArgs, Input, AOutput, AGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs>
+    for ADConstantAdd<A, B>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f and f + b, and the constant, can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    B: Clone + CheckValue,
+    // ensure f + b can be evaluated from the checked results of f
+    ADConstantAdd<ADEvaluated<Input, AOutput, AGrad>, B>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADConstantAdd", 0))?;
+        check_shapes("ADConstantAdd", &f, &self.1)?;
+
+        let h = ADConstantAdd(ADEvaluated::value(f), self.1.clone());
+        check_value("ADConstantAdd", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADConstantAdd", 0))?;
+        check_shapes("ADConstantAdd", &f.0, &self.1)?;
+
+        let h = ADConstantAdd(ADEvaluated::all(f), self.1.clone());
+        check_all("ADConstantAdd", h.eval_all(x, static_args))
+    }
+}
+
+impl<StaticArgs, Input, Output, AOutput, A, B> ForwardDiffable<StaticArgs> for ADConstantAdd<A, B>
 where
     A: ForwardDiffable<StaticArgs, Input = Input, Output = AOutput>,
     // ensure A + B is defined and Output = Output
-    AOutput: Sub<B, Output = Output>,
+    AOutput: Add<B, Output = Output>,
     B: Clone + InstZero,
 {
     fn eval_forward(
@@ -1626,8 +3777,9 @@ where
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval_forward(x, static_args).sub(self.1.clone())
+        self.0.eval_forward(x, static_args).add(self.1.clone())
     }
+
     fn eval_forward_grad(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
@@ -1639,7 +3791,7 @@ where
     ) {
         let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
 
-        (f.sub(self.1.clone()), df.sub(self.1.zero()))
+        (f.add(self.1.clone()), df.add(self.1.zero()))
     }
     fn eval_forward_conj_grad(
         &self,
@@ -1652,7 +3804,7 @@ where
     ) {
         let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
 
-        (f.sub(self.1.clone()), df.sub(self.1.zero()))
+        (f.add(self.1.clone()), df.add(self.1.zero()))
     }
 
     fn forward_grad(
@@ -1661,7 +3813,7 @@ where
         dx: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.forward_grad(x, dx, static_args).sub(self.1.zero())
+        self.0.forward_grad(x, dx, static_args).add(self.1.zero())
     }
 
     fn forward_conj_grad(
@@ -1672,161 +3824,213 @@ where
     ) -> <Self as Diffable<StaticArgs>>::Output {
         self.0
             .forward_conj_grad(x, dx, static_args)
-            .sub(self.1.zero())
+            .add(self.1.zero())
     }
 }
 
-#[derive(FuncCompose, Debug, Clone, Copy)]
-pub struct ADConstantMul<A, B>(pub A, pub B);
-
-impl<A: Diffable<StaticArgs>, B, StaticArgs> Diffable<StaticArgs> for ADConstantMul<A, B>
+impl<StaticArgs, Input, Output, Grad, A, B> ReverseDiffable<StaticArgs> for ADConstantAdd<A, B>
 where
-    A::Output: Mul<B>,
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f + b is defined and is Output
+    Output: Clone + InstZero + InstOne + Add<B, Output = Output>,
     B: Clone,
 {
-    type Input = A::Input;
-    type Output = <A::Output as Mul<B>>::Output;
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
+
+        // d(f + b)/df = 1
+        let (one, zero) = (f.one(), f.zero());
+
+        tape.unary(f.add(self.1.clone()), (u, one, zero))
+    }
 }
 
-impl<StaticArgs, Input, Output, Grad, AOutput, AGrad, A, B> AutoDiffable<StaticArgs>
-    for ADConstantMul<A, B>
+impl<StaticArgs, Input, Output, A, B> VjpDiffable<StaticArgs> for ADConstantAdd<A, B>
 where
-    A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
-    Input: GradientType<AOutput, GradientType = AGrad>,
-    // ensure A * B is defined and Output = A * B
-    AOutput: Mul<B, Output = Output>,
-    AGrad: Mul<B, Output = Grad>,
-    // ensure B is Clone
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f + b is defined and is Output
+    Output: Add<B, Output = Output>,
     B: Clone,
-    // assign gradient type
-    Input: GradientType<Output, GradientType = Grad>,
 {
-    fn eval(
+    fn eval_primal(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval(x, static_args).mul(self.1.clone())
+        self.0.eval_primal(x, static_args).add(self.1.clone())
     }
 
-    fn eval_grad(
+    fn eval_vjp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
-    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        let (f, df) = self.0.eval_grad(x, static_args);
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        // c^T d(f + b) = c^T df
 
-        (f.mul(self.1.clone()), df.mul(self.1.clone()))
-    }
+        let (f, vf) = self.0.eval_vjp(x, cotangent, static_args);
 
-    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
-        self.0.grad(x, static_args).mul(self.1.clone())
+        (f.add(self.1.clone()), vf)
     }
 
-    fn eval_conj_grad(
+    fn eval_conj_vjp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
-    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        let (f, df) = self.0.eval_conj_grad(x, static_args);
-
-        (f.mul(self.1.clone()), df.mul(self.1.clone()))
-    }
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, vf) = self.0.eval_conj_vjp(x, cotangent, static_args);
 
-    fn conj_grad(
-        &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> Grad {
-        self.0.conj_grad(x, static_args).mul(self.1.clone())
+        (f.add(self.1.clone()), vf)
     }
 }
 
-impl<StaticArgs, Input, Output, AOutput, A, B> ForwardDiffable<StaticArgs> for ADConstantMul<A, B>
+impl<StaticArgs, Input, Output, Grad, Hess, A, B> HessianDiffable<StaticArgs>
+    for ADConstantAdd<A, B>
 where
-    A: ForwardDiffable<StaticArgs, Input = Input, Output = AOutput>,
-    // ensure A * B is defined and Output = A * B
-    AOutput: Mul<B, Output = Output>,
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f + b is defined and is Output
+    Output: Add<B, Output = Output>,
+    Grad: Outer<Grad, Output = Hess>,
     B: Clone,
 {
-    fn eval_forward(
-        &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval_forward(x, static_args).mul(self.1.clone())
-    }
-    fn eval_forward_grad(
+    fn eval_hessian(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
-    ) -> (
-        <Self as Diffable<StaticArgs>>::Output,
-        <Self as Diffable<StaticArgs>>::Output,
-    ) {
-        let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
-
-        (f.mul(self.1.clone()), df.mul(self.1.clone()))
-    }
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Hess) {
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
 
-    fn forward_grad(
-        &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.forward_grad(x, dx, static_args).mul(self.1.clone())
+        (f.add(self.1.clone()), df, d2f)
     }
+}
 
-    fn eval_forward_conj_grad(
+impl<StaticArgs, Input, Output, A, B> HvpDiffable<StaticArgs> for ADConstantAdd<A, B>
+where
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f + b is defined and is Output
+    Output: Add<B, Output = Output>,
+    B: Clone,
+{
+    fn eval_hvp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
+        v: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        dcotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
     ) -> (
         <Self as Diffable<StaticArgs>>::Output,
-        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+        <Self as Diffable<StaticArgs>>::Input,
     ) {
-        let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+        let (f, vf, dvf) = self.0.eval_hvp(x, v, cotangent, dcotangent, static_args);
 
-        (f.mul(self.1.clone()), df.mul(self.1.clone()))
+        (f.add(self.1.clone()), vf, dvf)
     }
+}
 
-    fn forward_conj_grad(
+impl<StaticArgs, Input, Output, A, B, const K: usize> TaylorDiffable<StaticArgs, K>
+    for ADConstantAdd<A, B>
+where
+    A: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    // ensure f + b is defined and is Output
+    Output: TaylorCoefficient + Add<B, Output = Output>,
+    B: Clone,
+{
+    fn eval_taylor_series(
         &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
+        x: &Taylor<<Self as Diffable<StaticArgs>>::Input, K>,
         static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
+    ) -> Taylor<<Self as Diffable<StaticArgs>>::Output, K> {
         self.0
-            .forward_conj_grad(x, dx, static_args)
-            .mul(self.1.clone())
+            .eval_taylor_series(x, static_args)
+            .add_constant(self.1.clone())
     }
 }
 
 #[derive(FuncCompose, Debug, Clone, Copy)]
-pub struct ADConstantDiv<A, B>(pub A, pub B);
+pub struct ADConstantSub<A, B>(pub A, pub B);
 
-impl<A: Diffable<StaticArgs>, B, StaticArgs> Diffable<StaticArgs> for ADConstantDiv<A, B>
+impl<A: Diffable<StaticArgs>, B, StaticArgs> Diffable<StaticArgs> for ADConstantSub<A, B>
 where
-    A::Output: Div<B>,
+    A::Output: Sub<B>,
     B: Clone,
 {
     type Input = A::Input;
-    type Output = <A::Output as Div<B>>::Output;
+    type Output = <A::Output as Sub<B>>::Output;
+}
+
+impl<A, B> Symbolic for ADConstantSub<A, B>
+where
+    A: Symbolic,
+    B: SymbolicConstant,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::Sub(
+            Box::new(self.0.symbolic(x)),
+            Box::new(self.1.symbolic_constant()),
+        )
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADConstantSub<A, B>
+where
+    A: ToDot<StaticArgs>,
+    B: SymbolicConstant,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let c = graph.constant(&self.1.symbolic_constant().to_string());
+        graph.op(
+            "-",
+            self.annotate_at(at),
+            &[(f, Some("lhs")), (c, Some("rhs"))],
+        )
+    }
+}
+
+impl<StaticArgs, T, A, B> ToDynExpr<StaticArgs, T> for ADConstantSub<A, B>
+where
+    A: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+    // ensure the constant can be written as a scalar, e.g. the value of a named constant
+    B: Clone,
+    T: Add<B, Output = T>,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::Sub(
+            Box::new(self.0.to_dyn_expr()),
+            Box::new(DynExpr::Const(T::from_constant(&self.1))),
+        )
+    }
 }
 
 impl<StaticArgs, Input, Output, Grad, AOutput, AGrad, A, B> AutoDiffable<StaticArgs>
-    for ADConstantDiv<A, B>
+    for ADConstantSub<A, B>
 where
     A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
     Input: GradientType<AOutput, GradientType = AGrad>,
-    // ensure A / B is defined and Output = A * B
-    AOutput: Div<B, Output = Output>,
-    AGrad: Div<B, Output = Grad>,
-    // ensure B is Clone
-    B: Clone,
+    // ensure A + B is defined and Output = Output
+    AOutput: Sub<B, Output = Output>,
+    AGrad: Sub<B, Output = Grad>,
+    // ensure B is Clone and B.zero is defined
+    B: Clone + InstZero,
     // assign gradient type
     Input: GradientType<Output, GradientType = Grad>,
 {
@@ -1835,7 +4039,7 @@ where
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval(x, static_args).div(self.1.clone())
+        self.0.eval(x, static_args).sub(self.1.clone())
     }
 
     fn eval_grad(
@@ -1845,11 +4049,11 @@ where
     ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
         let (f, df) = self.0.eval_grad(x, static_args);
 
-        (f.div(self.1.clone()), df.div(self.1.clone()))
+        (f.sub(self.1.clone()), df.sub(self.1.zero()))
     }
 
     fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
-        self.0.grad(x, static_args).div(self.1.clone())
+        self.0.grad(x, static_args).sub(self.1.zero())
     }
 
     fn eval_conj_grad(
@@ -1859,7 +4063,7 @@ where
     ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
         let (f, df) = self.0.eval_conj_grad(x, static_args);
 
-        (f.div(self.1.clone()), df.div(self.1.clone()))
+        (f.sub(self.1.clone()), df.sub(self.1.zero()))
     }
 
     fn conj_grad(
@@ -1867,23 +4071,81 @@ where
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> Grad {
-        self.0.conj_grad(x, static_args).div(self.1.clone())
+        self.0.conj_grad(x, static_args).sub(self.1.zero())
+    }
+
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+
+        (
+            f.sub(self.1.clone()),
+            df.sub(self.1.zero()),
+            dfdconjz.sub(self.1.zero()),
+        )
     }
 }
 
-impl<StaticArgs, Input, Output, AOutput, A, B> ForwardDiffable<StaticArgs> for ADConstantDiv<A, B>
+impl<StaticArgs, Input, AOutput, AGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs>
+    for ADConstantSub<A, B>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f and f - b, and the constant, can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    B: Clone + CheckValue,
+    // ensure f - b can be evaluated from the checked results of f
+    ADConstantSub<ADEvaluated<Input, AOutput, AGrad>, B>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADConstantSub", 0))?;
+        check_shapes("ADConstantSub", &f, &self.1)?;
+
+        let h = ADConstantSub(ADEvaluated::value(f), self.1.clone());
+        check_value("ADConstantSub", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADConstantSub", 0))?;
+        check_shapes("ADConstantSub", &f.0, &self.1)?;
+
+        let h = ADConstantSub(ADEvaluated::all(f), self.1.clone());
+        check_all("ADConstantSub", h.eval_all(x, static_args))
+    }
+}
+
+impl<StaticArgs, Input, Output, AOutput, A, B> ForwardDiffable<StaticArgs> for ADConstantSub<A, B>
 where
     A: ForwardDiffable<StaticArgs, Input = Input, Output = AOutput>,
-    // ensure A / B is defined and Output = A * B
-    AOutput: Div<B, Output = Output>,
-    B: Clone,
+    // ensure A + B is defined and Output = Output
+    AOutput: Sub<B, Output = Output>,
+    B: Clone + InstZero,
 {
     fn eval_forward(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval_forward(x, static_args).div(self.1.clone())
+        self.0.eval_forward(x, static_args).sub(self.1.clone())
     }
     fn eval_forward_grad(
         &self,
@@ -1896,18 +4158,8 @@ where
     ) {
         let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
 
-        (f.div(self.1.clone()), df.div(self.1.clone()))
-    }
-
-    fn forward_grad(
-        &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.forward_grad(x, dx, static_args).div(self.1.clone())
+        (f.sub(self.1.clone()), df.sub(self.1.zero()))
     }
-
     fn eval_forward_conj_grad(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
@@ -1919,7 +4171,16 @@ where
     ) {
         let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
 
-        (f.div(self.1.clone()), df.div(self.1.clone()))
+        (f.sub(self.1.clone()), df.sub(self.1.zero()))
+    }
+
+    fn forward_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.forward_grad(x, dx, static_args).sub(self.1.zero())
     }
 
     fn forward_conj_grad(
@@ -1930,176 +4191,2747 @@ where
     ) -> <Self as Diffable<StaticArgs>>::Output {
         self.0
             .forward_conj_grad(x, dx, static_args)
-            .div(self.1.clone())
+            .sub(self.1.zero())
     }
 }
 
-#[derive(FuncCompose, Debug, Clone, Copy)]
-pub struct ADConstantPow<A, B>(pub A, pub B);
-
-impl<A: Diffable<StaticArgs>, B, StaticArgs> Diffable<StaticArgs> for ADConstantPow<A, B>
+impl<StaticArgs, Input, Output, Grad, A, B> ReverseDiffable<StaticArgs> for ADConstantSub<A, B>
 where
-    A::Output: Pow<B>,
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f - b is defined and is Output
+    Output: Clone + InstZero + InstOne + Sub<B, Output = Output>,
     B: Clone,
 {
-    type Input = A::Input;
-    type Output = <A::Output as Pow<B>>::Output;
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
+
+        // d(f - b)/df = 1
+        let (one, zero) = (f.one(), f.zero());
+
+        tape.unary(f.sub(self.1.clone()), (u, one, zero))
+    }
 }
 
-impl<StaticArgs, Input, Output, Grad, AOutput, AGrad, ADB, A, B> AutoDiffable<StaticArgs>
-    for ADConstantPow<A, B>
+impl<StaticArgs, Input, Output, A, B> VjpDiffable<StaticArgs> for ADConstantSub<A, B>
 where
-    A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
-    Input: GradientType<AOutput, GradientType = AGrad>,
-    // ensure A is Clone and A^B is defined and is Output
-    AOutput: Clone + Pow<B, Output = Output>,
-    // ensure B is Clone and B.one is defined and B-1 is B
-    B: Clone + InstOne + Sub<B, Output = B>,
-    // ensure A^(B-1) * B is defined and is ADB
-    Output: Mul<B, Output = ADB>,
-    // ensure dA * A^(B-1) * B is defined and is Grad
-    AGrad: Mul<ADB, Output = Grad>,
-    // assign gradient type
-    Input: GradientType<Output, GradientType = Grad>,
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f - b is defined and is Output
+    Output: Sub<B, Output = Output>,
+    B: Clone,
 {
-    fn eval(
+    fn eval_primal(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval(x, static_args).pow(self.1.clone())
+        self.0.eval_primal(x, static_args).sub(self.1.clone())
     }
 
-    fn eval_grad(
+    fn eval_vjp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
-    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        let (f, df) = self.0.eval_grad(x, static_args);
-
-        // d(f^p) = p * f^(p-1) * df
-        // = df * ((f^(p-1)) * p)
-        //
-        // this is true for Wirtinger calculus as well, since the outer function in the chain rule
-        // is z^p, which has conjugate derivative 0
-
-        (
-            f.clone().pow(self.1.clone()),
-            (df.mul(f.pow(self.1.clone().sub(self.1.one())).mul(self.1.clone()))),
-        )
-    }
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        // c^T d(f - b) = c^T df
 
-    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
-        let df = self.0.grad(x, static_args);
+        let (f, vf) = self.0.eval_vjp(x, cotangent, static_args);
 
-        df.mul(
-            self.0
-                .eval(x, static_args)
-                .pow(self.1.clone().sub(self.1.one()))
-                .mul(self.1.clone()),
-        )
+        (f.sub(self.1.clone()), vf)
     }
 
-    fn eval_conj_grad(
+    fn eval_conj_vjp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
-    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        let (f, df) = self.0.eval_conj_grad(x, static_args);
-
-        // d(f^p) = p * f^(p-1) * df
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, vf) = self.0.eval_conj_vjp(x, cotangent, static_args);
 
-        (
-            f.clone().pow(self.1.clone()),
-            (df.mul(f.pow(self.1.clone().sub(self.1.one())).mul(self.1.clone()))),
-        )
+        (f.sub(self.1.clone()), vf)
     }
+}
 
-    fn conj_grad(
+impl<StaticArgs, Input, Output, Grad, Hess, A, B> HessianDiffable<StaticArgs>
+    for ADConstantSub<A, B>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f - b is defined and is Output
+    Output: Sub<B, Output = Output>,
+    Grad: Outer<Grad, Output = Hess>,
+    B: Clone,
+{
+    fn eval_hessian(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
-    ) -> Grad {
-        let df = self.0.conj_grad(x, static_args);
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Hess) {
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
 
-        df.mul(
-            self.0
-                .eval(x, static_args)
-                .pow(self.1.clone().sub(self.1.one()))
-                .mul(self.1.clone()),
-        )
+        (f.sub(self.1.clone()), df, d2f)
     }
 }
 
-impl<StaticArgs, Input, Output, AOutput, APBB, A, B> ForwardDiffable<StaticArgs>
-    for ADConstantPow<A, B>
+impl<StaticArgs, Input, Output, A, B> HvpDiffable<StaticArgs> for ADConstantSub<A, B>
 where
-    A: ForwardDiffable<StaticArgs, Input = Input, Output = AOutput>,
-    // ensure A is Clone and A^B is defined and is Output
-    AOutput: Clone + Pow<B, Output = Output>,
-    // ensure B is Clone and B.one is defined and B-1 is B
-    B: Clone + InstOne + Sub<B, Output = B>,
-    // ensure A^(B-1) * B is defined and is APBB
-    Output: Mul<B, Output = APBB>,
-    // ensure A * A^(B-1) * B is defined and is Output
-    AOutput: Mul<APBB, Output = Output>,
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f - b is defined and is Output
+    Output: Sub<B, Output = Output>,
+    B: Clone,
 {
-    fn eval_forward(
-        &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval_forward(x, static_args).pow(self.1.clone())
-    }
-    fn eval_forward_grad(
+    fn eval_hvp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
+        v: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        dcotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
     ) -> (
         <Self as Diffable<StaticArgs>>::Output,
-        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+        <Self as Diffable<StaticArgs>>::Input,
     ) {
-        let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
-
-        // d(f^p) = p * f^(p-1) * df
-        // = df * ((f^(p-1)) * p)
+        let (f, vf, dvf) = self.0.eval_hvp(x, v, cotangent, dcotangent, static_args);
 
-        (
-            f.clone().pow(self.1.clone()),
-            df.mul(f.pow(self.1.clone().sub(self.1.one())).mul(self.1.clone())),
-        )
+        (f.sub(self.1.clone()), vf, dvf)
     }
+}
 
-    fn forward_grad(
+impl<StaticArgs, Input, Output, A, B, const K: usize> TaylorDiffable<StaticArgs, K>
+    for ADConstantSub<A, B>
+where
+    A: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    // ensure f - b is defined and is Output
+    Output: TaylorCoefficient + Sub<B, Output = Output>,
+    B: Clone,
+{
+    fn eval_taylor_series(
         &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
+        x: &Taylor<<Self as Diffable<StaticArgs>>::Input, K>,
         static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
-        let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
-
-        df.mul(f.pow(self.1.clone().sub(self.1.one())).mul(self.1.clone()))
+    ) -> Taylor<<Self as Diffable<StaticArgs>>::Output, K> {
+        self.0
+            .eval_taylor_series(x, static_args)
+            .sub_constant(self.1.clone())
     }
+}
 
-    fn eval_forward_conj_grad(
-        &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> (
-        <Self as Diffable<StaticArgs>>::Output,
-        <Self as Diffable<StaticArgs>>::Output,
-    ) {
-        let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+#[derive(FuncCompose, Debug, Clone, Copy)]
+pub struct ADConstantMul<A, B>(pub A, pub B);
 
-        // d(f^p) = p * f^(p-1) * df
+impl<A: Diffable<StaticArgs>, B, StaticArgs> Diffable<StaticArgs> for ADConstantMul<A, B>
+where
+    A::Output: Mul<B>,
+    B: Clone,
+{
+    type Input = A::Input;
+    type Output = <A::Output as Mul<B>>::Output;
+}
 
-        (
-            f.clone().pow(self.1.clone()),
-            df.mul(f.pow(self.1.clone().sub(self.1.one())).mul(self.1.clone())),
+impl<A, B> Symbolic for ADConstantMul<A, B>
+where
+    A: Symbolic,
+    B: SymbolicConstant,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::Mul(
+            Box::new(self.0.symbolic(x)),
+            Box::new(self.1.symbolic_constant()),
         )
     }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADConstantMul<A, B>
+where
+    A: ToDot<StaticArgs>,
+    B: SymbolicConstant,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let c = graph.constant(&self.1.symbolic_constant().to_string());
+        graph.op("*", self.annotate_at(at), &[(f, None), (c, None)])
+    }
+}
+
+impl<StaticArgs, T, A, B> ToDynExpr<StaticArgs, T> for ADConstantMul<A, B>
+where
+    A: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+    // ensure the constant can be written as a scalar, e.g. the value of a named constant
+    B: Clone,
+    T: Add<B, Output = T>,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::Mul(
+            Box::new(self.0.to_dyn_expr()),
+            Box::new(DynExpr::Const(T::from_constant(&self.1))),
+        )
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, AOutput, AGrad, A, B> AutoDiffable<StaticArgs>
+    for ADConstantMul<A, B>
+where
+    A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    // ensure A * B is defined and Output = A * B
+    AOutput: Mul<B, Output = Output>,
+    AGrad: Mul<B, Output = Grad>,
+    // ensure B is Clone
+    B: Clone,
+    // assign gradient type
+    Input: GradientType<Output, GradientType = Grad>,
+{
+    fn eval(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval(x, static_args).mul(self.1.clone())
+    }
+
+    fn eval_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        let (f, df) = self.0.eval_grad(x, static_args);
+
+        (f.mul(self.1.clone()), df.mul(self.1.clone()))
+    }
+
+    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
+        self.0.grad(x, static_args).mul(self.1.clone())
+    }
+
+    fn eval_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        let (f, df) = self.0.eval_conj_grad(x, static_args);
+
+        (f.mul(self.1.clone()), df.mul(self.1.clone()))
+    }
+
+    fn conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> Grad {
+        self.0.conj_grad(x, static_args).mul(self.1.clone())
+    }
+
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+
+        (
+            f.mul(self.1.clone()),
+            df.mul(self.1.clone()),
+            dfdconjz.mul(self.1.clone()),
+        )
+    }
+}
+
+impl<StaticArgs, Input, AOutput, AGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs>
+    for ADConstantMul<A, B>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f and f * b, and the constant, can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    B: Clone + CheckValue,
+    // ensure f * b can be evaluated from the checked results of f
+    ADConstantMul<ADEvaluated<Input, AOutput, AGrad>, B>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADConstantMul", 0))?;
+        check_shapes("ADConstantMul", &f, &self.1)?;
+
+        let h = ADConstantMul(ADEvaluated::value(f), self.1.clone());
+        check_value("ADConstantMul", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADConstantMul", 0))?;
+        check_shapes("ADConstantMul", &f.0, &self.1)?;
+
+        let h = ADConstantMul(ADEvaluated::all(f), self.1.clone());
+        check_all("ADConstantMul", h.eval_all(x, static_args))
+    }
+}
+
+impl<StaticArgs, Input, Output, AOutput, A, B> ForwardDiffable<StaticArgs> for ADConstantMul<A, B>
+where
+    A: ForwardDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    // ensure A * B is defined and Output = A * B
+    AOutput: Mul<B, Output = Output>,
+    B: Clone,
+{
+    fn eval_forward(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval_forward(x, static_args).mul(self.1.clone())
+    }
+    fn eval_forward_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Output,
+    ) {
+        let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+
+        (f.mul(self.1.clone()), df.mul(self.1.clone()))
+    }
+
+    fn forward_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.forward_grad(x, dx, static_args).mul(self.1.clone())
+    }
+
+    fn eval_forward_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Output,
+    ) {
+        let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+
+        (f.mul(self.1.clone()), df.mul(self.1.clone()))
+    }
+
+    fn forward_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0
+            .forward_conj_grad(x, dx, static_args)
+            .mul(self.1.clone())
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, A, B> ReverseDiffable<StaticArgs> for ADConstantMul<A, B>
+where
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f * b is defined and is Output
+    Output: Clone + InstZero + InstOne + Mul<B, Output = Output>,
+    B: Clone,
+{
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
+
+        // d(f * b)/df = b
+        let (one, zero) = (f.one(), f.zero());
+
+        tape.unary(f.mul(self.1.clone()), (u, one.mul(self.1.clone()), zero))
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B> VjpDiffable<StaticArgs> for ADConstantMul<A, B>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f * b and the cotangent c * b are defined and are Output
+    Output: Clone + Mul<B, Output = Output>,
+    B: Clone,
+{
+    fn eval_primal(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval_primal(x, static_args).mul(self.1.clone())
+    }
+
+    fn eval_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        // c^T d(f * b) = (c * b)^T df
+
+        let (f, vf) = self
+            .0
+            .eval_vjp(x, &cotangent.clone().mul(self.1.clone()), static_args);
+
+        (f.mul(self.1.clone()), vf)
+    }
+
+    fn eval_conj_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, vf) = self
+            .0
+            .eval_conj_vjp(x, &cotangent.clone().mul(self.1.clone()), static_args);
+
+        (f.mul(self.1.clone()), vf)
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, Hess, A, B> HessianDiffable<StaticArgs>
+    for ADConstantMul<A, B>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f * b, df * b and d²f * b are defined
+    Output: Mul<B, Output = Output>,
+    Grad: Mul<B, Output = Grad> + Outer<Grad, Output = Hess>,
+    Hess: Mul<B, Output = Hess>,
+    B: Clone,
+{
+    fn eval_hessian(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Hess) {
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
+
+        (
+            f.mul(self.1.clone()),
+            df.mul(self.1.clone()),
+            d2f.mul(self.1.clone()),
+        )
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B> HvpDiffable<StaticArgs> for ADConstantMul<A, B>
+where
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f * b and c * b are defined and are Output
+    Output: Clone + Mul<B, Output = Output>,
+    B: Clone,
+{
+    fn eval_hvp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        v: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, vf, dvf) = self.0.eval_hvp(
+            x,
+            v,
+            &cotangent.clone().mul(self.1.clone()),
+            &dcotangent.clone().mul(self.1.clone()),
+            static_args,
+        );
+
+        (f.mul(self.1.clone()), vf, dvf)
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B, const K: usize> TaylorDiffable<StaticArgs, K>
+    for ADConstantMul<A, B>
+where
+    A: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    // ensure f * b is defined and is Output
+    Output: Mul<B, Output = Output>,
+    B: Clone,
+{
+    fn eval_taylor_series(
+        &self,
+        x: &Taylor<<Self as Diffable<StaticArgs>>::Input, K>,
+        static_args: &StaticArgs,
+    ) -> Taylor<<Self as Diffable<StaticArgs>>::Output, K> {
+        self.0
+            .eval_taylor_series(x, static_args)
+            .mul_constant(self.1.clone())
+    }
+}
+
+#[derive(FuncCompose, Debug, Clone, Copy)]
+pub struct ADConstantDiv<A, B>(pub A, pub B);
+
+impl<A: Diffable<StaticArgs>, B, StaticArgs> Diffable<StaticArgs> for ADConstantDiv<A, B>
+where
+    A::Output: Div<B>,
+    B: Clone,
+{
+    type Input = A::Input;
+    type Output = <A::Output as Div<B>>::Output;
+}
+
+impl<A, B> Symbolic for ADConstantDiv<A, B>
+where
+    A: Symbolic,
+    B: SymbolicConstant,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::Div(
+            Box::new(self.0.symbolic(x)),
+            Box::new(self.1.symbolic_constant()),
+        )
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADConstantDiv<A, B>
+where
+    A: ToDot<StaticArgs>,
+    B: SymbolicConstant,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let c = graph.constant(&self.1.symbolic_constant().to_string());
+        graph.op(
+            "/",
+            self.annotate_at(at),
+            &[(f, Some("lhs")), (c, Some("rhs"))],
+        )
+    }
+}
+
+impl<StaticArgs, T, A, B> ToDynExpr<StaticArgs, T> for ADConstantDiv<A, B>
+where
+    A: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+    // ensure the constant can be written as a scalar, e.g. the value of a named constant
+    B: Clone,
+    T: Add<B, Output = T>,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::Div(
+            Box::new(self.0.to_dyn_expr()),
+            Box::new(DynExpr::Const(T::from_constant(&self.1))),
+        )
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, AOutput, AGrad, A, B> AutoDiffable<StaticArgs>
+    for ADConstantDiv<A, B>
+where
+    A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    // ensure A / B is defined and Output = A * B
+    AOutput: Div<B, Output = Output>,
+    AGrad: Div<B, Output = Grad>,
+    // ensure B is Clone
+    B: Clone,
+    // assign gradient type
+    Input: GradientType<Output, GradientType = Grad>,
+{
+    fn eval(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval(x, static_args).div(self.1.clone())
+    }
+
+    fn eval_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        let (f, df) = self.0.eval_grad(x, static_args);
+
+        (f.div(self.1.clone()), df.div(self.1.clone()))
+    }
+
+    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
+        self.0.grad(x, static_args).div(self.1.clone())
+    }
+
+    fn eval_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        let (f, df) = self.0.eval_conj_grad(x, static_args);
+
+        (f.div(self.1.clone()), df.div(self.1.clone()))
+    }
+
+    fn conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> Grad {
+        self.0.conj_grad(x, static_args).div(self.1.clone())
+    }
+
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+
+        (
+            f.div(self.1.clone()),
+            df.div(self.1.clone()),
+            dfdconjz.div(self.1.clone()),
+        )
+    }
+}
+
+impl<StaticArgs, Input, AOutput, AGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs>
+    for ADConstantDiv<A, B>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f and f / b, and the constant, can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    B: Clone + CheckValue,
+    // ensure f / b can be evaluated from the checked results of f
+    ADConstantDiv<ADEvaluated<Input, AOutput, AGrad>, B>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADConstantDiv", 0))?;
+        check_shapes("ADConstantDiv", &f, &self.1)?;
+        if self.1.has_zero() {
+            return Err(AutoDiffError::domain("ADConstantDiv", "division by zero"));
+        }
+
+        let h = ADConstantDiv(ADEvaluated::value(f), self.1.clone());
+        check_value("ADConstantDiv", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADConstantDiv", 0))?;
+        check_shapes("ADConstantDiv", &f.0, &self.1)?;
+        if self.1.has_zero() {
+            return Err(AutoDiffError::domain("ADConstantDiv", "division by zero"));
+        }
+
+        let h = ADConstantDiv(ADEvaluated::all(f), self.1.clone());
+        check_all("ADConstantDiv", h.eval_all(x, static_args))
+    }
+}
+
+impl<StaticArgs, Input, Output, AOutput, A, B> ForwardDiffable<StaticArgs> for ADConstantDiv<A, B>
+where
+    A: ForwardDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    // ensure A / B is defined and Output = A * B
+    AOutput: Div<B, Output = Output>,
+    B: Clone,
+{
+    fn eval_forward(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval_forward(x, static_args).div(self.1.clone())
+    }
+    fn eval_forward_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Output,
+    ) {
+        let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+
+        (f.div(self.1.clone()), df.div(self.1.clone()))
+    }
+
+    fn forward_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.forward_grad(x, dx, static_args).div(self.1.clone())
+    }
+
+    fn eval_forward_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Output,
+    ) {
+        let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+
+        (f.div(self.1.clone()), df.div(self.1.clone()))
+    }
+
+    fn forward_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0
+            .forward_conj_grad(x, dx, static_args)
+            .div(self.1.clone())
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, A, B> ReverseDiffable<StaticArgs> for ADConstantDiv<A, B>
+where
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f / b is defined and is Output
+    Output: Clone + InstZero + InstOne + Div<B, Output = Output>,
+    B: Clone,
+{
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
+
+        // d(f / b)/df = 1 / b
+        let (one, zero) = (f.one(), f.zero());
+
+        tape.unary(f.div(self.1.clone()), (u, one.div(self.1.clone()), zero))
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B> VjpDiffable<StaticArgs> for ADConstantDiv<A, B>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f / b and the cotangent c / b are defined and are Output
+    Output: Clone + Div<B, Output = Output>,
+    B: Clone,
+{
+    fn eval_primal(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval_primal(x, static_args).div(self.1.clone())
+    }
+
+    fn eval_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        // c^T d(f / b) = (c / b)^T df
+
+        let (f, vf) = self
+            .0
+            .eval_vjp(x, &cotangent.clone().div(self.1.clone()), static_args);
+
+        (f.div(self.1.clone()), vf)
+    }
+
+    fn eval_conj_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, vf) = self
+            .0
+            .eval_conj_vjp(x, &cotangent.clone().div(self.1.clone()), static_args);
+
+        (f.div(self.1.clone()), vf)
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, Hess, A, B> HessianDiffable<StaticArgs>
+    for ADConstantDiv<A, B>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f / b, df / b and d²f / b are defined
+    Output: Div<B, Output = Output>,
+    Grad: Div<B, Output = Grad> + Outer<Grad, Output = Hess>,
+    Hess: Div<B, Output = Hess>,
+    B: Clone,
+{
+    fn eval_hessian(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Hess) {
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
+
+        (
+            f.div(self.1.clone()),
+            df.div(self.1.clone()),
+            d2f.div(self.1.clone()),
+        )
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B> HvpDiffable<StaticArgs> for ADConstantDiv<A, B>
+where
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f / b and c / b are defined and are Output
+    Output: Clone + Div<B, Output = Output>,
+    B: Clone,
+{
+    fn eval_hvp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        v: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, vf, dvf) = self.0.eval_hvp(
+            x,
+            v,
+            &cotangent.clone().div(self.1.clone()),
+            &dcotangent.clone().div(self.1.clone()),
+            static_args,
+        );
+
+        (f.div(self.1.clone()), vf, dvf)
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B, const K: usize> TaylorDiffable<StaticArgs, K>
+    for ADConstantDiv<A, B>
+where
+    A: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    // ensure f / b is defined and is Output
+    Output: Div<B, Output = Output>,
+    B: Clone,
+{
+    fn eval_taylor_series(
+        &self,
+        x: &Taylor<<Self as Diffable<StaticArgs>>::Input, K>,
+        static_args: &StaticArgs,
+    ) -> Taylor<<Self as Diffable<StaticArgs>>::Output, K> {
+        self.0
+            .eval_taylor_series(x, static_args)
+            .div_constant(self.1.clone())
+    }
+}
+
+#[derive(FuncCompose, Debug, Clone, Copy)]
+pub struct ADConstantPow<A, B>(pub A, pub B);
+
+impl<A: Diffable<StaticArgs>, B, StaticArgs> Diffable<StaticArgs> for ADConstantPow<A, B>
+where
+    A::Output: Pow<B>,
+    B: Clone,
+{
+    type Input = A::Input;
+    type Output = <A::Output as Pow<B>>::Output;
+}
+
+impl<A, B> Symbolic for ADConstantPow<A, B>
+where
+    A: Symbolic,
+    B: SymbolicConstant,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::Pow(
+            Box::new(self.0.symbolic(x)),
+            Box::new(self.1.symbolic_constant()),
+        )
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADConstantPow<A, B>
+where
+    A: ToDot<StaticArgs>,
+    B: SymbolicConstant,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let c = graph.constant(&self.1.symbolic_constant().to_string());
+        graph.op(
+            "^",
+            self.annotate_at(at),
+            &[(f, Some("lhs")), (c, Some("rhs"))],
+        )
+    }
+}
+
+impl<StaticArgs, T, A, B> ToDynExpr<StaticArgs, T> for ADConstantPow<A, B>
+where
+    A: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+    // ensure the constant can be written as a scalar, e.g. the value of a named constant
+    B: Clone,
+    T: Add<B, Output = T>,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::Pow(Box::new(self.0.to_dyn_expr()), T::from_constant(&self.1))
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, AOutput, AGrad, ADB, A, B> AutoDiffable<StaticArgs>
+    for ADConstantPow<A, B>
+where
+    A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    // ensure A is Clone and A^B is defined and is Output
+    AOutput: Clone + Pow<B, Output = Output>,
+    // ensure B is Clone and B.one is defined and B-1 is B
+    B: Clone + InstOne + Sub<B, Output = B>,
+    // ensure A^(B-1) * B is defined and is ADB
+    Output: Mul<B, Output = ADB>,
+    // ensure dA * A^(B-1) * B is defined and is Grad
+    AGrad: Mul<ADB, Output = Grad>,
+    // assign gradient type
+    Input: GradientType<Output, GradientType = Grad>,
+{
+    fn eval(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval(x, static_args).pow(self.1.clone())
+    }
+
+    fn eval_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        let (f, df) = self.0.eval_grad(x, static_args);
+
+        // d(f^p) = p * f^(p-1) * df
+        // = df * ((f^(p-1)) * p)
+        //
+        // this is true for Wirtinger calculus as well, since the outer function in the chain rule
+        // is z^p, which has conjugate derivative 0
+
+        (
+            f.clone().pow(self.1.clone()),
+            (df.mul(f.pow(self.1.clone().sub(self.1.one())).mul(self.1.clone()))),
+        )
+    }
+
+    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
+        let df = self.0.grad(x, static_args);
+
+        df.mul(
+            self.0
+                .eval(x, static_args)
+                .pow(self.1.clone().sub(self.1.one()))
+                .mul(self.1.clone()),
+        )
+    }
+
+    fn eval_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        let (f, df) = self.0.eval_conj_grad(x, static_args);
+
+        // d(f^p) = p * f^(p-1) * df
+
+        (
+            f.clone().pow(self.1.clone()),
+            (df.mul(f.pow(self.1.clone().sub(self.1.one())).mul(self.1.clone()))),
+        )
+    }
+
+    fn conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> Grad {
+        let df = self.0.conj_grad(x, static_args);
+
+        df.mul(
+            self.0
+                .eval(x, static_args)
+                .pow(self.1.clone().sub(self.1.one()))
+                .mul(self.1.clone()),
+        )
+    }
+
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+
+        // d(f^p) = p * f^(p-1) * df for both derivatives, since z^p is holomorphic
+
+        let p_1 = self.1.clone().sub(self.1.one());
+        (
+            f.clone().pow(self.1.clone()),
+            df.mul(f.clone().pow(p_1.clone()).mul(self.1.clone())),
+            dfdconjz.mul(f.pow(p_1).mul(self.1.clone())),
+        )
+    }
+}
+
+impl<StaticArgs, Input, AOutput, AGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs>
+    for ADConstantPow<A, B>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f and f^b, and the constant, can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    B: Clone + CheckValue,
+    // ensure f^b can be evaluated from the checked results of f
+    ADConstantPow<ADEvaluated<Input, AOutput, AGrad>, B>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADConstantPow", 0))?;
+        check_shapes("ADConstantPow", &f, &self.1)?;
+
+        let h = ADConstantPow(ADEvaluated::value(f), self.1.clone());
+        check_value("ADConstantPow", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADConstantPow", 0))?;
+        check_shapes("ADConstantPow", &f.0, &self.1)?;
+
+        let h = ADConstantPow(ADEvaluated::all(f), self.1.clone());
+        check_all("ADConstantPow", h.eval_all(x, static_args))
+    }
+}
+
+impl<StaticArgs, Input, Output, AOutput, APBB, A, B> ForwardDiffable<StaticArgs>
+    for ADConstantPow<A, B>
+where
+    A: ForwardDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    // ensure A is Clone and A^B is defined and is Output
+    AOutput: Clone + Pow<B, Output = Output>,
+    // ensure B is Clone and B.one is defined and B-1 is B
+    B: Clone + InstOne + Sub<B, Output = B>,
+    // ensure A^(B-1) * B is defined and is APBB
+    Output: Mul<B, Output = APBB>,
+    // ensure A * A^(B-1) * B is defined and is Output
+    AOutput: Mul<APBB, Output = Output>,
+{
+    fn eval_forward(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval_forward(x, static_args).pow(self.1.clone())
+    }
+    fn eval_forward_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Output,
+    ) {
+        let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+
+        // d(f^p) = p * f^(p-1) * df
+        // = df * ((f^(p-1)) * p)
+
+        (
+            f.clone().pow(self.1.clone()),
+            df.mul(f.pow(self.1.clone().sub(self.1.one())).mul(self.1.clone())),
+        )
+    }
+
+    fn forward_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+
+        df.mul(f.pow(self.1.clone().sub(self.1.one())).mul(self.1.clone()))
+    }
+
+    fn eval_forward_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Output,
+    ) {
+        let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+
+        // d(f^p) = p * f^(p-1) * df
+
+        (
+            f.clone().pow(self.1.clone()),
+            df.mul(f.pow(self.1.clone().sub(self.1.one())).mul(self.1.clone())),
+        )
+    }
+
+    fn forward_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+
+        df.mul(f.pow(self.1.clone().sub(self.1.one())).mul(self.1.clone()))
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, A, B> ReverseDiffable<StaticArgs> for ADConstantPow<A, B>
+where
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f^p and f^(p-1) * p are defined and are Output
+    Output: Clone + InstZero + InstOne + Pow<B, Output = Output> + Mul<B, Output = Output>,
+    // ensure B is Clone and B.one is defined and B-1 is B
+    B: Clone + InstOne + Sub<B, Output = B>,
+{
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
+
+        // d(f^p)/df = f^(p-1) * p
+        let zero = f.zero();
+        let dfp = f
+            .clone()
+            .pow(self.1.clone().sub(self.1.one()))
+            .mul(self.1.clone());
+
+        tape.unary(f.pow(self.1.clone()), (u, dfp, zero))
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B> VjpDiffable<StaticArgs> for ADConstantPow<A, B>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f^p, f^(p-1) * p and the cotangent c * f^(p-1) * p are defined and are Output
+    Output:
+        Clone + Pow<B, Output = Output> + Mul<B, Output = Output> + Mul<Output, Output = Output>,
+    // ensure B is Clone and B.one is defined and B-1 is B
+    B: Clone + InstOne + Sub<B, Output = B>,
+{
+    fn eval_primal(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval_primal(x, static_args).pow(self.1.clone())
+    }
+
+    fn eval_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let f = self.0.eval_primal(x, static_args);
+
+        // c^T d(f^p) = (c * f^(p-1) * p)^T df
+
+        let dfp = f
+            .clone()
+            .pow(self.1.clone().sub(self.1.one()))
+            .mul(self.1.clone());
+        let vf = self.0.vjp(x, &cotangent.clone().mul(dfp), static_args);
+
+        (f.pow(self.1.clone()), vf)
+    }
+
+    fn eval_conj_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let f = self.0.eval_primal(x, static_args);
+
+        let dfp = f
+            .clone()
+            .pow(self.1.clone().sub(self.1.one()))
+            .mul(self.1.clone());
+        let vf = self.0.conj_vjp(x, &cotangent.clone().mul(dfp), static_args);
+
+        (f.pow(self.1.clone()), vf)
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, Hess, A, B> HessianDiffable<StaticArgs>
+    for ADConstantPow<A, B>
+where
+    A: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f^p, f^(p-1) * p and f^(p-2) * p * (p-1) are defined and are Output
+    Output:
+        Clone + Pow<B, Output = Output> + Mul<B, Output = Output> + Mul<Output, Output = Output>,
+    // ensure B is Clone and B.one is defined and B-1 is B
+    B: Clone + InstOne + Sub<B, Output = B>,
+    Grad: Clone + Mul<Output, Output = Grad> + Outer<Grad, Output = Hess>,
+    Hess: Add<Hess, Output = Hess> + Mul<Output, Output = Hess>,
+{
+    fn eval_hessian(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Hess) {
+        let (f, df, d2f) = self.0.eval_hessian(x, static_args);
+
+        // d²(f^p) = f^(p-2) * p * (p-1) * df ⊗ df + f^(p-1) * p * d²f
+
+        let p_m1 = self.1.clone().sub(self.1.one());
+        let p_m2 = p_m1.clone().sub(self.1.one());
+        let dfp = f.clone().pow(p_m1.clone()).mul(self.1.clone());
+        let d2fp = f.clone().pow(p_m2).mul(self.1.clone()).mul(p_m1);
+
+        (
+            f.pow(self.1.clone()),
+            df.clone().mul(dfp.clone()),
+            df.outer(&df).mul(d2fp).add(d2f.mul(dfp)),
+        )
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B> HvpDiffable<StaticArgs> for ADConstantPow<A, B>
+where
+    A: HvpDiffable<StaticArgs, Input = Input, Output = Output>
+        + ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f^p, f^(p-1) * p and f^(p-2) * p * (p-1) are defined and are Output
+    Output: Clone
+        + Pow<B, Output = Output>
+        + Mul<B, Output = Output>
+        + Add<Output, Output = Output>
+        + Mul<Output, Output = Output>,
+    // ensure B is Clone and B.one is defined and B-1 is B
+    B: Clone + InstOne + Sub<B, Output = B>,
+{
+    fn eval_hvp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        v: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let (f, df) = self.0.eval_forward_grad(x, v, static_args);
+
+        // the cotangent of f is c * f^(p-1) * p, so along v
+        // d(c * f^(p-1) * p) = dc * f^(p-1) * p + c * f^(p-2) * p * (p-1) * df
+
+        let p_m1 = self.1.clone().sub(self.1.one());
+        let p_m2 = p_m1.clone().sub(self.1.one());
+        let dfp = f.clone().pow(p_m1.clone()).mul(self.1.clone());
+        let d2fp = f.clone().pow(p_m2).mul(self.1.clone()).mul(p_m1);
+
+        let cf = cotangent.clone().mul(dfp.clone());
+        let dcf = dcotangent
+            .clone()
+            .mul(dfp)
+            .add(cotangent.clone().mul(d2fp).mul(df));
+
+        let (_, vf, dvf) = self.0.eval_hvp(x, v, &cf, &dcf, static_args);
+
+        (f.pow(self.1.clone()), vf, dvf)
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B, const K: usize> TaylorDiffable<StaticArgs, K>
+    for ADConstantPow<A, B>
+where
+    A: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    // ensure f^p is defined on the series of f and is Output
+    Output: Pow<B, Output = Output>,
+    Taylor<Output, K>: Pow<B, Output = Taylor<Output, K>>,
+    B: Clone,
+{
+    fn eval_taylor_series(
+        &self,
+        x: &Taylor<<Self as Diffable<StaticArgs>>::Input, K>,
+        static_args: &StaticArgs,
+    ) -> Taylor<<Self as Diffable<StaticArgs>>::Output, K> {
+        self.0
+            .eval_taylor_series(x, static_args)
+            .pow(self.1.clone())
+    }
+}
+
+// cotangent^T * dg(f)/dz for a unary operation g(f) with Wirtinger derivatives dg/df and
+// dg/dconjf. In the Wirtinger calculus we have
+//
+// dg(f)/dz = dg/df * df/dz + dg/dconjf * conj(df/dconjz)
+//
+// so c^T dg(f)/dz = (c * dg/df)^T df/dz + conj(conj(c * dg/dconjf)^T df/dconjz)
+fn wirtinger_vjp<StaticArgs, Input, Output, A>(
+    f: &A,
+    x: &Input,
+    cotangent: &Output,
+    dgdf: Output,
+    dgdconjf: Output,
+    static_args: &StaticArgs,
+) -> Input
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: Add<Input, Output = Input> + Conjugate<Output = Input>,
+    Output: Clone + Mul<Output, Output = Output> + Conjugate<Output = Output>,
+{
+    let vf = f.vjp(x, &cotangent.clone().mul(dgdf), static_args);
+    let vfconj = f
+        .conj_vjp(x, &cotangent.clone().mul(dgdconjf).conj(), static_args)
+        .conj();
+
+    vf.add(vfconj)
+}
+
+// cotangent^T * dg(f)/dconjz for a unary operation g(f) with Wirtinger derivatives dg/df and
+// dg/dconjf. In the Wirtinger calculus we have
+//
+// dg(f)/dconjz = dg/df * df/dconjz + dg/dconjf * conj(df/dz)
+//
+// so c^T dg(f)/dconjz = (c * dg/df)^T df/dconjz + conj(conj(c * dg/dconjf)^T df/dz)
+fn wirtinger_conj_vjp<StaticArgs, Input, Output, A>(
+    f: &A,
+    x: &Input,
+    cotangent: &Output,
+    dgdf: Output,
+    dgdconjf: Output,
+    static_args: &StaticArgs,
+) -> Input
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: Add<Input, Output = Input> + Conjugate<Output = Input>,
+    Output: Clone + Mul<Output, Output = Output> + Conjugate<Output = Output>,
+{
+    let vf = f.conj_vjp(x, &cotangent.clone().mul(dgdf), static_args);
+    let vfconj = f
+        .vjp(x, &cotangent.clone().mul(dgdconjf).conj(), static_args)
+        .conj();
+
+    vf.add(vfconj)
+}
+
+#[derive(FuncCompose, Debug, Clone, Copy)]
+pub struct ADAbs<A>(pub A);
+
+impl<A: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs> for ADAbs<A> {
+    type Input = A::Input;
+    type Output = A::Output;
+}
+
+impl<A> Symbolic for ADAbs<A>
+where
+    A: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::call("abs", vec![self.0.symbolic(x)])
+    }
+}
+
+impl<StaticArgs, A> ToDot<StaticArgs> for ADAbs<A>
+where
+    A: ToDot<StaticArgs>,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        graph.op("abs", self.annotate_at(at), &[(f, None)])
+    }
+}
+
+impl<StaticArgs, T, A> ToDynExpr<StaticArgs, T> for ADAbs<A>
+where
+    A: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+    // ensure |x| can be boxed as an opaque function of the simplified f
+    ADAbs<Identity<StaticArgs, T>>: AutoDiffable<StaticArgs, Input = T, Output = T>
+        + ForwardDiffable<StaticArgs, Input = T, Output = T>
+        + Symbolic
+        + Clone
+        + 'static,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::apply(ADAbs(Identity::new()), self.0.to_dyn_expr())
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, A> AutoDiffable<StaticArgs> for ADAbs<A>
+where
+    A: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: PossiblyComplex + GradientType<Output, GradientType = Grad>,
+    Output: Clone
+        + PossiblyComplex
+        + Add<Output, Output = Output>
+        + InstOne
+        + Div<Output, Output = Output>
+        + Abs<Output = Output>
+        + Signum<Output = Output>
+        + Conjugate<Output = Output>,
+    Grad: Conjugate<Output = Grad> + Mul<Output, Output = Grad> + Add<Grad, Output = Grad>,
+{
+    fn eval(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval(x, static_args).abs()
+    }
+
+    fn eval_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        if Input::is_always_real() && Output::is_always_real() {
+            let (f, df) = self.0.eval_grad(x, static_args);
+
+            (f.clone().abs(), df.mul(f.signum()))
+        } else {
+            // both Wirtinger derivatives of f are needed, see eval_all
+            let (f, df, _) = self.eval_all(x, static_args);
+            (f, df)
+        }
+    }
+
+    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
+        if Input::is_always_real() && Output::is_always_real() {
+            let df = self.0.grad(x, static_args);
+
+            df.mul(self.0.eval(x, static_args).signum())
+        } else {
+            self.eval_all(x, static_args).1
+        }
+    }
+
+    fn eval_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        if Input::is_always_real() && Output::is_always_real() {
+            let (f, df) = self.0.eval_conj_grad(x, static_args);
+
+            (f.clone().abs(), df.mul(f.signum()))
+        } else {
+            let (f, _, dfdconjz) = self.eval_all(x, static_args);
+            (f, dfdconjz)
+        }
+    }
+
+    fn conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> Grad {
+        if Input::is_always_real() && Output::is_always_real() {
+            let df = self.0.conj_grad(x, static_args);
+
+            df.mul(self.0.eval(x, static_args).signum())
+        } else {
+            self.eval_all(x, static_args).2
+        }
+    }
+
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+
+        if Input::is_always_real() && Output::is_always_real() {
+            let sign = f.clone().signum();
+
+            (f.abs(), df.mul(sign.clone()), dfdconjz.mul(sign))
+        } else {
+            // in the Wirtinger calculus we have
+            // |z| = sqrt(z * conj(z))
+            // d|z|/dz = 1/2 * conj(z) * (z * conj(z))^(-1/2)
+            //         = 1/2 * conj(z) * |z|^(-1)
+            // cannot simplify further, since we would be assuming the "sign" of z
+            // d|z|/dconjz = 1/2 * z * (z * conj(z))^(-1/2)
+            //            = 1/2 * z * |z|^(-1)
+            // cannot simplify further, since we would be assuming the "sign" of z
+            //
+            // now for |f|, we have
+            //
+            // d|f|/dz = d|f|/df * df/dz + d|f|/dconjf * conj(df/dconjz)
+            //        = 0.5*conj(f)/|f| * df/dz + 0.5*f/|f| * conj(df/dconjz)
+            //
+            // and
+            //
+            // d|f|/dconjz = d|f|/df * df/dconjz + d|f|/dconjf * conj(df/dz)
+            //          = 0.5*conj(f)/|f| * df/dconjz + 0.5*f/|f| * conj(df/dz)
+            //
+            // so f is evaluated once, with both of its derivatives
+
+            // note that for purely real z, this reduces to the real case
+
+            let dconjfdz = dfdconjz.conj();
+            let dconjfdconjz = df.conj();
+            let two = f.one().add(f.one());
+            let dabsdf = f.conj().signum().div(two.clone());
+            let dabsdconjf = f.clone().signum().div(two);
+
+            (
+                f.abs(),
+                df.mul(dabsdf.clone()).add(dconjfdz.mul(dabsdconjf.clone())),
+                dfdconjz.mul(dabsdf).add(dconjfdconjz.mul(dabsdconjf)),
+            )
+        }
+    }
+}
+
+impl<StaticArgs, Input, AOutput, AGrad, Output, Grad, A> TryAutoDiffable<StaticArgs> for ADAbs<A>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f and |f| can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    // ensure |f| can be evaluated from the checked results of f
+    ADAbs<ADEvaluated<Input, AOutput, AGrad>>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADAbs", 0))?;
+        check_value("ADAbs", ADAbs(ADEvaluated::value(f)).eval(x, static_args))
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADAbs", 0))?;
+        check_all("ADAbs", ADAbs(ADEvaluated::all(f)).eval_all(x, static_args))
+    }
+}
+
+impl<StaticArgs, Input, Output, A> ForwardDiffable<StaticArgs> for ADAbs<A>
+where
+    A: ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: PossiblyComplex,
+    Output: Clone
+        + PossiblyComplex
+        + Add<Output, Output = Output>
+        + InstOne
+        + Div<Output, Output = Output>
+        + Signum<Output = Output>
+        + Abs<Output = Output>
+        + Conjugate<Output = Output>
+        + Mul<Output, Output = Output>
+        + Add<Output, Output = Output>,
+{
+    fn eval_forward(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval_forward(x, static_args).abs()
+    }
+    fn eval_forward_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Output,
+    ) {
+        if Input::is_always_real() && Output::is_always_real() {
+            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+
+            (f.clone().abs(), df.mul(f.signum()))
+        } else {
+            // in the Wirtinger calculus we have
+            // |z| = sqrt(z * conj(z))
+            // d|z|/dz = 1/2 * conj(z) * (z * conj(z))^(-1/2)
+            //         = 1/2 * conj(z) * |z|^(-1)
+            // cannot simplify further, since we would be assuming the "sign" of z
+            // d|z|/dconjz = 1/2 * z * (z * conj(z))^(-1/2)
+            //            = 1/2 * z * |z|^(-1)
+            // cannot simplify further, since we would be assuming the "sign" of z
+            //
+            // now for |f|, we have
+            //
+            // d|f|/dz = d|f|/df * df/dz + d|f|/dconjf * conj(df/dconjz)
+            //        = 0.5*conj(f)/|f| * df/dz + 0.5*f/|f| * conj(df/dconjz)
+            //
+            // and
+            //
+            // d|f|/dconjz = d|f|/df * df/dconjz + d|f|/dconjf * conj(df/dz)
+            //          = 0.5*conj(f)/|f| * df/dconjz + 0.5*f/|f| * conj(df/dz)
+
+            // note that for purely real z, this reduces to the real case
+
+            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+            let fconj = f.conj();
+            let dconjfdz = self.0.forward_conj_grad(x, dx, static_args).conj();
+            let two = f.one().add(f.one());
+
+            (
+                f.clone().abs(),
+                df.mul(fconj.signum().div(two.clone()))
+                    .add(dconjfdz.mul(f.signum()).div(two)),
+            )
+        }
+    }
+
+    fn forward_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        if Input::is_always_real() && Output::is_always_real() {
+            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+
+            df.mul(f.signum())
+        } else {
+            // in the Wirtinger calculus we have
+            // |z| = sqrt(z * conj(z))
+            // d|z|/dz = 1/2 * conj(z) * (z * conj(z))^(-1/2)
+            //         = 1/2 * conj(z) * |z|^(-1)
+            // cannot simplify further, since we would be assuming the "sign" of z
+            // d|z|/dconjz = 1/2 * z * (z * conj(z))^(-1/2)
+            //            = 1/2 * z * |z|^(-1)
+            // cannot simplify further, since we would be assuming the "sign" of z
+            //
+            // now for |f|, we have
+            //
+            // d|f|/dz = d|f|/df * df/dz + d|f|/dconjf * conj(df/dconjz)
+            //        = 0.5*conj(f)/|f| * df/dz + 0.5*f/|f| * conj(df/dconjz)
+            //
+            // and
+            //
+            // d|f|/dconjz = d|f|/df * df/dconjz + d|f|/dconjf * conj(df/dz)
+            //          = 0.5*conj(f)/|f| * df/dconjz + 0.5*f/|f| * conj(df/dz)
+
+            // note that for purely real z, this reduces to the real case
+
+            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+            let fconj = f.conj();
+            let dconjfdz = self.0.forward_conj_grad(x, dx, static_args).conj();
+            let two = f.one().add(f.one());
+
+            df.mul(fconj.signum().div(two.clone()))
+                .add(dconjfdz.mul(f.signum()).div(two))
+        }
+    }
+
+    fn eval_forward_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Output,
+    ) {
+        if Input::is_always_real() && Output::is_always_real() {
+            let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+
+            (f.clone().abs(), df.mul(f.signum()))
+        } else {
+            // in the Wirtinger calculus we have
+            // |z| = sqrt(z * conj(z))
+            // d|z|/dz = 1/2 * conj(z) * (z * conj(z))^(-1/2)
+            //         = 1/2 * conj(z) * |z|^(-1)
+            // cannot simplify further, since we would be assuming the "sign" of z
+            // d|z|/dconjz = 1/2 * z * (z * conj(z))^(-1/2)
+            //            = 1/2 * z * |z|^(-1)
+            // cannot simplify further, since we would be assuming the "sign" of z
+            //
+            // now for |f|, we have
+            //
+            // d|f|/dz = d|f|/df * df/dz + d|f|/dconjf * conj(df/dconjz)
+            //        = 0.5*conj(f)/|f| * df/dz + 0.5*f/|f| * conj(df/dconjz)
+            //
+            // and
+            //
+            // d|f|/dconjz = d|f|/df * df/dconjz + d|f|/dconjf * conj(df/dz)
+            //          = 0.5*conj(f)/|f| * df/dconjz + 0.5*f/|f| * conj(df/dz)
+
+            // note that for purely real z, this reduces to the real case
+
+            let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+            let fconj = f.conj();
+            let dconjfdconjz = self.0.forward_grad(x, dx, static_args).conj();
+            let two = f.one().add(f.one());
+
+            (
+                f.clone().abs(),
+                df.mul(fconj.signum().div(two.clone()))
+                    .add(dconjfdconjz.mul(f.signum().div(two))),
+            )
+        }
+    }
+
+    fn forward_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        if Input::is_always_real() && Output::is_always_real() {
+            let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+
+            df.mul(f.signum())
+        } else {
+            // in the Wirtinger calculus we have
+            // |z| = sqrt(z * conj(z))
+            // d|z|/dz = 1/2 * conj(z) * (z * conj(z))^(-1/2)
+            //         = 1/2 * conj(z) * |z|^(-1)
+            // cannot simplify further, since we would be assuming the "sign" of z
+            // d|z|/dconjz = 1/2 * z * (z * conj(z))^(-1/2)
+            //            = 1/2 * z * |z|^(-1)
+            // cannot simplify further, since we would be assuming the "sign" of z
+            //
+            // now for |f|, we have
+            //
+            // d|f|/dz = d|f|/df * df/dz + d|f|/dconjf * conj(df/dconjz)
+            //        = 0.5*conj(f)/|f| * df/dz + 0.5*f/|f| * conj(df/dconjz)
+            //
+            // and
+            //
+            // d|f|/dconjz = d|f|/df * df/dconjz + d|f|/dconjf * conj(df/dz)
+            //          = 0.5*conj(f)/|f| * df/dconjz + 0.5*f/|f| * conj(df/dz)
+
+            // note that for purely real z, this reduces to the real case
+
+            let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+            let fconj = f.conj();
+            let dconjfdconjz = self.0.forward_grad(x, dx, static_args).conj();
+            let two = f.one().add(f.one());
+
+            df.mul(fconj.signum().div(two.clone()))
+                .add(dconjfdconjz.mul(f.signum().div(two)))
+        }
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, A> ReverseDiffable<StaticArgs> for ADAbs<A>
+where
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: PossiblyComplex + GradientType<Output, GradientType = Grad>,
+    // ensure |f| and its Wirtinger derivatives are defined and are Output
+    Output: Clone
+        + PossiblyComplex
+        + InstZero
+        + InstOne
+        + Div<Output, Output = Output>
+        + Abs<Output = Output>
+        + Signum<Output = Output>
+        + Conjugate<Output = Output>,
+{
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
+
+        if Input::is_always_real() && Output::is_always_real() {
+            // d|f|/df = sign(f)
+            let zero = f.zero();
+
+            tape.unary(f.clone().abs(), (u, f.signum(), zero))
+        } else {
+            // d|f|/df = 1/2 * conj(f)/|f|, d|f|/dconjf = 1/2 * f/|f|
+            let two = f.one().add(f.one());
+            let dabsdf = f.conj().signum().div(two.clone());
+            let dabsdconjf = f.clone().signum().div(two);
+
+            tape.unary(f.abs(), (u, dabsdf, dabsdconjf))
+        }
+    }
+}
+
+impl<StaticArgs, Input, Output, A> VjpDiffable<StaticArgs> for ADAbs<A>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: PossiblyComplex + Add<Input, Output = Input> + Conjugate<Output = Input>,
+    // ensure |f| and its Wirtinger derivatives are defined and are Output
+    Output: Clone
+        + PossiblyComplex
+        + InstOne
+        + Add<Output, Output = Output>
+        + Mul<Output, Output = Output>
+        + Div<Output, Output = Output>
+        + Abs<Output = Output>
+        + Signum<Output = Output>
+        + Conjugate<Output = Output>,
+{
+    fn eval_primal(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval_primal(x, static_args).abs()
+    }
+
+    fn eval_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let f = self.0.eval_primal(x, static_args);
+
+        if Input::is_always_real() && Output::is_always_real() {
+            // d|f|/df = sign(f)
+
+            let dgdf = f.clone().signum();
+            let vf = self.0.vjp(x, &cotangent.clone().mul(dgdf), static_args);
+
+            (f.abs(), vf)
+        } else {
+            // d|f|/df = 1/2 * conj(f)/|f|, d|f|/dconjf = 1/2 * f/|f|
+            let two = f.one().add(f.one());
+            let dgdf = f.conj().signum().div(two.clone());
+            let dgdconjf = f.clone().signum().div(two);
+            let vf = wirtinger_vjp(&self.0, x, cotangent, dgdf, dgdconjf, static_args);
+
+            (f.abs(), vf)
+        }
+    }
+
+    fn eval_conj_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let f = self.0.eval_primal(x, static_args);
+
+        if Input::is_always_real() && Output::is_always_real() {
+            let dgdf = f.clone().signum();
+            let vf = self
+                .0
+                .conj_vjp(x, &cotangent.clone().mul(dgdf), static_args);
+
+            (f.abs(), vf)
+        } else {
+            let two = f.one().add(f.one());
+            let dgdf = f.conj().signum().div(two.clone());
+            let dgdconjf = f.clone().signum().div(two);
+            let vf = wirtinger_conj_vjp(&self.0, x, cotangent, dgdf, dgdconjf, static_args);
+
+            (f.abs(), vf)
+        }
+    }
+}
+
+#[derive(FuncCompose, Debug, Clone, Copy)]
+pub struct ADAbsSqr<A>(pub A);
+
+impl<A: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs> for ADAbsSqr<A> {
+    type Input = A::Input;
+    type Output = A::Output;
+}
+
+impl<A> Symbolic for ADAbsSqr<A>
+where
+    A: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::call("abs_sqr", vec![self.0.symbolic(x)])
+    }
+}
+
+impl<StaticArgs, A> ToDot<StaticArgs> for ADAbsSqr<A>
+where
+    A: ToDot<StaticArgs>,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        graph.op("abs_sqr", self.annotate_at(at), &[(f, None)])
+    }
+}
+
+impl<StaticArgs, T, A> ToDynExpr<StaticArgs, T> for ADAbsSqr<A>
+where
+    A: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+    // ensure |x|^2 can be boxed as an opaque function of the simplified f
+    ADAbsSqr<Identity<StaticArgs, T>>: AutoDiffable<StaticArgs, Input = T, Output = T>
+        + ForwardDiffable<StaticArgs, Input = T, Output = T>
+        + Symbolic
+        + Clone
+        + 'static,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::apply(ADAbsSqr(Identity::new()), self.0.to_dyn_expr())
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, A> AutoDiffable<StaticArgs> for ADAbsSqr<A>
+where
+    A: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: PossiblyComplex + GradientType<Output, GradientType = Grad>,
+    Output: PossiblyComplex
+        + AbsSqr<Output = Output>
+        + Conjugate<Output = Output>
+        + Add<Output, Output = Output>
+        + Clone
+        + Mul<Grad, Output = Grad>,
+    Grad: Conjugate<Output = Grad> + Mul<Output, Output = Grad> + Add<Grad, Output = Grad>,
+{
+    fn eval(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval(x, static_args).abs_sqr()
+    }
+
+    fn eval_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        if Input::is_always_real() && Output::is_always_real() {
+            // for real z, |z|^2 -> 2 |z| * sign(z) = 2z
+            // |f|^2 -> 2f * df/dz
+
+            let (f, df) = self.0.eval_grad(x, static_args);
+
+            (f.clone().abs_sqr(), df.mul(f.clone().add(f)))
+        } else {
+            // both Wirtinger derivatives of f are needed, see eval_all
+            let (f, df, _) = self.eval_all(x, static_args);
+            (f, df)
+        }
+    }
+
+    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
+        if Input::is_always_real() && Output::is_always_real() {
+            // for real z, |z|^2 -> 2 |z| * sign(z) = 2z
+            // |f|^2 -> 2f * df/dz
+
+            let (f, df) = self.0.eval_grad(x, static_args);
+
+            df.mul(f.clone().add(f))
+        } else {
+            self.eval_all(x, static_args).1
+        }
+    }
+
+    fn eval_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        if Input::is_always_real() && Output::is_always_real() {
+            // for real z, |z|^2 -> 2 |z| * sign(z) = 2z
+            // |f|^2 -> 2f * df/dz
+
+            let (f, df) = self.0.eval_conj_grad(x, static_args);
+
+            (f.clone().abs_sqr(), df.mul(f.clone().add(f)))
+        } else {
+            let (f, _, dfdconjz) = self.eval_all(x, static_args);
+            (f, dfdconjz)
+        }
+    }
+
+    fn conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> Grad {
+        if Input::is_always_real() && Output::is_always_real() {
+            // for real z, |z|^2 -> 2 |z| * sign(z) = 2z
+            // |f|^2 -> 2f * df/dz
+
+            let (f, df) = self.0.eval_conj_grad(x, static_args);
+
+            df.mul(f.clone().add(f))
+        } else {
+            self.eval_all(x, static_args).2
+        }
+    }
+
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+
+        if Input::is_always_real() && Output::is_always_real() {
+            // for real z, |z|^2 -> 2 |z| * sign(z) = 2z
+            // |f|^2 -> 2f * df/dz
+
+            let two_f = f.clone().add(f.clone());
+
+            (f.abs_sqr(), df.mul(two_f.clone()), dfdconjz.mul(two_f))
+        } else {
+            // in the Wirtinger calculus we have
+            // |z|^2 = z * conj(z)
+            // d|z|^2/dz = conj(z)
+            // d|z|^2/dconjz = z
+            //
+            // now for |f|^2, we have
+            //
+            // d|f|^2/dz = d|f|^2/df * df/dz + d|f|^2/dconjf * conj(df/dconjz)
+            //           = conj(f) * df/dz + f * conj(df/dconjz)
+            // d|f|^2/dconjz = d|f|^2/df * df/dconjz + d|f|^2/dconjf * conj(df/dz)
+            //           = conj(f) * df/dconjz + f * conj(df/dz)
+            //
+            // so f is evaluated once, with both of its derivatives
+
+            let fconj = f.conj();
+            let dconjfdz = dfdconjz.conj();
+            let dconjfdconjz = df.conj();
+
+            (
+                f.clone().abs_sqr(),
+                df.mul(fconj.clone()).add(f.clone().mul(dconjfdz)),
+                dfdconjz.mul(fconj).add(f.mul(dconjfdconjz)),
+            )
+        }
+    }
+}
+
+impl<StaticArgs, Input, AOutput, AGrad, Output, Grad, A> TryAutoDiffable<StaticArgs> for ADAbsSqr<A>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f and |f|^2 can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    // ensure |f|^2 can be evaluated from the checked results of f
+    ADAbsSqr<ADEvaluated<Input, AOutput, AGrad>>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADAbsSqr", 0))?;
+        check_value(
+            "ADAbsSqr",
+            ADAbsSqr(ADEvaluated::value(f)).eval(x, static_args),
+        )
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADAbsSqr", 0))?;
+        check_all(
+            "ADAbsSqr",
+            ADAbsSqr(ADEvaluated::all(f)).eval_all(x, static_args),
+        )
+    }
+}
+
+impl<StaticArgs, Input, Output, A> ForwardDiffable<StaticArgs> for ADAbsSqr<A>
+where
+    A: ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: PossiblyComplex,
+    Output: PossiblyComplex
+        + AbsSqr<Output = Output>
+        + Conjugate<Output = Output>
+        + Mul<Output, Output = Output>
+        + Add<Output, Output = Output>
+        + Clone,
+{
+    fn eval_forward(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval_forward(x, static_args).abs_sqr()
+    }
+    fn eval_forward_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Output,
+    ) {
+        if Input::is_always_real() && Output::is_always_real() {
+            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+
+            (f.clone().abs_sqr(), df.mul(f.clone().add(f)))
+        } else {
+            // in the Wirtinger calculus we have
+            // |z|^2 = z * conj(z)
+            // d|z|^2/dz = conj(z)
+            //
+            // now for |f|^2, we have
+            //
+            // d|f|^2/dz = d|f|^2/df * df/dz + d|f|^2/dconjf * conj(df/dconjz)
+            //           = conj(f) * df/dz + f * conj(df/dconjz)
+
+            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+            let fconj = f.conj();
+            let dconjf = self.0.forward_conj_grad(x, dx, static_args).conj();
+
+            (f.clone().abs_sqr(), df.mul(fconj).add(f.mul(dconjf)))
+        }
+    }
+
+    fn forward_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        if Input::is_always_real() && Output::is_always_real() {
+            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+
+            df.mul(f.clone().add(f))
+        } else {
+            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+            let fconj = f.conj();
+            let dconjf = self.0.forward_conj_grad(x, dx, static_args).conj();
+
+            df.mul(fconj).add(f.mul(dconjf))
+        }
+    }
+
+    fn eval_forward_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Output,
+    ) {
+        if Input::is_always_real() && Output::is_always_real() {
+            let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+
+            (f.clone().abs_sqr(), df.mul(f.clone().add(f)))
+        } else {
+            // in the Wirtinger calculus we have
+            // |z|^2 = z * conj(z)
+            // d|z|^2/dconjz = z
+            //
+            // now for |f|^2, we have
+            //
+            // d|f|^2/dconjz = d|f|^2/df * df/dconjz + d|f|^2/dconjf * conj(df/dz)
+            //           = conj(f) * df/dconjz + f * conj(df/dz)
+
+            let (f, dfdconjz) = self.0.eval_forward_conj_grad(x, dx, static_args);
+            let fconj = f.conj();
+            let dconjfdconjz = self.0.forward_grad(x, dx, static_args).conj();
+
+            (
+                f.clone().abs_sqr(),
+                dfdconjz.mul(fconj).add(f.mul(dconjfdconjz)),
+            )
+        }
+    }
+
+    fn forward_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        if Input::is_always_real() && Output::is_always_real() {
+            let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+
+            df.mul(f.clone().add(f))
+        } else {
+            let (f, dfdconjz) = self.0.eval_forward_conj_grad(x, dx, static_args);
+            let fconj = f.conj();
+            let dconjfdconjz = self.0.forward_grad(x, dx, static_args).conj();
+
+            dfdconjz.mul(fconj).add(f.mul(dconjfdconjz))
+        }
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, A> ReverseDiffable<StaticArgs> for ADAbsSqr<A>
+where
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: PossiblyComplex + GradientType<Output, GradientType = Grad>,
+    // ensure |f|^2 and its Wirtinger derivatives are defined and are Output
+    Output:
+        Clone + PossiblyComplex + InstZero + AbsSqr<Output = Output> + Conjugate<Output = Output>,
+{
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
+
+        if Input::is_always_real() && Output::is_always_real() {
+            // d|f|^2/df = 2f
+            let zero = f.zero();
+
+            tape.unary(f.clone().abs_sqr(), (u, f.clone().add(f), zero))
+        } else {
+            // d|f|^2/df = conj(f), d|f|^2/dconjf = f
+            let fconj = f.conj();
+
+            tape.unary(f.clone().abs_sqr(), (u, fconj, f))
+        }
+    }
+}
+
+impl<StaticArgs, Input, Output, A> VjpDiffable<StaticArgs> for ADAbsSqr<A>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: PossiblyComplex + Add<Input, Output = Input> + Conjugate<Output = Input>,
+    // ensure |f|^2 and its Wirtinger derivatives are defined and are Output
+    Output: Clone
+        + PossiblyComplex
+        + Add<Output, Output = Output>
+        + Mul<Output, Output = Output>
+        + AbsSqr<Output = Output>
+        + Conjugate<Output = Output>,
+{
+    fn eval_primal(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval_primal(x, static_args).abs_sqr()
+    }
+
+    fn eval_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let f = self.0.eval_primal(x, static_args);
+
+        if Input::is_always_real() && Output::is_always_real() {
+            // d|f|^2/df = 2f
+
+            let dgdf = f.clone().add(f.clone());
+            let vf = self.0.vjp(x, &cotangent.clone().mul(dgdf), static_args);
+
+            (f.abs_sqr(), vf)
+        } else {
+            // d|f|^2/df = conj(f), d|f|^2/dconjf = f
+            let (dgdf, dgdconjf) = (f.conj(), f.clone());
+            let vf = wirtinger_vjp(&self.0, x, cotangent, dgdf, dgdconjf, static_args);
+
+            (f.abs_sqr(), vf)
+        }
+    }
+
+    fn eval_conj_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let f = self.0.eval_primal(x, static_args);
+
+        if Input::is_always_real() && Output::is_always_real() {
+            let dgdf = f.clone().add(f.clone());
+            let vf = self
+                .0
+                .conj_vjp(x, &cotangent.clone().mul(dgdf), static_args);
+
+            (f.abs_sqr(), vf)
+        } else {
+            let (dgdf, dgdconjf) = (f.conj(), f.clone());
+            let vf = wirtinger_conj_vjp(&self.0, x, cotangent, dgdf, dgdconjf, static_args);
+
+            (f.abs_sqr(), vf)
+        }
+    }
+}
+
+#[derive(FuncCompose, Debug, Clone, Copy)]
+pub struct ADSignum<A>(pub A);
+
+impl<A: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs> for ADSignum<A> {
+    type Input = A::Input;
+    type Output = A::Output;
+}
+
+impl<A> Symbolic for ADSignum<A>
+where
+    A: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::call("signum", vec![self.0.symbolic(x)])
+    }
+}
+
+impl<StaticArgs, A> ToDot<StaticArgs> for ADSignum<A>
+where
+    A: ToDot<StaticArgs>,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        graph.op("signum", self.annotate_at(at), &[(f, None)])
+    }
+}
+
+impl<StaticArgs, T, A> ToDynExpr<StaticArgs, T> for ADSignum<A>
+where
+    A: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+    // ensure signum(x) can be boxed as an opaque function of the simplified f
+    ADSignum<Identity<StaticArgs, T>>: AutoDiffable<StaticArgs, Input = T, Output = T>
+        + ForwardDiffable<StaticArgs, Input = T, Output = T>
+        + Symbolic
+        + Clone
+        + 'static,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::apply(ADSignum(Identity::new()), self.0.to_dyn_expr())
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, A> AutoDiffable<StaticArgs> for ADSignum<A>
+where
+    A: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: PossiblyComplex + GradientType<Output, GradientType = Grad>,
+    Output: Clone
+        + PossiblyComplex
+        + Signum<Output = Output>
+        + Abs<Output = Output>
+        + Conjugate<Output = Output>
+        + InstOne
+        + InstZero
+        + Mul<Grad, Output = Grad>
+        + Mul<Output, Output = Output>
+        + Add<Output, Output = Output>
+        + Neg<Output = Output>
+        + Div<Output, Output = Output>,
+    Grad: Conjugate<Output = Grad> + InstZero,
+{
+    fn eval(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval(x, static_args).signum()
+    }
+
+    fn eval_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        if Input::is_always_real() && Output::is_always_real() {
+            let (f, df) = self.0.eval_grad(x, static_args);
+
+            (f.signum(), df.zero())
+        } else {
+            // both Wirtinger derivatives of f are needed, see eval_all
+            let (f, df, _) = self.eval_all(x, static_args);
+            (f, df)
+        }
+    }
+
+    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
+        if Input::is_always_real() && Output::is_always_real() {
+            self.0.grad(x, static_args).zero()
+        } else {
+            self.eval_all(x, static_args).1
+        }
+    }
+
+    fn eval_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+        if Input::is_always_real() && Output::is_always_real() {
+            let (f, df) = self.0.eval_conj_grad(x, static_args);
+
+            (f.signum(), df.zero())
+        } else {
+            let (f, _, dfdconjz) = self.eval_all(x, static_args);
+            (f, dfdconjz)
+        }
+    }
+
+    fn conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> Grad {
+        if Input::is_always_real() && Output::is_always_real() {
+            self.0.conj_grad(x, static_args).conj().zero()
+        } else {
+            self.eval_all(x, static_args).2
+        }
+    }
+
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        // defining signum(f) = f/|f| if f != 0, 0 otherwise
+        // this works for both purely real and complex f
+        //
+        // in the real case:
+        // d/dx (f/|f|) = (|f| * df/dx - f * d|f|/dx) / |f|^2
+        //
+        // and
+        //
+        // d/dx |f| = f * df/dx / |f|
+        //
+        // so d/dx signum(f) = (|f| * df/dx - f^2 * df/dx / |f|) / |f|^2
+        //                   = (|f| * df/dx - |f| * df/dx) / |f|^2
+        //                   = 0
+        // as expected
+        //
+        // in the complex case:
+        //
+        // d/dz (f/|f|)
+        //
+        // d/dz (z/|z|) = d/dz sqrt(z / conj(z)) = 1/(2 sqrt(z * conj(z)))
+        //            = 1/(2 |z|)
+        // d/dconjz (z/|z|) = d/dconjz sqrt(z / conj(z))
+        //                 = sqrt(z) * d/dconjz conj(z)^(-1/2)
+        //                 = sqrt(z) * -1/2 * conj(z)^(-3/2)
+        //                 = -1/2 (z/conj(z)^3)^(1/2)
+        //                 = -1/2 (z^4/|z|^6)^(1/2)
+        //                 = -1/2 (z^2/|z|^3)
+        //
+        // so from the chain rule then
+        //
+        // d/dz (f/|f|) = (1/(2 |f|)) * df/dz - 1/2 (f^2/|f|^3) * conj(df/dconjz)
+        // d/dconjz (f/|f|) = (1/(2 |f|)) * df/dconjz - 1/2 (f^2/|f|^3) * conj(df/dz)
+        // which would be 0 in the real case as expected
+
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+
+        if Input::is_always_real() && Output::is_always_real() {
+            (f.signum(), df.zero(), dfdconjz.zero())
+        } else {
+            let dconjfdz = dfdconjz.conj();
+            let dconjfdconjz = df.conj();
+            let fabs = f.clone().abs();
+            let dsdf = f.one().div(fabs.clone().add(fabs.clone()));
+            let dsdconjf_half_denom = fabs.clone().mul(fabs.clone().mul(fabs));
+            let dsdconjf = f
+                .clone()
+                .mul(f.clone())
+                .div(dsdconjf_half_denom.clone().add(dsdconjf_half_denom))
+                .neg();
+
+            (
+                f.signum(),
+                dsdf.clone().mul(df).add(dsdconjf.clone().mul(dconjfdz)),
+                dsdf.mul(dfdconjz).add(dsdconjf.mul(dconjfdconjz)),
+            )
+        }
+    }
+}
+
+impl<StaticArgs, Input, AOutput, AGrad, Output, Grad, A> TryAutoDiffable<StaticArgs> for ADSignum<A>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f and sgn(f) can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    // ensure sgn(f) can be evaluated from the checked results of f
+    ADSignum<ADEvaluated<Input, AOutput, AGrad>>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADSignum", 0))?;
+        check_value(
+            "ADSignum",
+            ADSignum(ADEvaluated::value(f)).eval(x, static_args),
+        )
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADSignum", 0))?;
+        check_all(
+            "ADSignum",
+            ADSignum(ADEvaluated::all(f)).eval_all(x, static_args),
+        )
+    }
+}
+
+impl<StaticArgs, Input, Output, A> ForwardDiffable<StaticArgs> for ADSignum<A>
+where
+    A: ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: PossiblyComplex,
+    Output: Clone
+        + PossiblyComplex
+        + Signum<Output = Output>
+        + Abs<Output = Output>
+        + Conjugate<Output = Output>
+        + InstOne
+        + InstZero
+        + Mul<Output, Output = Output>
+        + Add<Output, Output = Output>
+        + Neg<Output = Output>
+        + Div<Output, Output = Output>,
+{
+    fn eval_forward(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval_forward(x, static_args).signum()
+    }
+    fn eval_forward_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Output,
+    ) {
+        if Input::is_always_real() && Output::is_always_real() {
+            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+
+            (f.signum(), df.zero())
+        } else {
+            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+            let dconjf = self.0.forward_conj_grad(x, dx, static_args);
+            let fabs = f.clone().abs();
+            let dsdf = f.one().div(fabs.clone().add(fabs.clone()));
+            let dsdconjf_half_denom = fabs.clone().mul(fabs.clone().mul(fabs));
+            let dsdconjf = f
+                .clone()
+                .mul(f.clone())
+                .div(dsdconjf_half_denom.clone().add(dsdconjf_half_denom))
+                .neg();
+
+            (f.signum(), dsdf.mul(df).add(dsdconjf.mul(dconjf)))
+        }
+    }
+
+    fn forward_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        if Input::is_always_real() && Output::is_always_real() {
+            self.0.forward_grad(x, dx, static_args).zero()
+        } else {
+            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+            let dconjf = self.0.forward_conj_grad(x, dx, static_args);
+            let fabs = f.clone().abs();
+            let dsdf = f.one().div(fabs.clone().add(fabs.clone()));
+            let dsdconjf_half_denom = fabs.clone().mul(fabs.clone().mul(fabs));
+            let dsdconjf = f
+                .clone()
+                .mul(f.clone())
+                .div(dsdconjf_half_denom.clone().add(dsdconjf_half_denom))
+                .neg();
+
+            dsdf.mul(df).add(dsdconjf.mul(dconjf))
+        }
+    }
+
+    fn eval_forward_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Output,
+    ) {
+        if Input::is_always_real() && Output::is_always_real() {
+            let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+
+            (f.signum(), df.zero())
+        } else {
+            let (f, dfdconjz) = self.0.eval_forward_conj_grad(x, dx, static_args);
+            let dconjfdconjz = self.0.forward_grad(x, dx, static_args).conj();
+            let fabs = f.clone().abs();
+            let dsdf = f.one().div(fabs.clone().add(fabs.clone()));
+            let dsdconjf_half_denom = fabs.clone().mul(fabs.clone().mul(fabs));
+            let dsdconjf = f
+                .clone()
+                .mul(f.clone())
+                .div(dsdconjf_half_denom.clone().add(dsdconjf_half_denom))
+                .neg();
+
+            (
+                f.signum(),
+                dsdf.mul(dfdconjz).add(dsdconjf.mul(dconjfdconjz)),
+            )
+        }
+    }
 
     fn forward_conj_grad(
         &self,
@@ -2107,232 +6939,323 @@ where
         dx: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+        if Input::is_always_real() && Output::is_always_real() {
+            self.0.forward_conj_grad(x, dx, static_args).zero()
+        } else {
+            let (f, dfdconjz) = self.0.eval_forward_conj_grad(x, dx, static_args);
+            let dconjfdconjz = self.0.forward_grad(x, dx, static_args).conj();
+            let fabs = f.clone().abs();
+            let dsdf = f.one().div(fabs.clone().add(fabs.clone()));
+            let dsdconjf_half_denom = fabs.clone().mul(fabs.clone().mul(fabs));
+            let dsdconjf = f
+                .clone()
+                .mul(f.clone())
+                .div(dsdconjf_half_denom.clone().add(dsdconjf_half_denom))
+                .neg();
 
-        df.mul(f.pow(self.1.clone().sub(self.1.one())).mul(self.1.clone()))
+            dsdf.mul(dfdconjz).add(dsdconjf.mul(dconjfdconjz))
+        }
     }
 }
 
-#[derive(FuncCompose, Debug, Clone, Copy)]
-pub struct ADAbs<A>(pub A);
+impl<StaticArgs, Input, Output, Grad, A> ReverseDiffable<StaticArgs> for ADSignum<A>
+where
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: PossiblyComplex + GradientType<Output, GradientType = Grad>,
+    // ensure signum(f) and its Wirtinger derivatives are defined and are Output
+    Output: Clone
+        + PossiblyComplex
+        + InstZero
+        + InstOne
+        + Signum<Output = Output>
+        + Abs<Output = Output>
+        + Neg<Output = Output>
+        + Div<Output, Output = Output>,
+{
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
 
-impl<A: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs> for ADAbs<A> {
-    type Input = A::Input;
-    type Output = A::Output;
+        if Input::is_always_real() && Output::is_always_real() {
+            // d(signum(f))/df = 0
+            let zero = f.zero();
+
+            tape.unary(f.signum(), (u, zero.clone(), zero))
+        } else {
+            // d(f/|f|)/df = 1/(2 |f|), d(f/|f|)/dconjf = -1/2 (f^2/|f|^3)
+            let fabs = f.clone().abs();
+            let dsdf = f.one().div(fabs.clone().add(fabs.clone()));
+            let dsdconjf_half_denom = fabs.clone().mul(fabs.clone().mul(fabs));
+            let dsdconjf = f
+                .clone()
+                .mul(f.clone())
+                .div(dsdconjf_half_denom.clone().add(dsdconjf_half_denom))
+                .neg();
+
+            tape.unary(f.signum(), (u, dsdf, dsdconjf))
+        }
+    }
 }
 
-impl<StaticArgs, Input, Output, Grad, A> AutoDiffable<StaticArgs> for ADAbs<A>
+impl<StaticArgs, Input, Output, A> VjpDiffable<StaticArgs> for ADSignum<A>
 where
-    A: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
-    Input: PossiblyComplex + GradientType<Output, GradientType = Grad>,
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: PossiblyComplex + Add<Input, Output = Input> + Conjugate<Output = Input>,
+    // ensure signum(f) and its Wirtinger derivatives are defined and are Output
     Output: Clone
         + PossiblyComplex
-        + Add<Output, Output = Output>
+        + InstZero
         + InstOne
+        + Add<Output, Output = Output>
+        + Mul<Output, Output = Output>
         + Div<Output, Output = Output>
-        + Abs<Output = Output>
+        + Neg<Output = Output>
         + Signum<Output = Output>
+        + Abs<Output = Output>
         + Conjugate<Output = Output>,
-    Grad: Conjugate<Output = Grad> + Mul<Output, Output = Grad> + Add<Grad, Output = Grad>,
 {
-    fn eval(
+    fn eval_primal(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval(x, static_args).abs()
+        self.0.eval_primal(x, static_args).signum()
     }
 
-    fn eval_grad(
+    fn eval_vjp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
-    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        if Input::is_always_real() && Output::is_always_real() {
-            let (f, df) = self.0.eval_grad(x, static_args);
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let f = self.0.eval_primal(x, static_args);
 
-            (f.clone().abs(), df.mul(f.signum()))
-        } else {
-            // in the Wirtinger calculus we have
-            // |z| = sqrt(z * conj(z))
-            // d|z|/dz = 1/2 * conj(z) * (z * conj(z))^(-1/2)
-            //         = 1/2 * conj(z) * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            // d|z|/dconjz = 1/2 * z * (z * conj(z))^(-1/2)
-            //            = 1/2 * z * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            //
-            // now for |f|, we have
-            //
-            // d|f|/dz = d|f|/df * df/dz + d|f|/dconjf * conj(df/dconjz)
-            //        = 0.5*conj(f)/|f| * df/dz + 0.5*f/|f| * conj(df/dconjz)
-            //
-            // and
-            //
-            // d|f|/dconjz = d|f|/df * df/dconjz + d|f|/dconjf * conj(df/dz)
-            //          = 0.5*conj(f)/|f| * df/dconjz + 0.5*f/|f| * conj(df/dz)
+        if Input::is_always_real() && Output::is_always_real() {
+            // d(signum(f))/df = 0
 
-            // note that for purely real z, this reduces to the real case
+            let dgdf = f.zero();
+            let vf = self.0.vjp(x, &cotangent.clone().mul(dgdf), static_args);
 
-            let (f, df) = self.0.eval_grad(x, static_args);
-            let fconj = f.conj();
-            let dconjfdz = self.0.conj_grad(x, static_args).conj();
-            let two = f.one().add(f.one());
+            (f.signum(), vf)
+        } else {
+            // d(f/|f|)/df = 1/(2 |f|), d(f/|f|)/dconjf = -1/2 (f^2/|f|^3)
+            let fabs = f.clone().abs();
+            let half_denom = fabs.clone().mul(fabs.clone().mul(fabs.clone()));
+            let dgdf = f.one().div(fabs.clone().add(fabs));
+            let dgdconjf = f
+                .clone()
+                .mul(f.clone())
+                .div(half_denom.clone().add(half_denom))
+                .neg();
+            let vf = wirtinger_vjp(&self.0, x, cotangent, dgdf, dgdconjf, static_args);
 
-            (
-                f.clone().abs(),
-                df.mul(fconj.signum().div(two.clone()))
-                    .add(dconjfdz.mul(f.signum().div(two))),
-            )
+            (f.signum(), vf)
         }
     }
 
-    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
+    fn eval_conj_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let f = self.0.eval_primal(x, static_args);
+
         if Input::is_always_real() && Output::is_always_real() {
-            let df = self.0.grad(x, static_args);
+            let dgdf = f.zero();
+            let vf = self
+                .0
+                .conj_vjp(x, &cotangent.clone().mul(dgdf), static_args);
 
-            df.mul(self.0.eval(x, static_args).signum())
+            (f.signum(), vf)
         } else {
-            // in the Wirtinger calculus we have
-            // |z| = sqrt(z * conj(z))
-            // d|z|/dz = 1/2 * conj(z) * (z * conj(z))^(-1/2)
-            //         = 1/2 * conj(z) * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            // d|z|/dconjz = 1/2 * z * (z * conj(z))^(-1/2)
-            //            = 1/2 * z * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            //
-            // now for |f|, we have
-            //
-            // d|f|/dz = d|f|/df * df/dz + d|f|/dconjf * conj(df/dconjz)
-            //        = 0.5*conj(f)/|f| * df/dz + 0.5*f/|f| * conj(df/dconjz)
-            //
-            // and
-            //
-            // d|f|/dconjz = d|f|/df * df/dconjz + d|f|/dconjf * conj(df/dz)
-            //          = 0.5*conj(f)/|f| * df/dconjz + 0.5*f/|f| * conj(df/dz)
+            let fabs = f.clone().abs();
+            let half_denom = fabs.clone().mul(fabs.clone().mul(fabs.clone()));
+            let dgdf = f.one().div(fabs.clone().add(fabs));
+            let dgdconjf = f
+                .clone()
+                .mul(f.clone())
+                .div(half_denom.clone().add(half_denom))
+                .neg();
+            let vf = wirtinger_conj_vjp(&self.0, x, cotangent, dgdf, dgdconjf, static_args);
 
-            // note that for purely real z, this reduces to the real case
+            (f.signum(), vf)
+        }
+    }
+}
+
+#[derive(FuncCompose, Debug, Clone, Copy)]
+pub struct ADConjugate<A>(pub A);
+
+impl<A: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs> for ADConjugate<A>
+where
+    A::Output: Conjugate<Output = A::Output>,
+{
+    type Input = A::Input;
+    type Output = A::Output;
+}
+
+impl<A> Symbolic for ADConjugate<A>
+where
+    A: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::call("conj", vec![self.0.symbolic(x)])
+    }
+}
+
+impl<StaticArgs, A> ToDot<StaticArgs> for ADConjugate<A>
+where
+    A: ToDot<StaticArgs>,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        graph.op("conj", self.annotate_at(at), &[(f, None)])
+    }
+}
+
+impl<StaticArgs, T, A> ToDynExpr<StaticArgs, T> for ADConjugate<A>
+where
+    A: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::Conj(Box::new(self.0.to_dyn_expr()))
+    }
+}
+
+impl<StaticArgs, Input, AOutput, AGrad, A> AutoDiffable<StaticArgs> for ADConjugate<A>
+where
+    A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    // make sure A.conj() is defined and Output = Output
+    AOutput: Conjugate<Output = AOutput>,
+    // make sure dA.conj() is defined and Output = Grad
+    AGrad: Conjugate<Output = AGrad>,
+{
+    fn eval(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval(x, static_args).conj()
+    }
+
+    fn eval_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, AGrad) {
+        // Wirtinger derivative dconj(f)/dz = conj(df/dconjz)
+
+        let (f, dfdzconj) = self.0.eval_conj_grad(x, static_args);
 
-            let (f, df) = self.0.eval_grad(x, static_args);
-            let fconj = f.conj();
-            let dconjfdz = self.0.conj_grad(x, static_args).conj();
-            let two = f.one().add(f.one());
+        (f.conj(), dfdzconj.conj())
+    }
 
-            df.mul(fconj.signum().div(two.clone()))
-                .add(dconjfdz.mul(f.signum().div(two)))
-        }
+    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> AGrad {
+        self.0.conj_grad(x, static_args).conj()
     }
 
     fn eval_conj_grad(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
-    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        if Input::is_always_real() && Output::is_always_real() {
-            let (f, df) = self.0.eval_conj_grad(x, static_args);
-
-            (f.clone().abs(), df.mul(f.signum()))
-        } else {
-            // in the Wirtinger calculus we have
-            // |z| = sqrt(z * conj(z))
-            // d|z|/dz = 1/2 * conj(z) * (z * conj(z))^(-1/2)
-            //         = 1/2 * conj(z) * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            // d|z|/dconjz = 1/2 * z * (z * conj(z))^(-1/2)
-            //            = 1/2 * z * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            //
-            // now for |f|, we have
-            //
-            // d|f|/dz = d|f|/df * df/dz + d|f|/dconjf * conj(df/dconjz)
-            //        = 0.5*conj(f)/|f| * df/dz + 0.5*f/|f| * conj(df/dconjz)
-            //
-            // and
-            //
-            // d|f|/dconjz = d|f|/df * df/dconjz + d|f|/dconjf * conj(df/dz)
-            //          = 0.5*conj(f)/|f| * df/dconjz + 0.5*f/|f| * conj(df/dz)
-
-            // note that for purely real z, this reduces to the real case
+    ) -> (<Self as Diffable<StaticArgs>>::Output, AGrad) {
+        // Wirtinger derivative dconj(f)/dconj(z) = conj(df/dz)
 
-            let (f, dfdconjz) = self.0.eval_conj_grad(x, static_args);
-            let fconj = f.conj();
-            let dconjfdconjz = self.0.grad(x, static_args).conj();
-            let two = f.one().add(f.one());
+        let (f, df) = self.0.eval_grad(x, static_args);
 
-            (
-                f.clone().abs(),
-                dfdconjz
-                    .mul(fconj.signum().div(two.clone()))
-                    .add(dconjfdconjz.mul(f.signum().div(two))),
-            )
-        }
+        (f.conj(), df.conj())
     }
 
     fn conj_grad(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
-    ) -> Grad {
-        if Input::is_always_real() && Output::is_always_real() {
-            let df = self.0.conj_grad(x, static_args);
+    ) -> AGrad {
+        self.0.grad(x, static_args).conj()
+    }
 
-            df.mul(self.0.eval(x, static_args).signum())
-        } else {
-            // in the Wirtinger calculus we have
-            // |z| = sqrt(z * conj(z))
-            // d|z|/dz = 1/2 * conj(z) * (z * conj(z))^(-1/2)
-            //         = 1/2 * conj(z) * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            // d|z|/dconjz = 1/2 * z * (z * conj(z))^(-1/2)
-            //            = 1/2 * z * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            //
-            // now for |f|, we have
-            //
-            // d|f|/dz = d|f|/df * df/dz + d|f|/dconjf * conj(df/dconjz)
-            //        = 0.5*conj(f)/|f| * df/dz + 0.5*f/|f| * conj(df/dconjz)
-            //
-            // and
-            //
-            // d|f|/dconjz = d|f|/df * df/dconjz + d|f|/dconjf * conj(df/dz)
-            //          = 0.5*conj(f)/|f| * df/dconjz + 0.5*f/|f| * conj(df/dz)
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, AGrad, AGrad) {
+        // the derivatives of f are swapped and conjugated
 
-            // note that for purely real z, this reduces to the real case
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
 
-            let (f, dfdconjz) = self.0.eval_conj_grad(x, static_args);
-            let fconj = f.conj();
-            let dconjfdconjz = self.0.grad(x, static_args).conj();
-            let two = f.one().add(f.one());
+        (f.conj(), dfdconjz.conj(), df.conj())
+    }
+}
 
-            dfdconjz
-                .mul(fconj.signum().div(two.clone()))
-                .add(dconjfdconjz.mul(f.signum().div(two)))
-        }
+impl<StaticArgs, Input, AOutput, AGrad, Output, Grad, A> TryAutoDiffable<StaticArgs>
+    for ADConjugate<A>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f and conj(f) can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    // ensure conj(f) can be evaluated from the checked results of f
+    ADConjugate<ADEvaluated<Input, AOutput, AGrad>>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADConjugate", 0))?;
+        check_value(
+            "ADConjugate",
+            ADConjugate(ADEvaluated::value(f)).eval(x, static_args),
+        )
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADConjugate", 0))?;
+        check_all(
+            "ADConjugate",
+            ADConjugate(ADEvaluated::all(f)).eval_all(x, static_args),
+        )
     }
 }
 
-impl<StaticArgs, Input, Output, A> ForwardDiffable<StaticArgs> for ADAbs<A>
+impl<StaticArgs, Input, AOutput, A> ForwardDiffable<StaticArgs> for ADConjugate<A>
 where
-    A: ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
-    Input: PossiblyComplex,
-    Output: Clone
-        + PossiblyComplex
-        + Add<Output, Output = Output>
-        + InstOne
-        + Div<Output, Output = Output>
-        + Signum<Output = Output>
-        + Abs<Output = Output>
-        + Conjugate<Output = Output>
-        + Mul<Output, Output = Output>
-        + Add<Output, Output = Output>,
+    A: ForwardDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    AOutput: Conjugate<Output = AOutput>,
 {
     fn eval_forward(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval_forward(x, static_args).abs()
+        self.0.eval_forward(x, static_args).conj()
     }
     fn eval_forward_grad(
         &self,
@@ -2343,43 +7266,11 @@ where
         <Self as Diffable<StaticArgs>>::Output,
         <Self as Diffable<StaticArgs>>::Output,
     ) {
-        if Input::is_always_real() && Output::is_always_real() {
-            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
-
-            (f.clone().abs(), df.mul(f.signum()))
-        } else {
-            // in the Wirtinger calculus we have
-            // |z| = sqrt(z * conj(z))
-            // d|z|/dz = 1/2 * conj(z) * (z * conj(z))^(-1/2)
-            //         = 1/2 * conj(z) * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            // d|z|/dconjz = 1/2 * z * (z * conj(z))^(-1/2)
-            //            = 1/2 * z * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            //
-            // now for |f|, we have
-            //
-            // d|f|/dz = d|f|/df * df/dz + d|f|/dconjf * conj(df/dconjz)
-            //        = 0.5*conj(f)/|f| * df/dz + 0.5*f/|f| * conj(df/dconjz)
-            //
-            // and
-            //
-            // d|f|/dconjz = d|f|/df * df/dconjz + d|f|/dconjf * conj(df/dz)
-            //          = 0.5*conj(f)/|f| * df/dconjz + 0.5*f/|f| * conj(df/dz)
-
-            // note that for purely real z, this reduces to the real case
+        // Wirtinger derivative conj(df/dz * dz) = dconj(f)/dconj(z) * dconj(z)
 
-            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
-            let fconj = f.conj();
-            let dconjfdz = self.0.forward_conj_grad(x, dx, static_args).conj();
-            let two = f.one().add(f.one());
+        let (f, dfconj) = self.0.eval_forward_conj_grad(x, dx, static_args);
 
-            (
-                f.clone().abs(),
-                df.mul(fconj.signum().div(two.clone()))
-                    .add(dconjfdz.mul(f.signum()).div(two)),
-            )
-        }
+        (f.conj(), dfconj.conj())
     }
 
     fn forward_grad(
@@ -2388,40 +7279,9 @@ where
         dx: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        if Input::is_always_real() && Output::is_always_real() {
-            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
-
-            df.mul(f.signum())
-        } else {
-            // in the Wirtinger calculus we have
-            // |z| = sqrt(z * conj(z))
-            // d|z|/dz = 1/2 * conj(z) * (z * conj(z))^(-1/2)
-            //         = 1/2 * conj(z) * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            // d|z|/dconjz = 1/2 * z * (z * conj(z))^(-1/2)
-            //            = 1/2 * z * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            //
-            // now for |f|, we have
-            //
-            // d|f|/dz = d|f|/df * df/dz + d|f|/dconjf * conj(df/dconjz)
-            //        = 0.5*conj(f)/|f| * df/dz + 0.5*f/|f| * conj(df/dconjz)
-            //
-            // and
-            //
-            // d|f|/dconjz = d|f|/df * df/dconjz + d|f|/dconjf * conj(df/dz)
-            //          = 0.5*conj(f)/|f| * df/dconjz + 0.5*f/|f| * conj(df/dz)
-
-            // note that for purely real z, this reduces to the real case
-
-            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
-            let fconj = f.conj();
-            let dconjfdz = self.0.forward_conj_grad(x, dx, static_args).conj();
-            let two = f.one().add(f.one());
+        // Wirtinger derivative conj(df/dz * dz) = dconj(f)/dconj(z) * dconj(z)
 
-            df.mul(fconj.signum().div(two.clone()))
-                .add(dconjfdz.mul(f.signum()).div(two))
-        }
+        self.0.forward_conj_grad(x, dx, static_args).conj()
     }
 
     fn eval_forward_conj_grad(
@@ -2433,114 +7293,748 @@ where
         <Self as Diffable<StaticArgs>>::Output,
         <Self as Diffable<StaticArgs>>::Output,
     ) {
-        if Input::is_always_real() && Output::is_always_real() {
-            let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+        // Wirtinger derivative conj(df/dz * dz) = dconj(f)/dconj(z) * dconj(z)
 
-            (f.clone().abs(), df.mul(f.signum()))
-        } else {
-            // in the Wirtinger calculus we have
-            // |z| = sqrt(z * conj(z))
-            // d|z|/dz = 1/2 * conj(z) * (z * conj(z))^(-1/2)
-            //         = 1/2 * conj(z) * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            // d|z|/dconjz = 1/2 * z * (z * conj(z))^(-1/2)
-            //            = 1/2 * z * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            //
-            // now for |f|, we have
-            //
-            // d|f|/dz = d|f|/df * df/dz + d|f|/dconjf * conj(df/dconjz)
-            //        = 0.5*conj(f)/|f| * df/dz + 0.5*f/|f| * conj(df/dconjz)
-            //
-            // and
-            //
-            // d|f|/dconjz = d|f|/df * df/dconjz + d|f|/dconjf * conj(df/dz)
-            //          = 0.5*conj(f)/|f| * df/dconjz + 0.5*f/|f| * conj(df/dz)
+        let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
 
-            // note that for purely real z, this reduces to the real case
+        (f.conj(), df.conj())
+    }
 
-            let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
-            let fconj = f.conj();
-            let dconjfdconjz = self.0.forward_grad(x, dx, static_args).conj();
-            let two = f.one().add(f.one());
+    fn forward_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        // Wirtinger derivative conj(df/dz * dz) = dconj(f)/dconj(z) * dconj(z)
 
-            (
-                f.clone().abs(),
-                df.mul(fconj.signum().div(two.clone()))
-                    .add(dconjfdconjz.mul(f.signum().div(two))),
-            )
-        }
+        self.0.forward_grad(x, dx, static_args).conj()
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, A> ReverseDiffable<StaticArgs> for ADConjugate<A>
+where
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure conj(f) is defined and is Output
+    Output: Clone + InstZero + InstOne + Conjugate<Output = Output>,
+{
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
+
+        // dconj(f)/df = 0, dconj(f)/dconjf = 1
+        let (one, zero) = (f.one(), f.zero());
+
+        tape.unary(f.conj(), (u, zero, one))
+    }
+}
+
+impl<StaticArgs, Input, Output, A> VjpDiffable<StaticArgs> for ADConjugate<A>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure conj(f) and conj(c) are defined and are Output
+    Output: Conjugate<Output = Output>,
+    Input: Conjugate<Output = Input>,
+{
+    fn eval_primal(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval_primal(x, static_args).conj()
+    }
+
+    fn eval_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        // Wirtinger derivative dconj(f)/dz = conj(df/dconjz), so
+        // c^T dconj(f)/dz = conj(conj(c)^T df/dconjz)
+
+        let (f, vf) = self.0.eval_conj_vjp(x, &cotangent.conj(), static_args);
+
+        (f.conj(), vf.conj())
     }
 
-    fn forward_conj_grad(
+    fn eval_conj_vjp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
-        if Input::is_always_real() && Output::is_always_real() {
-            let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        // Wirtinger derivative dconj(f)/dconj(z) = conj(df/dz), so
+        // c^T dconj(f)/dconjz = conj(conj(c)^T df/dz)
 
-            df.mul(f.signum())
-        } else {
-            // in the Wirtinger calculus we have
-            // |z| = sqrt(z * conj(z))
-            // d|z|/dz = 1/2 * conj(z) * (z * conj(z))^(-1/2)
-            //         = 1/2 * conj(z) * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            // d|z|/dconjz = 1/2 * z * (z * conj(z))^(-1/2)
-            //            = 1/2 * z * |z|^(-1)
-            // cannot simplify further, since we would be assuming the "sign" of z
-            //
-            // now for |f|, we have
-            //
-            // d|f|/dz = d|f|/df * df/dz + d|f|/dconjf * conj(df/dconjz)
-            //        = 0.5*conj(f)/|f| * df/dz + 0.5*f/|f| * conj(df/dconjz)
-            //
-            // and
-            //
-            // d|f|/dconjz = d|f|/df * df/dconjz + d|f|/dconjf * conj(df/dz)
-            //          = 0.5*conj(f)/|f| * df/dconjz + 0.5*f/|f| * conj(df/dz)
+        let (f, vf) = self.0.eval_vjp(x, &cotangent.conj(), static_args);
 
-            // note that for purely real z, this reduces to the real case
+        (f.conj(), vf.conj())
+    }
+}
 
-            let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
-            let fconj = f.conj();
-            let dconjfdconjz = self.0.forward_grad(x, dx, static_args).conj();
-            let two = f.one().add(f.one());
+// Implements a unary AutoDiff operation g(f) for an outer function g that is holomorphic, i.e.
+// dg/dconj(f) = 0. The derivative dg/df is given as a closure-like expression of f, along with
+// any extra bounds on the output type needed to compute it.
+//
+// in the Wirtinger calculus we have
+//
+// dg(f)/dz = dg/df * df/dz + dg/dconjf * conj(df/dconjz)
+//          = dg/df * df/dz
+//
+// and
+//
+// dg(f)/dconjz = dg/df * df/dconjz + dg/dconjf * conj(df/dz)
+//              = dg/df * df/dconjz
+//
+// so, unlike ADAbs, the real and complex cases coincide and no branching on
+// PossiblyComplex::is_always_real is needed
+macro_rules! impl_holomorphic_adop {
+    (
+        $(#[$meta:meta])*
+        $name:ident, $trait:ident, $method:ident, |$f:ident| $dgdf:expr $(, $bound:path)* $(,)?
+    ) => {
+        $(#[$meta])*
+        #[derive(FuncCompose, Debug, Clone, Copy)]
+        pub struct $name<A>(pub A);
+
+        impl<A: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs> for $name<A> {
+            type Input = A::Input;
+            type Output = A::Output;
+        }
 
-            df.mul(fconj.signum().div(two.clone()))
-                .add(dconjfdconjz.mul(f.signum().div(two)))
+        impl<A: Symbolic> Symbolic for $name<A> {
+            fn symbolic(&self, x: &Expr) -> Expr {
+                Expr::call(stringify!($method), vec![self.0.symbolic(x)])
+            }
         }
-    }
+
+        impl<StaticArgs, A> ToDot<StaticArgs> for $name<A>
+        where
+            A: ToDot<StaticArgs>,
+            Self: DotAnnotate<StaticArgs, Input = A::Input>,
+        {
+            fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+                let f = self.0.dot_node(graph, at);
+                graph.op(stringify!($method), self.annotate_at(at), &[(f, None)])
+            }
+        }
+
+        impl<StaticArgs, T, A> ToDynExpr<StaticArgs, T> for $name<A>
+        where
+            A: ToDynExpr<StaticArgs, T>,
+            T: DynExprScalar,
+            // ensure g(x) can be boxed as an opaque function of the simplified f
+            $name<Identity<StaticArgs, T>>: AutoDiffable<StaticArgs, Input = T, Output = T>
+                + ForwardDiffable<StaticArgs, Input = T, Output = T>
+                + Symbolic
+                + Clone
+                + 'static,
+        {
+            fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+                DynExpr::apply($name(Identity::new()), self.0.to_dyn_expr())
+            }
+        }
+
+        impl<StaticArgs, Input, Output, Grad, A> AutoDiffable<StaticArgs> for $name<A>
+        where
+            A: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+            Input: GradientType<Output, GradientType = Grad>,
+            // ensure g(f) is defined and is Output, and dg/df can be computed
+            Output: Clone + $trait<Output = Output> $(+ $bound)*,
+            // ensure df * dg/df is defined and is Grad
+            Grad: Mul<Output, Output = Grad>,
+        {
+            fn eval(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                self.0.eval(x, static_args).$method()
+            }
+
+            fn eval_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+                let (f, df) = self.0.eval_grad(x, static_args);
+
+                // dg(f)/dz = dg/df * df/dz
+
+                let dgdf = {
+                    let $f = f.clone();
+                    $dgdf
+                };
+
+                (f.$method(), df.mul(dgdf))
+            }
+
+            fn grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> Grad {
+                let (f, df) = self.0.eval_grad(x, static_args);
+
+                // dg(f)/dz = dg/df * df/dz
+
+                let $f = f;
+                df.mul($dgdf)
+            }
+
+            fn eval_conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+                let (f, dfdconjz) = self.0.eval_conj_grad(x, static_args);
+
+                // dg(f)/dconjz = dg/df * df/dconjz
+
+                let dgdf = {
+                    let $f = f.clone();
+                    $dgdf
+                };
+
+                (f.$method(), dfdconjz.mul(dgdf))
+            }
+
+            fn conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> Grad {
+                let (f, dfdconjz) = self.0.eval_conj_grad(x, static_args);
+
+                // dg(f)/dconjz = dg/df * df/dconjz
+
+                let $f = f;
+                dfdconjz.mul($dgdf)
+            }
+
+            fn eval_all(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+                let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+
+                // both derivatives follow the chain rule through dg/df, since g is holomorphic
+
+                let dgdf = {
+                    let $f = f.clone();
+                    $dgdf
+                };
+
+                (f.$method(), df.mul(dgdf.clone()), dfdconjz.mul(dgdf))
+            }
+        }
+
+        impl<StaticArgs, Input, AOutput, AGrad, Output, Grad, A> TryAutoDiffable<StaticArgs>
+            for $name<A>
+        where
+            A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+            Input: GradientType<AOutput, GradientType = AGrad>,
+            Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+            Input: GradientType<Output, GradientType = Grad>,
+            // ensure the results of f and g(f) can be checked
+            AOutput: CheckValue,
+            AGrad: CheckValue,
+            Output: CheckValue,
+            Grad: CheckValue,
+            // ensure g(f) can be evaluated from the checked results of f
+            $name<ADEvaluated<Input, AOutput, AGrad>>:
+                AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+        {
+            fn try_eval(
+                &self,
+                x: &Input,
+                static_args: &StaticArgs,
+            ) -> Result<Output, AutoDiffError> {
+                let f = self
+                    .0
+                    .try_eval(x, static_args)
+                    .map_err(|e| e.within(stringify!($name), 0))?;
+                check_value(
+                    stringify!($name),
+                    $name(ADEvaluated::value(f)).eval(x, static_args),
+                )
+            }
+
+            fn try_eval_all(
+                &self,
+                x: &Input,
+                static_args: &StaticArgs,
+            ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+                let f = self
+                    .0
+                    .try_eval_all(x, static_args)
+                    .map_err(|e| e.within(stringify!($name), 0))?;
+                check_all(
+                    stringify!($name),
+                    $name(ADEvaluated::all(f)).eval_all(x, static_args),
+                )
+            }
+        }
+
+        impl<StaticArgs, Input, Output, A> ForwardDiffable<StaticArgs> for $name<A>
+        where
+            A: ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+            // ensure g(f) is defined and is Output, and dg/df can be computed
+            Output: Clone + $trait<Output = Output> + Mul<Output, Output = Output> $(+ $bound)*,
+        {
+            fn eval_forward(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                self.0.eval_forward(x, static_args).$method()
+            }
+
+            fn eval_forward_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Output,
+            ) {
+                let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+
+                // dg(f) = dg/df * df
+
+                let dgdf = {
+                    let $f = f.clone();
+                    $dgdf
+                };
+
+                (f.$method(), df.mul(dgdf))
+            }
+
+            fn forward_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+
+                // dg(f) = dg/df * df
+
+                let $f = f;
+                df.mul($dgdf)
+            }
+
+            fn eval_forward_conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Output,
+            ) {
+                let (f, dfconj) = self.0.eval_forward_conj_grad(x, dx, static_args);
+
+                // dg(f)/dconjz * dx = dg/df * df/dconjz * dx
+
+                let dgdf = {
+                    let $f = f.clone();
+                    $dgdf
+                };
+
+                (f.$method(), dfconj.mul(dgdf))
+            }
+
+            fn forward_conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                let (f, dfconj) = self.0.eval_forward_conj_grad(x, dx, static_args);
+
+                // dg(f)/dconjz * dx = dg/df * df/dconjz * dx
+
+                let $f = f;
+                dfconj.mul($dgdf)
+            }
+        }
+
+        impl<StaticArgs, Input, Output, Grad, A> ReverseDiffable<StaticArgs> for $name<A>
+        where
+            A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+            Input: GradientType<Output, GradientType = Grad>,
+            // ensure g(f) is defined and is Output, and dg/df can be computed
+            Output: Clone + InstZero + $trait<Output = Output> $(+ $bound)*,
+        {
+            fn eval_reverse<'a>(
+                &'a self,
+                x: &'a <Self as Diffable<StaticArgs>>::Input,
+                tape: &mut Tape<'a, Grad, Output>,
+                static_args: &'a StaticArgs,
+            ) -> Var {
+                let u = self.0.eval_reverse(x, tape, static_args);
+                let f = tape.value(u).clone();
+
+                // dg(f)/df = g'(f), dg(f)/dconjf = 0
+
+                let zero = f.zero();
+                let dgdf = {
+                    let $f = f.clone();
+                    $dgdf
+                };
+
+                tape.unary(f.$method(), (u, dgdf, zero))
+            }
+        }
+
+        impl<StaticArgs, Input, Output, A> VjpDiffable<StaticArgs> for $name<A>
+        where
+            A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+            // ensure g(f) is defined and is Output, and dg/df can be computed
+            Output: Clone + $trait<Output = Output> + Mul<Output, Output = Output> $(+ $bound)*,
+        {
+            fn eval_primal(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                self.0.eval_primal(x, static_args).$method()
+            }
+
+            fn eval_vjp(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Input,
+            ) {
+                let f = self.0.eval_primal(x, static_args);
+
+                // c^T dg(f)/dz = (c * dg/df)^T df/dz
+
+                let dgdf = {
+                    let $f = f.clone();
+                    $dgdf
+                };
+                let vf = self.0.vjp(x, &cotangent.clone().mul(dgdf), static_args);
+
+                (f.$method(), vf)
+            }
+
+            fn eval_conj_vjp(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Input,
+            ) {
+                let f = self.0.eval_primal(x, static_args);
+
+                // c^T dg(f)/dconjz = (c * dg/df)^T df/dconjz
+
+                let dgdf = {
+                    let $f = f.clone();
+                    $dgdf
+                };
+                let vf = self.0.conj_vjp(x, &cotangent.clone().mul(dgdf), static_args);
+
+                (f.$method(), vf)
+            }
+        }
+
+        impl<StaticArgs, Input, Output, A, const K: usize> TaylorDiffable<StaticArgs, K> for $name<A>
+        where
+            A: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+            // ensure the function is defined on the series of f
+            Taylor<Output, K>: $trait<Output = Taylor<Output, K>>,
+        {
+            fn eval_taylor_series(
+                &self,
+                x: &Taylor<<Self as Diffable<StaticArgs>>::Input, K>,
+                static_args: &StaticArgs,
+            ) -> Taylor<<Self as Diffable<StaticArgs>>::Output, K> {
+                self.0.eval_taylor_series(x, static_args).$method()
+            }
+        }
+    };
+}
+
+// Implements HessianDiffable and HvpDiffable for a unary operation g(f) defined by
+// impl_holomorphic_adop, for which the chain rule is
+//
+// d²g(f) = d²g/df² * df ⊗ df + dg/df * d²f
+//
+// and the cotangent of f is c * dg/df, whose derivative along v is
+//
+// dc * dg/df + c * d²g/df² * df
+//
+// dg/df and d²g/df² are given as closure-like expressions of f, followed by the extra bounds
+// needed on the output type to compute them.
+macro_rules! impl_holomorphic_second_order {
+    (
+        $name:ident, $trait:ident, $method:ident,
+        |$f:ident| $dgdf:expr, |$f2:ident| $d2gdf2:expr $(, $bound:path)* $(,)?
+    ) => {
+        impl<StaticArgs, Input, Output, Grad, Hess, A> HessianDiffable<StaticArgs> for $name<A>
+        where
+            A: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+            Input: GradientType<Output, GradientType = Grad>,
+            // ensure g(f) is defined and is Output, and dg/df and d²g/df² can be computed
+            Output: Clone + $trait<Output = Output> $(+ $bound)*,
+            // ensure df * dg/df and df ⊗ df * d²g/df² are defined and are Grad and Hess
+            Grad: Clone + Mul<Output, Output = Grad> + Outer<Grad, Output = Hess>,
+            Hess: Add<Hess, Output = Hess> + Mul<Output, Output = Hess>,
+        {
+            fn eval_hessian(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Hess) {
+                let (f, df, d2f) = self.0.eval_hessian(x, static_args);
+
+                let dgdf = {
+                    let $f = f.clone();
+                    $dgdf
+                };
+                let d2gdf2 = {
+                    let $f2 = f.clone();
+                    $d2gdf2
+                };
+
+                (
+                    f.$method(),
+                    df.clone().mul(dgdf.clone()),
+                    df.outer(&df).mul(d2gdf2).add(d2f.mul(dgdf)),
+                )
+            }
+        }
+
+        impl<StaticArgs, Input, Output, A> HvpDiffable<StaticArgs> for $name<A>
+        where
+            A: HvpDiffable<StaticArgs, Input = Input, Output = Output>
+                + ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+            // ensure g(f) is defined and is Output, and dg/df and d²g/df² can be computed
+            Output: Clone
+                + $trait<Output = Output>
+                + Add<Output, Output = Output>
+                + Mul<Output, Output = Output>
+                $(+ $bound)*,
+        {
+            fn eval_hvp(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                v: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Input,
+                <Self as Diffable<StaticArgs>>::Input,
+            ) {
+                let (f, df) = self.0.eval_forward_grad(x, v, static_args);
+
+                let dgdf = {
+                    let $f = f.clone();
+                    $dgdf
+                };
+                let d2gdf2 = {
+                    let $f2 = f.clone();
+                    $d2gdf2
+                };
+
+                let cf = cotangent.clone().mul(dgdf.clone());
+                let dcf = dcotangent
+                    .clone()
+                    .mul(dgdf)
+                    .add(cotangent.clone().mul(d2gdf2).mul(df));
+
+                let (_, vf, dvf) = self.0.eval_hvp(x, v, &cf, &dcf, static_args);
+
+                (f.$method(), vf, dvf)
+            }
+        }
+    };
 }
 
+impl_holomorphic_adop!(
+    /// e^f
+    ADExp, Exp, exp,
+    // d(e^f)/df = e^f
+    |f| f.exp(),
+);
+
+impl_holomorphic_second_order!(
+    ADExp,
+    Exp,
+    exp,
+    |f| f.exp(),
+    // d²(exp(f))/df² = e^f
+    |f| f.exp(),
+);
+
+impl_holomorphic_adop!(
+    /// ln(f), the principal branch for complex f
+    ADLn, Ln, ln,
+    // d(ln(f))/df = 1/f
+    |f| f.one().div(f),
+    InstOne,
+    Div<Output, Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADLn, Ln, ln,
+    |f| f.one().div(f),
+    // d²(ln(f))/df² = -1/f^2
+    |f| f.one().div(f.clone().mul(f)).neg(),
+    InstOne,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+    Neg<Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// log_2(f), the principal branch for complex f
+    ADLog2, Log2, log2,
+    // d(log_2(f))/df = 1/(f ln(2)) = log_2(e)/f
+    |f| f.one().exp().log2().div(f),
+    InstOne,
+    Exp<Output = Output>,
+    Div<Output, Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADLog2, Log2, log2,
+    |f| f.one().exp().log2().div(f),
+    // d²(log2(f))/df² = -log_2(e)/f^2
+    |f| f.one().exp().log2().div(f.clone().mul(f)).neg(),
+    InstOne,
+    Exp<Output = Output>,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+    Neg<Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// log_10(f), the principal branch for complex f
+    ADLog10, Log10, log10,
+    // d(log_10(f))/df = 1/(f ln(10)) = log_10(e)/f
+    |f| f.one().exp().log10().div(f),
+    InstOne,
+    Exp<Output = Output>,
+    Div<Output, Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADLog10, Log10, log10,
+    |f| f.one().exp().log10().div(f),
+    // d²(log10(f))/df² = -log_10(e)/f^2
+    |f| f.one().exp().log10().div(f.clone().mul(f)).neg(),
+    InstOne,
+    Exp<Output = Output>,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+    Neg<Output = Output>,
+);
+
+/// log_b(f) for a constant base b
 #[derive(FuncCompose, Debug, Clone, Copy)]
-pub struct ADAbsSqr<A>(pub A);
+pub struct ADLog<A, B>(pub A, pub B);
 
-impl<A: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs> for ADAbsSqr<A> {
+impl<A: Diffable<StaticArgs>, B, StaticArgs> Diffable<StaticArgs> for ADLog<A, B>
+where
+    A::Output: Log<B>,
+    B: Clone,
+{
     type Input = A::Input;
-    type Output = A::Output;
+    type Output = <A::Output as Log<B>>::Output;
 }
 
-impl<StaticArgs, Input, Output, Grad, A> AutoDiffable<StaticArgs> for ADAbsSqr<A>
+impl<A, B> Symbolic for ADLog<A, B>
 where
-    A: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
-    Input: PossiblyComplex + GradientType<Output, GradientType = Grad>,
-    Output: PossiblyComplex
-        + AbsSqr<Output = Output>
-        + Conjugate<Output = Output>
-        + Add<Output, Output = Output>
+    A: Symbolic,
+    B: SymbolicConstant,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::call("log", vec![self.0.symbolic(x), self.1.symbolic_constant()])
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADLog<A, B>
+where
+    A: ToDot<StaticArgs>,
+    B: SymbolicConstant,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let c = graph.constant(&self.1.symbolic_constant().to_string());
+        graph.op(
+            "log",
+            self.annotate_at(at),
+            &[(f, Some("lhs")), (c, Some("base"))],
+        )
+    }
+}
+
+impl<StaticArgs, T, A, B> ToDynExpr<StaticArgs, T> for ADLog<A, B>
+where
+    A: ToDynExpr<StaticArgs, T>,
+    T: DynExprScalar,
+    B: Clone,
+    // ensure log_b(x) can be boxed as an opaque function of the simplified f
+    ADLog<Identity<StaticArgs, T>, B>: AutoDiffable<StaticArgs, Input = T, Output = T>
+        + ForwardDiffable<StaticArgs, Input = T, Output = T>
+        + Symbolic
         + Clone
-        + Mul<Grad, Output = Grad>,
-    Grad: Conjugate<Output = Grad> + Mul<Output, Output = Grad> + Add<Grad, Output = Grad>,
+        + 'static,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::apply(ADLog(Identity::new(), self.1.clone()), self.0.to_dyn_expr())
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, A, B> AutoDiffable<StaticArgs> for ADLog<A, B>
+where
+    A: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure log_b(f) is defined and is Output, and 1/(f ln(b)) is defined and is Output
+    Output: Clone
+        + Log<B, Output = Output>
+        + InstOne
+        + Mul<B, Output = Output>
+        + Div<Output, Output = Output>,
+    // ensure B is Clone and ln(b) is defined and is B
+    B: Clone + Ln<Output = B>,
+    // ensure df * 1/(f ln(b)) is defined and is Grad
+    Grad: Mul<Output, Output = Grad>,
 {
     fn eval(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval(x, static_args).abs_sqr()
+        self.0.eval(x, static_args).log(self.1.clone())
     }
 
     fn eval_grad(
@@ -2548,55 +8042,23 @@ where
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        if Input::is_always_real() && Output::is_always_real() {
-            // for real z, |z|^2 -> 2 |z| * sign(z) = 2z
-            // |f|^2 -> 2f * df/dz
-
-            let (f, df) = self.0.eval_grad(x, static_args);
-
-            (f.clone().abs_sqr(), df.mul(f.clone().add(f)))
-        } else {
-            // in the Wirtinger calculus we have
-            // |z|^2 = z * conj(z)
-            // d|z|^2/dz = conj(z)
-            //
-            // now for |f|^2, we have
-            //
-            // d|f|^2/dz = d|f|^2/df * df/dz + d|f|^2/dconjf * conj(df/dconjz)
-            //           = conj(f) * df/dz + f * conj(df/dconjz)
+        let (f, df) = self.0.eval_grad(x, static_args);
 
-            let (f, df) = self.0.eval_grad(x, static_args);
-            let fconj = f.conj();
-            let dconjfdz = self.0.conj_grad(x, static_args).conj();
+        // d(log_b(f)) = df / (f * ln(b))
+        //
+        // this is true for Wirtinger calculus as well, since the outer function in the chain rule
+        // is log_b(z), which has conjugate derivative 0
 
-            (f.clone().abs_sqr(), df.mul(fconj).add(f.mul(dconjfdz)))
-        }
+        (
+            f.clone().log(self.1.clone()),
+            df.mul(f.one().div(f.mul(self.1.clone().ln()))),
+        )
     }
 
     fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
-        if Input::is_always_real() && Output::is_always_real() {
-            // for real z, |z|^2 -> 2 |z| * sign(z) = 2z
-            // |f|^2 -> 2f * df/dz
-
-            let (f, df) = self.0.eval_grad(x, static_args);
-
-            df.mul(f.clone().add(f))
-        } else {
-            // in the Wirtinger calculus we have
-            // |z|^2 = z * conj(z)
-            // d|z|^2/dz = conj(z)
-            //
-            // now for |f|^2, we have
-            //
-            // d|f|^2/dz = d|f|^2/df * df/dz + d|f|^2/dconjf * conj(df/dconjz)
-            //           = conj(f) * df/dz + f * conj(df/dconjz)
-
-            let (f, df) = self.0.eval_grad(x, static_args);
-            let fconj = f.conj();
-            let dconjfdz = self.0.conj_grad(x, static_args).conj();
+        let (f, df) = self.0.eval_grad(x, static_args);
 
-            df.mul(fconj).add(f.mul(dconjfdz))
-        }
+        df.mul(f.one().div(f.mul(self.1.clone().ln())))
     }
 
     fn eval_conj_grad(
@@ -2604,32 +8066,14 @@ where
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        if Input::is_always_real() && Output::is_always_real() {
-            // for real z, |z|^2 -> 2 |z| * sign(z) = 2z
-            // |f|^2 -> 2f * df/dz
-
-            let (f, df) = self.0.eval_conj_grad(x, static_args);
-
-            (f.clone().abs_sqr(), df.mul(f.clone().add(f)))
-        } else {
-            // in the Wirtinger calculus we have
-            // |z|^2 = z * conj(z)
-            // d|z|^2/dconjz = z
-            //
-            // now for |f|^2, we have
-            //
-            // d|f|^2/dconjz = d|f|^2/df * df/dconjz + d|f|^2/dconjf * conj(df/dz)
-            //           = conj(f) * df/dconjz + f * conj(df/dz)
+        let (f, df) = self.0.eval_conj_grad(x, static_args);
 
-            let (f, dfdconjz) = self.0.eval_conj_grad(x, static_args);
-            let fconj = f.conj();
-            let dconjfdconjz = self.0.grad(x, static_args).conj();
+        // d(log_b(f)) = df / (f * ln(b))
 
-            (
-                f.clone().abs_sqr(),
-                dfdconjz.mul(fconj).add(f.mul(dconjfdconjz)),
-            )
-        }
+        (
+            f.clone().log(self.1.clone()),
+            df.mul(f.one().div(f.mul(self.1.clone().ln()))),
+        )
     }
 
     fn conj_grad(
@@ -2637,50 +8081,93 @@ where
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> Grad {
-        if Input::is_always_real() && Output::is_always_real() {
-            // for real z, |z|^2 -> 2 |z| * sign(z) = 2z
-            // |f|^2 -> 2f * df/dz
+        let (f, df) = self.0.eval_conj_grad(x, static_args);
 
-            let (f, df) = self.0.eval_conj_grad(x, static_args);
+        df.mul(f.one().div(f.mul(self.1.clone().ln())))
+    }
 
-            df.mul(f.clone().add(f))
-        } else {
-            // in the Wirtinger calculus we have
-            // |z|^2 = z * conj(z)
-            // d|z|^2/dconjz = z
-            //
-            // now for |f|^2, we have
-            //
-            // d|f|^2/dconjz = d|f|^2/df * df/dconjz + d|f|^2/dconjf * conj(df/dz)
-            //           = conj(f) * df/dconjz + f * conj(df/dz)
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
 
-            let (f, dfdconjz) = self.0.eval_conj_grad(x, static_args);
-            let fconj = f.conj();
-            let dconjfdconjz = self.0.grad(x, static_args).conj();
+        // both derivatives are scaled by 1 / (f * ln(b)), since log_b is holomorphic
 
-            dfdconjz.mul(fconj).add(f.mul(dconjfdconjz))
-        }
+        let dlogdf = f.one().div(f.clone().mul(self.1.clone().ln()));
+
+        (
+            f.log(self.1.clone()),
+            df.mul(dlogdf.clone()),
+            dfdconjz.mul(dlogdf),
+        )
     }
 }
 
-impl<StaticArgs, Input, Output, A> ForwardDiffable<StaticArgs> for ADAbsSqr<A>
+impl<StaticArgs, Input, AOutput, AGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs>
+    for ADLog<A, B>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f and log_b(f) can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    B: Clone,
+    // ensure log_b(f) can be evaluated from the checked results of f
+    ADLog<ADEvaluated<Input, AOutput, AGrad>, B>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADLog", 0))?;
+
+        let h = ADLog(ADEvaluated::value(f), self.1.clone());
+        check_value("ADLog", h.eval(x, static_args))
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADLog", 0))?;
+
+        let h = ADLog(ADEvaluated::all(f), self.1.clone());
+        check_all("ADLog", h.eval_all(x, static_args))
+    }
+}
+
+impl<StaticArgs, Input, Output, A, B> ForwardDiffable<StaticArgs> for ADLog<A, B>
 where
     A: ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
-    Input: PossiblyComplex,
-    Output: PossiblyComplex
-        + AbsSqr<Output = Output>
-        + Conjugate<Output = Output>
+    // ensure log_b(f) is defined and is Output, and df/(f ln(b)) is defined and is Output
+    Output: Clone
+        + Log<B, Output = Output>
+        + InstOne
+        + Mul<B, Output = Output>
         + Mul<Output, Output = Output>
-        + Add<Output, Output = Output>
-        + Clone,
+        + Div<Output, Output = Output>,
+    // ensure B is Clone and ln(b) is defined and is B
+    B: Clone + Ln<Output = B>,
 {
     fn eval_forward(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval_forward(x, static_args).abs_sqr()
+        self.0.eval_forward(x, static_args).log(self.1.clone())
     }
+
     fn eval_forward_grad(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
@@ -2690,133 +8177,579 @@ where
         <Self as Diffable<StaticArgs>>::Output,
         <Self as Diffable<StaticArgs>>::Output,
     ) {
-        if Input::is_always_real() && Output::is_always_real() {
-            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+        let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
 
-            (f.clone().abs_sqr(), df.mul(f.clone().add(f)))
-        } else {
-            // in the Wirtinger calculus we have
-            // |z|^2 = z * conj(z)
-            // d|z|^2/dz = conj(z)
-            //
-            // now for |f|^2, we have
-            //
-            // d|f|^2/dz = d|f|^2/df * df/dz + d|f|^2/dconjf * conj(df/dconjz)
-            //           = conj(f) * df/dz + f * conj(df/dconjz)
+        // d(log_b(f)) = df / (f * ln(b))
+
+        (
+            f.clone().log(self.1.clone()),
+            df.mul(f.one().div(f.mul(self.1.clone().ln()))),
+        )
+    }
+
+    fn forward_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+
+        df.mul(f.one().div(f.mul(self.1.clone().ln())))
+    }
+
+    fn eval_forward_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Output,
+    ) {
+        let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
 
-            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
-            let fconj = f.conj();
-            let dconjf = self.0.forward_conj_grad(x, dx, static_args).conj();
+        // d(log_b(f)) = df / (f * ln(b))
 
-            (f.clone().abs_sqr(), df.mul(fconj).add(f.mul(dconjf)))
-        }
+        (
+            f.clone().log(self.1.clone()),
+            df.mul(f.one().div(f.mul(self.1.clone().ln()))),
+        )
     }
 
-    fn forward_grad(
+    fn forward_conj_grad(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         dx: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        if Input::is_always_real() && Output::is_always_real() {
-            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+        let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
 
-            df.mul(f.clone().add(f))
-        } else {
-            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
-            let fconj = f.conj();
-            let dconjf = self.0.forward_conj_grad(x, dx, static_args).conj();
+        df.mul(f.one().div(f.mul(self.1.clone().ln())))
+    }
+}
 
-            df.mul(fconj).add(f.mul(dconjf))
-        }
+impl<StaticArgs, Input, Output, Grad, A, B> ReverseDiffable<StaticArgs> for ADLog<A, B>
+where
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure log_b(f) and 1/(f ln(b)) are defined and are Output
+    Output: Clone
+        + InstZero
+        + InstOne
+        + Log<B, Output = Output>
+        + Mul<B, Output = Output>
+        + Div<Output, Output = Output>,
+    // ensure B is Clone and ln(b) is defined and is B
+    B: Clone + Ln<Output = B>,
+{
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
+
+        // d(log_b(f))/df = 1 / (f * ln(b))
+        let zero = f.zero();
+        let dlogdf = f.one().div(f.clone().mul(self.1.clone().ln()));
+
+        tape.unary(f.log(self.1.clone()), (u, dlogdf, zero))
     }
+}
 
-    fn eval_forward_conj_grad(
+impl<StaticArgs, Input, Output, A, B> VjpDiffable<StaticArgs> for ADLog<A, B>
+where
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure log_b(f), 1/(f ln(b)) and the cotangent c/(f ln(b)) are defined and are Output
+    Output: Clone
+        + InstOne
+        + Log<B, Output = Output>
+        + Mul<B, Output = Output>
+        + Mul<Output, Output = Output>
+        + Div<Output, Output = Output>,
+    // ensure B is Clone and ln(b) is defined and is B
+    B: Clone + Ln<Output = B>,
+{
+    fn eval_primal(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output {
+        self.0.eval_primal(x, static_args).log(self.1.clone())
+    }
+
+    fn eval_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
     ) -> (
         <Self as Diffable<StaticArgs>>::Output,
-        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
     ) {
-        if Input::is_always_real() && Output::is_always_real() {
-            let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+        let f = self.0.eval_primal(x, static_args);
 
-            (f.clone().abs_sqr(), df.mul(f.clone().add(f)))
-        } else {
-            // in the Wirtinger calculus we have
-            // |z|^2 = z * conj(z)
-            // d|z|^2/dconjz = z
-            //
-            // now for |f|^2, we have
-            //
-            // d|f|^2/dconjz = d|f|^2/df * df/dconjz + d|f|^2/dconjf * conj(df/dz)
-            //           = conj(f) * df/dconjz + f * conj(df/dz)
+        // c^T d(log_b(f)) = (c / (f * ln(b)))^T df
 
-            let (f, dfdconjz) = self.0.eval_forward_conj_grad(x, dx, static_args);
-            let fconj = f.conj();
-            let dconjfdconjz = self.0.forward_grad(x, dx, static_args).conj();
+        let dlogdf = f.one().div(f.clone().mul(self.1.clone().ln()));
+        let vf = self.0.vjp(x, &cotangent.clone().mul(dlogdf), static_args);
 
-            (
-                f.clone().abs_sqr(),
-                dfdconjz.mul(fconj).add(f.mul(dconjfdconjz)),
-            )
-        }
+        (f.log(self.1.clone()), vf)
     }
 
-    fn forward_conj_grad(
+    fn eval_conj_vjp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
-        if Input::is_always_real() && Output::is_always_real() {
-            let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let f = self.0.eval_primal(x, static_args);
 
-            df.mul(f.clone().add(f))
-        } else {
-            let (f, dfdconjz) = self.0.eval_forward_conj_grad(x, dx, static_args);
-            let fconj = f.conj();
-            let dconjfdconjz = self.0.forward_grad(x, dx, static_args).conj();
+        let dlogdf = f.one().div(f.clone().mul(self.1.clone().ln()));
+        let vf = self
+            .0
+            .conj_vjp(x, &cotangent.clone().mul(dlogdf), static_args);
 
-            dfdconjz.mul(fconj).add(f.mul(dconjfdconjz))
-        }
+        (f.log(self.1.clone()), vf)
     }
 }
 
+impl_holomorphic_adop!(
+    /// sin(f)
+    ADSin, Sin, sin,
+    // d(sin(f))/df = cos(f)
+    |f| f.cos(),
+    Cos<Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADSin,
+    Sin,
+    sin,
+    |f| f.cos(),
+    // d²(sin(f))/df² = -sin(f)
+    |f| f.sin().neg(),
+    Cos<Output = Output>,
+    Neg<Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// cos(f)
+    ADCos, Cos, cos,
+    // d(cos(f))/df = -sin(f)
+    |f| f.sin().neg(),
+    Sin<Output = Output>,
+    Neg<Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADCos,
+    Cos,
+    cos,
+    |f| f.sin().neg(),
+    // d²(cos(f))/df² = -cos(f)
+    |f| f.cos().neg(),
+    Sin<Output = Output>,
+    Neg<Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// tan(f)
+    ADTan, Tan, tan,
+    // d(tan(f))/df = 1 + tan(f)^2
+    |f| {
+        let one = f.one();
+        let tan_f = f.tan();
+        one.add(tan_f.clone().mul(tan_f))
+    },
+    InstOne,
+    Add<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADTan, Tan, tan,
+    |f| {
+        let tan_f = f.tan();
+        tan_f.one().add(tan_f.clone().mul(tan_f))
+    },
+    // d²(tan(f))/df² = 2 tan(f) (1 + tan(f)^2)
+    |f| {
+        let tan_f = f.tan();
+        let dtan_f = tan_f.one().add(tan_f.clone().mul(tan_f.clone()));
+        tan_f.clone().add(tan_f).mul(dtan_f)
+    },
+    InstOne,
+    Add<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// asin(f), the principal branch for complex f
+    ADAsin, Asin, asin,
+    // d(asin(f))/df = 1/sqrt(1 - f^2)
+    |f| f.one().div(f.one().sub(f.clone().mul(f)).sqrt()),
+    InstOne,
+    Sub<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+    Sqrt<Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADAsin, Asin, asin,
+    |f| f.one().div(f.one().sub(f.clone().mul(f)).sqrt()),
+    // d²(asin(f))/df² = f/(1 - f^2)^(3/2)
+    |f| {
+        let r = f.one().div(f.one().sub(f.clone().mul(f.clone())).sqrt());
+        f.mul(r.clone()).mul(r.clone()).mul(r)
+    },
+    InstOne,
+    Sub<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+    Sqrt<Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// acos(f), the principal branch for complex f
+    ADAcos, Acos, acos,
+    // d(acos(f))/df = -1/sqrt(1 - f^2)
+    |f| f.one().div(f.one().sub(f.clone().mul(f)).sqrt()).neg(),
+    InstOne,
+    Sub<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+    Neg<Output = Output>,
+    Sqrt<Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADAcos, Acos, acos,
+    |f| f.one().div(f.one().sub(f.clone().mul(f)).sqrt()).neg(),
+    // d²(acos(f))/df² = -f/(1 - f^2)^(3/2)
+    |f| {
+        let r = f.one().div(f.one().sub(f.clone().mul(f.clone())).sqrt());
+        f.mul(r.clone()).mul(r.clone()).mul(r).neg()
+    },
+    InstOne,
+    Sub<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+    Neg<Output = Output>,
+    Sqrt<Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// atan(f), the principal branch for complex f
+    ADAtan, Atan, atan,
+    // d(atan(f))/df = 1/(1 + f^2)
+    |f| f.one().div(f.one().add(f.clone().mul(f))),
+    InstOne,
+    Add<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADAtan, Atan, atan,
+    |f| f.one().div(f.one().add(f.clone().mul(f))),
+    // d²(atan(f))/df² = -2f/(1 + f^2)^2
+    |f| {
+        let r = f.one().div(f.one().add(f.clone().mul(f.clone())));
+        f.clone().add(f).mul(r.clone()).mul(r).neg()
+    },
+    InstOne,
+    Add<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+    Neg<Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// sinh(f)
+    ADSinh, Sinh, sinh,
+    // d(sinh(f))/df = cosh(f)
+    |f| f.cosh(),
+    Cosh<Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADSinh,
+    Sinh,
+    sinh,
+    |f| f.cosh(),
+    // d²(sinh(f))/df² = sinh(f)
+    |f| f.sinh(),
+    Cosh<Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// cosh(f)
+    ADCosh, Cosh, cosh,
+    // d(cosh(f))/df = sinh(f)
+    |f| f.sinh(),
+    Sinh<Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADCosh,
+    Cosh,
+    cosh,
+    |f| f.sinh(),
+    // d²(cosh(f))/df² = cosh(f)
+    |f| f.cosh(),
+    Sinh<Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// tanh(f)
+    ADTanh, Tanh, tanh,
+    // d(tanh(f))/df = 1 - tanh(f)^2
+    |f| {
+        let one = f.one();
+        let tanh_f = f.tanh();
+        one.sub(tanh_f.clone().mul(tanh_f))
+    },
+    InstOne,
+    Sub<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADTanh, Tanh, tanh,
+    |f| {
+        let tanh_f = f.tanh();
+        tanh_f.one().sub(tanh_f.clone().mul(tanh_f))
+    },
+    // d²(tanh(f))/df² = -2 tanh(f) (1 - tanh(f)^2)
+    |f| {
+        let tanh_f = f.tanh();
+        let dtanh_f = tanh_f.one().sub(tanh_f.clone().mul(tanh_f.clone()));
+        tanh_f.clone().add(tanh_f).mul(dtanh_f).neg()
+    },
+    InstOne,
+    Add<Output, Output = Output>,
+    Sub<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Neg<Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// asinh(f), the principal branch for complex f
+    ADAsinh, Asinh, asinh,
+    // d(asinh(f))/df = 1/sqrt(f^2 + 1)
+    |f| f.one().div(f.clone().mul(f.clone()).add(f.one()).sqrt()),
+    InstOne,
+    Add<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+    Sqrt<Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADAsinh, Asinh, asinh,
+    |f| f.one().div(f.clone().mul(f.clone()).add(f.one()).sqrt()),
+    // d²(asinh(f))/df² = -f/(f^2 + 1)^(3/2)
+    |f| {
+        let r = f.one().div(f.clone().mul(f.clone()).add(f.one()).sqrt());
+        f.mul(r.clone()).mul(r.clone()).mul(r).neg()
+    },
+    InstOne,
+    Add<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+    Neg<Output = Output>,
+    Sqrt<Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// acosh(f), the principal branch for complex f
+    ADAcosh, Acosh, acosh,
+    // d(acosh(f))/df = 1/(sqrt(f - 1) * sqrt(f + 1))
+    //
+    // this is 1/sqrt(f^2 - 1) for real f > 1, but the split form keeps the derivative on the
+    // same branch as the principal acosh for complex f
+    |f| {
+        let one = f.one();
+        one.clone().div(
+            f.clone()
+                .sub(one.clone())
+                .sqrt()
+                .mul(f.add(one).sqrt()),
+        )
+    },
+    InstOne,
+    Add<Output, Output = Output>,
+    Sub<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+    Sqrt<Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADAcosh, Acosh, acosh,
+    |f| {
+        let one = f.one();
+        one.clone().div(f.clone().sub(one.clone()).sqrt().mul(f.add(one).sqrt()))
+    },
+    // d²(acosh(f))/df² = -f/(sqrt(f - 1) * sqrt(f + 1))^3
+    |f| {
+        let one = f.one();
+        let r = one.clone().div(f.clone().sub(one.clone()).sqrt().mul(f.clone().add(one).sqrt()));
+        f.mul(r.clone()).mul(r.clone()).mul(r).neg()
+    },
+    InstOne,
+    Add<Output, Output = Output>,
+    Sub<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+    Neg<Output = Output>,
+    Sqrt<Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// atanh(f), the principal branch for complex f
+    ADAtanh, Atanh, atanh,
+    // d(atanh(f))/df = 1/(1 - f^2)
+    |f| f.one().div(f.one().sub(f.clone().mul(f))),
+    InstOne,
+    Sub<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADAtanh, Atanh, atanh,
+    |f| f.one().div(f.one().sub(f.clone().mul(f))),
+    // d²(atanh(f))/df² = 2f/(1 - f^2)^2
+    |f| {
+        let r = f.one().div(f.one().sub(f.clone().mul(f.clone())));
+        f.clone().add(f).mul(r.clone()).mul(r)
+    },
+    InstOne,
+    Add<Output, Output = Output>,
+    Sub<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+);
+
+/// f^g where both the base f and the exponent g are functions of the input
+///
+/// Since the derivative with respect to g involves ln(f), the gradient is NaN for a non-positive
+/// real base, unless g does not depend on the input or f^g is zero, where the term in ln(f) is
+/// dropped. Use ADConstantPow for constant exponents.
 #[derive(FuncCompose, Debug, Clone, Copy)]
-pub struct ADSignum<A>(pub A);
+pub struct ADPow<A, B>(pub A, pub B);
 
-impl<A: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs> for ADSignum<A> {
+// (f^g, d(f^g)/df, d(f^g)/dg) = (f^g, g * f^(g-1), f^g * ln(f)), where d(f^g)/dg is taken as zero
+// where f^g is, instead of 0 * ln(0)
+fn pow_partials<T>(f: T, g: T) -> (T, T, T)
+where
+    T: Clone + InstZero + InstOne + Pow<T, Output = T> + Sub<T, Output = T> + Ln<Output = T>,
+{
+    let fg = f.clone().pow(g.clone());
+    let dfg_df = g.clone().mul(f.clone().pow(g.sub(f.one())));
+    let dfg_dg = match fg.is_zero() {
+        true => fg.zero(),
+        false => fg.clone().mul(f.ln()),
+    };
+
+    (fg, dfg_df, dfg_dg)
+}
+
+// d(f^g) = d(f^g)/df * df + d(f^g)/dg * dg, without the dg term when g is constant, as
+// d(f^g)/dg may be NaN (e.g. for a negative real base)
+fn pow_chain<D, T>(df: D, dfg_df: T, dg: D, dfg_dg: T) -> D
+where
+    D: InstZero + Mul<T, Output = D>,
+{
+    match dg.is_zero() {
+        true => df.mul(dfg_df),
+        false => df.mul(dfg_df).add(dg.mul(dfg_dg)),
+    }
+}
+
+impl<A: Diffable<StaticArgs>, B: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs>
+    for ADPow<A, B>
+where
+    A::Output: Pow<B::Output>,
+{
     type Input = A::Input;
-    type Output = A::Output;
+    type Output = <A::Output as Pow<B::Output>>::Output;
 }
 
-impl<StaticArgs, Input, Output, Grad, A> AutoDiffable<StaticArgs> for ADSignum<A>
+impl<A, B> Symbolic for ADPow<A, B>
+where
+    A: Symbolic,
+    B: Symbolic,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::Pow(Box::new(self.0.symbolic(x)), Box::new(self.1.symbolic(x)))
+    }
+}
+
+impl<StaticArgs, A, B> ToDot<StaticArgs> for ADPow<A, B>
+where
+    A: ToDot<StaticArgs>,
+    B: ToDot<StaticArgs, Input = A::Input>,
+    Self: DotAnnotate<StaticArgs, Input = A::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+        let f = self.0.dot_node(graph, at);
+        let g = self.1.dot_node(graph, at);
+        graph.op(
+            "^",
+            self.annotate_at(at),
+            &[(f, Some("lhs")), (g, Some("rhs"))],
+        )
+    }
+}
+
+impl<StaticArgs, T, A, B> ToDynExpr<StaticArgs, T> for ADPow<A, B>
+where
+    T: DynExprScalar,
+    // ensure f^g can be boxed as an opaque function, since it is not simplified
+    Self: AutoDiffable<StaticArgs, Input = T, Output = T>
+        + ForwardDiffable<StaticArgs, Input = T, Output = T>
+        + Symbolic
+        + Clone
+        + 'static,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::apply(self.clone(), DynExpr::X)
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, A, B> AutoDiffable<StaticArgs> for ADPow<A, B>
 where
     A: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
-    Input: PossiblyComplex + GradientType<Output, GradientType = Grad>,
+    B: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f^g, g * f^(g-1) and f^g * ln(f) are defined and are Output
     Output: Clone
-        + PossiblyComplex
-        + Signum<Output = Output>
-        + Abs<Output = Output>
-        + Conjugate<Output = Output>
-        + InstOne
+        + Pow<Output, Output = Output>
         + InstZero
-        + Mul<Grad, Output = Grad>
+        + InstOne
+        + Sub<Output, Output = Output>
         + Mul<Output, Output = Output>
-        + Add<Output, Output = Output>
-        + Neg<Output = Output>
-        + Div<Output, Output = Output>,
-    Grad: Conjugate<Output = Grad> + InstZero,
+        + Ln<Output = Output>,
+    // ensure df * g * f^(g-1) + dg * f^g * ln(f) is defined and is Grad, and that dg can be
+    // checked for zero
+    Grad: InstZero + Mul<Output, Output = Grad> + Add<Grad, Output = Grad>,
 {
     fn eval(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval(x, static_args).signum()
+        self.0.eval(x, static_args).pow(self.1.eval(x, static_args))
     }
 
     fn eval_grad(
@@ -2824,76 +8757,26 @@ where
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        // defining signum(f) = f/|f| if f != 0, 0 otherwise
-        // this works for both purely real and complex f
-        //
-        // in the real case:
-        // d/dx (f/|f|) = (|f| * df/dx - f * d|f|/dx) / |f|^2
-        //
-        // and
-        //
-        // d/dx |f| = f * df/dx / |f|
-        //
-        // so d/dx signum(f) = (|f| * df/dx - f^2 * df/dx / |f|) / |f|^2
-        //                   = (|f| * df/dx - |f| * df/dx) / |f|^2
-        //                   = 0
-        // as expected
-        //
-        // in the complex case:
-        //
-        // d/dz (f/|f|)
-        //
-        // d/dz (z/|z|) = d/dz sqrt(z / conj(z)) = 1/(2 sqrt(z * conj(z)))
-        //            = 1/(2 |z|)
-        // d/dconjz (z/|z|) = d/dconjz sqrt(z / conj(z))
-        //                 = sqrt(z) * d/dconjz conj(z)^(-1/2)
-        //                 = sqrt(z) * -1/2 * conj(z)^(-3/2)
-        //                 = -1/2 (z/conj(z)^3)^(1/2)
-        //                 = -1/2 (z^4/|z|^6)^(1/2)
-        //                 = -1/2 (z^2/|z|^3)
-        //
-        // so from the chain rule then
-        //
-        // d/dz (f/|f|) = (1/(2 |f|)) * df/dz - 1/2 (f^2/|f|^3) * conj(df/dconjz)
-        // which would be 0 in the real case as expected
+        let (f, df) = self.0.eval_grad(x, static_args);
+        let (g, dg) = self.1.eval_grad(x, static_args);
 
-        if Input::is_always_real() && Output::is_always_real() {
-            let (f, df) = self.0.eval_grad(x, static_args);
+        // d(f^g) = g * f^(g-1) * df + f^g * ln(f) * dg
+        //
+        // this is true for Wirtinger calculus as well, since f^g = exp(g * ln(f)) is holomorphic
+        // in both f and g, so its conjugate derivatives with respect to f and g are 0
 
-            (f.signum(), df.zero())
-        } else {
-            let (f, df) = self.0.eval_grad(x, static_args);
-            let dconjfdz = self.0.conj_grad(x, static_args).conj();
-            let fabs = f.clone().abs();
-            let dsdf = f.one().div(fabs.clone().add(fabs.clone()));
-            let dsdconjf_half_denom = fabs.clone().mul(fabs.clone().mul(fabs));
-            let dsdconjf = f
-                .clone()
-                .mul(f.clone())
-                .div(dsdconjf_half_denom.clone().add(dsdconjf_half_denom))
-                .neg();
+        let (fg, dfg_df, dfg_dg) = pow_partials(f, g);
 
-            (f.signum(), dsdf.mul(df).add(dsdconjf.mul(dconjfdz)))
-        }
+        (fg, pow_chain(df, dfg_df, dg, dfg_dg))
     }
 
     fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> Grad {
-        if Input::is_always_real() && Output::is_always_real() {
-            self.0.grad(x, static_args).zero()
-        } else {
-            let (f, df) = self.0.eval_grad(x, static_args);
-            let dconjfdz = self.0.conj_grad(x, static_args).conj();
-            let fabs = f.clone().abs();
-            let dsdf = f.one().div(fabs.clone().add(fabs.clone()));
-            let dsdconjf_half_denom = fabs.clone().mul(fabs.clone().mul(fabs));
-            let dsdconjf = f
-                .clone()
-                .mul(f.clone())
-                .div(dsdconjf_half_denom.clone().add(dsdconjf_half_denom))
-                .neg();
+        let (f, df) = self.0.eval_grad(x, static_args);
+        let (g, dg) = self.1.eval_grad(x, static_args);
 
-            dsdf.mul(df).add(dsdconjf.mul(dconjfdz))
-        }
+        let (_, dfg_df, dfg_dg) = pow_partials(f, g);
+
+        pow_chain(df, dfg_df, dg, dfg_dg)
     }
 
     fn eval_conj_grad(
@@ -2901,29 +8784,14 @@ where
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
-        if Input::is_always_real() && Output::is_always_real() {
-            let (f, df) = self.0.eval_conj_grad(x, static_args);
+        let (f, df) = self.0.eval_conj_grad(x, static_args);
+        let (g, dg) = self.1.eval_conj_grad(x, static_args);
 
-            (f.signum(), df.zero())
-        } else {
-            // now we use df/dconjz and conj(df/dz)
+        // d(f^g)/dconjz = g * f^(g-1) * df/dconjz + f^g * ln(f) * dg/dconjz
 
-            let (f, dfdconjz) = self.0.eval_conj_grad(x, static_args);
-            let dconjfdconjz = self.0.grad(x, static_args).conj();
-            let fabs = f.clone().abs();
-            let dsdf = f.one().div(fabs.clone().add(fabs.clone()));
-            let dsdconjf_half_denom = fabs.clone().mul(fabs.clone().mul(fabs));
-            let dsdconjf = f
-                .clone()
-                .mul(f.clone())
-                .div(dsdconjf_half_denom.clone().add(dsdconjf_half_denom))
-                .neg();
+        let (fg, dfg_df, dfg_dg) = pow_partials(f, g);
 
-            (
-                f.signum(),
-                dsdf.mul(dfdconjz).add(dsdconjf.mul(dconjfdconjz)),
-            )
-        }
+        (fg, pow_chain(df, dfg_df, dg, dfg_dg))
     }
 
     fn conj_grad(
@@ -2931,48 +8799,114 @@ where
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> Grad {
-        if Input::is_always_real() && Output::is_always_real() {
-            self.0.conj_grad(x, static_args).conj().zero()
-        } else {
-            let (f, dfdconjz) = self.0.eval_conj_grad(x, static_args);
-            let dconjfdconjz = self.0.grad(x, static_args).conj();
-            let fabs = f.clone().abs();
-            let dsdf = f.one().div(fabs.clone().add(fabs.clone()));
-            let dsdconjf_half_denom = fabs.clone().mul(fabs.clone().mul(fabs));
-            let dsdconjf = f
-                .clone()
-                .mul(f.clone())
-                .div(dsdconjf_half_denom.clone().add(dsdconjf_half_denom))
-                .neg();
+        let (f, df) = self.0.eval_conj_grad(x, static_args);
+        let (g, dg) = self.1.eval_conj_grad(x, static_args);
+
+        let (_, dfg_df, dfg_dg) = pow_partials(f, g);
+
+        pow_chain(df, dfg_df, dg, dfg_dg)
+    }
+
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+        let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+        let (g, dg, dgdconjz) = self.1.eval_all(x, static_args);
+
+        // both derivatives follow the chain rule through d(f^g)/df and d(f^g)/dg, since f^g is
+        // holomorphic in both f and g
+
+        let (fg, dfg_df, dfg_dg) = pow_partials(f, g);
+
+        (
+            fg,
+            pow_chain(df, dfg_df.clone(), dg, dfg_dg.clone()),
+            pow_chain(dfdconjz, dfg_df, dgdconjz, dfg_dg),
+        )
+    }
+}
+
+impl<StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, Output, Grad, A, B>
+    TryAutoDiffable<StaticArgs> for ADPow<A, B>
+where
+    A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+    Input: GradientType<AOutput, GradientType = AGrad>,
+    B: TryAutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+    Input: GradientType<BOutput, GradientType = BGrad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the results of f, g and f^g can be checked
+    AOutput: CheckValue,
+    AGrad: CheckValue,
+    BOutput: CheckValue,
+    BGrad: CheckValue,
+    Output: CheckValue,
+    Grad: CheckValue,
+    // ensure f^g can be evaluated from the checked results of f and g
+    ADPow<ADEvaluated<Input, AOutput, AGrad>, ADEvaluated<Input, BOutput, BGrad>>:
+        AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        let f = self
+            .0
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADPow", 0))?;
+        let g = self
+            .1
+            .try_eval(x, static_args)
+            .map_err(|e| e.within("ADPow", 1))?;
+        check_shapes("ADPow", &f, &g)?;
+
+        let h = ADPow(ADEvaluated::value(f), ADEvaluated::value(g));
+        check_value("ADPow", h.eval(x, static_args))
+    }
 
-            dsdf.mul(dfdconjz).add(dsdconjf.mul(dconjfdconjz))
-        }
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        let f = self
+            .0
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADPow", 0))?;
+        let g = self
+            .1
+            .try_eval_all(x, static_args)
+            .map_err(|e| e.within("ADPow", 1))?;
+        check_shapes("ADPow", &f.0, &g.0)?;
+
+        let h = ADPow(ADEvaluated::all(f), ADEvaluated::all(g));
+        check_all("ADPow", h.eval_all(x, static_args))
     }
 }
 
-impl<StaticArgs, Input, Output, A> ForwardDiffable<StaticArgs> for ADSignum<A>
+impl<StaticArgs, Input, Output, A, B> ForwardDiffable<StaticArgs> for ADPow<A, B>
 where
     A: ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
-    Input: PossiblyComplex,
+    B: ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f^g, g * f^(g-1) * df and f^g * ln(f) * dg are defined and are Output
     Output: Clone
-        + PossiblyComplex
-        + Signum<Output = Output>
-        + Abs<Output = Output>
-        + Conjugate<Output = Output>
-        + InstOne
+        + Pow<Output, Output = Output>
         + InstZero
-        + Mul<Output, Output = Output>
+        + InstOne
         + Add<Output, Output = Output>
-        + Neg<Output = Output>
-        + Div<Output, Output = Output>,
+        + Sub<Output, Output = Output>
+        + Mul<Output, Output = Output>
+        + Ln<Output = Output>,
 {
     fn eval_forward(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval_forward(x, static_args).signum()
+        self.0
+            .eval_forward(x, static_args)
+            .pow(self.1.eval_forward(x, static_args))
     }
+
     fn eval_forward_grad(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
@@ -2982,24 +8916,14 @@ where
         <Self as Diffable<StaticArgs>>::Output,
         <Self as Diffable<StaticArgs>>::Output,
     ) {
-        if Input::is_always_real() && Output::is_always_real() {
-            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+        let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+        let (g, dg) = self.1.eval_forward_grad(x, dx, static_args);
 
-            (f.signum(), df.zero())
-        } else {
-            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
-            let dconjf = self.0.forward_conj_grad(x, dx, static_args);
-            let fabs = f.clone().abs();
-            let dsdf = f.one().div(fabs.clone().add(fabs.clone()));
-            let dsdconjf_half_denom = fabs.clone().mul(fabs.clone().mul(fabs));
-            let dsdconjf = f
-                .clone()
-                .mul(f.clone())
-                .div(dsdconjf_half_denom.clone().add(dsdconjf_half_denom))
-                .neg();
+        // d(f^g) = g * f^(g-1) * df + f^g * ln(f) * dg
 
-            (f.signum(), dsdf.mul(df).add(dsdconjf.mul(dconjf)))
-        }
+        let (fg, dfg_df, dfg_dg) = pow_partials(f, g);
+
+        (fg, pow_chain(df, dfg_df, dg, dfg_dg))
     }
 
     fn forward_grad(
@@ -3008,22 +8932,12 @@ where
         dx: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        if Input::is_always_real() && Output::is_always_real() {
-            self.0.forward_grad(x, dx, static_args).zero()
-        } else {
-            let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
-            let dconjf = self.0.forward_conj_grad(x, dx, static_args);
-            let fabs = f.clone().abs();
-            let dsdf = f.one().div(fabs.clone().add(fabs.clone()));
-            let dsdconjf_half_denom = fabs.clone().mul(fabs.clone().mul(fabs));
-            let dsdconjf = f
-                .clone()
-                .mul(f.clone())
-                .div(dsdconjf_half_denom.clone().add(dsdconjf_half_denom))
-                .neg();
+        let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+        let (g, dg) = self.1.eval_forward_grad(x, dx, static_args);
 
-            dsdf.mul(df).add(dsdconjf.mul(dconjf))
-        }
+        let (_, dfg_df, dfg_dg) = pow_partials(f, g);
+
+        pow_chain(df, dfg_df, dg, dfg_dg)
     }
 
     fn eval_forward_conj_grad(
@@ -3035,27 +8949,14 @@ where
         <Self as Diffable<StaticArgs>>::Output,
         <Self as Diffable<StaticArgs>>::Output,
     ) {
-        if Input::is_always_real() && Output::is_always_real() {
-            let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+        let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+        let (g, dg) = self.1.eval_forward_conj_grad(x, dx, static_args);
 
-            (f.signum(), df.zero())
-        } else {
-            let (f, dfdconjz) = self.0.eval_forward_conj_grad(x, dx, static_args);
-            let dconjfdconjz = self.0.forward_grad(x, dx, static_args).conj();
-            let fabs = f.clone().abs();
-            let dsdf = f.one().div(fabs.clone().add(fabs.clone()));
-            let dsdconjf_half_denom = fabs.clone().mul(fabs.clone().mul(fabs));
-            let dsdconjf = f
-                .clone()
-                .mul(f.clone())
-                .div(dsdconjf_half_denom.clone().add(dsdconjf_half_denom))
-                .neg();
+        // d(f^g)/dconjz = g * f^(g-1) * df/dconjz + f^g * ln(f) * dg/dconjz
 
-            (
-                f.signum(),
-                dsdf.mul(dfdconjz).add(dsdconjf.mul(dconjfdconjz)),
-            )
-        }
+        let (fg, dfg_df, dfg_dg) = pow_partials(f, g);
+
+        (fg, pow_chain(df, dfg_df, dg, dfg_dg))
     }
 
     fn forward_conj_grad(
@@ -3064,153 +8965,1615 @@ where
         dx: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        if Input::is_always_real() && Output::is_always_real() {
-            self.0.forward_conj_grad(x, dx, static_args).zero()
-        } else {
-            let (f, dfdconjz) = self.0.eval_forward_conj_grad(x, dx, static_args);
-            let dconjfdconjz = self.0.forward_grad(x, dx, static_args).conj();
-            let fabs = f.clone().abs();
-            let dsdf = f.one().div(fabs.clone().add(fabs.clone()));
-            let dsdconjf_half_denom = fabs.clone().mul(fabs.clone().mul(fabs));
-            let dsdconjf = f
-                .clone()
-                .mul(f.clone())
-                .div(dsdconjf_half_denom.clone().add(dsdconjf_half_denom))
-                .neg();
+        let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+        let (g, dg) = self.1.eval_forward_conj_grad(x, dx, static_args);
 
-            dsdf.mul(dfdconjz).add(dsdconjf.mul(dconjfdconjz))
-        }
+        let (_, dfg_df, dfg_dg) = pow_partials(f, g);
+
+        pow_chain(df, dfg_df, dg, dfg_dg)
     }
 }
 
-#[derive(FuncCompose, Debug, Clone, Copy)]
-pub struct ADConjugate<A>(pub A);
-
-impl<A: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs> for ADConjugate<A>
+impl<StaticArgs, Input, Output, Grad, A, B> ReverseDiffable<StaticArgs> for ADPow<A, B>
 where
-    A::Output: Conjugate<Output = A::Output>,
+    A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure f^g, g * f^(g-1) and f^g * ln(f) are defined and are Output
+    Output: Clone
+        + InstZero
+        + InstOne
+        + Pow<Output, Output = Output>
+        + Sub<Output, Output = Output>
+        + Ln<Output = Output>
+        + Conjugate<Output = Output>
+        + CheckValue,
+    // ensure the gradient of g can be accumulated to check if it is zero
+    Grad: Clone + InstZero + Mul<Output, Output = Grad> + Conjugate<Output = Grad>,
 {
-    type Input = A::Input;
-    type Output = A::Output;
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        let u = self.0.eval_reverse(x, tape, static_args);
+        let v = self.1.eval_reverse(x, tape, static_args);
+        let f = tape.value(u).clone();
+        let g = tape.value(v).clone();
+
+        // d(f^g)/df = g * f^(g-1), d(f^g)/dg = f^g * ln(f)
+        let zero = f.zero();
+        let (fg, dfg_df, mut dfg_dg) = pow_partials(f, g);
+
+        // where d(f^g)/dg is NaN, the dg term is dropped if g does not depend on the input
+        if dfg_dg.has_nan() {
+            let (dg, dgdconj) = tape.backward(v);
+            if dg.is_zero() && dgdconj.is_zero() {
+                dfg_dg = zero.clone();
+            }
+        }
+
+        tape.binary(fg, (u, dfg_df, zero.clone()), (v, dfg_dg, zero))
+    }
 }
 
-impl<StaticArgs, Input, AOutput, AGrad, A> AutoDiffable<StaticArgs> for ADConjugate<A>
+impl<StaticArgs, Input, Output, A, B> VjpDiffable<StaticArgs> for ADPow<A, B>
 where
-    A: AutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
-    Input: GradientType<AOutput, GradientType = AGrad>,
-    // make sure A.conj() is defined and Output = Output
-    AOutput: Conjugate<Output = AOutput>,
-    // make sure dA.conj() is defined and Output = Grad
-    AGrad: Conjugate<Output = AGrad>,
+    A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    B: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure f^g, g * f^(g-1) and f^g * ln(f) are defined and are Output
+    Output: Clone
+        + InstZero
+        + InstOne
+        + Pow<Output, Output = Output>
+        + Sub<Output, Output = Output>
+        + Mul<Output, Output = Output>
+        + Ln<Output = Output>
+        + CheckValue,
+    // ensure the vector-Jacobian products of f and g can be summed, and checked for zero
+    Input: InstZero,
 {
-    fn eval(
+    fn eval_primal(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
         static_args: &StaticArgs,
     ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval(x, static_args).conj()
+        self.0
+            .eval_primal(x, static_args)
+            .pow(self.1.eval_primal(x, static_args))
     }
 
-    fn eval_grad(
+    fn eval_vjp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
-    ) -> (<Self as Diffable<StaticArgs>>::Output, AGrad) {
-        // Wirtinger derivative dconj(f)/dz = conj(df/dconjz)
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let f = self.0.eval_primal(x, static_args);
+        let g = self.1.eval_primal(x, static_args);
 
-        let (f, dfdzconj) = self.0.eval_conj_grad(x, static_args);
+        // c^T d(f^g) = (c * g * f^(g-1))^T df + (c * f^g * ln(f))^T dg
 
-        (f.conj(), dfdzconj.conj())
-    }
+        let (fg, dfg_df, dfg_dg) = pow_partials(f, g);
 
-    fn grad(&self, x: &<Self as Diffable<StaticArgs>>::Input, static_args: &StaticArgs) -> AGrad {
-        self.0.conj_grad(x, static_args).conj()
+        // where d(f^g)/dg is NaN, the dg term is dropped if it vanishes for the cotangent itself,
+        // i.e. g does not depend on the input
+        let cg = cotangent.clone().mul(dfg_dg);
+        let vf = self.0.vjp(x, &cotangent.clone().mul(dfg_df), static_args);
+        let vg = match cg.has_nan() {
+            true => Some(self.1.vjp(x, cotangent, static_args)).filter(InstZero::is_zero),
+            false => None,
+        }
+        .unwrap_or_else(|| self.1.vjp(x, &cg, static_args));
+
+        (fg, vf.add(vg))
     }
 
-    fn eval_conj_grad(
+    fn eval_conj_vjp(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
         static_args: &StaticArgs,
-    ) -> (<Self as Diffable<StaticArgs>>::Output, AGrad) {
-        // Wirtinger derivative dconj(f)/dconj(z) = conj(df/dz)
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    ) {
+        let f = self.0.eval_primal(x, static_args);
+        let g = self.1.eval_primal(x, static_args);
 
-        let (f, df) = self.0.eval_grad(x, static_args);
+        let (fg, dfg_df, dfg_dg) = pow_partials(f, g);
 
-        (f.conj(), df.conj())
-    }
+        let cg = cotangent.clone().mul(dfg_dg);
+        let vf = self
+            .0
+            .conj_vjp(x, &cotangent.clone().mul(dfg_df), static_args);
+        let vg = match cg.has_nan() {
+            true => Some(self.1.conj_vjp(x, cotangent, static_args)).filter(InstZero::is_zero),
+            false => None,
+        }
+        .unwrap_or_else(|| self.1.conj_vjp(x, &cg, static_args));
 
-    fn conj_grad(
-        &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> AGrad {
-        self.0.grad(x, static_args).conj()
+        (fg, vf.add(vg))
     }
 }
 
-impl<StaticArgs, Input, AOutput, A> ForwardDiffable<StaticArgs> for ADConjugate<A>
+impl<StaticArgs, Input, Output, A, B, const K: usize> TaylorDiffable<StaticArgs, K> for ADPow<A, B>
 where
-    A: ForwardDiffable<StaticArgs, Input = Input, Output = AOutput>,
-    AOutput: Conjugate<Output = AOutput>,
+    A: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    B: TaylorDiffable<StaticArgs, K, Input = Input, Output = Output>,
+    // ensure f^g is Output, and that e^(g * ln(f)) and f^g for a constant g are defined on the
+    // series of f and g
+    Output: TaylorCoefficient + Pow<Output, Output = Output>,
+    Taylor<Output, K>: Ln<Output = Taylor<Output, K>>
+        + Exp<Output = Taylor<Output, K>>
+        + Pow<Output, Output = Taylor<Output, K>>,
 {
-    fn eval_forward(
+    fn eval_taylor_series(
         &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
+        x: &Taylor<<Self as Diffable<StaticArgs>>::Input, K>,
         static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
-        self.0.eval_forward(x, static_args).conj()
+    ) -> Taylor<<Self as Diffable<StaticArgs>>::Output, K> {
+        let f = self.0.eval_taylor_series(x, static_args);
+        let g = self.1.eval_taylor_series(x, static_args);
+
+        // a constant exponent is applied directly, since ln(f) is singular for f(x0) <= 0 even
+        // when f^g is not, e.g. x^2 at x0 = 0
+        match g.0.split_first() {
+            Some((g0, rest)) if rest.iter().all(InstZero::is_zero) => f.pow(g0.clone()),
+            _ => g.mul(f.ln()).exp(),
+        }
     }
-    fn eval_forward_grad(
-        &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> (
-        <Self as Diffable<StaticArgs>>::Output,
-        <Self as Diffable<StaticArgs>>::Output,
-    ) {
-        // Wirtinger derivative conj(df/dz * dz) = dconj(f)/dconj(z) * dconj(z)
+}
 
-        let (f, dfconj) = self.0.eval_forward_conj_grad(x, dx, static_args);
+// Implements a binary AutoDiff operation h(f, g) of two real valued functions f and g. The
+// partial derivatives (dh/df, dh/dg) are given as a closure-like expression of f and g, along
+// with any extra bounds on the output type needed to compute them.
+//
+// since f and g are real, the chain rule is simply
+//
+// dh = dh/df * df + dh/dg * dg
+//
+// and the conjugate gradient follows by using the conjugate gradients of f and g
+macro_rules! impl_binary_adop {
+    (
+        $(#[$meta:meta])*
+        $name:ident, $trait:ident, $method:ident,
+        |$f:ident, $g:ident| $dh:expr $(, $bound:path)* $(,)?
+    ) => {
+        $(#[$meta])*
+        #[derive(FuncCompose, Debug, Clone, Copy)]
+        pub struct $name<A, B>(pub A, pub B);
+
+        impl<A: Diffable<StaticArgs>, B: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs>
+            for $name<A, B>
+        where
+            A::Output: $trait<B::Output>,
+        {
+            type Input = A::Input;
+            type Output = <A::Output as $trait<B::Output>>::Output;
+        }
 
-        (f.conj(), dfconj.conj())
-    }
+        impl<A: Symbolic, B: Symbolic> Symbolic for $name<A, B> {
+            fn symbolic(&self, x: &Expr) -> Expr {
+                Expr::call(stringify!($method), vec![self.0.symbolic(x), self.1.symbolic(x)])
+            }
+        }
 
-    fn forward_grad(
-        &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
-        // Wirtinger derivative conj(df/dz * dz) = dconj(f)/dconj(z) * dconj(z)
+        impl<StaticArgs, A, B> ToDot<StaticArgs> for $name<A, B>
+        where
+            A: ToDot<StaticArgs>,
+            B: ToDot<StaticArgs, Input = A::Input>,
+            Self: DotAnnotate<StaticArgs, Input = A::Input>,
+        {
+            fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+                let f = self.0.dot_node(graph, at);
+                let g = self.1.dot_node(graph, at);
+                graph.op(stringify!($method), self.annotate_at(at), &[(f, Some("lhs")), (g, Some("rhs"))])
+            }
+        }
 
-        self.0.forward_conj_grad(x, dx, static_args).conj()
-    }
+        impl<StaticArgs, T, A, B> ToDynExpr<StaticArgs, T> for $name<A, B>
+        where
+            T: DynExprScalar,
+            // ensure g(f, h) can be boxed as an opaque function, since it is not simplified
+            Self: AutoDiffable<StaticArgs, Input = T, Output = T>
+                + ForwardDiffable<StaticArgs, Input = T, Output = T>
+                + Symbolic
+                + Clone
+                + 'static,
+        {
+            fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+                DynExpr::apply(self.clone(), DynExpr::X)
+            }
+        }
 
-    fn eval_forward_conj_grad(
-        &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> (
-        <Self as Diffable<StaticArgs>>::Output,
-        <Self as Diffable<StaticArgs>>::Output,
-    ) {
-        // Wirtinger derivative conj(df/dz * dz) = dconj(f)/dconj(z) * dconj(z)
+        impl<StaticArgs, Input, Output, Grad, A, B> AutoDiffable<StaticArgs> for $name<A, B>
+        where
+            A: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+            B: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+            Input: GradientType<Output, GradientType = Grad>,
+            // ensure h(f, g) is defined and is Output, and dh/df, dh/dg can be computed
+            Output: Clone + $trait<Output, Output = Output> $(+ $bound)*,
+            // ensure df * dh/df + dg * dh/dg is defined and is Grad
+            Grad: Mul<Output, Output = Grad> + Add<Grad, Output = Grad>,
+        {
+            fn eval(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                self.0
+                    .eval(x, static_args)
+                    .$method(self.1.eval(x, static_args))
+            }
+
+            fn eval_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+                let (f, df) = self.0.eval_grad(x, static_args);
+                let (g, dg) = self.1.eval_grad(x, static_args);
+
+                // dh = dh/df * df + dh/dg * dg
+
+                let (dhdf, dhdg) = {
+                    let ($f, $g) = (f.clone(), g.clone());
+                    $dh
+                };
+
+                (f.$method(g), df.mul(dhdf).add(dg.mul(dhdg)))
+            }
+
+            fn grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> Grad {
+                let (f, df) = self.0.eval_grad(x, static_args);
+                let (g, dg) = self.1.eval_grad(x, static_args);
+
+                // dh = dh/df * df + dh/dg * dg
+
+                let (dhdf, dhdg) = {
+                    let ($f, $g) = (f, g);
+                    $dh
+                };
+
+                df.mul(dhdf).add(dg.mul(dhdg))
+            }
+
+            fn eval_conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+                let (f, df) = self.0.eval_conj_grad(x, static_args);
+                let (g, dg) = self.1.eval_conj_grad(x, static_args);
+
+                // dh/dconjz = dh/df * df/dconjz + dh/dg * dg/dconjz
+
+                let (dhdf, dhdg) = {
+                    let ($f, $g) = (f.clone(), g.clone());
+                    $dh
+                };
+
+                (f.$method(g), df.mul(dhdf).add(dg.mul(dhdg)))
+            }
+
+            fn conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> Grad {
+                let (f, df) = self.0.eval_conj_grad(x, static_args);
+                let (g, dg) = self.1.eval_conj_grad(x, static_args);
+
+                // dh/dconjz = dh/df * df/dconjz + dh/dg * dg/dconjz
+
+                let (dhdf, dhdg) = {
+                    let ($f, $g) = (f, g);
+                    $dh
+                };
+
+                df.mul(dhdf).add(dg.mul(dhdg))
+            }
+
+            fn eval_all(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+                let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+                let (g, dg, dgdconjz) = self.1.eval_all(x, static_args);
+
+                // both derivatives follow the chain rule through dh/df and dh/dg
+
+                let (dhdf, dhdg) = {
+                    let ($f, $g) = (f.clone(), g.clone());
+                    $dh
+                };
+
+                (
+                    f.$method(g),
+                    df.mul(dhdf.clone()).add(dg.mul(dhdg.clone())),
+                    dfdconjz.mul(dhdf).add(dgdconjz.mul(dhdg)),
+                )
+            }
+        }
 
-        let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+        impl<StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, Output, Grad, A, B>
+            TryAutoDiffable<StaticArgs> for $name<A, B>
+        where
+            A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+            Input: GradientType<AOutput, GradientType = AGrad>,
+            B: TryAutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+            Input: GradientType<BOutput, GradientType = BGrad>,
+            Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+            Input: GradientType<Output, GradientType = Grad>,
+            // ensure the results of f, g and h(f, g) can be checked
+            AOutput: CheckValue,
+            AGrad: CheckValue,
+            BOutput: CheckValue,
+            BGrad: CheckValue,
+            Output: CheckValue,
+            Grad: CheckValue,
+            // ensure h(f, g) can be evaluated from the checked results of f and g
+            $name<ADEvaluated<Input, AOutput, AGrad>, ADEvaluated<Input, BOutput, BGrad>>:
+                AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+        {
+            fn try_eval(
+                &self,
+                x: &Input,
+                static_args: &StaticArgs,
+            ) -> Result<Output, AutoDiffError> {
+                let f = self
+                    .0
+                    .try_eval(x, static_args)
+                    .map_err(|e| e.within(stringify!($name), 0))?;
+                let g = self
+                    .1
+                    .try_eval(x, static_args)
+                    .map_err(|e| e.within(stringify!($name), 1))?;
+                check_shapes(stringify!($name), &f, &g)?;
+
+                let h = $name(ADEvaluated::value(f), ADEvaluated::value(g));
+                check_value(stringify!($name), h.eval(x, static_args))
+            }
+
+            fn try_eval_all(
+                &self,
+                x: &Input,
+                static_args: &StaticArgs,
+            ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+                let f = self
+                    .0
+                    .try_eval_all(x, static_args)
+                    .map_err(|e| e.within(stringify!($name), 0))?;
+                let g = self
+                    .1
+                    .try_eval_all(x, static_args)
+                    .map_err(|e| e.within(stringify!($name), 1))?;
+                check_shapes(stringify!($name), &f.0, &g.0)?;
+
+                let h = $name(ADEvaluated::all(f), ADEvaluated::all(g));
+                check_all(stringify!($name), h.eval_all(x, static_args))
+            }
+        }
 
-        (f.conj(), df.conj())
-    }
+        impl<StaticArgs, Input, Output, A, B> ForwardDiffable<StaticArgs> for $name<A, B>
+        where
+            A: ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+            B: ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+            // ensure h(f, g) is defined and is Output, and dh/df * df + dh/dg * dg can be computed
+            Output: Clone
+                + $trait<Output, Output = Output>
+                + Mul<Output, Output = Output>
+                + Add<Output, Output = Output>
+                $(+ $bound)*,
+        {
+            fn eval_forward(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                self.0
+                    .eval_forward(x, static_args)
+                    .$method(self.1.eval_forward(x, static_args))
+            }
+
+            fn eval_forward_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Output,
+            ) {
+                let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+                let (g, dg) = self.1.eval_forward_grad(x, dx, static_args);
+
+                // dh = dh/df * df + dh/dg * dg
+
+                let (dhdf, dhdg) = {
+                    let ($f, $g) = (f.clone(), g.clone());
+                    $dh
+                };
+
+                (f.$method(g), df.mul(dhdf).add(dg.mul(dhdg)))
+            }
+
+            fn forward_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+                let (g, dg) = self.1.eval_forward_grad(x, dx, static_args);
+
+                // dh = dh/df * df + dh/dg * dg
+
+                let (dhdf, dhdg) = {
+                    let ($f, $g) = (f, g);
+                    $dh
+                };
+
+                df.mul(dhdf).add(dg.mul(dhdg))
+            }
+
+            fn eval_forward_conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Output,
+            ) {
+                let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+                let (g, dg) = self.1.eval_forward_conj_grad(x, dx, static_args);
+
+                // dh/dconjz * dx = dh/df * df/dconjz * dx + dh/dg * dg/dconjz * dx
+
+                let (dhdf, dhdg) = {
+                    let ($f, $g) = (f.clone(), g.clone());
+                    $dh
+                };
+
+                (f.$method(g), df.mul(dhdf).add(dg.mul(dhdg)))
+            }
+
+            fn forward_conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+                let (g, dg) = self.1.eval_forward_conj_grad(x, dx, static_args);
+
+                // dh/dconjz * dx = dh/df * df/dconjz * dx + dh/dg * dg/dconjz * dx
+
+                let (dhdf, dhdg) = {
+                    let ($f, $g) = (f, g);
+                    $dh
+                };
+
+                df.mul(dhdf).add(dg.mul(dhdg))
+            }
+        }
+        impl<StaticArgs, Input, Output, Grad, A, B> ReverseDiffable<StaticArgs> for $name<A, B>
+        where
+            A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+            B: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+            Input: GradientType<Output, GradientType = Grad>,
+            // ensure h(f, g) is defined and is Output, and dh/df, dh/dg can be computed
+            Output: Clone + InstZero + $trait<Output, Output = Output> $(+ $bound)*,
+        {
+            fn eval_reverse<'a>(
+                &'a self,
+                x: &'a <Self as Diffable<StaticArgs>>::Input,
+                tape: &mut Tape<'a, Grad, Output>,
+                static_args: &'a StaticArgs,
+            ) -> Var {
+                let u = self.0.eval_reverse(x, tape, static_args);
+                let v = self.1.eval_reverse(x, tape, static_args);
+                let f = tape.value(u).clone();
+                let g = tape.value(v).clone();
+
+                let zero = f.zero();
+                let (dhdf, dhdg) = {
+                    let ($f, $g) = (f.clone(), g.clone());
+                    $dh
+                };
+
+                tape.binary(f.$method(g), (u, dhdf, zero.clone()), (v, dhdg, zero))
+            }
+        }
 
-    fn forward_conj_grad(
-        &self,
-        x: &<Self as Diffable<StaticArgs>>::Input,
-        dx: &<Self as Diffable<StaticArgs>>::Input,
-        static_args: &StaticArgs,
-    ) -> <Self as Diffable<StaticArgs>>::Output {
-        // Wirtinger derivative conj(df/dz * dz) = dconj(f)/dconj(z) * dconj(z)
+        impl<StaticArgs, Input, Output, A, B> VjpDiffable<StaticArgs> for $name<A, B>
+        where
+            A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+            B: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+            // ensure h(f, g) is defined and is Output, and dh/df, dh/dg can be computed
+            Output: Clone + $trait<Output, Output = Output> + Mul<Output, Output = Output> $(+ $bound)*,
+            // ensure the vector-Jacobian products of f and g can be summed
+            Input: Add<Input, Output = Input>,
+        {
+            fn eval_primal(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                self.0
+                    .eval_primal(x, static_args)
+                    .$method(self.1.eval_primal(x, static_args))
+            }
+
+            fn eval_vjp(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Input,
+            ) {
+                let f = self.0.eval_primal(x, static_args);
+                let g = self.1.eval_primal(x, static_args);
+
+                // c^T dh = (c * dh/df)^T df + (c * dh/dg)^T dg
+
+                let (dhdf, dhdg) = {
+                    let ($f, $g) = (f.clone(), g.clone());
+                    $dh
+                };
+                let vf = self.0.vjp(x, &cotangent.clone().mul(dhdf), static_args);
+                let vg = self.1.vjp(x, &cotangent.clone().mul(dhdg), static_args);
+
+                (f.$method(g), vf.add(vg))
+            }
+
+            fn eval_conj_vjp(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Input,
+            ) {
+                let f = self.0.eval_primal(x, static_args);
+                let g = self.1.eval_primal(x, static_args);
+
+                let (dhdf, dhdg) = {
+                    let ($f, $g) = (f.clone(), g.clone());
+                    $dh
+                };
+                let vf = self.0.conj_vjp(x, &cotangent.clone().mul(dhdf), static_args);
+                let vg = self.1.conj_vjp(x, &cotangent.clone().mul(dhdg), static_args);
+
+                (f.$method(g), vf.add(vg))
+            }
+        }
+    };
+}
 
-        self.0.forward_grad(x, dx, static_args).conj()
-    }
+impl_binary_adop!(
+    /// atan2(f, g), the angle of the point (g, f) in (-pi, pi]
+    ADAtan2, Atan2, atan2,
+    // d(atan2(f, g)) = (g * df - f * dg) / (f^2 + g^2)
+    |f, g| {
+        let r2 = f.clone().mul(f.clone()).add(g.clone().mul(g.clone()));
+        (g.div(r2.clone()), f.neg().div(r2))
+    },
+    Add<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Div<Output, Output = Output>,
+    Neg<Output = Output>,
+);
+
+impl_binary_adop!(
+    /// hypot(f, g) = sqrt(f^2 + g^2), computed without undue overflow or underflow
+    ADHypot, Hypot, hypot,
+    // d(hypot(f, g)) = (f * df + g * dg) / hypot(f, g)
+    |f, g| {
+        let h = f.clone().hypot(g.clone());
+        (f.div(h.clone()), g.div(h))
+    },
+    Div<Output, Output = Output>,
+);
+
+// Implements a binary AutoDiff operation that selects one of its two real valued operands f and g.
+// The value and the (sub)gradient are both taken from the selected operand, which is f whenever
+// `f $cmp g` holds, and g otherwise. In particular ties (f == g) select f, and the gradient of the
+// unselected operand never contributes to the result.
+macro_rules! impl_select_adop {
+    (
+        $(#[$meta:meta])*
+        $name:ident, $trait:ident, $method:ident, $cmp:tt
+    ) => {
+        $(#[$meta])*
+        #[derive(FuncCompose, Debug, Clone, Copy)]
+        pub struct $name<A, B>(pub A, pub B);
+
+        impl<A: Diffable<StaticArgs>, B: Diffable<StaticArgs>, StaticArgs> Diffable<StaticArgs>
+            for $name<A, B>
+        {
+            type Input = A::Input;
+            type Output = A::Output;
+        }
+
+        impl<A: Symbolic, B: Symbolic> Symbolic for $name<A, B> {
+            fn symbolic(&self, x: &Expr) -> Expr {
+                Expr::call(stringify!($method), vec![self.0.symbolic(x), self.1.symbolic(x)])
+            }
+        }
+
+        impl<StaticArgs, A, B> ToDot<StaticArgs> for $name<A, B>
+        where
+            A: ToDot<StaticArgs>,
+            B: ToDot<StaticArgs, Input = A::Input>,
+            Self: DotAnnotate<StaticArgs, Input = A::Input>,
+        {
+            fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+                let f = self.0.dot_node(graph, at);
+                let g = self.1.dot_node(graph, at);
+                graph.op(stringify!($method), self.annotate_at(at), &[(f, None), (g, None)])
+            }
+        }
+
+        impl<StaticArgs, T, A, B> ToDynExpr<StaticArgs, T> for $name<A, B>
+        where
+            T: DynExprScalar,
+            // ensure the selection can be boxed as an opaque function, since it is not simplified
+            Self: AutoDiffable<StaticArgs, Input = T, Output = T>
+                + ForwardDiffable<StaticArgs, Input = T, Output = T>
+                + Symbolic
+                + Clone
+                + 'static,
+        {
+            fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+                DynExpr::apply(self.clone(), DynExpr::X)
+            }
+        }
+
+        impl<StaticArgs, Input, Output, Grad, A, B> AutoDiffable<StaticArgs> for $name<A, B>
+        where
+            A: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+            B: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+            Input: GradientType<Output, GradientType = Grad>,
+            // ensure f and g can be compared
+            Output: PartialOrd,
+        {
+            fn eval(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                let f = self.0.eval(x, static_args);
+                let g = self.1.eval(x, static_args);
+
+                if f $cmp g {
+                    f
+                } else {
+                    g
+                }
+            }
+
+            fn eval_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+                let f = self.0.eval(x, static_args);
+                let g = self.1.eval(x, static_args);
+
+                // route the gradient to the selected operand only
+
+                if f $cmp g {
+                    (f, self.0.grad(x, static_args))
+                } else {
+                    (g, self.1.grad(x, static_args))
+                }
+            }
+
+            fn grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> Grad {
+                self.eval_grad(x, static_args).1
+            }
+
+            fn eval_conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+                let f = self.0.eval(x, static_args);
+                let g = self.1.eval(x, static_args);
+
+                // route the conjugate gradient to the selected operand only
+
+                if f $cmp g {
+                    (f, self.0.conj_grad(x, static_args))
+                } else {
+                    (g, self.1.conj_grad(x, static_args))
+                }
+            }
+
+            fn conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> Grad {
+                self.eval_conj_grad(x, static_args).1
+            }
+
+            fn eval_all(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+                // both derivatives are those of the selected operand
+
+                if self.0.eval(x, static_args) $cmp self.1.eval(x, static_args) {
+                    self.0.eval_all(x, static_args)
+                } else {
+                    self.1.eval_all(x, static_args)
+                }
+            }
+        }
+
+        impl<StaticArgs, Input, AOutput, BOutput, AGrad, BGrad, Output, Grad, A, B>
+            TryAutoDiffable<StaticArgs> for $name<A, B>
+        where
+            A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+            Input: GradientType<AOutput, GradientType = AGrad>,
+            B: TryAutoDiffable<StaticArgs, Input = Input, Output = BOutput>,
+            Input: GradientType<BOutput, GradientType = BGrad>,
+            Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+            Input: GradientType<Output, GradientType = Grad>,
+            // ensure the results of f, g and h(f, g) can be checked
+            AOutput: CheckValue,
+            AGrad: CheckValue,
+            BOutput: CheckValue,
+            BGrad: CheckValue,
+            Output: CheckValue,
+            Grad: CheckValue,
+            // ensure h(f, g) can be evaluated from the checked results of f and g
+            $name<ADEvaluated<Input, AOutput, AGrad>, ADEvaluated<Input, BOutput, BGrad>>:
+                AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+        {
+            fn try_eval(
+                &self,
+                x: &Input,
+                static_args: &StaticArgs,
+            ) -> Result<Output, AutoDiffError> {
+                let f = self
+                    .0
+                    .try_eval(x, static_args)
+                    .map_err(|e| e.within(stringify!($name), 0))?;
+                let g = self
+                    .1
+                    .try_eval(x, static_args)
+                    .map_err(|e| e.within(stringify!($name), 1))?;
+                check_shapes(stringify!($name), &f, &g)?;
+
+                let h = $name(ADEvaluated::value(f), ADEvaluated::value(g));
+                check_value(stringify!($name), h.eval(x, static_args))
+            }
+
+            fn try_eval_all(
+                &self,
+                x: &Input,
+                static_args: &StaticArgs,
+            ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+                let f = self
+                    .0
+                    .try_eval_all(x, static_args)
+                    .map_err(|e| e.within(stringify!($name), 0))?;
+                let g = self
+                    .1
+                    .try_eval_all(x, static_args)
+                    .map_err(|e| e.within(stringify!($name), 1))?;
+                check_shapes(stringify!($name), &f.0, &g.0)?;
+
+                let h = $name(ADEvaluated::all(f), ADEvaluated::all(g));
+                check_all(stringify!($name), h.eval_all(x, static_args))
+            }
+        }
+
+        impl<StaticArgs, Input, Output, A, B> ForwardDiffable<StaticArgs> for $name<A, B>
+        where
+            A: ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+            B: ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+            // ensure f and g can be compared
+            Output: PartialOrd,
+        {
+            fn eval_forward(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                let f = self.0.eval_forward(x, static_args);
+                let g = self.1.eval_forward(x, static_args);
+
+                if f $cmp g {
+                    f
+                } else {
+                    g
+                }
+            }
+
+            fn eval_forward_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Output,
+            ) {
+                let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+                let (g, dg) = self.1.eval_forward_grad(x, dx, static_args);
+
+                if f $cmp g {
+                    (f, df)
+                } else {
+                    (g, dg)
+                }
+            }
+
+            fn forward_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                self.eval_forward_grad(x, dx, static_args).1
+            }
+
+            fn eval_forward_conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Output,
+            ) {
+                let (f, df) = self.0.eval_forward_conj_grad(x, dx, static_args);
+                let (g, dg) = self.1.eval_forward_conj_grad(x, dx, static_args);
+
+                if f $cmp g {
+                    (f, df)
+                } else {
+                    (g, dg)
+                }
+            }
+
+            fn forward_conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                self.eval_forward_conj_grad(x, dx, static_args).1
+            }
+        }
+        impl<StaticArgs, Input, Output, Grad, A, B> ReverseDiffable<StaticArgs> for $name<A, B>
+        where
+            A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+            B: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+            Input: GradientType<Output, GradientType = Grad>,
+            // ensure f and g can be compared, and the partials 1 and 0 can be made
+            Output: Clone + PartialOrd + InstZero + InstOne,
+        {
+            fn eval_reverse<'a>(
+                &'a self,
+                x: &'a <Self as Diffable<StaticArgs>>::Input,
+                tape: &mut Tape<'a, Grad, Output>,
+                static_args: &'a StaticArgs,
+            ) -> Var {
+                let u = self.0.eval_reverse(x, tape, static_args);
+                let v = self.1.eval_reverse(x, tape, static_args);
+                let f = tape.value(u).clone();
+                let g = tape.value(v).clone();
+
+                // only the selected operand is a parent of the result
+                let (one, zero) = (f.one(), f.zero());
+                if f $cmp g {
+                    tape.unary(f, (u, one, zero))
+                } else {
+                    tape.unary(g, (v, one, zero))
+                }
+            }
+        }
+
+        impl<StaticArgs, Input, Output, A, B> VjpDiffable<StaticArgs> for $name<A, B>
+        where
+            A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+            B: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+            // ensure f and g can be compared
+            Output: PartialOrd,
+        {
+            fn eval_primal(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                let f = self.0.eval_primal(x, static_args);
+                let g = self.1.eval_primal(x, static_args);
+
+                if f $cmp g {
+                    f
+                } else {
+                    g
+                }
+            }
+
+            fn eval_vjp(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Input,
+            ) {
+                let f = self.0.eval_primal(x, static_args);
+                let g = self.1.eval_primal(x, static_args);
+
+                // only the selected operand receives the cotangent
+
+                if f $cmp g {
+                    (f, self.0.vjp(x, cotangent, static_args))
+                } else {
+                    (g, self.1.vjp(x, cotangent, static_args))
+                }
+            }
+
+            fn eval_conj_vjp(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Input,
+            ) {
+                let f = self.0.eval_primal(x, static_args);
+                let g = self.1.eval_primal(x, static_args);
+
+                if f $cmp g {
+                    (f, self.0.conj_vjp(x, cotangent, static_args))
+                } else {
+                    (g, self.1.conj_vjp(x, cotangent, static_args))
+                }
+            }
+        }
+    };
+}
+
+impl_select_adop!(
+    /// min(f, g), the subgradient is that of the smaller operand, and that of f on a tie
+    ADMin, Min, min, <=
+);
+
+impl_select_adop!(
+    /// max(f, g), the subgradient is that of the larger operand, and that of f on a tie
+    ADMax, Max, max, >=
+);
+
+// the special functions are only implemented for real types, for which the holomorphic chain rule
+// reduces to the real one
+
+impl_holomorphic_adop!(
+    /// erf(f), the error function
+    ADErf, Erf, erf,
+    // d(erf(f))/df = 2/sqrt(pi) * exp(-f^2)
+    |f| f.clone().mul(f).neg().exp().mul(Output::FRAC_2_SQRT_PI()),
+    Mul<Output, Output = Output>,
+    Neg<Output = Output>,
+    Exp<Output = Output>,
+    FloatConst,
+);
+
+impl_holomorphic_second_order!(
+    ADErf, Erf, erf,
+    |f| f.clone().mul(f).neg().exp().mul(Output::FRAC_2_SQRT_PI()),
+    // d²(erf(f))/df² = -2f * 2/sqrt(pi) * exp(-f^2)
+    |f| {
+        let df = f.clone().mul(f.clone()).neg().exp().mul(Output::FRAC_2_SQRT_PI());
+        f.clone().add(f).mul(df).neg()
+    },
+    Add<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Neg<Output = Output>,
+    Exp<Output = Output>,
+    FloatConst,
+);
+
+impl_holomorphic_adop!(
+    /// erfc(f) = 1 - erf(f), the complementary error function
+    ADErfc, Erfc, erfc,
+    // d(erfc(f))/df = -2/sqrt(pi) * exp(-f^2)
+    |f| f.clone().mul(f).neg().exp().mul(Output::FRAC_2_SQRT_PI()).neg(),
+    Mul<Output, Output = Output>,
+    Neg<Output = Output>,
+    Exp<Output = Output>,
+    FloatConst,
+);
+
+impl_holomorphic_second_order!(
+    ADErfc, Erfc, erfc,
+    |f| f.clone().mul(f).neg().exp().mul(Output::FRAC_2_SQRT_PI()).neg(),
+    // d²(erfc(f))/df² = 2f * 2/sqrt(pi) * exp(-f^2)
+    |f| {
+        let df = f.clone().mul(f.clone()).neg().exp().mul(Output::FRAC_2_SQRT_PI());
+        f.clone().add(f).mul(df)
+    },
+    Add<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Neg<Output = Output>,
+    Exp<Output = Output>,
+    FloatConst,
+);
+
+impl_holomorphic_adop!(
+    /// gamma(f), the gamma function
+    ADGamma, Gamma, gamma,
+    // d(gamma(f))/df = gamma(f) * digamma(f)
+    |f| f.clone().gamma().mul(f.digamma()),
+    Mul<Output, Output = Output>,
+    Digamma<Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADGamma, Gamma, gamma,
+    |f| f.clone().gamma().mul(f.digamma()),
+    // d²(gamma(f))/df² = gamma(f) * (digamma(f)^2 + trigamma(f))
+    |f| {
+        let digamma_f = f.clone().digamma();
+        f.clone()
+            .gamma()
+            .mul(digamma_f.clone().mul(digamma_f).add(f.trigamma()))
+    },
+    Add<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+    Digamma<Output = Output>,
+    Trigamma<Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// lgamma(f) = ln(|gamma(f)|), the log of the absolute value of the gamma function
+    ADLgamma, Lgamma, lgamma,
+    // d(lgamma(f))/df = digamma(f)
+    |f| f.digamma(),
+    Digamma<Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADLgamma,
+    Lgamma,
+    lgamma,
+    |f| f.digamma(),
+    // d²(lgamma(f))/df² = trigamma(f)
+    |f| f.trigamma(),
+    Digamma<Output = Output>,
+    Trigamma<Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// digamma(f) = d(lgamma(f))/df
+    ADDigamma, Digamma, digamma,
+    // d(digamma(f))/df = trigamma(f)
+    |f| f.trigamma(),
+    Trigamma<Output = Output>,
+);
+
+// Implements a unary AutoDiff operation g(f; p) with a constant parameter p, for an outer function
+// g that is holomorphic in f (or f is real). The derivative dg/df is given as a closure-like
+// expression of f and p, followed by the extra bounds needed on the output type and on the
+// parameter type to compute it.
+macro_rules! impl_parametric_adop {
+    (
+        $(#[$meta:meta])*
+        $name:ident, $trait:ident, $method:ident,
+        |$f:ident, $p:ident| $dgdf:expr,
+        [$($bound:path),* $(,)?],
+        [$($pbound:path),* $(,)?] $(,)?
+    ) => {
+        $(#[$meta])*
+        #[derive(FuncCompose, Debug, Clone, Copy)]
+        pub struct $name<A, B>(pub A, pub B);
+
+        impl<A: Diffable<StaticArgs>, B, StaticArgs> Diffable<StaticArgs> for $name<A, B>
+        where
+            A::Output: $trait<B>,
+            B: Clone,
+        {
+            type Input = A::Input;
+            type Output = <A::Output as $trait<B>>::Output;
+        }
+
+        impl<A: Symbolic, B: SymbolicConstant> Symbolic for $name<A, B> {
+            fn symbolic(&self, x: &Expr) -> Expr {
+                Expr::call(
+                    stringify!($method),
+                    vec![self.0.symbolic(x), self.1.symbolic_constant()],
+                )
+            }
+        }
+
+        impl<StaticArgs, A, B> ToDot<StaticArgs> for $name<A, B>
+        where
+            A: ToDot<StaticArgs>,
+            B: SymbolicConstant,
+            Self: DotAnnotate<StaticArgs, Input = A::Input>,
+        {
+            fn dot_node(&self, graph: &mut DotGraph, at: Option<(&A::Input, &StaticArgs)>) -> usize {
+                let f = self.0.dot_node(graph, at);
+                let c = graph.constant(&self.1.symbolic_constant().to_string());
+                graph.op(stringify!($method), self.annotate_at(at), &[(f, None), (c, None)])
+            }
+        }
+
+        impl<StaticArgs, T, A, B> ToDynExpr<StaticArgs, T> for $name<A, B>
+        where
+            A: ToDynExpr<StaticArgs, T>,
+            T: DynExprScalar,
+            B: Clone,
+            // ensure g(x) can be boxed as an opaque function of the simplified f
+            $name<Identity<StaticArgs, T>, B>: AutoDiffable<StaticArgs, Input = T, Output = T>
+                + ForwardDiffable<StaticArgs, Input = T, Output = T>
+                + Symbolic
+                + Clone
+                + 'static,
+        {
+            fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+                DynExpr::apply($name(Identity::new(), self.1.clone()), self.0.to_dyn_expr())
+            }
+        }
+
+        impl<StaticArgs, Input, Output, Grad, A, B> AutoDiffable<StaticArgs> for $name<A, B>
+        where
+            A: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+            Input: GradientType<Output, GradientType = Grad>,
+            // ensure g(f; p) is defined and is Output, and dg/df can be computed
+            Output: Clone + $trait<B, Output = Output> $(+ $bound)*,
+            B: Clone $(+ $pbound)*,
+            // ensure df * dg/df is defined and is Grad
+            Grad: Mul<Output, Output = Grad>,
+        {
+            fn eval(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                self.0.eval(x, static_args).$method(self.1.clone())
+            }
+
+            fn eval_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+                let (f, df) = self.0.eval_grad(x, static_args);
+
+                // dg(f; p)/dz = dg/df * df/dz
+
+                let dgdf = {
+                    let ($f, $p) = (f.clone(), self.1.clone());
+                    $dgdf
+                };
+
+                (f.$method(self.1.clone()), df.mul(dgdf))
+            }
+
+            fn grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> Grad {
+                let (f, df) = self.0.eval_grad(x, static_args);
+
+                // dg(f; p)/dz = dg/df * df/dz
+
+                let ($f, $p) = (f, self.1.clone());
+                df.mul($dgdf)
+            }
+
+            fn eval_conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (<Self as Diffable<StaticArgs>>::Output, Grad) {
+                let (f, dfdconjz) = self.0.eval_conj_grad(x, static_args);
+
+                // dg(f; p)/dconjz = dg/df * df/dconjz
+
+                let dgdf = {
+                    let ($f, $p) = (f.clone(), self.1.clone());
+                    $dgdf
+                };
+
+                (f.$method(self.1.clone()), dfdconjz.mul(dgdf))
+            }
+
+            fn conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> Grad {
+                let (f, dfdconjz) = self.0.eval_conj_grad(x, static_args);
+
+                // dg(f; p)/dconjz = dg/df * df/dconjz
+
+                let ($f, $p) = (f, self.1.clone());
+                dfdconjz.mul($dgdf)
+            }
+
+            fn eval_all(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (<Self as Diffable<StaticArgs>>::Output, Grad, Grad) {
+                let (f, df, dfdconjz) = self.0.eval_all(x, static_args);
+
+                // both derivatives follow the chain rule through dg/df
+
+                let dgdf = {
+                    let ($f, $p) = (f.clone(), self.1.clone());
+                    $dgdf
+                };
+
+                (f.$method(self.1.clone()), df.mul(dgdf.clone()), dfdconjz.mul(dgdf))
+            }
+        }
+
+        impl<StaticArgs, Input, AOutput, AGrad, Output, Grad, A, B> TryAutoDiffable<StaticArgs>
+            for $name<A, B>
+        where
+            A: TryAutoDiffable<StaticArgs, Input = Input, Output = AOutput>,
+            Input: GradientType<AOutput, GradientType = AGrad>,
+            Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+            Input: GradientType<Output, GradientType = Grad>,
+            // ensure the results of f and g(f; p) can be checked
+            AOutput: CheckValue,
+            AGrad: CheckValue,
+            Output: CheckValue,
+            Grad: CheckValue,
+            B: Clone,
+            // ensure g(f; p) can be evaluated from the checked results of f
+            $name<ADEvaluated<Input, AOutput, AGrad>, B>:
+                AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+        {
+            fn try_eval(
+                &self,
+                x: &Input,
+                static_args: &StaticArgs,
+            ) -> Result<Output, AutoDiffError> {
+                let f = self
+                    .0
+                    .try_eval(x, static_args)
+                    .map_err(|e| e.within(stringify!($name), 0))?;
+
+                let h = $name(ADEvaluated::value(f), self.1.clone());
+                check_value(stringify!($name), h.eval(x, static_args))
+            }
+
+            fn try_eval_all(
+                &self,
+                x: &Input,
+                static_args: &StaticArgs,
+            ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+                let f = self
+                    .0
+                    .try_eval_all(x, static_args)
+                    .map_err(|e| e.within(stringify!($name), 0))?;
+
+                let h = $name(ADEvaluated::all(f), self.1.clone());
+                check_all(stringify!($name), h.eval_all(x, static_args))
+            }
+        }
+
+        impl<StaticArgs, Input, Output, A, B> ForwardDiffable<StaticArgs> for $name<A, B>
+        where
+            A: ForwardDiffable<StaticArgs, Input = Input, Output = Output>,
+            // ensure g(f; p) is defined and is Output, and dg/df can be computed
+            Output: Clone
+                + $trait<B, Output = Output>
+                + Mul<Output, Output = Output>
+                $(+ $bound)*,
+            B: Clone $(+ $pbound)*,
+        {
+            fn eval_forward(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                self.0.eval_forward(x, static_args).$method(self.1.clone())
+            }
+
+            fn eval_forward_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Output,
+            ) {
+                let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+
+                // dg(f; p) = dg/df * df
+
+                let dgdf = {
+                    let ($f, $p) = (f.clone(), self.1.clone());
+                    $dgdf
+                };
+
+                (f.$method(self.1.clone()), df.mul(dgdf))
+            }
+
+            fn forward_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                let (f, df) = self.0.eval_forward_grad(x, dx, static_args);
+
+                // dg(f; p) = dg/df * df
+
+                let ($f, $p) = (f, self.1.clone());
+                df.mul($dgdf)
+            }
+
+            fn eval_forward_conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Output,
+            ) {
+                let (f, dfconj) = self.0.eval_forward_conj_grad(x, dx, static_args);
+
+                // dg(f; p)/dconjz * dx = dg/df * df/dconjz * dx
+
+                let dgdf = {
+                    let ($f, $p) = (f.clone(), self.1.clone());
+                    $dgdf
+                };
+
+                (f.$method(self.1.clone()), dfconj.mul(dgdf))
+            }
+
+            fn forward_conj_grad(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                dx: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                let (f, dfconj) = self.0.eval_forward_conj_grad(x, dx, static_args);
+
+                // dg(f; p)/dconjz * dx = dg/df * df/dconjz * dx
+
+                let ($f, $p) = (f, self.1.clone());
+                dfconj.mul($dgdf)
+            }
+        }
+        impl<StaticArgs, Input, Output, Grad, A, B> ReverseDiffable<StaticArgs> for $name<A, B>
+        where
+            A: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+            Input: GradientType<Output, GradientType = Grad>,
+            // ensure g(f; p) is defined and is Output, and dg/df can be computed
+            Output: Clone + InstZero + $trait<B, Output = Output> $(+ $bound)*,
+            B: Clone $(+ $pbound)*,
+        {
+            fn eval_reverse<'a>(
+                &'a self,
+                x: &'a <Self as Diffable<StaticArgs>>::Input,
+                tape: &mut Tape<'a, Grad, Output>,
+                static_args: &'a StaticArgs,
+            ) -> Var {
+                let u = self.0.eval_reverse(x, tape, static_args);
+                let f = tape.value(u).clone();
+
+                // dg(f; p)/df = g'(f; p), dg(f; p)/dconjf = 0
+
+                let zero = f.zero();
+                let dgdf = {
+                    let ($f, $p) = (f.clone(), self.1.clone());
+                    $dgdf
+                };
+
+                tape.unary(f.$method(self.1.clone()), (u, dgdf, zero))
+            }
+        }
+
+        impl<StaticArgs, Input, Output, A, B> VjpDiffable<StaticArgs> for $name<A, B>
+        where
+            A: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+            // ensure g(f; p) is defined and is Output, and dg/df can be computed
+            Output: Clone + $trait<B, Output = Output> + Mul<Output, Output = Output> $(+ $bound)*,
+            B: Clone $(+ $pbound)*,
+        {
+            fn eval_primal(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                static_args: &StaticArgs,
+            ) -> <Self as Diffable<StaticArgs>>::Output {
+                self.0.eval_primal(x, static_args).$method(self.1.clone())
+            }
+
+            fn eval_vjp(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Input,
+            ) {
+                let f = self.0.eval_primal(x, static_args);
+
+                // c^T dg(f; p)/dz = (c * dg/df)^T df/dz
+
+                let dgdf = {
+                    let ($f, $p) = (f.clone(), self.1.clone());
+                    $dgdf
+                };
+                let vf = self.0.vjp(x, &cotangent.clone().mul(dgdf), static_args);
+
+                (f.$method(self.1.clone()), vf)
+            }
+
+            fn eval_conj_vjp(
+                &self,
+                x: &<Self as Diffable<StaticArgs>>::Input,
+                cotangent: &<Self as Diffable<StaticArgs>>::Output,
+                static_args: &StaticArgs,
+            ) -> (
+                <Self as Diffable<StaticArgs>>::Output,
+                <Self as Diffable<StaticArgs>>::Input,
+            ) {
+                let f = self.0.eval_primal(x, static_args);
+
+                // c^T dg(f; p)/dconjz = (c * dg/df)^T df/dconjz
+
+                let dgdf = {
+                    let ($f, $p) = (f.clone(), self.1.clone());
+                    $dgdf
+                };
+                let vf = self.0.conj_vjp(x, &cotangent.clone().mul(dgdf), static_args);
+
+                (f.$method(self.1.clone()), vf)
+            }
+        }
+    };
 }
+
+// activation functions, these are only implemented for real types (and elementwise for arrays of
+// real types), for which the holomorphic chain rule reduces to the real one. The kinks of relu,
+// leaky_relu and elu at 0 use the derivative from the left.
+
+impl_holomorphic_adop!(
+    /// relu(f) = max(f, 0)
+    ADRelu, Relu, relu,
+    // d(relu(f))/df = H(f)
+    |f| f.heaviside(),
+    Heaviside<Output = Output>,
+);
+
+impl_parametric_adop!(
+    /// leaky_relu(f; alpha) = f for f > 0, alpha * f otherwise
+    ADLeakyRelu, LeakyRelu, leaky_relu,
+    // d(leaky_relu(f; alpha))/df = H(f) * (1 - alpha) + alpha
+    |f, alpha| f.heaviside().mul(alpha.one().sub(alpha.clone())).add(alpha),
+    [Heaviside<Output = Output>, Mul<B, Output = Output>, Add<B, Output = Output>],
+    [InstOne, Sub<B, Output = B>],
+);
+
+impl_holomorphic_adop!(
+    /// sigmoid(f) = 1 / (1 + e^-f)
+    ADSigmoid, Sigmoid, sigmoid,
+    // d(sigmoid(f))/df = sigmoid(f) * (1 - sigmoid(f)) = sigmoid(f) * sigmoid(-f), where the
+    // latter keeps the tails that 1 - sigmoid(f) rounds to 0 for large f
+    |f| f.clone().sigmoid().mul(f.neg().sigmoid()),
+    Neg<Output = Output>,
+    Mul<Output, Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADSigmoid, Sigmoid, sigmoid,
+    |f| f.clone().sigmoid().mul(f.neg().sigmoid()),
+    // d²(sigmoid(f))/df² = sigmoid(f) * (1 - sigmoid(f)) * (1 - 2 sigmoid(f))
+    //                    = sigmoid(f) * sigmoid(-f) * (sigmoid(-f) - sigmoid(f))
+    |f| {
+        let (sigmoid_f, sigmoid_neg_f) = (f.clone().sigmoid(), f.neg().sigmoid());
+        let dsigmoid_f = sigmoid_f.clone().mul(sigmoid_neg_f.clone());
+        dsigmoid_f.mul(sigmoid_neg_f.sub(sigmoid_f))
+    },
+    Neg<Output = Output>,
+    Sub<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// softplus(f) = ln(1 + e^f), computed without overflow for large f
+    ADSoftplus, Softplus, softplus,
+    // d(softplus(f))/df = sigmoid(f)
+    |f| f.sigmoid(),
+    Sigmoid<Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADSoftplus, Softplus, softplus,
+    |f| f.sigmoid(),
+    // d²(softplus(f))/df² = sigmoid(f) * sigmoid(-f)
+    |f| f.clone().sigmoid().mul(f.neg().sigmoid()),
+    Sigmoid<Output = Output>,
+    Neg<Output = Output>,
+    Mul<Output, Output = Output>,
+);
+
+impl_holomorphic_adop!(
+    /// gelu(f) = f * Phi(f), where Phi is the standard normal CDF
+    ADGelu, Gelu, gelu,
+    // d(gelu(f))/df = Phi(f) + f * phi(f), where phi is the standard normal PDF
+    |f| f.clone().norm_cdf().add(f.clone().mul(f.norm_pdf())),
+    NormCdf<Output = Output>,
+    NormPdf<Output = Output>,
+    Add<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+);
+
+impl_holomorphic_second_order!(
+    ADGelu, Gelu, gelu,
+    |f| f.clone().norm_cdf().add(f.clone().mul(f.norm_pdf())),
+    // d²(gelu(f))/df² = phi(f) * (2 - f^2)
+    |f| {
+        let two = f.one().add(f.one());
+        f.clone().norm_pdf().mul(two.sub(f.clone().mul(f)))
+    },
+    InstOne,
+    NormCdf<Output = Output>,
+    NormPdf<Output = Output>,
+    Add<Output, Output = Output>,
+    Sub<Output, Output = Output>,
+    Mul<Output, Output = Output>,
+);
+
+impl_parametric_adop!(
+    /// elu(f; alpha) = f for f > 0, alpha * (e^f - 1) otherwise
+    ADElu, Elu, elu,
+    // d(elu(f; alpha))/df = H(f) + (1 - H(f)) * (elu(f; alpha) + alpha)
+    //
+    // since for f <= 0, elu(f; alpha) + alpha = alpha * e^f, and this form never computes e^f for
+    // large positive f
+    |f, alpha| {
+        let h = f.clone().heaviside();
+        let one = h.one();
+        h.clone().add(one.sub(h).mul(f.elu(alpha.clone()).add(alpha)))
+    },
+    [
+        Heaviside<Output = Output>,
+        InstOne,
+        Add<Output, Output = Output>,
+        Sub<Output, Output = Output>,
+        Add<B, Output = Output>,
+    ],
+    [],
+);
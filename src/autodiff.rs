@@ -1,9 +1,21 @@
 use crate::adops::*;
-use crate::autodiffable::{AutoDiffable, Diffable, ForwardDiffable};
+use crate::autodiffable::{
+    AutoDiffable, Diffable, ForwardDiffable, HessianDiffable, HvpDiffable, VjpDiffable,
+};
 use crate::compose::*;
+use crate::dynautodiff::{DynAutoDiff, DynDiff};
+use crate::forward::Outer;
 use crate::func_traits;
+use crate::funcs::GradFn;
 use crate::gradienttype::GradientType;
+use crate::graphviz::{DotAnnotate, DotGraph, ToDot};
+use crate::reverse::{ReverseDiffable, Tape, Var};
+use crate::shared::{evaluate, ADShared};
+use crate::simplify::{DynExpr, DynExprScalar, ToDynExpr};
+use crate::symbolic::{Expr, Symbolic};
+use crate::taylor::{Taylor, TaylorDiffable};
 use crate::traits::{InstOne, InstZero};
+use crate::tryautodiff::{AutoDiffError, CheckValue, TryAutoDiffable};
 use num::traits::Pow;
 use std::marker::PhantomData;
 use std::ops::{Add, Deref, Div, Mul, Neg, Sub};
@@ -44,6 +56,43 @@ impl<StaticArgs, T> AutoDiff<StaticArgs, T> {
     ) -> AutoDiff<(NewStaticArgs, StaticArgs), ADPrependStaticArgs<T, NewStaticArgs>> {
         AutoDiff(ADPrependStaticArgs(self.0, PhantomData), PhantomData)
     }
+
+    /// The gradient of this function as a function of its own, whose gradient is the Hessian
+    pub fn grad_fn(self) -> AutoDiff<StaticArgs, GradFn<T>> {
+        AutoDiff(GradFn(self.0), PhantomData)
+    }
+
+    /// Erase the type of this function, see `DynDiff`
+    pub fn boxed<Input, Output>(self) -> DynAutoDiff<StaticArgs, Input, Output>
+    where
+        T: AutoDiffable<StaticArgs, Input = Input, Output = Output>
+            + ForwardDiffable<StaticArgs, Input = Input, Output = Output>
+            + Clone
+            + 'static,
+        Input: GradientType<Output>,
+    {
+        AutoDiff(DynDiff::new(self.0), PhantomData)
+    }
+
+    /// Share this function between several parts of an expression, so that it is evaluated once
+    /// for all of them, see `ADShared`
+    pub fn share(self) -> AutoDiff<StaticArgs, ADShared<StaticArgs, T>>
+    where
+        T: Diffable<StaticArgs>,
+        T::Input: GradientType<T::Output>,
+    {
+        AutoDiff(ADShared::new(self.0), PhantomData)
+    }
+
+    /// An equivalent function with identities removed, constants folded and polynomials merged,
+    /// e.g. `(f * 1.0 + 0.0).conj()` of a real `f` becomes `f`, see `DynExpr::simplify`
+    pub fn simplify<Input>(&self) -> AutoDiff<StaticArgs, DynExpr<StaticArgs, Input>>
+    where
+        T: ToDynExpr<StaticArgs, Input>,
+        Input: DynExprScalar,
+    {
+        AutoDiff(self.0.to_dyn_expr().simplify(), PhantomData)
+    }
 }
 
 /// Impl of `Diffable<StaticArgs>` for `AutoDiff`
@@ -62,23 +111,52 @@ where
     Input: GradientType<Output, GradientType = Grad>,
 {
     fn eval(&self, x: &Self::Input, static_args: &StaticArgs) -> Self::Output {
-        self.0.eval(x, static_args)
+        evaluate(x, static_args, || self.0.eval(x, static_args))
     }
 
     fn eval_grad(&self, x: &Self::Input, static_args: &StaticArgs) -> (Self::Output, Grad) {
-        self.0.eval_grad(x, static_args)
+        evaluate(x, static_args, || self.0.eval_grad(x, static_args))
     }
 
     fn grad(&self, x: &Self::Input, static_args: &StaticArgs) -> Grad {
-        self.0.grad(x, static_args)
+        evaluate(x, static_args, || self.0.grad(x, static_args))
     }
 
     fn eval_conj_grad(&self, x: &Self::Input, static_args: &StaticArgs) -> (Self::Output, Grad) {
-        self.0.eval_conj_grad(x, static_args)
+        evaluate(x, static_args, || self.0.eval_conj_grad(x, static_args))
     }
 
     fn conj_grad(&self, x: &Self::Input, static_args: &StaticArgs) -> Grad {
-        self.0.conj_grad(x, static_args)
+        evaluate(x, static_args, || self.0.conj_grad(x, static_args))
+    }
+
+    fn eval_all(&self, x: &Self::Input, static_args: &StaticArgs) -> (Self::Output, Grad, Grad) {
+        evaluate(x, static_args, || self.0.eval_all(x, static_args))
+    }
+}
+
+/// Impl of `TryAutoDiffable` for `AutoDiff`
+impl<StaticArgs, Input, Output, Grad, T> TryAutoDiffable<StaticArgs> for AutoDiff<StaticArgs, T>
+where
+    T: TryAutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    Output: CheckValue,
+    Grad: CheckValue,
+{
+    fn try_eval(
+        &self,
+        x: &Self::Input,
+        static_args: &StaticArgs,
+    ) -> Result<Self::Output, AutoDiffError> {
+        self.0.try_eval(x, static_args)
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Self::Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Self::Output, Grad, Grad), AutoDiffError> {
+        self.0.try_eval_all(x, static_args)
     }
 }
 
@@ -119,6 +197,174 @@ where
     }
 }
 
+/// Impl of VjpDiffable for AutoDiff
+impl<StaticArgs, Input, Output, T> VjpDiffable<StaticArgs> for AutoDiff<StaticArgs, T>
+where
+    T: VjpDiffable<StaticArgs, Input = Input, Output = Output>,
+{
+    fn eval_primal(&self, x: &Self::Input, static_args: &StaticArgs) -> Self::Output {
+        self.0.eval_primal(x, static_args)
+    }
+
+    fn eval_vjp(
+        &self,
+        x: &Self::Input,
+        cotangent: &Self::Output,
+        static_args: &StaticArgs,
+    ) -> (Self::Output, Self::Input) {
+        self.0.eval_vjp(x, cotangent, static_args)
+    }
+
+    fn eval_conj_vjp(
+        &self,
+        x: &Self::Input,
+        cotangent: &Self::Output,
+        static_args: &StaticArgs,
+    ) -> (Self::Output, Self::Input) {
+        self.0.eval_conj_vjp(x, cotangent, static_args)
+    }
+
+    fn vjp(
+        &self,
+        x: &Self::Input,
+        cotangent: &Self::Output,
+        static_args: &StaticArgs,
+    ) -> Self::Input {
+        self.0.vjp(x, cotangent, static_args)
+    }
+
+    fn conj_vjp(
+        &self,
+        x: &Self::Input,
+        cotangent: &Self::Output,
+        static_args: &StaticArgs,
+    ) -> Self::Input {
+        self.0.conj_vjp(x, cotangent, static_args)
+    }
+}
+
+/// Impl of HessianDiffable for AutoDiff
+impl<StaticArgs, Input, Output, Grad, T> HessianDiffable<StaticArgs> for AutoDiff<StaticArgs, T>
+where
+    T: HessianDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    Grad: Outer<Grad>,
+{
+    fn eval_hessian(
+        &self,
+        x: &Self::Input,
+        static_args: &StaticArgs,
+    ) -> (Output, Grad, <Grad as Outer<Grad>>::Output) {
+        self.0.eval_hessian(x, static_args)
+    }
+
+    fn hessian(&self, x: &Self::Input, static_args: &StaticArgs) -> <Grad as Outer<Grad>>::Output {
+        self.0.hessian(x, static_args)
+    }
+}
+
+/// Impl of HvpDiffable for AutoDiff
+impl<StaticArgs, T> HvpDiffable<StaticArgs> for AutoDiff<StaticArgs, T>
+where
+    T: HvpDiffable<StaticArgs>,
+{
+    fn eval_hvp(
+        &self,
+        x: &Self::Input,
+        v: &Self::Input,
+        cotangent: &Self::Output,
+        dcotangent: &Self::Output,
+        static_args: &StaticArgs,
+    ) -> (Self::Output, Self::Input, Self::Input) {
+        self.0.eval_hvp(x, v, cotangent, dcotangent, static_args)
+    }
+}
+
+/// Impl of TaylorDiffable for AutoDiff
+impl<StaticArgs, T, const K: usize> TaylorDiffable<StaticArgs, K> for AutoDiff<StaticArgs, T>
+where
+    T: TaylorDiffable<StaticArgs, K>,
+{
+    fn eval_taylor_series(
+        &self,
+        x: &Taylor<Self::Input, K>,
+        static_args: &StaticArgs,
+    ) -> Taylor<Self::Output, K> {
+        self.0.eval_taylor_series(x, static_args)
+    }
+}
+
+/// Impl of ReverseDiffable for AutoDiff
+impl<StaticArgs, Input, Output, Grad, T> ReverseDiffable<StaticArgs> for AutoDiff<StaticArgs, T>
+where
+    T: ReverseDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+{
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a Self::Input,
+        tape: &mut Tape<'a, Grad, Output>,
+        static_args: &'a StaticArgs,
+    ) -> Var {
+        self.0.eval_reverse(x, tape, static_args)
+    }
+}
+
+/// Impl of Symbolic for AutoDiff
+impl<StaticArgs, T: Symbolic> Symbolic for AutoDiff<StaticArgs, T> {
+    fn symbolic(&self, x: &Expr) -> Expr {
+        self.0.symbolic(x)
+    }
+}
+
+/// Impl of Display for AutoDiff, in infix notation of the input `x`
+impl<StaticArgs, T: Symbolic> std::fmt::Display for AutoDiff<StaticArgs, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbolic(&Expr::var("x")))
+    }
+}
+
+/// Impl of ToDot for AutoDiff
+impl<StaticArgs, T> ToDot<StaticArgs> for AutoDiff<StaticArgs, T>
+where
+    T: ToDot<StaticArgs>,
+    Self: DotAnnotate<StaticArgs, Input = T::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&T::Input, &StaticArgs)>) -> usize {
+        self.0.dot_node(graph, at)
+    }
+}
+
+impl<StaticArgs, Input, T> ToDynExpr<StaticArgs, Input> for AutoDiff<StaticArgs, T>
+where
+    T: ToDynExpr<StaticArgs, Input>,
+    Input: DynExprScalar,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, Input> {
+        self.0.to_dyn_expr()
+    }
+}
+
+impl<StaticArgs, T> AutoDiff<StaticArgs, T>
+where
+    T: ToDot<StaticArgs>,
+{
+    /// The computation graph of the function in Graphviz DOT format
+    pub fn to_dot(&self) -> String {
+        let mut graph = DotGraph::new();
+        self.0.dot_node(&mut graph, None);
+        graph.to_string()
+    }
+
+    /// The computation graph of the function in Graphviz DOT format, with every node annotated
+    /// with its value and gradient at `x`
+    pub fn to_dot_at(&self, x: &T::Input, static_args: &StaticArgs) -> String {
+        let mut graph = DotGraph::new();
+        self.0.dot_node(&mut graph, Some((x, static_args)));
+        graph.to_string()
+    }
+}
+
 /// Impl of Deref for AutoDiff
 impl<StaticArgs, T> Deref for AutoDiff<StaticArgs, T> {
     type Target = T;
@@ -180,6 +426,19 @@ where
     }
 }
 
+/// Impl of Pow for AutoDiff
+impl<StaticArgs, A, B> Pow<AutoDiff<StaticArgs, B>> for AutoDiff<StaticArgs, A>
+where
+    A: Diffable<StaticArgs>,
+    B: Diffable<StaticArgs>,
+{
+    type Output = AutoDiff<StaticArgs, ADPow<A, B>>;
+
+    fn pow(self, _other: AutoDiff<StaticArgs, B>) -> Self::Output {
+        AutoDiff(ADPow(self.0, _other.0), PhantomData)
+    }
+}
+
 /// Impl of Neg for AutoDiff
 impl<StaticArgs, A> Neg for AutoDiff<StaticArgs, A> {
     type Output = AutoDiff<StaticArgs, ADNeg<A>>;
@@ -298,3 +557,259 @@ impl<StaticArgs, A: Clone> func_traits::Conjugate for AutoDiff<StaticArgs, A> {
         AutoDiff(ADConjugate(self.0.clone()), PhantomData)
     }
 }
+
+/// Impl Exp
+impl<StaticArgs, A> func_traits::Exp for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADExp<A>>;
+    fn exp(self) -> Self::Output {
+        AutoDiff(ADExp(self.0), PhantomData)
+    }
+}
+
+/// Impl Ln
+impl<StaticArgs, A> func_traits::Ln for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADLn<A>>;
+    fn ln(self) -> Self::Output {
+        AutoDiff(ADLn(self.0), PhantomData)
+    }
+}
+
+/// Impl Log with a constant base
+impl<StaticArgs, A, B> func_traits::Log<B> for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADLog<A, B>>;
+    fn log(self, base: B) -> Self::Output {
+        AutoDiff(ADLog(self.0, base), PhantomData)
+    }
+}
+
+/// Impl Log2
+impl<StaticArgs, A> func_traits::Log2 for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADLog2<A>>;
+    fn log2(self) -> Self::Output {
+        AutoDiff(ADLog2(self.0), PhantomData)
+    }
+}
+
+/// Impl Log10
+impl<StaticArgs, A> func_traits::Log10 for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADLog10<A>>;
+    fn log10(self) -> Self::Output {
+        AutoDiff(ADLog10(self.0), PhantomData)
+    }
+}
+
+/// Impl Sin
+impl<StaticArgs, A> func_traits::Sin for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADSin<A>>;
+    fn sin(self) -> Self::Output {
+        AutoDiff(ADSin(self.0), PhantomData)
+    }
+}
+
+/// Impl Cos
+impl<StaticArgs, A> func_traits::Cos for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADCos<A>>;
+    fn cos(self) -> Self::Output {
+        AutoDiff(ADCos(self.0), PhantomData)
+    }
+}
+
+/// Impl Tan
+impl<StaticArgs, A> func_traits::Tan for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADTan<A>>;
+    fn tan(self) -> Self::Output {
+        AutoDiff(ADTan(self.0), PhantomData)
+    }
+}
+
+/// Impl Asin
+impl<StaticArgs, A> func_traits::Asin for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADAsin<A>>;
+    fn asin(self) -> Self::Output {
+        AutoDiff(ADAsin(self.0), PhantomData)
+    }
+}
+
+/// Impl Acos
+impl<StaticArgs, A> func_traits::Acos for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADAcos<A>>;
+    fn acos(self) -> Self::Output {
+        AutoDiff(ADAcos(self.0), PhantomData)
+    }
+}
+
+/// Impl Atan
+impl<StaticArgs, A> func_traits::Atan for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADAtan<A>>;
+    fn atan(self) -> Self::Output {
+        AutoDiff(ADAtan(self.0), PhantomData)
+    }
+}
+
+/// Impl Sinh
+impl<StaticArgs, A> func_traits::Sinh for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADSinh<A>>;
+    fn sinh(self) -> Self::Output {
+        AutoDiff(ADSinh(self.0), PhantomData)
+    }
+}
+
+/// Impl Cosh
+impl<StaticArgs, A> func_traits::Cosh for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADCosh<A>>;
+    fn cosh(self) -> Self::Output {
+        AutoDiff(ADCosh(self.0), PhantomData)
+    }
+}
+
+/// Impl Tanh
+impl<StaticArgs, A> func_traits::Tanh for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADTanh<A>>;
+    fn tanh(self) -> Self::Output {
+        AutoDiff(ADTanh(self.0), PhantomData)
+    }
+}
+
+/// Impl Asinh
+impl<StaticArgs, A> func_traits::Asinh for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADAsinh<A>>;
+    fn asinh(self) -> Self::Output {
+        AutoDiff(ADAsinh(self.0), PhantomData)
+    }
+}
+
+/// Impl Acosh
+impl<StaticArgs, A> func_traits::Acosh for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADAcosh<A>>;
+    fn acosh(self) -> Self::Output {
+        AutoDiff(ADAcosh(self.0), PhantomData)
+    }
+}
+
+/// Impl Atanh
+impl<StaticArgs, A> func_traits::Atanh for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADAtanh<A>>;
+    fn atanh(self) -> Self::Output {
+        AutoDiff(ADAtanh(self.0), PhantomData)
+    }
+}
+
+/// Impl Atan2
+impl<StaticArgs, A, B> func_traits::Atan2<AutoDiff<StaticArgs, B>> for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADAtan2<A, B>>;
+    fn atan2(self, other: AutoDiff<StaticArgs, B>) -> Self::Output {
+        AutoDiff(ADAtan2(self.0, other.0), PhantomData)
+    }
+}
+
+/// Impl Hypot
+impl<StaticArgs, A, B> func_traits::Hypot<AutoDiff<StaticArgs, B>> for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADHypot<A, B>>;
+    fn hypot(self, other: AutoDiff<StaticArgs, B>) -> Self::Output {
+        AutoDiff(ADHypot(self.0, other.0), PhantomData)
+    }
+}
+
+/// Impl Min
+impl<StaticArgs, A, B> func_traits::Min<AutoDiff<StaticArgs, B>> for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADMin<A, B>>;
+    fn min(self, other: AutoDiff<StaticArgs, B>) -> Self::Output {
+        AutoDiff(ADMin(self.0, other.0), PhantomData)
+    }
+}
+
+/// Impl Max
+impl<StaticArgs, A, B> func_traits::Max<AutoDiff<StaticArgs, B>> for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADMax<A, B>>;
+    fn max(self, other: AutoDiff<StaticArgs, B>) -> Self::Output {
+        AutoDiff(ADMax(self.0, other.0), PhantomData)
+    }
+}
+
+/// Impl Erf
+impl<StaticArgs, A> func_traits::Erf for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADErf<A>>;
+    fn erf(self) -> Self::Output {
+        AutoDiff(ADErf(self.0), PhantomData)
+    }
+}
+
+/// Impl Erfc
+impl<StaticArgs, A> func_traits::Erfc for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADErfc<A>>;
+    fn erfc(self) -> Self::Output {
+        AutoDiff(ADErfc(self.0), PhantomData)
+    }
+}
+
+/// Impl Gamma
+impl<StaticArgs, A> func_traits::Gamma for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADGamma<A>>;
+    fn gamma(self) -> Self::Output {
+        AutoDiff(ADGamma(self.0), PhantomData)
+    }
+}
+
+/// Impl Lgamma
+impl<StaticArgs, A> func_traits::Lgamma for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADLgamma<A>>;
+    fn lgamma(self) -> Self::Output {
+        AutoDiff(ADLgamma(self.0), PhantomData)
+    }
+}
+
+/// Impl Digamma
+impl<StaticArgs, A> func_traits::Digamma for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADDigamma<A>>;
+    fn digamma(self) -> Self::Output {
+        AutoDiff(ADDigamma(self.0), PhantomData)
+    }
+}
+
+/// Impl Relu
+impl<StaticArgs, A> func_traits::Relu for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADRelu<A>>;
+    fn relu(self) -> Self::Output {
+        AutoDiff(ADRelu(self.0), PhantomData)
+    }
+}
+
+/// Impl Sigmoid
+impl<StaticArgs, A> func_traits::Sigmoid for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADSigmoid<A>>;
+    fn sigmoid(self) -> Self::Output {
+        AutoDiff(ADSigmoid(self.0), PhantomData)
+    }
+}
+
+/// Impl Softplus
+impl<StaticArgs, A> func_traits::Softplus for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADSoftplus<A>>;
+    fn softplus(self) -> Self::Output {
+        AutoDiff(ADSoftplus(self.0), PhantomData)
+    }
+}
+
+/// Impl Gelu
+impl<StaticArgs, A> func_traits::Gelu for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADGelu<A>>;
+    fn gelu(self) -> Self::Output {
+        AutoDiff(ADGelu(self.0), PhantomData)
+    }
+}
+
+/// Impl LeakyRelu with a constant alpha
+impl<StaticArgs, A, B> func_traits::LeakyRelu<B> for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADLeakyRelu<A, B>>;
+    fn leaky_relu(self, alpha: B) -> Self::Output {
+        AutoDiff(ADLeakyRelu(self.0, alpha), PhantomData)
+    }
+}
+
+/// Impl Elu with a constant alpha
+impl<StaticArgs, A, B> func_traits::Elu<B> for AutoDiff<StaticArgs, A> {
+    type Output = AutoDiff<StaticArgs, ADElu<A, B>>;
+    fn elu(self, alpha: B) -> Self::Output {
+        AutoDiff(ADElu(self.0, alpha), PhantomData)
+    }
+}
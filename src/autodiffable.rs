@@ -1,4 +1,6 @@
+use crate::forward::Outer;
 use crate::gradienttype::GradientType;
+use crate::traits::{InstOne, InstZero};
 
 // re-export Diffable<StaticArgs>
 pub use crate::diffable::Diffable;
@@ -9,6 +11,7 @@ where
 {
     /// Evaluate the function and its gradient for a given input and static arguments.
     /// Returns `(f(x, static_args): <Self as Diffable<StaticArgs>>::Output, df/dx(x, static_args): <<Self as Diffable<StaticArgs>>::Input as GradientType<<Self as Diffable<StaticArgs>>::Output>>::GradientType)`
+    #[allow(clippy::type_complexity)]
     fn eval_grad(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
@@ -21,6 +24,7 @@ where
     );
 
     /// Evaluate the function and its gradient wrt the conjugate of the input for a given input and static arguments.
+    #[allow(clippy::type_complexity)]
     fn eval_conj_grad(
         &self,
         x: &<Self as Diffable<StaticArgs>>::Input,
@@ -63,6 +67,27 @@ where
     >>::GradientType {
         self.eval_conj_grad(x, static_args).1
     }
+
+    /// Evaluate the function, its gradient, and its gradient wrt the conjugate of the input for a given input and static arguments.
+    /// Returns `(f(x, static_args), df/dx(x, static_args), df/dconj(x)(x, static_args))`
+    /// By default this calls `eval_grad` and `conj_grad`. Combinators which need both gradients of their operands (e.g. the composition of complex functions) override it so that every node is evaluated only once per call.
+    #[allow(clippy::type_complexity)]
+    fn eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <<Self as Diffable<StaticArgs>>::Input as GradientType<
+            <Self as Diffable<StaticArgs>>::Output,
+        >>::GradientType,
+        <<Self as Diffable<StaticArgs>>::Input as GradientType<
+            <Self as Diffable<StaticArgs>>::Output,
+        >>::GradientType,
+    ) {
+        let (f, df) = self.eval_grad(x, static_args);
+        (f, df, self.conj_grad(x, static_args))
+    }
 }
 
 pub trait ForwardDiffable<StaticArgs>: Diffable<StaticArgs> {
@@ -123,3 +148,146 @@ pub trait ForwardDiffable<StaticArgs>: Diffable<StaticArgs> {
         self.eval_forward_conj_grad(x, dx, static_args).1
     }
 }
+
+pub trait VjpDiffable<StaticArgs>: Diffable<StaticArgs> {
+    /// Evaluate the function for a given input `x` and static arguments
+    /// This is the primal pass of the vector-Jacobian product, which combinators use to compute the cotangents of their operands
+    fn eval_primal(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Output;
+
+    /// Evaluate the function and its vector-Jacobian product for a given input `x`, cotangent `cotangent`, and static arguments
+    /// Returns `(f(x, static_args): <Self as Diffable<StaticArgs>>::Output, cotangent^T * df/dx(x, static_args): <Self as Diffable<StaticArgs>>::Input)`
+    /// This is the dual of `ForwardDiffable::eval_forward_grad`: the cotangent has the shape of `f` and the result has the shape of `x`, so the Jacobian is never materialized.
+    /// For a scalar valued loss and a cotangent of one, this is the gradient of the loss with memory proportional to the input.
+    fn eval_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    );
+
+    /// Evaluate the function and its vector-Jacobian product wrt the conjugate of the input for a given input `x`, cotangent `cotangent`, and static arguments
+    /// Returns `(f(x, static_args), cotangent^T * df/dconj(x)(x, static_args))`
+    fn eval_conj_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+    );
+
+    /// Evaluate the vector-Jacobian product for a given input `x`, cotangent `cotangent`, and static arguments
+    fn vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Input {
+        self.eval_vjp(x, cotangent, static_args).1
+    }
+
+    /// Evaluate the vector-Jacobian product wrt the conjugate of the input for a given input `x`, cotangent `cotangent`, and static arguments
+    fn conj_vjp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Input {
+        self.eval_conj_vjp(x, cotangent, static_args).1
+    }
+}
+
+pub trait HessianDiffable<StaticArgs>: Diffable<StaticArgs>
+where
+    <Self as Diffable<StaticArgs>>::Input: GradientType<<Self as Diffable<StaticArgs>>::Output>,
+    <<Self as Diffable<StaticArgs>>::Input as GradientType<
+        <Self as Diffable<StaticArgs>>::Output,
+    >>::GradientType: Outer<
+        <<Self as Diffable<StaticArgs>>::Input as GradientType<
+            <Self as Diffable<StaticArgs>>::Output,
+        >>::GradientType,
+    >,
+{
+    /// Evaluate the function, its gradient and its Hessian for a given input and static arguments.
+    /// Returns `(f(x, static_args), df/dx(x, static_args), d²f/dx²(x, static_args))`
+    /// The Hessian has the type of the outer product of two gradients, `<Grad as Outer<Grad>>::Output`, so it is a scalar for scalar inputs, a nested `AutoTuple` for `AutoTuple` inputs and an array with twice the dimension of the input for array inputs. For array outputs the Hessian has the axes of the input and output twice, and holds the second derivatives of each element of the output on the diagonal of the output axes.
+    /// For complex inputs this is the holomorphic second derivative d²f/dz²
+    #[allow(clippy::type_complexity)]
+    fn eval_hessian(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <<Self as Diffable<StaticArgs>>::Input as GradientType<
+            <Self as Diffable<StaticArgs>>::Output,
+        >>::GradientType,
+        <<<Self as Diffable<StaticArgs>>::Input as GradientType<
+            <Self as Diffable<StaticArgs>>::Output,
+        >>::GradientType as Outer<
+            <<Self as Diffable<StaticArgs>>::Input as GradientType<
+                <Self as Diffable<StaticArgs>>::Output,
+            >>::GradientType,
+        >>::Output,
+    );
+
+    /// Evaluate the Hessian for a given input and static arguments.
+    #[allow(clippy::type_complexity)]
+    fn hessian(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <<<Self as Diffable<StaticArgs>>::Input as GradientType<
+        <Self as Diffable<StaticArgs>>::Output,
+    >>::GradientType as Outer<
+        <<Self as Diffable<StaticArgs>>::Input as GradientType<
+            <Self as Diffable<StaticArgs>>::Output,
+        >>::GradientType,
+    >>::Output {
+        self.eval_hessian(x, static_args).2
+    }
+}
+
+pub trait HvpDiffable<StaticArgs>: Diffable<StaticArgs> {
+    /// Evaluate the function, its vector-Jacobian product and the derivative of the vector-Jacobian product along `v` for a given input `x`, tangent `v`, cotangent `cotangent` whose derivative along `v` is `dcotangent`, and static arguments
+    /// Returns `(f(x, static_args), c^T df/dx(x, static_args), dc^T df/dx(x, static_args) + c^T d²f/dx²(x, static_args) * v)`
+    /// This is forward mode over the vector-Jacobian product: combinators get the tangents of their operands from `ForwardDiffable::eval_forward_grad` to compute the derivatives of the cotangents they pass on, so neither the gradient nor the Hessian is ever materialized.
+    /// For complex inputs this uses the holomorphic second derivative d²f/dz², like `HessianDiffable`
+    fn eval_hvp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        v: &<Self as Diffable<StaticArgs>>::Input,
+        cotangent: &<Self as Diffable<StaticArgs>>::Output,
+        dcotangent: &<Self as Diffable<StaticArgs>>::Output,
+        static_args: &StaticArgs,
+    ) -> (
+        <Self as Diffable<StaticArgs>>::Output,
+        <Self as Diffable<StaticArgs>>::Input,
+        <Self as Diffable<StaticArgs>>::Input,
+    );
+
+    /// Evaluate the Hessian-vector product `d²f/dx² * v` of a function with a scalar output for a given input `x`, tangent `v`, and static arguments
+    /// The result has the shape of `x`, and no tensor with the shape of the gradient or the Hessian is allocated
+    fn hvp(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        v: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> <Self as Diffable<StaticArgs>>::Input
+    where
+        Self: ForwardDiffable<StaticArgs>,
+        <Self as Diffable<StaticArgs>>::Output: InstOne + InstZero,
+    {
+        // the gradient of a scalar function is its vector-Jacobian product with a constant cotangent of one
+        let f = self.eval_forward(x, static_args);
+        self.eval_hvp(x, v, &f.one(), &f.zero(), static_args).2
+    }
+}
@@ -1,8 +1,9 @@
-use crate::forward::ForwardMul;
+use crate::finitediff::{CentralDifference, Components, FiniteDiffInput};
+use crate::forward::{ForwardMul, Outer};
 use crate::gradienttype::GradientType;
-use crate::traits::{InstOne, InstZero};
+use crate::traits::{Conjugate, GradientZero, InstOne, InstZero};
 use num::complex::Complex;
-use num::traits::{Num, NumOps, One, Pow, Signed, Zero};
+use num::traits::{Float, Num, NumOps, One, Pow, Signed, Zero};
 use paste::paste;
 use std::ops::{Add, Deref, Div, Mul, Neg, Rem, Sub};
 
@@ -321,6 +322,17 @@ macro_rules! autotuple_unary_ops {
                     AutoTuple::new(($( self.0.$idx.one(), )+))
                 }
             }
+            impl<$([<T $idx>],)+> Conjugate for AutoTuple<($([<T $idx>],)+)>
+            where
+                $([<T $idx>]: Conjugate<Output = [<T $idx>]>,)+
+                ($([<T $idx>],)+): Clone + PartialEq,
+            {
+                type Output = AutoTuple<($([<T $idx>],)+)>;
+
+                fn conj(&self) -> Self::Output {
+                    AutoTuple::new(($( self.0.$idx.conj(), )+))
+                }
+            }
             impl<$([<T $idx>],)+> Signed for AutoTuple<($([<T $idx>],)+)>
             where
                 $([<T $idx>]: Signed + Num + Neg<Output = [<T $idx>]>,)+
@@ -446,6 +458,298 @@ size_1_autotuple_gradient_type!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13);
 size_1_autotuple_gradient_type!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14);
 size_1_autotuple_gradient_type!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
 
+// macro for GradientZero for AutoTuple, with the zero gradient of each element of the output
+// wrt the corresponding element of the input, as in GradientType
+macro_rules! autotuple_grad_zero {
+    ($($idx:literal),+) => {
+        paste! {
+
+            // size n input size n output
+            impl<$([<T $idx>],)+ $([<U $idx>],)+ $([<G $idx>],)+> GradientZero<AutoTuple<($([<U $idx>],)+)>> for AutoTuple<($([<T $idx>],)+)>
+            where
+                $([<T $idx>]: GradientZero<[<U $idx>]> + GradientType<[<U $idx>], GradientType = [<G $idx>]>,)+
+                ($([<T $idx>],)+): Clone + PartialEq,
+                ($([<U $idx>],)+): Clone + PartialEq,
+                ($([<G $idx>],)+): Clone + PartialEq,
+            {
+                fn grad_zero(&self, output: &AutoTuple<($([<U $idx>],)+)>) -> AutoTuple<($([<G $idx>],)+)> {
+                    AutoTuple::new(($(self.0.$idx.grad_zero(&output.0.$idx),)+))
+                }
+            }
+        }
+    }
+}
+
+macro_rules! size_1_autotuple_grad_zero {
+    ($($idx:literal),+) => {
+        paste! {
+
+            // size 1 input size n output
+            impl<T, $([<U $idx>],)+ $([<G $idx>],)+> GradientZero<AutoTuple<($([<U $idx>],)+)>> for AutoTuple<(T,)>
+            where
+                $(T: GradientZero<[<U $idx>]> + GradientType<[<U $idx>], GradientType = [<G $idx>]>,)+
+                (T,): Clone + PartialEq,
+                ($([<U $idx>],)+): Clone + PartialEq,
+                ($([<G $idx>],)+): Clone + PartialEq,
+            {
+                fn grad_zero(&self, output: &AutoTuple<($([<U $idx>],)+)>) -> AutoTuple<($([<G $idx>],)+)> {
+                    AutoTuple::new(($(self.0.0.grad_zero(&output.0.$idx),)+))
+                }
+            }
+
+            // size n input size 1 output
+            impl<$([<T $idx>],)+ U, $([<G $idx>],)+> GradientZero<AutoTuple<(U,)>> for AutoTuple<($([<T $idx>],)+)>
+            where
+                $([<T $idx>]: GradientZero<U> + GradientType<U, GradientType = [<G $idx>]>,)+
+                ($([<T $idx>],)+): Clone + PartialEq,
+                (U,): Clone + PartialEq,
+                ($([<G $idx>],)+): Clone + PartialEq,
+            {
+                fn grad_zero(&self, output: &AutoTuple<(U,)>) -> AutoTuple<($([<G $idx>],)+)> {
+                    AutoTuple::new(($(self.0.$idx.grad_zero(&output.0.0),)+))
+                }
+            }
+        }
+    }
+}
+
+autotuple_grad_zero!(0);
+autotuple_grad_zero!(0, 1);
+autotuple_grad_zero!(0, 1, 2);
+autotuple_grad_zero!(0, 1, 2, 3);
+autotuple_grad_zero!(0, 1, 2, 3, 4);
+autotuple_grad_zero!(0, 1, 2, 3, 4, 5);
+autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6);
+autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7);
+autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8);
+autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9);
+autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10);
+autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11);
+autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
+autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13);
+autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14);
+autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+size_1_autotuple_grad_zero!(0, 1);
+size_1_autotuple_grad_zero!(0, 1, 2);
+size_1_autotuple_grad_zero!(0, 1, 2, 3);
+size_1_autotuple_grad_zero!(0, 1, 2, 3, 4);
+size_1_autotuple_grad_zero!(0, 1, 2, 3, 4, 5);
+size_1_autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6);
+size_1_autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7);
+size_1_autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8);
+size_1_autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9);
+size_1_autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10);
+size_1_autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11);
+size_1_autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
+size_1_autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13);
+size_1_autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14);
+size_1_autotuple_grad_zero!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+
+// macro for FiniteDiffInput for AutoTuple, differentiating each element of the output wrt the
+// corresponding element of the input, as in GradientType
+macro_rules! autotuple_finite_diff_input {
+    ($($idx:literal),+) => {
+        paste! {
+
+            // size n input size n output
+            impl<R, $([<T $idx>],)+ $([<U $idx>],)+ $([<G $idx>],)+> FiniteDiffInput<AutoTuple<($([<U $idx>],)+)>> for AutoTuple<($([<T $idx>],)+)>
+            where
+                R: Float,
+                $([<T $idx>]: FiniteDiffInput<[<U $idx>], Real = R> + GradientType<[<U $idx>], GradientType = [<G $idx>]> + InstZero,)+
+                ($([<T $idx>],)+): Clone + PartialEq,
+                ($([<U $idx>],)+): Clone + PartialEq,
+                ($([<G $idx>],)+): Clone + PartialEq,
+            {
+                type Real = R;
+
+                fn finite_diff_grad(
+                    &self,
+                    f: impl Fn(&Self) -> AutoTuple<($([<U $idx>],)+)>,
+                    scheme: &CentralDifference<R>,
+                ) -> (AutoTuple<($([<G $idx>],)+)>, AutoTuple<($([<G $idx>],)+)>) {
+                    $(
+                        let ([<g $idx>], [<c $idx>]) = self.0.$idx.finite_diff_grad(
+                            |y| {
+                                let mut x = self.clone();
+                                x.0.$idx = y.clone();
+                                f(&x).0.$idx
+                            },
+                            scheme,
+                        );
+                    )+
+                    (AutoTuple::new(($([<g $idx>],)+)), AutoTuple::new(($([<c $idx>],)+)))
+                }
+
+                fn basis(&self) -> Vec<Self> {
+                    let zero = AutoTuple::new(($(self.0.$idx.zero(),)+));
+                    let mut basis = Vec::new();
+                    $(
+                        for b in self.0.$idx.basis() {
+                            let mut x = zero.clone();
+                            x.0.$idx = b;
+                            basis.push(x);
+                        }
+                    )+
+                    basis
+                }
+            }
+        }
+    }
+}
+
+macro_rules! size_1_autotuple_finite_diff_input {
+    ($($idx:literal),+) => {
+        paste! {
+
+            // size 1 input size n output
+            impl<R, T, $([<U $idx>],)+ $([<G $idx>],)+> FiniteDiffInput<AutoTuple<($([<U $idx>],)+)>> for AutoTuple<(T,)>
+            where
+                R: Float,
+                $(T: FiniteDiffInput<[<U $idx>], Real = R> + GradientType<[<U $idx>], GradientType = [<G $idx>]>,)+
+                (T,): Clone + PartialEq,
+                ($([<U $idx>],)+): Clone + PartialEq,
+                ($([<G $idx>],)+): Clone + PartialEq,
+            {
+                type Real = R;
+
+                fn finite_diff_grad(
+                    &self,
+                    f: impl Fn(&Self) -> AutoTuple<($([<U $idx>],)+)>,
+                    scheme: &CentralDifference<R>,
+                ) -> (AutoTuple<($([<G $idx>],)+)>, AutoTuple<($([<G $idx>],)+)>) {
+                    $(
+                        let ([<g $idx>], [<c $idx>]) = self.0.0.finite_diff_grad(
+                            |y| f(&AutoTuple::new((y.clone(),))).0.$idx,
+                            scheme,
+                        );
+                    )+
+                    (AutoTuple::new(($([<g $idx>],)+)), AutoTuple::new(($([<c $idx>],)+)))
+                }
+
+                fn basis(&self) -> Vec<Self> {
+                    <T as FiniteDiffInput<[<U 0>]>>::basis(&self.0.0)
+                        .into_iter()
+                        .map(|b| AutoTuple::new((b,)))
+                        .collect()
+                }
+            }
+
+            // size n input size 1 output
+            impl<R, $([<T $idx>],)+ U, $([<G $idx>],)+> FiniteDiffInput<AutoTuple<(U,)>> for AutoTuple<($([<T $idx>],)+)>
+            where
+                R: Float,
+                $([<T $idx>]: FiniteDiffInput<U, Real = R> + GradientType<U, GradientType = [<G $idx>]> + InstZero,)+
+                ($([<T $idx>],)+): Clone + PartialEq,
+                (U,): Clone + PartialEq,
+                ($([<G $idx>],)+): Clone + PartialEq,
+            {
+                type Real = R;
+
+                fn finite_diff_grad(
+                    &self,
+                    f: impl Fn(&Self) -> AutoTuple<(U,)>,
+                    scheme: &CentralDifference<R>,
+                ) -> (AutoTuple<($([<G $idx>],)+)>, AutoTuple<($([<G $idx>],)+)>) {
+                    $(
+                        let ([<g $idx>], [<c $idx>]) = self.0.$idx.finite_diff_grad(
+                            |y| {
+                                let mut x = self.clone();
+                                x.0.$idx = y.clone();
+                                f(&x).0.0
+                            },
+                            scheme,
+                        );
+                    )+
+                    (AutoTuple::new(($([<g $idx>],)+)), AutoTuple::new(($([<c $idx>],)+)))
+                }
+
+                fn basis(&self) -> Vec<Self> {
+                    let zero = AutoTuple::new(($(self.0.$idx.zero(),)+));
+                    let mut basis = Vec::new();
+                    $(
+                        for b in self.0.$idx.basis() {
+                            let mut x = zero.clone();
+                            x.0.$idx = b;
+                            basis.push(x);
+                        }
+                    )+
+                    basis
+                }
+            }
+        }
+    }
+}
+
+// implement for tuples of length 1-16
+autotuple_finite_diff_input!(0);
+autotuple_finite_diff_input!(0, 1);
+autotuple_finite_diff_input!(0, 1, 2);
+autotuple_finite_diff_input!(0, 1, 2, 3);
+autotuple_finite_diff_input!(0, 1, 2, 3, 4);
+autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5);
+autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6);
+autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7);
+autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8);
+autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9);
+autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10);
+autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11);
+autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
+autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13);
+autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14);
+autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+size_1_autotuple_finite_diff_input!(0, 1);
+size_1_autotuple_finite_diff_input!(0, 1, 2);
+size_1_autotuple_finite_diff_input!(0, 1, 2, 3);
+size_1_autotuple_finite_diff_input!(0, 1, 2, 3, 4);
+size_1_autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5);
+size_1_autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6);
+size_1_autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7);
+size_1_autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8);
+size_1_autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9);
+size_1_autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10);
+size_1_autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11);
+size_1_autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
+size_1_autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13);
+size_1_autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14);
+size_1_autotuple_finite_diff_input!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+
+// macro for Components for AutoTuple, concatenating the components of the elements
+macro_rules! autotuple_components {
+    ($($idx:literal),+) => {
+        paste! {
+            impl<$([<T $idx>],)+> Components for AutoTuple<($([<T $idx>],)+)>
+            where
+                ($([<T $idx>],)+): Clone + PartialEq,
+                $([<T $idx>]: Components,)+
+            {
+                fn components(&self) -> Vec<Complex<f64>> {
+                    let mut components = Vec::new();
+                    $(components.extend(self.0.$idx.components());)+
+                    components
+                }
+            }
+        }
+    }
+}
+
+// implement Components for tuples of length 1-16
+autotuple_components!(0);
+autotuple_components!(0, 1);
+autotuple_components!(0, 1, 2);
+autotuple_components!(0, 1, 2, 3);
+autotuple_components!(0, 1, 2, 3, 4);
+autotuple_components!(0, 1, 2, 3, 4, 5);
+autotuple_components!(0, 1, 2, 3, 4, 5, 6);
+autotuple_components!(0, 1, 2, 3, 4, 5, 6, 7);
+autotuple_components!(0, 1, 2, 3, 4, 5, 6, 7, 8);
+autotuple_components!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9);
+autotuple_components!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10);
+autotuple_components!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11);
+autotuple_components!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
+autotuple_components!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13);
+autotuple_components!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14);
+autotuple_components!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+
 // macro to implement Default for tuples of length 1-16 whose elements implement Default
 macro_rules! autotuple_default {
     ($($idx:literal),+) => {
@@ -638,7 +942,107 @@ size_1_autotuple_forward_mul!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13);
 size_1_autotuple_forward_mul!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14);
 size_1_autotuple_forward_mul!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
 
+// macro for scaling the elements of an AutoTuple by a primitive scalar,
+// e.g. the rows of the Hessian of a function of an AutoTuple
+macro_rules! autotuple_scalar_op {
+    ($trt:ident, $mth:ident, $type:ty, $($idx:literal),+) =>
+    {
+        paste! {
+            impl<$([<T $idx>],)+ $([<V $idx>],)+> $trt<$type> for AutoTuple<($([<T $idx>],)+)>
+            where
+                $([<T $idx>]: $trt<$type, Output=[<V $idx>]>,)+
+                ($([<T $idx>],)+): Clone + PartialEq,
+                ($([<V $idx>],)+): Clone + PartialEq,
+            {
+                type Output = AutoTuple<($([<V $idx>],)+)>;
+
+                fn $mth(self, rhs: $type) -> Self::Output {
+                    AutoTuple::new(($( self.0.$idx.$mth(rhs), )+))
+                }
+            }
+        }
+    }
+}
+
+// macro for implementing Mul and Div by each primitive scalar type
+macro_rules! autotuple_scalar_ops {
+    ($($idx:literal),+) =>
+    {
+        autotuple_scalar_op!(Mul, mul, f32, $($idx),+);
+        autotuple_scalar_op!(Mul, mul, f64, $($idx),+);
+        autotuple_scalar_op!(Mul, mul, Complex<f32>, $($idx),+);
+        autotuple_scalar_op!(Mul, mul, Complex<f64>, $($idx),+);
+        autotuple_scalar_op!(Div, div, f32, $($idx),+);
+        autotuple_scalar_op!(Div, div, f64, $($idx),+);
+        autotuple_scalar_op!(Div, div, Complex<f32>, $($idx),+);
+        autotuple_scalar_op!(Div, div, Complex<f64>, $($idx),+);
+    }
+}
+
+// implement scalar ops for tuples of length 1-16
+autotuple_scalar_ops!(0);
+autotuple_scalar_ops!(0, 1);
+autotuple_scalar_ops!(0, 1, 2);
+autotuple_scalar_ops!(0, 1, 2, 3);
+autotuple_scalar_ops!(0, 1, 2, 3, 4);
+autotuple_scalar_ops!(0, 1, 2, 3, 4, 5);
+autotuple_scalar_ops!(0, 1, 2, 3, 4, 5, 6);
+autotuple_scalar_ops!(0, 1, 2, 3, 4, 5, 6, 7);
+autotuple_scalar_ops!(0, 1, 2, 3, 4, 5, 6, 7, 8);
+autotuple_scalar_ops!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9);
+autotuple_scalar_ops!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10);
+autotuple_scalar_ops!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11);
+autotuple_scalar_ops!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
+autotuple_scalar_ops!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13);
+autotuple_scalar_ops!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14);
+autotuple_scalar_ops!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+
+// macro to implement Outer for tuples of length 1-16
+// the outer product df ⊗ dg of an AutoTuple df is the AutoTuple of dg scaled
+// by each element of df, so the Hessian of a function of an AutoTuple is the
+// AutoTuple of its rows
+macro_rules! autotuple_outer {
+    ($($idx:literal),+) => {
+        paste! {
+            impl<$([<T $idx>],)+ $([<R $idx>],)+ Other> Outer<Other> for AutoTuple<($([<T $idx>],)+)>
+            where
+                ($([<T $idx>],)+): Clone + PartialEq,
+                ($([<R $idx>],)+): Clone + PartialEq,
+                $(
+                    [<T $idx>]: Outer<Other, Output = [<R $idx>]>,
+                )+
+            {
+                type Output = AutoTuple<($([<R $idx>],)+)>;
+                fn outer(&self, other: &Other) -> Self::Output {
+                    AutoTuple::new(($(
+                                (&self.0).$idx.outer(other),
+                    )+))
+                }
+            }
+        }
+    }
+}
+
+// implement Outer for tuples of length 1-16
+autotuple_outer!(0);
+autotuple_outer!(0, 1);
+autotuple_outer!(0, 1, 2);
+autotuple_outer!(0, 1, 2, 3);
+autotuple_outer!(0, 1, 2, 3, 4);
+autotuple_outer!(0, 1, 2, 3, 4, 5);
+autotuple_outer!(0, 1, 2, 3, 4, 5, 6);
+autotuple_outer!(0, 1, 2, 3, 4, 5, 6, 7);
+autotuple_outer!(0, 1, 2, 3, 4, 5, 6, 7, 8);
+autotuple_outer!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9);
+autotuple_outer!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10);
+autotuple_outer!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11);
+autotuple_outer!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
+autotuple_outer!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13);
+autotuple_outer!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14);
+autotuple_outer!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+
 #[test]
+#[allow(clippy::explicit_auto_deref)]
 fn test_autotuple() {
     let a = AutoTuple::new((1u32, 1.0_f64));
     let b_tup = (2u32, -1.0_f64);
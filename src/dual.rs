@@ -0,0 +1,595 @@
+use crate::gradienttype::GradientType;
+use crate::traits::{
+    Abs, Conjugate, GradientIdentity, GradientZero, InstOne, InstZero, PossiblyComplex, Signum,
+};
+use num::traits::{Float, Num, NumCast, One, Pow, ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::num::FpCategory;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// The tangent part of a `Dual` number, either a single tangent `T` or `N` tangents `[T; N]`
+/// which are all propagated by the same chain rule
+pub trait Tangent<T>: Copy {
+    /// All tangents zero
+    fn zeros() -> Self;
+
+    fn all_zero(&self) -> bool;
+
+    /// Every tangent times s
+    fn scale(self, s: T) -> Self;
+
+    /// a * self + b * other for every tangent
+    fn lin_comb(self, a: T, other: Self, b: T) -> Self;
+}
+
+impl<T: Float> Tangent<T> for T {
+    fn zeros() -> Self {
+        T::zero()
+    }
+
+    fn all_zero(&self) -> bool {
+        Zero::is_zero(self)
+    }
+
+    fn scale(self, s: T) -> Self {
+        self * s
+    }
+
+    fn lin_comb(self, a: T, other: Self, b: T) -> Self {
+        a * self + b * other
+    }
+}
+
+impl<T: Float, const N: usize> Tangent<T> for [T; N] {
+    fn zeros() -> Self {
+        [T::zero(); N]
+    }
+
+    fn all_zero(&self) -> bool {
+        self.iter().all(|t| Zero::is_zero(t))
+    }
+
+    fn scale(self, s: T) -> Self {
+        self.map(|t| t * s)
+    }
+
+    fn lin_comb(self, a: T, other: Self, b: T) -> Self {
+        std::array::from_fn(|i| a * self[i] + b * other[i])
+    }
+}
+
+/// A dual number value + tangent ε with ε² = 0, so that evaluating any function written
+/// generically over `num::Float` at `Dual::variable(x)` gives f(x) + f'(x) ε.
+///
+/// Comparisons only look at the value, so that branches in generic code take the same path as
+/// they would for the value alone, and the derivative is that of the branch taken.
+#[derive(Debug, Clone, Copy)]
+pub struct Dual<T, D = T> {
+    pub value: T,
+    pub tangent: D,
+}
+
+/// A dual number with N tangents, which gives the gradient of a function of N variables in a
+/// single evaluation when seeded with `DualN::variables`
+pub type DualN<T, const N: usize> = Dual<T, [T; N]>;
+
+impl<T, D> Dual<T, D> {
+    pub fn new(value: T, tangent: D) -> Self {
+        Dual { value, tangent }
+    }
+}
+
+impl<T: Float, D: Tangent<T>> Dual<T, D> {
+    /// A constant, with all tangents zero
+    pub fn constant(value: T) -> Self {
+        Dual::new(value, D::zeros())
+    }
+
+    // f(self) given f and df/dself at the value of self
+    fn chain(self, f: T, df: T) -> Self {
+        Dual::new(f, self.tangent.scale(df))
+    }
+
+    // f(self, other) given f, df/dself and df/dother at the values of self and other
+    fn chain2(self, other: Self, f: T, dfa: T, dfb: T) -> Self {
+        Dual::new(f, self.tangent.lin_comb(dfa, other.tangent, dfb))
+    }
+}
+
+impl<T: Float> Dual<T> {
+    /// The variable wrt which to differentiate, with tangent 1
+    pub fn variable(value: T) -> Self {
+        Dual::new(value, T::one())
+    }
+}
+
+impl<T: Float, const N: usize> DualN<T, N> {
+    /// N variables, the ith of which has a tangent of 1 in its ith component and 0 elsewhere
+    pub fn variables(values: [T; N]) -> [Self; N] {
+        std::array::from_fn(|i| {
+            let mut tangent = [T::zero(); N];
+            tangent[i] = T::one();
+            Dual::new(values[i], tangent)
+        })
+    }
+}
+
+impl<T: PartialEq, D> PartialEq for Dual<T, D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: PartialOrd, D> PartialOrd for Dual<T, D> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+// implements a binary operation between dual numbers, references to them, and constants, given
+// the value and partial derivatives of the operation in terms of the values a and b
+macro_rules! impl_dual_binop {
+    ($trait:ident, $method:ident, |$a:ident, $b:ident| ($f:expr, $dfa:expr, $dfb:expr)) => {
+        impl<T: Float, D: Tangent<T>> $trait<Dual<T, D>> for Dual<T, D> {
+            type Output = Dual<T, D>;
+            fn $method(self, rhs: Dual<T, D>) -> Self::Output {
+                let ($a, $b) = (self.value, rhs.value);
+                self.chain2(rhs, $f, $dfa, $dfb)
+            }
+        }
+
+        impl<'a, T: Float, D: Tangent<T>> $trait<&'a Dual<T, D>> for Dual<T, D> {
+            type Output = Dual<T, D>;
+            fn $method(self, rhs: &'a Dual<T, D>) -> Self::Output {
+                self.$method(*rhs)
+            }
+        }
+
+        impl<'a, T: Float, D: Tangent<T>> $trait<Dual<T, D>> for &'a Dual<T, D> {
+            type Output = Dual<T, D>;
+            fn $method(self, rhs: Dual<T, D>) -> Self::Output {
+                (*self).$method(rhs)
+            }
+        }
+
+        impl<'a, 'b, T: Float, D: Tangent<T>> $trait<&'b Dual<T, D>> for &'a Dual<T, D> {
+            type Output = Dual<T, D>;
+            fn $method(self, rhs: &'b Dual<T, D>) -> Self::Output {
+                (*self).$method(*rhs)
+            }
+        }
+
+        impl<T: Float, D: Tangent<T>> $trait<T> for Dual<T, D> {
+            type Output = Dual<T, D>;
+            fn $method(self, rhs: T) -> Self::Output {
+                self.$method(Dual::constant(rhs))
+            }
+        }
+
+        impl<'a, T: Float, D: Tangent<T>> $trait<&'a T> for Dual<T, D> {
+            type Output = Dual<T, D>;
+            fn $method(self, rhs: &'a T) -> Self::Output {
+                self.$method(Dual::constant(*rhs))
+            }
+        }
+    };
+}
+
+impl_dual_binop!(Add, add, |a, b| (a + b, T::one(), T::one()));
+impl_dual_binop!(Sub, sub, |a, b| (a - b, T::one(), -T::one()));
+impl_dual_binop!(Mul, mul, |a, b| (a * b, b, a));
+impl_dual_binop!(Div, div, |a, b| (a / b, b.recip(), -a / (b * b)));
+impl_dual_binop!(Rem, rem, |a, b| (a % b, T::one(), -(a / b).trunc()));
+
+impl<T: Float, D: Tangent<T>> Neg for Dual<T, D> {
+    type Output = Dual<T, D>;
+    fn neg(self) -> Self::Output {
+        self.chain(-self.value, -T::one())
+    }
+}
+
+impl<T: Float, D: Tangent<T>> Pow<Dual<T, D>> for Dual<T, D> {
+    type Output = Dual<T, D>;
+    fn pow(self, rhs: Dual<T, D>) -> Self::Output {
+        self.powf(rhs)
+    }
+}
+
+impl<'a, T: Float, D: Tangent<T>> Pow<&'a Dual<T, D>> for Dual<T, D> {
+    type Output = Dual<T, D>;
+    fn pow(self, rhs: &'a Dual<T, D>) -> Self::Output {
+        self.powf(*rhs)
+    }
+}
+
+impl<T: Float, D: Tangent<T>> Pow<T> for Dual<T, D> {
+    type Output = Dual<T, D>;
+    fn pow(self, rhs: T) -> Self::Output {
+        self.powf(Dual::constant(rhs))
+    }
+}
+
+impl<'a, T: Float, D: Tangent<T>> Pow<&'a T> for Dual<T, D> {
+    type Output = Dual<T, D>;
+    fn pow(self, rhs: &'a T) -> Self::Output {
+        self.powf(Dual::constant(*rhs))
+    }
+}
+
+impl<T: Float, D: Tangent<T>> Zero for Dual<T, D> {
+    fn zero() -> Self {
+        Dual::constant(T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value.is_zero() && self.tangent.all_zero()
+    }
+}
+
+impl<T: Float, D: Tangent<T>> One for Dual<T, D> {
+    fn one() -> Self {
+        Dual::constant(T::one())
+    }
+}
+
+impl<T: Float, D: Tangent<T>> Num for Dual<T, D> {
+    type FromStrRadixErr = T::FromStrRadixErr;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        T::from_str_radix(str, radix).map(Dual::constant)
+    }
+}
+
+impl<T: Float, D> ToPrimitive for Dual<T, D> {
+    fn to_i64(&self) -> Option<i64> {
+        self.value.to_i64()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.value.to_u64()
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        self.value.to_f64()
+    }
+}
+
+impl<T: Float, D: Tangent<T>> NumCast for Dual<T, D> {
+    fn from<N: ToPrimitive>(n: N) -> Option<Self> {
+        T::from(n).map(Dual::constant)
+    }
+}
+
+impl<T: Float, D: Tangent<T>> Float for Dual<T, D> {
+    fn nan() -> Self {
+        Dual::constant(T::nan())
+    }
+
+    fn infinity() -> Self {
+        Dual::constant(T::infinity())
+    }
+
+    fn neg_infinity() -> Self {
+        Dual::constant(T::neg_infinity())
+    }
+
+    fn neg_zero() -> Self {
+        Dual::constant(T::neg_zero())
+    }
+
+    fn min_value() -> Self {
+        Dual::constant(T::min_value())
+    }
+
+    fn min_positive_value() -> Self {
+        Dual::constant(T::min_positive_value())
+    }
+
+    fn epsilon() -> Self {
+        Dual::constant(T::epsilon())
+    }
+
+    fn max_value() -> Self {
+        Dual::constant(T::max_value())
+    }
+
+    fn is_nan(self) -> bool {
+        self.value.is_nan()
+    }
+
+    fn is_infinite(self) -> bool {
+        self.value.is_infinite()
+    }
+
+    fn is_finite(self) -> bool {
+        self.value.is_finite()
+    }
+
+    fn is_normal(self) -> bool {
+        self.value.is_normal()
+    }
+
+    fn classify(self) -> FpCategory {
+        self.value.classify()
+    }
+
+    fn floor(self) -> Self {
+        self.chain(self.value.floor(), T::zero())
+    }
+
+    fn ceil(self) -> Self {
+        self.chain(self.value.ceil(), T::zero())
+    }
+
+    fn round(self) -> Self {
+        self.chain(self.value.round(), T::zero())
+    }
+
+    fn trunc(self) -> Self {
+        self.chain(self.value.trunc(), T::zero())
+    }
+
+    fn fract(self) -> Self {
+        self.chain(self.value.fract(), T::one())
+    }
+
+    fn abs(self) -> Self {
+        self.chain(self.value.abs(), self.value.signum())
+    }
+
+    fn signum(self) -> Self {
+        self.chain(self.value.signum(), T::zero())
+    }
+
+    fn is_sign_positive(self) -> bool {
+        self.value.is_sign_positive()
+    }
+
+    fn is_sign_negative(self) -> bool {
+        self.value.is_sign_negative()
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        self * a + b
+    }
+
+    fn recip(self) -> Self {
+        let r = self.value.recip();
+        self.chain(r, -r * r)
+    }
+
+    fn powi(self, n: i32) -> Self {
+        let dfdx = match n {
+            0 => T::zero(),
+            // n is exactly representable in any float type
+            n => T::from(n).unwrap() * self.value.powi(n - 1),
+        };
+        self.chain(self.value.powi(n), dfdx)
+    }
+
+    fn powf(self, n: Self) -> Self {
+        let f = self.value.powf(n.value);
+        let dfdx = if n.value.is_zero() {
+            T::zero()
+        } else {
+            n.value * self.value.powf(n.value - T::one())
+        };
+
+        // the derivative wrt the exponent involves ln(x), so it is only included when the
+        // exponent is not constant, which keeps x^p of negative x finite
+        if n.tangent.all_zero() {
+            self.chain(f, dfdx)
+        } else {
+            self.chain2(n, f, dfdx, f * self.value.ln())
+        }
+    }
+
+    fn sqrt(self) -> Self {
+        let s = self.value.sqrt();
+        self.chain(s, (s + s).recip())
+    }
+
+    fn exp(self) -> Self {
+        let e = self.value.exp();
+        self.chain(e, e)
+    }
+
+    fn exp2(self) -> Self {
+        let e = self.value.exp2();
+        self.chain(e, e * T::from(2).unwrap().ln())
+    }
+
+    fn ln(self) -> Self {
+        self.chain(self.value.ln(), self.value.recip())
+    }
+
+    fn log(self, base: Self) -> Self {
+        self.ln() / base.ln()
+    }
+
+    fn log2(self) -> Self {
+        let ln2 = T::from(2).unwrap().ln();
+        self.chain(self.value.log2(), (self.value * ln2).recip())
+    }
+
+    fn log10(self) -> Self {
+        let ln10 = T::from(10).unwrap().ln();
+        self.chain(self.value.log10(), (self.value * ln10).recip())
+    }
+
+    fn max(self, other: Self) -> Self {
+        if other.value > self.value || self.value.is_nan() {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn min(self, other: Self) -> Self {
+        if other.value < self.value || self.value.is_nan() {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn abs_sub(self, other: Self) -> Self {
+        if self.value > other.value {
+            self - other
+        } else {
+            Dual::constant(T::zero())
+        }
+    }
+
+    fn cbrt(self) -> Self {
+        let c = self.value.cbrt();
+        self.chain(c, (T::from(3).unwrap() * c * c).recip())
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        let h = self.value.hypot(other.value);
+        self.chain2(other, h, self.value / h, other.value / h)
+    }
+
+    fn sin(self) -> Self {
+        self.chain(self.value.sin(), self.value.cos())
+    }
+
+    fn cos(self) -> Self {
+        self.chain(self.value.cos(), -self.value.sin())
+    }
+
+    fn tan(self) -> Self {
+        let t = self.value.tan();
+        self.chain(t, T::one() + t * t)
+    }
+
+    fn asin(self) -> Self {
+        let dfdx = (T::one() - self.value * self.value).sqrt().recip();
+        self.chain(self.value.asin(), dfdx)
+    }
+
+    fn acos(self) -> Self {
+        let dfdx = -(T::one() - self.value * self.value).sqrt().recip();
+        self.chain(self.value.acos(), dfdx)
+    }
+
+    fn atan(self) -> Self {
+        let dfdx = (T::one() + self.value * self.value).recip();
+        self.chain(self.value.atan(), dfdx)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        // d(atan2(y, x)) = (x dy - y dx) / (x^2 + y^2)
+        let (y, x) = (self.value, other.value);
+        let r2 = x * x + y * y;
+        self.chain2(other, y.atan2(x), x / r2, -y / r2)
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        (self.sin(), self.cos())
+    }
+
+    fn exp_m1(self) -> Self {
+        self.chain(self.value.exp_m1(), self.value.exp())
+    }
+
+    fn ln_1p(self) -> Self {
+        self.chain(self.value.ln_1p(), (T::one() + self.value).recip())
+    }
+
+    fn sinh(self) -> Self {
+        self.chain(self.value.sinh(), self.value.cosh())
+    }
+
+    fn cosh(self) -> Self {
+        self.chain(self.value.cosh(), self.value.sinh())
+    }
+
+    fn tanh(self) -> Self {
+        let t = self.value.tanh();
+        self.chain(t, T::one() - t * t)
+    }
+
+    fn asinh(self) -> Self {
+        let dfdx = (self.value * self.value + T::one()).sqrt().recip();
+        self.chain(self.value.asinh(), dfdx)
+    }
+
+    fn acosh(self) -> Self {
+        let dfdx = ((self.value - T::one()).sqrt() * (self.value + T::one()).sqrt()).recip();
+        self.chain(self.value.acosh(), dfdx)
+    }
+
+    fn atanh(self) -> Self {
+        let dfdx = (T::one() - self.value * self.value).recip();
+        self.chain(self.value.atanh(), dfdx)
+    }
+
+    fn integer_decode(self) -> (u64, i16, i8) {
+        self.value.integer_decode()
+    }
+}
+
+impl<T: Float, D: Tangent<T>> InstZero for Dual<T, D> {
+    fn zero(&self) -> Self {
+        <Self as Zero>::zero()
+    }
+
+    fn is_zero(&self) -> bool {
+        <Self as Zero>::is_zero(self)
+    }
+}
+
+impl<T: Float, D: Tangent<T>> InstOne for Dual<T, D> {
+    fn one(&self) -> Self {
+        <Self as One>::one()
+    }
+}
+
+impl<T, D> PossiblyComplex for Dual<T, D> {
+    fn is_always_real() -> bool {
+        true
+    }
+}
+
+impl<T: Float, D: Tangent<T>> Conjugate for Dual<T, D> {
+    type Output = Self;
+    fn conj(&self) -> Self::Output {
+        *self
+    }
+}
+
+impl<T: Float, D: Tangent<T>> Abs for Dual<T, D> {
+    type Output = Self;
+    fn abs(self) -> Self::Output {
+        Float::abs(self)
+    }
+}
+
+impl<T: Float, D: Tangent<T>> Signum for Dual<T, D> {
+    type Output = Self;
+    fn signum(self) -> Self::Output {
+        Float::signum(self)
+    }
+}
+
+impl<T, D, U> GradientType<U> for Dual<T, D> {
+    type GradientType = U;
+}
+
+impl<T, D, G> GradientIdentity for Dual<T, D>
+where
+    Self: GradientType<Self, GradientType = G>,
+    G: One,
+{
+    fn grad_identity(&self) -> G {
+        G::one()
+    }
+}
+
+impl<T, D, O: InstZero> GradientZero<O> for Dual<T, D> {
+    fn grad_zero(&self, output: &O) -> O {
+        output.zero()
+    }
+}
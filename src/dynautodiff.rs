@@ -0,0 +1,294 @@
+use crate::autodiff::AutoDiff;
+use crate::autodiffable::{AutoDiffable, Diffable, ForwardDiffable};
+use crate::gradienttype::GradientType;
+use crate::graphviz::{DotAnnotate, ToDot};
+use crate::simplify::{DynExpr, DynExprScalar, ToDynExpr};
+use crate::tryautodiff::{CheckValue, TryAutoDiffable};
+use std::marker::PhantomData;
+
+use crate as autodiff;
+use autodiff_derive::*;
+
+/// Object safe union of `AutoDiffable` and `ForwardDiffable` with fixed input and output types,
+/// implemented for every clonable function, which is what `DynDiff` boxes.
+trait DynDiffable<StaticArgs, Input, Output>
+where
+    Input: GradientType<Output>,
+{
+    fn eval(&self, x: &Input, static_args: &StaticArgs) -> Output;
+
+    fn eval_grad(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> (Output, <Input as GradientType<Output>>::GradientType);
+
+    fn grad(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> <Input as GradientType<Output>>::GradientType;
+
+    fn eval_conj_grad(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> (Output, <Input as GradientType<Output>>::GradientType);
+
+    fn conj_grad(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> <Input as GradientType<Output>>::GradientType;
+
+    fn eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> (
+        Output,
+        <Input as GradientType<Output>>::GradientType,
+        <Input as GradientType<Output>>::GradientType,
+    );
+
+    fn eval_forward(&self, x: &Input, static_args: &StaticArgs) -> Output;
+
+    fn eval_forward_grad(
+        &self,
+        x: &Input,
+        dx: &Input,
+        static_args: &StaticArgs,
+    ) -> (Output, Output);
+
+    fn forward_grad(&self, x: &Input, dx: &Input, static_args: &StaticArgs) -> Output;
+
+    fn eval_forward_conj_grad(
+        &self,
+        x: &Input,
+        dx: &Input,
+        static_args: &StaticArgs,
+    ) -> (Output, Output);
+
+    fn forward_conj_grad(&self, x: &Input, dx: &Input, static_args: &StaticArgs) -> Output;
+
+    fn clone_box(&self) -> Box<dyn DynDiffable<StaticArgs, Input, Output>>;
+}
+
+impl<StaticArgs, Input, Output, Grad, T> DynDiffable<StaticArgs, Input, Output> for T
+where
+    T: AutoDiffable<StaticArgs, Input = Input, Output = Output>
+        + ForwardDiffable<StaticArgs, Input = Input, Output = Output>
+        + Clone
+        + 'static,
+    Input: GradientType<Output, GradientType = Grad>,
+{
+    fn eval(&self, x: &Input, static_args: &StaticArgs) -> Output {
+        AutoDiffable::eval(self, x, static_args)
+    }
+
+    fn eval_grad(&self, x: &Input, static_args: &StaticArgs) -> (Output, Grad) {
+        AutoDiffable::eval_grad(self, x, static_args)
+    }
+
+    fn grad(&self, x: &Input, static_args: &StaticArgs) -> Grad {
+        AutoDiffable::grad(self, x, static_args)
+    }
+
+    fn eval_conj_grad(&self, x: &Input, static_args: &StaticArgs) -> (Output, Grad) {
+        AutoDiffable::eval_conj_grad(self, x, static_args)
+    }
+
+    fn conj_grad(&self, x: &Input, static_args: &StaticArgs) -> Grad {
+        AutoDiffable::conj_grad(self, x, static_args)
+    }
+
+    fn eval_all(&self, x: &Input, static_args: &StaticArgs) -> (Output, Grad, Grad) {
+        AutoDiffable::eval_all(self, x, static_args)
+    }
+
+    fn eval_forward(&self, x: &Input, static_args: &StaticArgs) -> Output {
+        ForwardDiffable::eval_forward(self, x, static_args)
+    }
+
+    fn eval_forward_grad(
+        &self,
+        x: &Input,
+        dx: &Input,
+        static_args: &StaticArgs,
+    ) -> (Output, Output) {
+        ForwardDiffable::eval_forward_grad(self, x, dx, static_args)
+    }
+
+    fn forward_grad(&self, x: &Input, dx: &Input, static_args: &StaticArgs) -> Output {
+        ForwardDiffable::forward_grad(self, x, dx, static_args)
+    }
+
+    fn eval_forward_conj_grad(
+        &self,
+        x: &Input,
+        dx: &Input,
+        static_args: &StaticArgs,
+    ) -> (Output, Output) {
+        ForwardDiffable::eval_forward_conj_grad(self, x, dx, static_args)
+    }
+
+    fn forward_conj_grad(&self, x: &Input, dx: &Input, static_args: &StaticArgs) -> Output {
+        ForwardDiffable::forward_conj_grad(self, x, dx, static_args)
+    }
+
+    fn clone_box(&self) -> Box<dyn DynDiffable<StaticArgs, Input, Output>> {
+        Box::new(self.clone())
+    }
+}
+
+/// A function whose concrete type is erased behind a box, so that functions built from different
+/// expressions (e.g. in match arms, or read from a config at runtime) have the same type
+/// `DynDiff<StaticArgs, Input, Output>` and can be stored together.
+///
+/// Every call goes through dynamic dispatch, so nested expressions are best boxed once at the
+/// top rather than at every node.
+#[derive(FuncCompose)]
+pub struct DynDiff<StaticArgs, Input, Output>(
+    Box<dyn DynDiffable<StaticArgs, Input, Output>>,
+    PhantomData<(StaticArgs, Input, Output)>,
+)
+where
+    Input: GradientType<Output>;
+
+/// A boxed `AutoDiff`, which supports all the operators of `AutoDiff`
+pub type DynAutoDiff<StaticArgs, Input, Output> =
+    AutoDiff<StaticArgs, DynDiff<StaticArgs, Input, Output>>;
+
+impl<StaticArgs, Input, Output> DynDiff<StaticArgs, Input, Output>
+where
+    Input: GradientType<Output>,
+{
+    pub fn new<T>(f: T) -> Self
+    where
+        T: AutoDiffable<StaticArgs, Input = Input, Output = Output>
+            + ForwardDiffable<StaticArgs, Input = Input, Output = Output>
+            + Clone
+            + 'static,
+    {
+        DynDiff(Box::new(f), PhantomData)
+    }
+}
+
+impl<StaticArgs, Input, Output> Clone for DynDiff<StaticArgs, Input, Output>
+where
+    Input: GradientType<Output>,
+{
+    fn clone(&self) -> Self {
+        DynDiff(self.0.clone_box(), PhantomData)
+    }
+}
+
+impl<StaticArgs, Input, Output> std::fmt::Debug for DynDiff<StaticArgs, Input, Output>
+where
+    Input: GradientType<Output>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DynDiff<{}, {}>",
+            std::any::type_name::<Input>(),
+            std::any::type_name::<Output>()
+        )
+    }
+}
+
+impl<StaticArgs, Input, Output> Diffable<StaticArgs> for DynDiff<StaticArgs, Input, Output>
+where
+    Input: GradientType<Output>,
+{
+    type Input = Input;
+    type Output = Output;
+}
+
+impl<StaticArgs, Input, Output> ToDot<StaticArgs> for DynDiff<StaticArgs, Input, Output>
+where
+    Input: GradientType<Output>,
+    Self: DotAnnotate<StaticArgs>,
+{
+}
+
+impl<StaticArgs: 'static, T: DynExprScalar> ToDynExpr<StaticArgs, T> for DynDiff<StaticArgs, T, T> {
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        DynExpr::opaque(self.clone())
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad> AutoDiffable<StaticArgs>
+    for DynDiff<StaticArgs, Input, Output>
+where
+    Input: GradientType<Output, GradientType = Grad>,
+{
+    fn eval(&self, x: &Input, static_args: &StaticArgs) -> Output {
+        self.0.eval(x, static_args)
+    }
+
+    fn eval_grad(&self, x: &Input, static_args: &StaticArgs) -> (Output, Grad) {
+        self.0.eval_grad(x, static_args)
+    }
+
+    fn grad(&self, x: &Input, static_args: &StaticArgs) -> Grad {
+        self.0.grad(x, static_args)
+    }
+
+    fn eval_conj_grad(&self, x: &Input, static_args: &StaticArgs) -> (Output, Grad) {
+        self.0.eval_conj_grad(x, static_args)
+    }
+
+    fn conj_grad(&self, x: &Input, static_args: &StaticArgs) -> Grad {
+        self.0.conj_grad(x, static_args)
+    }
+
+    fn eval_all(&self, x: &Input, static_args: &StaticArgs) -> (Output, Grad, Grad) {
+        self.0.eval_all(x, static_args)
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad> TryAutoDiffable<StaticArgs>
+    for DynDiff<StaticArgs, Input, Output>
+where
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the value and gradients can be checked
+    Output: CheckValue,
+    Grad: CheckValue,
+{
+}
+
+impl<StaticArgs, Input, Output> ForwardDiffable<StaticArgs> for DynDiff<StaticArgs, Input, Output>
+where
+    Input: GradientType<Output>,
+{
+    fn eval_forward(&self, x: &Input, static_args: &StaticArgs) -> Output {
+        self.0.eval_forward(x, static_args)
+    }
+
+    fn eval_forward_grad(
+        &self,
+        x: &Input,
+        dx: &Input,
+        static_args: &StaticArgs,
+    ) -> (Output, Output) {
+        self.0.eval_forward_grad(x, dx, static_args)
+    }
+
+    fn forward_grad(&self, x: &Input, dx: &Input, static_args: &StaticArgs) -> Output {
+        self.0.forward_grad(x, dx, static_args)
+    }
+
+    fn eval_forward_conj_grad(
+        &self,
+        x: &Input,
+        dx: &Input,
+        static_args: &StaticArgs,
+    ) -> (Output, Output) {
+        self.0.eval_forward_conj_grad(x, dx, static_args)
+    }
+
+    fn forward_conj_grad(&self, x: &Input, dx: &Input, static_args: &StaticArgs) -> Output {
+        self.0.forward_conj_grad(x, dx, static_args)
+    }
+}
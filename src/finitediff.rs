@@ -0,0 +1,531 @@
+use crate::autodiffable::{AutoDiffable, ForwardDiffable};
+use crate::diffable::Diffable;
+use crate::forward::ForwardMul;
+use crate::gradienttype::GradientType;
+use crate::graphviz::{DotAnnotate, ToDot};
+use crate::simplify::{DynExpr, DynExprScalar, ToDynExpr};
+use crate::traits::InstZero;
+use crate::tryautodiff::{CheckValue, TryAutoDiffable};
+use num::complex::Complex;
+use num::traits::Float;
+use std::marker::PhantomData;
+use std::ops::{Add, Mul, Sub};
+
+use crate as autodiff;
+use autodiff_derive::*;
+
+/// Central differences (g(h) - g(-h)) / 2h, with an error of O(h^2), or with Richardson
+/// extrapolation (4 D(h/2) - D(h)) / 3 of the central difference D, with an error of O(h^4)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CentralDifference<R> {
+    /// The step h, or None for the default of eps^(1/3), or eps^(1/5) with Richardson
+    /// extrapolation, which balance the truncation and the rounding errors for inputs of order 1
+    /// and are scaled by |x| for larger inputs
+    pub step: Option<R>,
+    pub richardson: bool,
+}
+
+impl<R: Float> CentralDifference<R> {
+    /// The step h along a real direction whose component of the input is x
+    pub fn step(&self, x: R) -> R {
+        self.step.unwrap_or_else(|| {
+            let h = match self.richardson {
+                false => R::epsilon().cbrt(),
+                true => R::epsilon().powf(R::from(0.2).unwrap()),
+            };
+            // the rounding error of x + h is relative to x
+            h * x.abs().max(R::one())
+        })
+    }
+
+    /// The derivative of g at 0 for a function g(t) of one real variable, which moves a
+    /// component x of the input to x + t
+    pub fn derivative<O>(&self, x: R, g: impl Fn(R) -> O) -> O
+    where
+        O: Sub<O, Output = O> + Mul<R, Output = O>,
+    {
+        let h = self.step(x);
+        let two = R::one() + R::one();
+        let central = |h: R| g(h).sub(g(-h)).mul((two * h).recip());
+
+        if self.richardson {
+            let three = two + R::one();
+            let coarse = central(h);
+            let fine = central(h / two);
+            fine.mul((two + two) / three).sub(coarse.mul(three.recip()))
+        } else {
+            central(h)
+        }
+    }
+}
+
+/// Complex-step differentiation f'(x) = Im(f(x + ih)) / h of a real-analytic function of one real
+/// variable, evaluated at complex arguments. Since there is no subtraction, the step can be
+/// made tiny and the derivative is accurate to machine precision.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComplexStep<R> {
+    /// The step h, or None for the default of 1e-20
+    pub step: Option<R>,
+}
+
+impl<R: Float> ComplexStep<R> {
+    pub fn step(&self) -> R {
+        self.step.unwrap_or_else(|| R::from(1e-20).unwrap())
+    }
+}
+
+/// Inputs of a function whose gradient can be estimated by finite differences along each of
+/// their real directions, assembled into `<Self as GradientType<O>>::GradientType`.
+pub trait FiniteDiffInput<O>: GradientType<O> + Clone {
+    /// The real scalar type of the steps
+    type Real: Float;
+
+    /// Estimates the gradient and the gradient wrt the conjugate of f at self
+    fn finite_diff_grad(
+        &self,
+        f: impl Fn(&Self) -> O,
+        scheme: &CentralDifference<Self::Real>,
+    ) -> (Self::GradientType, Self::GradientType);
+
+    /// The unit steps along each real direction of self, in the order of its components, e.g.
+    /// 1 and i for complex numbers
+    fn basis(&self) -> Vec<Self>;
+}
+
+macro_rules! impl_finite_diff_input_real {
+    ($($t:ty),*) => ($(
+        impl<O> FiniteDiffInput<O> for $t
+        where
+            O: InstZero + Sub<O, Output = O> + Mul<$t, Output = O>,
+        {
+            type Real = $t;
+
+            fn finite_diff_grad(
+                &self,
+                f: impl Fn(&Self) -> O,
+                scheme: &CentralDifference<$t>,
+            ) -> (O, O) {
+                let grad = scheme.derivative(*self, |t| f(&(self + t)));
+                let conj_grad = grad.zero();
+                (grad, conj_grad)
+            }
+
+            fn basis(&self) -> Vec<$t> {
+                vec![1.0]
+            }
+        }
+    )*)
+}
+
+impl_finite_diff_input_real!(f32, f64);
+
+impl<T, O> FiniteDiffInput<O> for Complex<T>
+where
+    T: Float,
+    Complex<T>: GradientType<O, GradientType = O>,
+    O: Clone
+        + Add<O, Output = O>
+        + Sub<O, Output = O>
+        + Mul<T, Output = O>
+        + Mul<Complex<T>, Output = O>,
+{
+    type Real = T;
+
+    fn finite_diff_grad(&self, f: impl Fn(&Self) -> O, scheme: &CentralDifference<T>) -> (O, O) {
+        // df/dz = (df/dx - i df/dy) / 2 and df/dconj(z) = (df/dx + i df/dy) / 2
+        let dfdx = scheme.derivative(self.re, |t| f(&(self + Complex::new(t, T::zero()))));
+        let dfdy = scheme.derivative(self.im, |t| f(&(self + Complex::new(T::zero(), t))));
+
+        let half = T::from(0.5).unwrap();
+        let grad = dfdx
+            .clone()
+            .mul(Complex::new(half, T::zero()))
+            .add(dfdy.clone().mul(Complex::new(T::zero(), -half)));
+        let conj_grad = dfdx
+            .mul(Complex::new(half, T::zero()))
+            .add(dfdy.mul(Complex::new(T::zero(), half)));
+
+        (grad, conj_grad)
+    }
+
+    fn basis(&self) -> Vec<Self> {
+        vec![Complex::new(T::one(), T::zero()), Complex::i()]
+    }
+}
+
+/// A black-box function, given only by a closure for its value, whose gradient is estimated by
+/// finite differences, so that it can be used in expressions alongside exact derivatives.
+///
+/// `ADFiniteDiff::new` uses central differences along each real direction of any
+/// `FiniteDiffInput` (real and complex scalars, `AutoTuple`s and arrays), and
+/// `ADFiniteDiff::complex_step` uses complex-step differentiation for real-analytic functions of
+/// one real variable, whose closure takes and returns `Complex` values.
+#[derive(Clone, Copy, FuncCompose, SimpleForwardDiffable)]
+pub struct ADFiniteDiff<F, I, O, M>(pub F, pub M, pub PhantomData<(I, O)>);
+
+impl<F, I, O, M: std::fmt::Debug> std::fmt::Debug for ADFiniteDiff<F, I, O, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ADFiniteDiff").field(&self.1).finish()
+    }
+}
+
+impl<F, I, O> ADFiniteDiff<F, I, O, CentralDifference<I::Real>>
+where
+    I: FiniteDiffInput<O>,
+{
+    /// f(x, s) given as a closure, differentiated by central differences with the default step
+    pub fn new<S>(f: F) -> Self
+    where
+        F: Fn(&I, &S) -> O,
+    {
+        ADFiniteDiff(
+            f,
+            CentralDifference {
+                step: None,
+                richardson: false,
+            },
+            PhantomData,
+        )
+    }
+
+    pub fn with_step(mut self, step: I::Real) -> Self {
+        self.1.step = Some(step);
+        self
+    }
+
+    /// Use Richardson extrapolation, which needs twice as many evaluations
+    pub fn with_richardson(mut self) -> Self {
+        self.1.richardson = true;
+        self
+    }
+}
+
+impl<F, T: Float> ADFiniteDiff<F, T, T, ComplexStep<T>> {
+    /// f(z, s) of a real-analytic function given as a closure over complex values, differentiated
+    /// by complex-step differentiation
+    pub fn complex_step<S>(f: F) -> Self
+    where
+        F: Fn(&Complex<T>, &S) -> Complex<T>,
+    {
+        ADFiniteDiff(f, ComplexStep { step: None }, PhantomData)
+    }
+
+    pub fn with_step(mut self, step: T) -> Self {
+        self.1.step = Some(step);
+        self
+    }
+}
+
+impl<S, F, I, O, M> Diffable<S> for ADFiniteDiff<F, I, O, M> {
+    type Input = I;
+    type Output = O;
+}
+
+impl<S, F, I, O, M> ToDot<S> for ADFiniteDiff<F, I, O, M> where Self: DotAnnotate<S> {}
+
+impl<S, F, T, M> ToDynExpr<S, T> for ADFiniteDiff<F, T, T, M>
+where
+    T: DynExprScalar,
+    // ensure the function can be boxed as an opaque function of x, since it is not simplified
+    Self: AutoDiffable<S, Input = T, Output = T>
+        + ForwardDiffable<S, Input = T, Output = T>
+        + Clone
+        + 'static,
+{
+    fn to_dyn_expr(&self) -> DynExpr<S, T> {
+        DynExpr::opaque(self.clone())
+    }
+}
+
+impl<S, F, I, O, G, R> AutoDiffable<S> for ADFiniteDiff<F, I, O, CentralDifference<R>>
+where
+    F: Fn(&I, &S) -> O,
+    I: FiniteDiffInput<O, Real = R> + GradientType<O, GradientType = G>,
+    R: Float,
+{
+    fn eval(&self, x: &I, s: &S) -> O {
+        (self.0)(x, s)
+    }
+
+    fn eval_grad(&self, x: &I, s: &S) -> (O, G) {
+        ((self.0)(x, s), self.grad(x, s))
+    }
+
+    fn grad(&self, x: &I, s: &S) -> G {
+        x.finite_diff_grad(|y| (self.0)(y, s), &self.1).0
+    }
+
+    fn eval_conj_grad(&self, x: &I, s: &S) -> (O, G) {
+        ((self.0)(x, s), self.conj_grad(x, s))
+    }
+
+    fn conj_grad(&self, x: &I, s: &S) -> G {
+        x.finite_diff_grad(|y| (self.0)(y, s), &self.1).1
+    }
+}
+
+impl<S, F, T> AutoDiffable<S> for ADFiniteDiff<F, T, T, ComplexStep<T>>
+where
+    F: Fn(&Complex<T>, &S) -> Complex<T>,
+    T: Float + GradientType<T, GradientType = T>,
+{
+    fn eval(&self, x: &T, s: &S) -> T {
+        (self.0)(&Complex::new(*x, T::zero()), s).re
+    }
+
+    fn eval_grad(&self, x: &T, s: &S) -> (T, T) {
+        (self.eval(x, s), self.grad(x, s))
+    }
+
+    fn grad(&self, x: &T, s: &S) -> T {
+        let h = self.1.step();
+        (self.0)(&Complex::new(*x, h), s).im / h
+    }
+
+    fn eval_conj_grad(&self, x: &T, s: &S) -> (T, T) {
+        (self.eval(x, s), T::zero())
+    }
+
+    fn conj_grad(&self, _: &T, _: &S) -> T {
+        T::zero()
+    }
+}
+
+impl<S, F, I, O, G, M> TryAutoDiffable<S> for ADFiniteDiff<F, I, O, M>
+where
+    Self: AutoDiffable<S, Input = I, Output = O>,
+    I: GradientType<O, GradientType = G>,
+    // ensure the value and gradients can be checked
+    O: CheckValue,
+    G: CheckValue,
+{
+}
+
+/// Values that can be compared component by component, flattened in a fixed order into complex
+/// numbers: scalars give one component, `AutoTuple`s the components of their elements in turn,
+/// and arrays the components of their elements in logical (row major) order.
+pub trait Components {
+    fn components(&self) -> Vec<Complex<f64>>;
+}
+
+macro_rules! impl_components {
+    ($($t:ty),*) => ($(
+        impl Components for $t {
+            fn components(&self) -> Vec<Complex<f64>> {
+                vec![Complex::new(*self as f64, 0.0)]
+            }
+        }
+
+        impl Components for Complex<$t> {
+            fn components(&self) -> Vec<Complex<f64>> {
+                vec![Complex::new(self.re as f64, self.im as f64)]
+            }
+        }
+    )*)
+}
+
+impl_components!(f32, f64);
+
+/// Which derivative of a function a `GradMismatch` was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GradCheckKind {
+    /// `grad`
+    Grad,
+    /// `conj_grad`
+    ConjGrad,
+    /// `eval_forward_grad` along each direction of `FiniteDiffInput::basis` in turn, with the
+    /// components of every direction numbered consecutively
+    ForwardGrad,
+    /// `eval_forward_grad` plus `eval_forward_conj_grad`, which should be the differential
+    /// `grad * dz + conj_grad * conj(dz)` of the computed `grad` and `conj_grad`, for dz = 1
+    /// (index 0) and dz = i (index 1). This is a consistency check between forward and reverse
+    /// mode, not a check against finite differences
+    Differential,
+    /// The computed and the numerical `conj_grad` of a function claimed to be holomorphic, which
+    /// should both be zero
+    Holomorphic,
+}
+
+/// A component of a derivative whose value differs from the numerical estimate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradMismatch {
+    pub kind: GradCheckKind,
+    /// The index of the component, in the order given by `Components`
+    pub index: usize,
+    /// The value computed by the function, or NaN if it has fewer components than the estimate
+    pub computed: Complex<f64>,
+    /// The numerical estimate, or NaN if it has fewer components than the computed value
+    pub numerical: Complex<f64>,
+}
+
+/// The result of `check_grad`, listing every mismatched component
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GradCheck {
+    pub mismatches: Vec<GradMismatch>,
+}
+
+impl GradCheck {
+    pub fn is_ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    fn compare(
+        &mut self,
+        kind: GradCheckKind,
+        computed: Vec<Complex<f64>>,
+        numerical: Vec<Complex<f64>>,
+        tol: f64,
+    ) {
+        let nan = Complex::new(f64::NAN, f64::NAN);
+        for index in 0..computed.len().max(numerical.len()) {
+            let c = computed.get(index).copied().unwrap_or(nan);
+            let n = numerical.get(index).copied().unwrap_or(nan);
+            let error = (c - n).norm();
+            // NaN errors are mismatches too
+            if error.is_nan() || error > tol * n.norm().max(1.0) {
+                self.mismatches.push(GradMismatch {
+                    kind,
+                    index,
+                    computed: c,
+                    numerical: n,
+                });
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for GradCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_ok() {
+            return write!(f, "gradient check passed");
+        }
+        write!(
+            f,
+            "gradient check failed for {} components:",
+            self.mismatches.len()
+        )?;
+        for m in &self.mismatches {
+            write!(
+                f,
+                "\n  {:?}[{}]: computed {}, numerical {}",
+                m.kind, m.index, m.computed, m.numerical
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks `grad`, `conj_grad` and `eval_forward_grad` (along each real basis direction of the
+/// input) of f at x against central differences with Richardson extrapolation, and reports every
+/// component whose error exceeds `tol` relative to the numerical estimate, or absolute if the
+/// estimate is smaller than 1.
+pub fn check_grad<S, F, I, O, G>(f: &F, x: &I, static_args: &S, tol: f64) -> GradCheck
+where
+    F: AutoDiffable<S> + ForwardDiffable<S> + Diffable<S, Input = I, Output = O>,
+    // ensure the input can be perturbed along each of its directions
+    I: FiniteDiffInput<O> + GradientType<O, GradientType = G>,
+    // ensure the gradients can be compared and applied to dx
+    G: Components + ForwardMul<I, I, ResultGrad = O>,
+    O: Components,
+{
+    let scheme = CentralDifference {
+        step: None,
+        richardson: true,
+    };
+    let (grad, conj_grad) = x.finite_diff_grad(|y| f.eval(y, static_args), &scheme);
+
+    let mut check = GradCheck::default();
+    check.compare(
+        GradCheckKind::Grad,
+        f.grad(x, static_args).components(),
+        grad.components(),
+        tol,
+    );
+    check.compare(
+        GradCheckKind::ConjGrad,
+        f.conj_grad(x, static_args).components(),
+        conj_grad.components(),
+        tol,
+    );
+
+    // the directional derivatives of the numerical gradient, which is checked component by
+    // component above
+    let (forward, numerical): (Vec<_>, Vec<_>) = x
+        .basis()
+        .iter()
+        .map(|dx| {
+            (
+                f.eval_forward_grad(x, dx, static_args).1.components(),
+                grad.forward_mul(dx).components(),
+            )
+        })
+        .unzip();
+    check.compare(
+        GradCheckKind::ForwardGrad,
+        forward.concat(),
+        numerical.concat(),
+        tol,
+    );
+    check
+}
+
+/// Checks the Wirtinger derivatives of a function of one complex variable at z: `grad` and
+/// `conj_grad` against central differences along the real and the imaginary axes, forward mode
+/// against the differential `df = grad * dz + conj_grad * conj(dz)`, and, if the function is
+/// claimed to be holomorphic, that its conjugate gradient is zero.
+/// Errors are measured as in `check_grad`.
+pub fn check_wirtinger<S, F, T>(
+    f: &F,
+    z: &Complex<T>,
+    static_args: &S,
+    tol: f64,
+    holomorphic: bool,
+) -> GradCheck
+where
+    F: AutoDiffable<S> + ForwardDiffable<S> + Diffable<S, Input = Complex<T>, Output = Complex<T>>,
+    T: Float,
+    // ensure z can be perturbed along both axes
+    Complex<T>: FiniteDiffInput<Complex<T>, Real = T>
+        + GradientType<Complex<T>, GradientType = Complex<T>>
+        + ForwardMul<Complex<T>, Complex<T>, ResultGrad = Complex<T>>
+        + Components,
+{
+    let mut check = check_grad(f, z, static_args, tol);
+
+    let (grad, conj_grad) = (f.grad(z, static_args), f.conj_grad(z, static_args));
+    let (forward, differential): (Vec<_>, Vec<_>) =
+        [Complex::new(T::one(), T::zero()), Complex::i()]
+            .iter()
+            .map(|dz| {
+                let df =
+                    f.forward_grad(z, dz, static_args)
+                        .add(f.forward_conj_grad(z, dz, static_args));
+                let expected = grad.mul(dz).add(conj_grad.mul(dz.conj()));
+                (df.components()[0], expected.components()[0])
+            })
+            .unzip();
+    check.compare(GradCheckKind::Differential, forward, differential, tol);
+
+    if holomorphic {
+        let scheme = CentralDifference {
+            step: None,
+            richardson: true,
+        };
+        let computed = conj_grad.components()[0];
+        let numerical = z
+            .finite_diff_grad(|y| f.eval(y, static_args), &scheme)
+            .1
+            .components()[0];
+        // NaNs are not zero either
+        let is_zero = |c: Complex<f64>| c.norm() <= tol;
+        if !(is_zero(computed) && is_zero(numerical)) {
+            check.mismatches.push(GradMismatch {
+                kind: GradCheckKind::Holomorphic,
+                index: 0,
+                computed,
+                numerical,
+            });
+        }
+    }
+    check
+}
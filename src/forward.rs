@@ -20,6 +20,12 @@ use std::ops::Mul;
 pub trait ForwardMul<SelfInput, OtherGrad> {
     type ResultGrad;
     fn forward_mul(&self, other: &OtherGrad) -> Self::ResultGrad;
+
+    /// `forward_mul`, or `None` if the shapes of `self` and `other` don't match instead of
+    /// panicking. Only types with shapes, such as arrays, need to override it.
+    fn try_forward_mul(&self, other: &OtherGrad) -> Option<Self::ResultGrad> {
+        Some(self.forward_mul(other))
+    }
 }
 
 // impl forward for simple types (commutative multiplication)
@@ -59,3 +65,53 @@ impl_forward_mul!(
     Complex<f32>,
     Complex<f64>
 );
+
+/// Outer product of two gradients, used for the second order terms of Hessians
+/// `d²(f * g) = d²f * g + f * d²g + df ⊗ dg + dg ⊗ df`
+/// where
+/// `df: Self`,
+/// `dg: Other`,
+/// `df ⊗ dg: Output`
+///
+/// The outer product of a gradient with itself is the type of the Hessian,
+/// e.g. a scalar for scalar inputs, a nested `AutoTuple` for `AutoTuple` inputs,
+/// and an array with the axes of both gradients for array inputs
+pub trait Outer<Other> {
+    type Output;
+    fn outer(&self, other: &Other) -> Self::Output;
+}
+
+// impl outer for simple types, which just scale the other gradient
+
+macro_rules! impl_outer {
+    ($($t:ty),*) => {
+        $(
+            impl<Other, Output> Outer<Other> for $t
+            where
+                Other: Clone + Mul<$t, Output = Output>,
+            {
+                type Output = Output;
+                fn outer(&self, other: &Other) -> Self::Output {
+                    other.clone().mul(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_outer!(
+    f32,
+    f64,
+    i8,
+    i16,
+    i32,
+    i64,
+    u8,
+    u16,
+    u32,
+    u64,
+    isize,
+    usize,
+    Complex<f32>,
+    Complex<f64>
+);
@@ -1,2 +1,7 @@
 // forward from traits
-pub use crate::traits::{Abs, AbsSqr, Arg, Conjugate, InstOne, InstZero, PossiblyComplex, Signum};
+pub use crate::traits::{
+    Abs, AbsSqr, Acos, Acosh, Arg, Asin, Asinh, Atan, Atan2, Atanh, Conjugate, Cos, Cosh, Digamma,
+    Elu, Erf, Erfc, Exp, Gamma, Gelu, Heaviside, Hypot, InstOne, InstZero, LeakyRelu, Lgamma, Ln,
+    Log, Log10, Log2, Max, Min, NormCdf, NormPdf, PossiblyComplex, Relu, Sigmoid, Signum, Sin,
+    Sinh, Softplus, Sqrt, Tan, Tanh, Trigamma,
+};
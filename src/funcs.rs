@@ -1,17 +1,25 @@
 #![allow(dead_code)]
 
 use crate::autodiffable::*;
+use crate::forward::Outer;
 use crate::gradienttype::GradientType;
-use crate::traits::{GradientIdentity, InstOne, InstZero};
+use crate::graphviz::{DotAnnotate, DotGraph, ToDot};
+use crate::reverse::{ReverseDiffable, Tape, Var};
+use crate::simplify::{DynExpr, DynExprScalar, ToDynExpr};
+use crate::symbolic::{Expr, Symbolic, SymbolicConstant};
+use crate::taylor::{Taylor, TaylorCoefficient, TaylorDiffable};
+use crate::traits::{Conjugate, GradientIdentity, GradientZero, InstOne, InstZero};
+use crate::tryautodiff::{CheckValue, TryAutoDiffable};
 use num::traits::Pow;
 use std::marker::PhantomData;
-use std::ops::{Mul, Sub};
+use std::ops::{Add, Mul, Sub};
 
 use crate as autodiff;
 use autodiff_derive::*;
 
-#[cfg(test)]
 use crate::autodiff::AutoDiff;
+#[cfg(test)]
+use crate::compose::*;
 
 #[derive(Debug, Clone, FuncCompose)]
 pub struct Identity<S, I>(pub PhantomData<(S, I)>);
@@ -35,6 +43,27 @@ impl<S, I> Diffable<S> for Identity<S, I> {
     type Output = I;
 }
 
+impl<S, I> Symbolic for Identity<S, I> {
+    fn symbolic(&self, x: &Expr) -> Expr {
+        x.clone()
+    }
+}
+
+impl<S, I> ToDot<S> for Identity<S, I>
+where
+    Self: DotAnnotate<S, Input = I>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&I, &S)>) -> usize {
+        graph.node("x", self.annotate_at(at))
+    }
+}
+
+impl<S, T: DynExprScalar> ToDynExpr<S, T> for Identity<S, T> {
+    fn to_dyn_expr(&self) -> DynExpr<S, T> {
+        DynExpr::X
+    }
+}
+
 impl<S, I: Clone + InstOne + GradientType<I, GradientType = G> + GradientIdentity, G: InstZero>
     AutoDiffable<S> for Identity<S, I>
 {
@@ -59,6 +88,16 @@ impl<S, I: Clone + InstOne + GradientType<I, GradientType = G> + GradientIdentit
     }
 }
 
+impl<S, I, G> TryAutoDiffable<S> for Identity<S, I>
+where
+    Self: AutoDiffable<S, Input = I, Output = I>,
+    I: GradientType<I, GradientType = G>,
+    // ensure the value and gradients can be checked
+    I: CheckValue,
+    G: CheckValue,
+{
+}
+
 impl<S, I: Clone + InstOne + InstZero + GradientType<I> + GradientIdentity> ForwardDiffable<S>
     for Identity<S, I>
 {
@@ -79,6 +118,60 @@ impl<S, I: Clone + InstOne + InstZero + GradientType<I> + GradientIdentity> Forw
     }
 }
 
+impl<S, I> ReverseDiffable<S> for Identity<S, I>
+where
+    Self: VjpDiffable<S, Input = I, Output = I>,
+    // ensure the gradient has the type of the input, so the adjoints are pulled back by the vjp
+    I: GradientType<I, GradientType = I> + InstZero + Conjugate<Output = I>,
+{
+    fn eval_reverse<'a>(&'a self, x: &'a I, tape: &mut Tape<'a, I, I>, s: &'a S) -> Var {
+        tape.record_vjp(self, x, s)
+    }
+}
+
+impl<S, I: Clone + InstZero> VjpDiffable<S> for Identity<S, I> {
+    fn eval_primal(&self, x: &I, _: &S) -> I {
+        x.clone()
+    }
+    fn eval_vjp(&self, x: &I, cotangent: &I, _: &S) -> (I, I) {
+        (x.clone(), cotangent.clone())
+    }
+    fn eval_conj_vjp(&self, x: &I, cotangent: &I, _: &S) -> (I, I) {
+        (x.clone(), cotangent.zero())
+    }
+    fn vjp(&self, _: &I, cotangent: &I, _: &S) -> I {
+        cotangent.clone()
+    }
+    fn conj_vjp(&self, _: &I, cotangent: &I, _: &S) -> I {
+        cotangent.zero()
+    }
+}
+
+impl<S, I, G, H> HessianDiffable<S> for Identity<S, I>
+where
+    I: Clone + GradientType<I, GradientType = G> + GradientIdentity,
+    G: Outer<G, Output = H>,
+    H: InstZero,
+{
+    fn eval_hessian(&self, x: &I, _: &S) -> (I, G, H) {
+        let dx = x.grad_identity();
+        let d2x = dx.outer(&dx).zero();
+        (x.clone(), dx, d2x)
+    }
+}
+
+impl<S, I: Clone> HvpDiffable<S> for Identity<S, I> {
+    fn eval_hvp(&self, x: &I, _: &I, cotangent: &I, dcotangent: &I, _: &S) -> (I, I, I) {
+        (x.clone(), cotangent.clone(), dcotangent.clone())
+    }
+}
+
+impl<S, I: Clone, const K: usize> TaylorDiffable<S, K> for Identity<S, I> {
+    fn eval_taylor_series(&self, x: &Taylor<I, K>, _: &S) -> Taylor<I, K> {
+        x.clone()
+    }
+}
+
 #[test]
 fn test_identity() {
     let x = 2.0;
@@ -103,6 +196,76 @@ impl<S, I, O> Diffable<S> for Polynomial<S, I, O> {
     type Output = O;
 }
 
+// c_0 + c_1 * x + c_2 * x^2 + ..., without the terms with zero coefficients, and with the terms
+// with negative coefficients subtracted, e.g. 1 - 3 * x^2 + x^3
+impl<S, I, O> Symbolic for Polynomial<S, I, O>
+where
+    O: SymbolicConstant + InstZero + InstOne + PartialEq,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        let terms = self.0.iter().enumerate().filter(|(_, c)| !c.is_zero());
+        let terms = terms.map(|(i, c)| {
+            let x_pow = match i {
+                0 => None,
+                1 => Some(x.clone()),
+                _ => Some(Expr::Pow(
+                    Box::new(x.clone()),
+                    Box::new(Expr::Const(i.to_string())),
+                )),
+            };
+            let term = |c: Expr, is_one: bool| match &x_pow {
+                None => c,
+                Some(x_pow) if is_one => x_pow.clone(),
+                Some(x_pow) => Expr::Mul(Box::new(c), Box::new(x_pow.clone())),
+            };
+            // the magnitude of a negative numeric coefficient, whose term is subtracted
+            let minus_c = match c.symbolic_constant() {
+                Expr::Const(v) => v
+                    .strip_prefix('-')
+                    .filter(|m| m.parse::<f64>().is_ok())
+                    .map(str::to_string),
+                _ => None,
+            };
+            let plus_term = term(c.symbolic_constant(), c.is_one());
+            match (minus_c, &x_pow) {
+                // a leading -x^n is written as a negation rather than -1 * x^n
+                (Some(m), Some(x_pow)) if m == "1" => {
+                    (Expr::Neg(Box::new(x_pow.clone())), x_pow.clone(), true)
+                }
+                (Some(m), _) => (plus_term, term(Expr::Const(m), false), true),
+                (None, _) => (plus_term.clone(), plus_term, false),
+            }
+        });
+        terms
+            .fold(None, |sum, (leading, term, subtract)| {
+                Some(match (sum, subtract) {
+                    (None, _) => leading,
+                    (Some(sum), false) => Expr::Add(Box::new(sum), Box::new(term)),
+                    (Some(sum), true) => Expr::Sub(Box::new(sum), Box::new(term)),
+                })
+            })
+            .unwrap_or_else(|| Expr::Const("0".to_string()))
+    }
+}
+
+impl<S, I, O> ToDot<S> for Polynomial<S, I, O>
+where
+    Self: DotAnnotate<S, Input = I> + Symbolic,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&I, &S)>) -> usize {
+        graph.node(
+            &self.symbolic(&Expr::var("x")).to_string(),
+            self.annotate_at(at),
+        )
+    }
+}
+
+impl<S, T: DynExprScalar> ToDynExpr<S, T> for Polynomial<S, T, T> {
+    fn to_dyn_expr(&self) -> DynExpr<S, T> {
+        DynExpr::Poly(self.0.clone(), Box::new(DynExpr::X))
+    }
+}
+
 impl<S, I: GradientType<O, GradientType = O>, O: InstZero + InstOne> AutoDiffable<S>
     for Polynomial<S, I, O>
 where
@@ -163,6 +326,15 @@ where
     }
 }
 
+impl<S, I, O> TryAutoDiffable<S> for Polynomial<S, I, O>
+where
+    Self: AutoDiffable<S, Input = I, Output = O>,
+    I: GradientType<O, GradientType = O>,
+    // ensure the value and gradients, which are both O, can be checked
+    O: CheckValue,
+{
+}
+
 impl<
         S,
         I: Clone + GradientType<O, GradientType = O>,
@@ -207,7 +379,7 @@ where
         (res, grad)
     }
 
-    fn forward_grad(&self, _: &I, dx: &I, _: &S) -> O {
+    fn forward_grad(&self, x: &I, dx: &I, _: &S) -> O {
         let mut grad = self.0[0].zero();
         let mut x_pow = self.0[0].one();
         let mut pow = self.0[0].zero();
@@ -215,7 +387,7 @@ where
         for i in 0..self.0.len() - 1 {
             pow = pow + self.0[0].one();
             grad = grad + &self.0[i + 1] * &pow * (&x_pow * dx);
-            x_pow = &x_pow * dx;
+            x_pow = &x_pow * x;
         }
 
         grad
@@ -226,6 +398,96 @@ where
     }
 }
 
+impl<S, I, O> ReverseDiffable<S> for Polynomial<S, I, O>
+where
+    Self: VjpDiffable<S, Input = I, Output = O>,
+    // ensure the gradient has the type of the input, so the adjoints are pulled back by the vjp
+    I: GradientType<O, GradientType = I> + InstZero + Conjugate<Output = I>,
+    O: InstZero + Conjugate<Output = O>,
+{
+    fn eval_reverse<'a>(&'a self, x: &'a I, tape: &mut Tape<'a, I, O>, s: &'a S) -> Var {
+        tape.record_vjp(self, x, s)
+    }
+}
+
+// polynomials are scalar functions, so the vector-Jacobian product is just the cotangent times
+// the derivative
+impl<S, I, O, G> VjpDiffable<S> for Polynomial<S, I, O>
+where
+    Self: AutoDiffable<S, Input = I, Output = O>,
+    I: GradientType<O, GradientType = G>,
+    O: Clone + Mul<G, Output = I>,
+{
+    fn eval_primal(&self, x: &I, s: &S) -> O {
+        self.eval(x, s)
+    }
+    fn eval_vjp(&self, x: &I, cotangent: &O, s: &S) -> (O, I) {
+        let (f, df) = self.eval_grad(x, s);
+        (f, cotangent.clone().mul(df))
+    }
+    fn eval_conj_vjp(&self, x: &I, cotangent: &O, s: &S) -> (O, I) {
+        let (f, df) = self.eval_conj_grad(x, s);
+        (f, cotangent.clone().mul(df))
+    }
+}
+
+impl<S, I, O> HessianDiffable<S> for Polynomial<S, I, O>
+where
+    Self: AutoDiffable<S, Input = I, Output = O>,
+    I: GradientType<O, GradientType = O>,
+    O: Clone + InstZero + InstOne + Outer<O, Output = O>,
+    for<'b> O: Mul<&'b O, Output = O>,
+    for<'b> &'b O: Mul<&'b I, Output = O> + Mul<&'b O, Output = O>,
+{
+    fn eval_hessian(&self, x: &I, s: &S) -> (O, O, O) {
+        let (res, grad) = self.eval_grad(x, s);
+        let mut hess = self.0[0].zero();
+        let mut x_pow = self.0[0].one();
+        // i and i - 1 for the coefficient c_i of x^i, starting at i = 2
+        let mut pow = self.0[0].one() + self.0[0].one();
+        let mut pow_m1 = self.0[0].one();
+
+        for i in 2..self.0.len() {
+            hess = hess + &self.0[i] * &pow * &pow_m1 * &x_pow;
+            x_pow = &x_pow * x;
+            pow_m1 = pow.clone();
+            pow = pow + self.0[0].one();
+        }
+
+        (res, grad, hess)
+    }
+}
+
+// with the scalar derivatives from the Hessian, d(c * p'(x)) = dc * p'(x) + c * p''(x) * v
+impl<S, T> HvpDiffable<S> for Polynomial<S, T, T>
+where
+    Self: HessianDiffable<S, Input = T, Output = T>,
+    T: Clone
+        + GradientType<T, GradientType = T>
+        + Outer<T, Output = T>
+        + Add<T, Output = T>
+        + Mul<T, Output = T>,
+{
+    fn eval_hvp(&self, x: &T, v: &T, cotangent: &T, dcotangent: &T, s: &S) -> (T, T, T) {
+        let (f, df, d2f) = self.eval_hessian(x, s);
+        let dvjp = dcotangent
+            .clone()
+            .mul(df.clone())
+            .add(cotangent.clone().mul(d2f).mul(v.clone()));
+
+        (f, cotangent.clone().mul(df), dvjp)
+    }
+}
+
+impl<S, T: TaylorCoefficient, const K: usize> TaylorDiffable<S, K> for Polynomial<S, T, T> {
+    fn eval_taylor_series(&self, x: &Taylor<T, K>, _: &S) -> Taylor<T, K> {
+        // Horner's method on the series
+        self.0.iter().rev().fold(x.zero(), |res, c| {
+            res.mul(x.clone()).add_constant(c.clone())
+        })
+    }
+}
+
 #[test]
 fn test_polynomial() {
     // p(x) = 3 + 2x + x^2
@@ -240,6 +502,10 @@ fn test_polynomial() {
     assert_eq!(p.eval(&x, &()), 11.0);
     assert_eq!(p.eval_forward_grad(&x, &dx, &()), (11.0, 6.0));
     assert_eq!(p.eval_forward_grad(&x, &dx2, &()), (11.0, 12.0));
+    assert_eq!(p.forward_grad(&x, &dx, &()), 6.0);
+    assert_eq!(p.forward_grad(&3.0, &dx2, &()), 16.0);
+    assert_eq!(p.eval_taylor(&x, 3, &()), [11.0, 6.0, 1.0, 0.0]);
+    assert_eq!(p.to_string(), "3 + 2 * x + x^2");
 }
 
 #[derive(Debug, Clone, FuncCompose)]
@@ -258,6 +524,30 @@ impl<S, I, P> Diffable<S> for Monomial<S, I, P> {
     type Output = I;
 }
 
+impl<S, I, P: SymbolicConstant> Symbolic for Monomial<S, I, P> {
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::Pow(Box::new(x.clone()), Box::new(self.0.symbolic_constant()))
+    }
+}
+
+impl<S, I, P> ToDot<S> for Monomial<S, I, P>
+where
+    Self: DotAnnotate<S, Input = I> + Symbolic,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&I, &S)>) -> usize {
+        graph.node(
+            &self.symbolic(&Expr::var("x")).to_string(),
+            self.annotate_at(at),
+        )
+    }
+}
+
+impl<S, T: DynExprScalar> ToDynExpr<S, T> for Monomial<S, T, T> {
+    fn to_dyn_expr(&self) -> DynExpr<S, T> {
+        DynExpr::Pow(Box::new(DynExpr::X), self.0.clone())
+    }
+}
+
 impl<
         S,
         I: Clone
@@ -295,6 +585,15 @@ where
     }
 }
 
+impl<S, I, P> TryAutoDiffable<S> for Monomial<S, I, P>
+where
+    Self: AutoDiffable<S, Input = I, Output = I>,
+    I: GradientType<I, GradientType = I>,
+    // ensure the value and gradients, which are both I, can be checked
+    I: CheckValue,
+{
+}
+
 impl<
         S,
         I: Clone
@@ -332,6 +631,103 @@ where
     }
 }
 
+impl<S, I, P> ReverseDiffable<S> for Monomial<S, I, P>
+where
+    Self: VjpDiffable<S, Input = I, Output = I>,
+    // ensure the gradient has the type of the input, so the adjoints are pulled back by the vjp
+    I: GradientType<I, GradientType = I> + InstZero + Conjugate<Output = I>,
+{
+    fn eval_reverse<'a>(&'a self, x: &'a I, tape: &mut Tape<'a, I, I>, s: &'a S) -> Var {
+        tape.record_vjp(self, x, s)
+    }
+}
+
+impl<S, I, P, G> VjpDiffable<S> for Monomial<S, I, P>
+where
+    Self: AutoDiffable<S, Input = I, Output = I>,
+    I: Clone + GradientType<I, GradientType = G> + Mul<G, Output = I>,
+{
+    fn eval_primal(&self, x: &I, s: &S) -> I {
+        self.eval(x, s)
+    }
+    fn eval_vjp(&self, x: &I, cotangent: &I, s: &S) -> (I, I) {
+        let (f, df) = self.eval_grad(x, s);
+        (f, cotangent.clone().mul(df))
+    }
+    fn eval_conj_vjp(&self, x: &I, cotangent: &I, s: &S) -> (I, I) {
+        let (f, df) = self.eval_conj_grad(x, s);
+        (f, cotangent.clone().mul(df))
+    }
+}
+
+impl<
+        S,
+        I: Clone
+            + InstOne
+            + InstZero
+            + Pow<P, Output = I>
+            + Mul<I, Output = I>
+            + GradientType<I, GradientType = I>
+            + Outer<I, Output = I>
+            + PartialEq,
+        P: InstOne,
+    > HessianDiffable<S> for Monomial<S, I, P>
+where
+    for<'b> I: Mul<&'b I, Output = I> + Mul<&'b P, Output = I> + Pow<&'b P, Output = I>,
+    for<'b> &'b I: Mul<&'b I, Output = I>,
+    for<'b> &'b P: Sub<&'b P, Output = P> + Sub<P, Output = P>,
+{
+    fn eval_hessian(&self, x: &I, _: &S) -> (I, I, I) {
+        let p_m1 = &self.0 - self.0.one();
+        let p_m2 = &p_m1 - p_m1.one();
+
+        // c * x^e, which is zero when c is, even where x^e diverges (e.g. the second derivative
+        // of x^1 at x = 0)
+        let scaled_pow = |c: I, e: &P| match c.is_zero() {
+            true => x.zero(),
+            false => x.clone().pow(e) * c,
+        };
+        let p = x.one() * &self.0;
+        let p_p_m1 = p.clone() * &p_m1;
+
+        (
+            x.clone().pow(&self.0),
+            scaled_pow(p, &p_m1),
+            scaled_pow(p_p_m1, &p_m2),
+        )
+    }
+}
+
+impl<S, I, P> HvpDiffable<S> for Monomial<S, I, P>
+where
+    Self: HessianDiffable<S, Input = I, Output = I>,
+    I: Clone
+        + GradientType<I, GradientType = I>
+        + Outer<I, Output = I>
+        + Add<I, Output = I>
+        + Mul<I, Output = I>,
+{
+    fn eval_hvp(&self, x: &I, v: &I, cotangent: &I, dcotangent: &I, s: &S) -> (I, I, I) {
+        let (f, df, d2f) = self.eval_hessian(x, s);
+        let dvjp = dcotangent
+            .clone()
+            .mul(df.clone())
+            .add(cotangent.clone().mul(d2f).mul(v.clone()));
+
+        (f, cotangent.clone().mul(df), dvjp)
+    }
+}
+
+impl<S, I, P, const K: usize> TaylorDiffable<S, K> for Monomial<S, I, P>
+where
+    P: Clone,
+    Taylor<I, K>: Clone + Pow<P, Output = Taylor<I, K>>,
+{
+    fn eval_taylor_series(&self, x: &Taylor<I, K>, _: &S) -> Taylor<I, K> {
+        x.clone().pow(self.0.clone())
+    }
+}
+
 #[test]
 fn test_monomial() {
     // p(x) = x^3
@@ -347,4 +743,290 @@ fn test_monomial() {
     assert_eq!(p.eval_grad(&x, &()), (8.0, 12.0));
     assert_eq!(p.eval_forward_grad(&x, &dx, &()), (8.0, 12.0));
     assert_eq!(p.eval_forward_grad(&x, &dx2, &()), (8.0, 24.0));
+    assert_eq!(p.eval_taylor(&x, 4, &()), [8.0, 12.0, 6.0, 1.0, 0.0]);
+    assert_eq!(p.eval_taylor(&x, 1, &()), [8.0, 12.0, 0.0]);
+    assert_eq!(p.to_string(), "x^3");
+}
+
+/// A function defined by closures for its value and its gradient, e.g. to wrap a function from
+/// an external library whose derivative is known.
+///
+/// The gradient wrt the conjugate of the input is zero unless a closure for it is given with
+/// `FnDiff::with_conj_grad`, i.e. the function is assumed to be real or holomorphic.
+#[derive(Clone, Copy, FuncCompose, SimpleForwardDiffable)]
+pub struct FnDiff<S, I, O, F, DF, DCF>(pub F, pub DF, pub Option<DCF>, pub PhantomData<(S, I, O)>);
+
+impl<S, I, O, G, F, DF> FnDiff<S, I, O, F, DF, fn(&I, &S) -> G>
+where
+    I: GradientType<O, GradientType = G>,
+    F: Fn(&I, &S) -> O,
+    DF: Fn(&I, &S) -> G,
+{
+    /// f(x, s) and df/dx(x, s) given as closures
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(f: F, df: DF) -> AutoDiff<S, Self> {
+        AutoDiff::new(FnDiff(f, df, None, PhantomData))
+    }
+}
+
+impl<S, I, O, G, F, DF, DCF> FnDiff<S, I, O, F, DF, DCF>
+where
+    I: GradientType<O, GradientType = G>,
+    F: Fn(&I, &S) -> O,
+    DF: Fn(&I, &S) -> G,
+    DCF: Fn(&I, &S) -> G,
+{
+    /// f(x, s), df/dx(x, s) and df/dconj(x)(x, s) given as closures
+    pub fn with_conj_grad(f: F, df: DF, dcf: DCF) -> AutoDiff<S, Self> {
+        AutoDiff::new(FnDiff(f, df, Some(dcf), PhantomData))
+    }
+}
+
+impl<S, I, O, F, DF, DCF> std::fmt::Debug for FnDiff<S, I, O, F, DF, DCF> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FnDiff")
+            .field("conj_grad", &self.2.is_some())
+            .finish()
+    }
+}
+
+impl<S, I, O, F, DF, DCF> Diffable<S> for FnDiff<S, I, O, F, DF, DCF> {
+    type Input = I;
+    type Output = O;
+}
+
+impl<S, I, O, F, DF, DCF> ToDot<S> for FnDiff<S, I, O, F, DF, DCF> where Self: DotAnnotate<S> {}
+
+impl<S, T, F, DF, DCF> ToDynExpr<S, T> for FnDiff<S, T, T, F, DF, DCF>
+where
+    T: DynExprScalar,
+    // ensure the function can be boxed as an opaque function of x, since it is not simplified
+    Self: AutoDiffable<S, Input = T, Output = T>
+        + ForwardDiffable<S, Input = T, Output = T>
+        + Clone
+        + 'static,
+{
+    fn to_dyn_expr(&self) -> DynExpr<S, T> {
+        DynExpr::opaque(self.clone())
+    }
+}
+
+impl<S, I, O, G, F, DF, DCF> AutoDiffable<S> for FnDiff<S, I, O, F, DF, DCF>
+where
+    I: GradientType<O, GradientType = G> + GradientZero<O>,
+    F: Fn(&I, &S) -> O,
+    DF: Fn(&I, &S) -> G,
+    DCF: Fn(&I, &S) -> G,
+{
+    fn eval(&self, x: &I, s: &S) -> O {
+        (self.0)(x, s)
+    }
+
+    fn eval_grad(&self, x: &I, s: &S) -> (O, G) {
+        ((self.0)(x, s), (self.1)(x, s))
+    }
+
+    fn grad(&self, x: &I, s: &S) -> G {
+        (self.1)(x, s)
+    }
+
+    fn eval_conj_grad(&self, x: &I, s: &S) -> (O, G) {
+        let f = (self.0)(x, s);
+        let conj_grad = match &self.2 {
+            Some(dcf) => dcf(x, s),
+            None => x.grad_zero(&f),
+        };
+        (f, conj_grad)
+    }
+
+    fn conj_grad(&self, x: &I, s: &S) -> G {
+        match &self.2 {
+            Some(dcf) => dcf(x, s),
+            // the zero gradient only needs the shape of the output, not the gradient
+            None => x.grad_zero(&(self.0)(x, s)),
+        }
+    }
+}
+
+impl<S, I, O, G, F, DF, DCF> TryAutoDiffable<S> for FnDiff<S, I, O, F, DF, DCF>
+where
+    Self: AutoDiffable<S, Input = I, Output = O>,
+    I: GradientType<O, GradientType = G>,
+    // ensure the value and gradients can be checked
+    O: CheckValue,
+    G: CheckValue,
+{
+}
+
+impl<S, I, O, F, DF, DCF> ReverseDiffable<S> for FnDiff<S, I, O, F, DF, DCF>
+where
+    Self: VjpDiffable<S, Input = I, Output = O>,
+    // ensure the gradient has the type of the input, so the adjoints are pulled back by the vjp
+    I: GradientType<O, GradientType = I> + InstZero + Conjugate<Output = I>,
+    O: InstZero + Conjugate<Output = O>,
+{
+    fn eval_reverse<'a>(&'a self, x: &'a I, tape: &mut Tape<'a, I, O>, s: &'a S) -> Var {
+        tape.record_vjp(self, x, s)
+    }
+}
+
+impl<S, I, O, G, F, DF, DCF> VjpDiffable<S> for FnDiff<S, I, O, F, DF, DCF>
+where
+    Self: AutoDiffable<S, Input = I, Output = O>,
+    I: GradientType<O, GradientType = G>,
+    O: Clone + Mul<G, Output = I>,
+{
+    fn eval_primal(&self, x: &I, s: &S) -> O {
+        self.eval(x, s)
+    }
+    fn eval_vjp(&self, x: &I, cotangent: &O, s: &S) -> (O, I) {
+        let (f, df) = self.eval_grad(x, s);
+        (f, cotangent.clone().mul(df))
+    }
+    fn eval_conj_vjp(&self, x: &I, cotangent: &O, s: &S) -> (O, I) {
+        let (f, df) = self.eval_conj_grad(x, s);
+        (f, cotangent.clone().mul(df))
+    }
+}
+
+#[test]
+fn test_fn_diff() {
+    // f(x) = sin(x) wrapped from closures composes with the other nodes like ADSin
+    let x = 0.3_f64;
+    let dx = 2.0_f64;
+    let f = FnDiff::new(|x: &f64, _: &()| x.sin(), |x: &f64, _: &()| x.cos());
+    assert_eq!(f.eval_grad(&x, &()), (x.sin(), x.cos()));
+    assert_eq!(f.eval_conj_grad(&x, &()), (x.sin(), 0.0));
+    assert_eq!(f.eval_forward_grad(&x, &dx, &()), (x.sin(), x.cos() * dx));
+
+    // the zero conjugate gradient doesn't evaluate the gradient
+    let unused = FnDiff::new(
+        |x: &f64, _: &()| x.sin(),
+        |_: &f64, _: &()| -> f64 { panic!("the gradient should not be evaluated") },
+    );
+    assert_eq!(unused.conj_grad(&x, &()), 0.0);
+    assert_eq!(unused.eval_conj_grad(&x, &()), (x.sin(), 0.0));
+
+    let p = AutoDiff::new(Polynomial::new(vec![1.0, 2.0]));
+    let g = f * f + f.compose(p.clone()) + 3.0;
+    let (g_x, dg_dx) = g.eval_grad(&x, &());
+    let y = 1.0 + 2.0 * x;
+    assert_eq!(g_x, x.sin() * x.sin() + y.sin() + 3.0);
+    assert!((dg_dx - (2.0 * x.sin() * x.cos() + 2.0 * y.cos())).abs() < 1e-15);
+
+    // f(z) = |z|^2 is not holomorphic, so it needs a conjugate gradient
+    let z = num::complex::Complex::new(1.0, -2.0);
+    let abs_sqr = FnDiff::with_conj_grad(
+        |z: &num::complex::Complex<f64>, _: &()| z * z.conj(),
+        |z: &num::complex::Complex<f64>, _: &()| z.conj(),
+        |z: &num::complex::Complex<f64>, _: &()| *z,
+    );
+    assert_eq!(abs_sqr.eval_grad(&z, &()), (z.norm_sqr().into(), z.conj()));
+    assert_eq!(abs_sqr.eval_conj_grad(&z, &()), (z.norm_sqr().into(), z));
+}
+
+/// The gradient of a function as a function of its own, so that it can be differentiated again.
+/// Its gradient is the Hessian of the wrapped function.
+///
+/// The gradient type of an `AutoTuple` wrt an `AutoTuple` is elementwise, so it cannot hold the
+/// full Hessian of a function of an `AutoTuple`, use `HessianDiffable::hessian` for those instead
+#[derive(Debug, Clone, Copy, FuncCompose)]
+pub struct GradFn<F>(pub F);
+
+impl<F> GradFn<F> {
+    pub fn new(f: F) -> Self {
+        GradFn(f)
+    }
+}
+
+impl<S, F> Diffable<S> for GradFn<F>
+where
+    F: Diffable<S>,
+    F::Input: GradientType<F::Output>,
+{
+    type Input = F::Input;
+    type Output = <F::Input as GradientType<F::Output>>::GradientType;
+}
+
+impl<S, F, I, O, G, H> AutoDiffable<S> for GradFn<F>
+where
+    F: HessianDiffable<S, Input = I, Output = O>,
+    I: GradientType<O, GradientType = G> + GradientType<G, GradientType = H>,
+    G: Outer<G, Output = H>,
+    H: InstZero,
+{
+    fn eval_grad(&self, x: &I, s: &S) -> (G, H) {
+        let (_, grad, hess) = self.0.eval_hessian(x, s);
+        (grad, hess)
+    }
+
+    fn grad(&self, x: &I, s: &S) -> H {
+        self.0.hessian(x, s)
+    }
+
+    fn eval_conj_grad(&self, x: &I, s: &S) -> (G, H) {
+        // the Hessian is the holomorphic second derivative, so the gradient is treated as
+        // holomorphic, like Identity
+        let (_, grad, hess) = self.0.eval_hessian(x, s);
+        (grad, hess.zero())
+    }
+}
+
+impl<S, F, I, G, H> TryAutoDiffable<S> for GradFn<F>
+where
+    Self: AutoDiffable<S, Input = I, Output = G>,
+    I: GradientType<G, GradientType = H>,
+    // ensure the value and gradients can be checked
+    G: CheckValue,
+    H: CheckValue,
+{
+}
+
+// for a scalar function the gradient has the type of the input, and its forward gradient along dx
+// is the Hessian-vector product, which is computed without forming the Hessian
+impl<S, F, I, O> ForwardDiffable<S> for GradFn<F>
+where
+    F: HvpDiffable<S, Input = I, Output = O> + ForwardDiffable<S, Input = I, Output = O>,
+    I: InstZero + GradientType<O, GradientType = I>,
+    O: InstOne + InstZero,
+{
+    fn eval_forward_grad(&self, x: &I, dx: &I, s: &S) -> (I, I) {
+        let f = self.0.eval_forward(x, s);
+        let (_, grad, hvp) = self.0.eval_hvp(x, dx, &f.one(), &f.zero(), s);
+        (grad, hvp)
+    }
+
+    fn eval_forward_conj_grad(&self, x: &I, dx: &I, s: &S) -> (I, I) {
+        // the gradient is treated as holomorphic, like in eval_conj_grad
+        let (grad, _) = self.eval_forward_grad(x, dx, s);
+        (grad, dx.zero())
+    }
+}
+
+#[test]
+fn test_grad_fn() {
+    // p(x) = 1 - 2x + 3x^2 + x^3
+    // p'(x) = -2 + 6x + 3x^2
+    // p''(x) = 6 + 6x
+    let x = 2.0;
+    let p = AutoDiff::new(Polynomial::new(vec![1.0, -2.0, 3.0, 1.0]));
+    assert_eq!(p.eval_hessian(&x, &()), (17.0, 22.0, 18.0));
+
+    let dp = p.clone().grad_fn();
+    assert_eq!(dp.eval(&x, &()), 22.0);
+    assert_eq!(dp.eval_grad(&x, &()), (22.0, 18.0));
+    assert_eq!(dp.conj_grad(&x, &()), 0.0);
+
+    // m(x) = x^4, m''(x) = 12x^2
+    let m = AutoDiff::new(Monomial::<(), f64, f64>::new(4.0));
+    assert_eq!(m.eval_hessian(&x, &()), (16.0, 32.0, 48.0));
+    assert_eq!(m.grad_fn().grad(&x, &()), 48.0);
+
+    let i = AutoDiff::new(Identity::<(), f64>::new());
+    assert_eq!(i.eval_hessian(&x, &()), (2.0, 1.0, 0.0));
+
+    // the forward gradient of the gradient is the Hessian-vector product
+    assert_eq!(dp.eval_forward_grad(&x, &0.5, &()), (22.0, 9.0));
+    assert_eq!(p.hvp(&x, &0.5, &()), 9.0);
+    assert_eq!(m.grad_fn().forward_grad(&x, &2.0, &()), 96.0);
 }
@@ -0,0 +1,143 @@
+use crate::autodiffable::{AutoDiffable, Diffable};
+use crate::gradienttype::GradientType;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A computation graph in Graphviz DOT format, with one node per function and an edge from each
+/// operand to the function using it, built by `ToDot`
+#[derive(Debug, Clone, Default)]
+pub struct DotGraph {
+    nodes: Vec<(String, bool)>,
+    edges: Vec<(usize, usize, Option<String>)>,
+    // the nodes of shared functions, by their key and annotation
+    shared: HashMap<(usize, Option<String>), usize>,
+}
+
+impl DotGraph {
+    pub fn new() -> Self {
+        DotGraph::default()
+    }
+
+    /// Adds a node for a function, with an optional annotation below its label, and returns its id
+    pub fn node(&mut self, label: &str, annotation: Option<String>) -> usize {
+        let label = match annotation {
+            Some(annotation) => format!("{}\n{}", label, annotation),
+            None => label.to_string(),
+        };
+        self.nodes.push((label, false));
+        self.nodes.len() - 1
+    }
+
+    /// Adds a node for a constant, drawn as a box, and returns its id
+    pub fn constant(&mut self, label: &str) -> usize {
+        self.nodes.push((label.to_string(), true));
+        self.nodes.len() - 1
+    }
+
+    /// Adds an edge from the operand `from` to the function `to`, with an optional label for
+    /// operands whose order matters
+    pub fn edge(&mut self, from: usize, to: usize, label: Option<&str>) {
+        self.edges.push((from, to, label.map(|l| l.to_string())));
+    }
+
+    /// The id of the node of a function shared by several parts of an expression, identified by
+    /// `key` (e.g. its address), which is added by `add` only the first time it is drawn with a
+    /// given annotation
+    pub fn node_once(
+        &mut self,
+        key: usize,
+        annotation: Option<String>,
+        add: impl FnOnce(&mut DotGraph) -> usize,
+    ) -> usize {
+        if let Some(id) = self.shared.get(&(key, annotation.clone())) {
+            return *id;
+        }
+        let id = add(self);
+        self.shared.insert((key, annotation), id);
+        id
+    }
+
+    /// Adds a node for a function of the given operands, with an edge from each, and returns its id
+    pub fn op(
+        &mut self,
+        label: &str,
+        annotation: Option<String>,
+        operands: &[(usize, Option<&str>)],
+    ) -> usize {
+        let id = self.node(label, annotation);
+        for (operand, edge_label) in operands {
+            self.edge(*operand, id, *edge_label);
+        }
+        id
+    }
+}
+
+// escape a label for a double quoted DOT string, where \n is a line break
+fn escape(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+impl fmt::Display for DotGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "digraph {{")?;
+        for (id, (label, constant)) in self.nodes.iter().enumerate() {
+            match constant {
+                true => writeln!(f, "    n{} [label=\"{}\", shape=box];", id, escape(label))?,
+                false => writeln!(f, "    n{} [label=\"{}\"];", id, escape(label))?,
+            }
+        }
+        for (from, to, label) in &self.edges {
+            match label {
+                Some(label) => {
+                    writeln!(f, "    n{} -> n{} [label=\"{}\"];", from, to, escape(label))?
+                }
+                None => writeln!(f, "    n{} -> n{};", from, to)?,
+            }
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Functions whose value and gradient can be printed, to annotate their nodes in a `DotGraph`
+pub trait DotAnnotate<StaticArgs>: Diffable<StaticArgs> {
+    fn annotate(&self, x: &Self::Input, static_args: &StaticArgs) -> String;
+
+    /// The annotation at `at`, if any
+    fn annotate_at(&self, at: Option<(&Self::Input, &StaticArgs)>) -> Option<String> {
+        at.map(|(x, static_args)| self.annotate(x, static_args))
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, F> DotAnnotate<StaticArgs> for F
+where
+    F: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the value and gradient can be printed
+    Output: fmt::Debug,
+    Grad: fmt::Debug,
+{
+    fn annotate(&self, x: &Input, static_args: &StaticArgs) -> String {
+        let (f, df) = self.eval_grad(x, static_args);
+        format!("value = {:?}\ngrad = {:?}", f, df)
+    }
+}
+
+/// Functions which can be drawn as a computation graph. Leaves such as custom functions can
+/// implement it with an empty impl, which draws them as a single node labelled by their type name.
+pub trait ToDot<StaticArgs>: DotAnnotate<StaticArgs> {
+    /// Adds the nodes of the function and its operands to the graph and returns the id of its
+    /// node. If `at` is given, every node is annotated with its value and gradient there.
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&Self::Input, &StaticArgs)>) -> usize {
+        graph.node(type_label::<Self>(), self.annotate_at(at))
+    }
+}
+
+// the name of a type without its path or generic parameters
+pub(crate) fn type_label<T: ?Sized>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
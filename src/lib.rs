@@ -7,11 +7,22 @@ pub mod autodiffable;
 pub mod autotuple;
 pub mod compose;
 pub mod diffable;
+pub mod dual;
+pub mod dynautodiff;
+pub mod finitediff;
 pub mod forward;
 pub mod func_traits;
 pub mod funcs;
 pub mod gradienttype;
+pub mod graphviz;
+pub mod reverse;
+pub mod shared;
+pub mod simplify;
+mod special;
+pub mod symbolic;
+pub mod taylor;
 pub mod traits;
+pub mod tryautodiff;
 
 // re-export
 pub use autodiff::*;
@@ -19,11 +30,21 @@ pub use autodiffable::*;
 pub use autotuple::*;
 pub use compose::*;
 //pub use diffable::*;
+pub use dual::*;
+pub use dynautodiff::*;
+pub use finitediff::*;
 pub use func_traits::*;
 //pub use funcs::*;
 pub use forward::*;
 pub use gradienttype::*;
+pub use graphviz::*;
+pub use reverse::*;
+pub use shared::*;
+pub use simplify::*;
+pub use symbolic::*;
+pub use taylor::*;
 pub use traits::*;
+pub use tryautodiff::*;
 
 #[cfg(feature = "ndarray")]
 pub mod ad_ndarray;
@@ -0,0 +1,357 @@
+use crate::autodiffable::{AutoDiffable, VjpDiffable};
+use crate::diffable::Diffable;
+use crate::gradienttype::GradientType;
+use crate::traits::{Conjugate, GradientZero, InstOne, InstZero};
+use std::fmt;
+use std::ops::{Add, Mul};
+use std::rc::Rc;
+
+/// A value recorded on a `Tape`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Var(usize);
+
+impl Var {
+    /// The position of the value on the tape
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+// a partial derivative of a node wrt one of its parents, (parent, dw/du, dw/dconju)
+type Partial<T> = (usize, T, T);
+
+// the adjoints (dL/dx, dL/dconjx) of the tape's input from the adjoints (dL/dw, dL/dconjw) of a
+// function of it
+type Pullback<'a, Grad, T> = Rc<dyn Fn(&T, &T) -> (Grad, Grad) + 'a>;
+
+// the zero gradient wrt the tape's input of an output with the given value
+type ZeroGrad<'a, Grad, T> = Rc<dyn Fn(&T) -> Grad + 'a>;
+
+#[derive(Clone)]
+enum Node<'a, Grad, T> {
+    // a value that does not depend on the tape's input
+    Constant,
+    // a function of the tape's input, with its gradient and conjugate gradient wrt the input
+    Leaf(Grad, Grad),
+    // a function of the tape's input, with the vector-Jacobian products of its gradients
+    Vjp(Pullback<'a, Grad, T>),
+    // a function of one earlier node
+    Unary(Partial<T>),
+    // a function of two earlier nodes
+    Binary(Partial<T>, Partial<T>),
+}
+
+impl<Grad: fmt::Debug, T: fmt::Debug> fmt::Debug for Node<'_, Grad, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Node::Constant => f.write_str("Constant"),
+            Node::Leaf(grad, conj_grad) => {
+                f.debug_tuple("Leaf").field(grad).field(conj_grad).finish()
+            }
+            Node::Vjp(_) => f.write_str("Vjp"),
+            Node::Unary(u) => f.debug_tuple("Unary").field(u).finish(),
+            Node::Binary(u, v) => f.debug_tuple("Binary").field(u).field(v).finish(),
+        }
+    }
+}
+
+/// A Wengert list for reverse mode differentiation of functions with a scalar output.
+///
+/// `ReverseDiffable::eval_reverse` records every operation of an expression on the tape, each
+/// with the local (Wirtinger) partial derivatives wrt its operands. `backward` then accumulates
+/// the adjoints from the output back to the input in a single sweep, so the gradient costs a
+/// small multiple of the evaluation regardless of the size of the input.
+///
+/// `Grad` is the gradient type of the function, `<Input as GradientType<T>>::GradientType`, and
+/// `T` is the scalar type of every value on the tape. Functions of the input recorded by their
+/// vector-Jacobian products borrow the function, the input and the static arguments for `'a`.
+#[derive(Clone)]
+pub struct Tape<'a, Grad, T> {
+    values: Vec<T>,
+    nodes: Vec<Node<'a, Grad, T>>,
+    zero_grad: ZeroGrad<'a, Grad, T>,
+}
+
+impl<Grad: fmt::Debug, T: fmt::Debug> fmt::Debug for Tape<'_, Grad, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tape")
+            .field("values", &self.values)
+            .field("nodes", &self.nodes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, Grad, T> Tape<'a, Grad, T> {
+    /// A new tape for functions of the input `x`, which gives the shape of the zero gradient of
+    /// outputs that do not depend on it
+    pub fn new<Input>(x: &'a Input) -> Self
+    where
+        Input: GradientZero<T, GradientType = Grad>,
+    {
+        Tape {
+            values: Vec::new(),
+            nodes: Vec::new(),
+            zero_grad: Rc::new(move |value| x.grad_zero(value)),
+        }
+    }
+
+    /// The number of values recorded on the tape
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The value of a recorded variable
+    pub fn value(&self, var: Var) -> &T {
+        &self.values[var.0]
+    }
+
+    fn push(&mut self, value: T, node: Node<'a, Grad, T>) -> Var {
+        self.values.push(value);
+        self.nodes.push(node);
+        Var(self.values.len() - 1)
+    }
+
+    /// Record a value that does not depend on the tape's input
+    pub fn constant(&mut self, value: T) -> Var {
+        self.push(value, Node::Constant)
+    }
+
+    /// Record a function of the tape's input, with its gradient and conjugate gradient wrt the
+    /// input
+    pub fn leaf(&mut self, value: T, grad: Grad, conj_grad: Grad) -> Var {
+        self.push(value, Node::Leaf(grad, conj_grad))
+    }
+
+    /// Record a function w of one variable u, with dw/du and dw/dconj(u)
+    pub fn unary(&mut self, value: T, u: (Var, T, T)) -> Var {
+        self.push(value, Node::Unary((u.0 .0, u.1, u.2)))
+    }
+
+    /// Record a function w of two variables u and v, with dw/du, dw/dconj(u), dw/dv and
+    /// dw/dconj(v)
+    pub fn binary(&mut self, value: T, u: (Var, T, T), v: (Var, T, T)) -> Var {
+        self.push(value, Node::Binary((u.0 .0, u.1, u.2), (v.0 .0, v.1, v.2)))
+    }
+
+    /// Record any AutoDiffable function of the tape's input as a single leaf, using its dense
+    /// gradient. Functions without a ReverseDiffable or VjpDiffable impl of their own can
+    /// implement ReverseDiffable with this.
+    pub fn record<StaticArgs, Input, F>(
+        &mut self,
+        f: &F,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Var
+    where
+        F: AutoDiffable<StaticArgs, Input = Input, Output = T>,
+        Input: GradientType<T, GradientType = Grad>,
+    {
+        let (value, grad) = f.eval_grad(x, static_args);
+        let conj_grad = f.conj_grad(x, static_args);
+
+        self.leaf(value, grad, conj_grad)
+    }
+
+    /// Record a VjpDiffable function of the tape's input as a single leaf, which is evaluated once
+    /// now and pulls the adjoints back to the input with `vjp` and `conj_vjp` in `backward`, so
+    /// its Jacobian is never materialized. The gradient of the scalar output has the type of the
+    /// input.
+    pub fn record_vjp<StaticArgs, F>(
+        &mut self,
+        f: &'a F,
+        x: &'a Grad,
+        static_args: &'a StaticArgs,
+    ) -> Var
+    where
+        F: VjpDiffable<StaticArgs, Input = Grad, Output = T>,
+        Grad: Add<Grad, Output = Grad> + Conjugate<Output = Grad>,
+        T: InstZero + Conjugate<Output = T>,
+    {
+        // in the Wirtinger calculus, the adjoints of the input are
+        //
+        // dL/dx = dL/dw * dw/dx + dL/dconjw * conj(dw/dconjx)
+        //       = vjp(dL/dw) + conj(conj_vjp(conj(dL/dconjw)))
+        // dL/dconjx = dL/dw * dw/dconjx + dL/dconjw * conj(dw/dx)
+        //           = conj_vjp(dL/dw) + conj(vjp(conj(dL/dconjw)))
+        //
+        // and dL/dconjw is zero for real functions, which only need the first term of each
+        let pullback = move |a: &T, b: &T| {
+            let dx = f.vjp(x, a, static_args);
+            let dconjx = f.conj_vjp(x, a, static_args);
+            if b.is_zero() {
+                return (dx, dconjx);
+            }
+
+            let bconj = b.conj();
+            (
+                dx.add(f.conj_vjp(x, &bconj, static_args).conj()),
+                dconjx.add(f.vjp(x, &bconj, static_args).conj()),
+            )
+        };
+
+        self.push(f.eval_primal(x, static_args), Node::Vjp(Rc::new(pullback)))
+    }
+}
+
+impl<Grad, T> Tape<'_, Grad, T>
+where
+    T: Clone + InstZero + InstOne + Conjugate<Output = T>,
+    Grad: Clone + Add<Grad, Output = Grad> + Mul<T, Output = Grad> + Conjugate<Output = Grad>,
+{
+    /// Accumulate the adjoints of every value recorded up to `output`.
+    /// Returns `(d output/dx, d output/dconj(x))`, which are the same as
+    /// `(AutoDiffable::grad, AutoDiffable::conj_grad)` of the recorded function, which are zero
+    /// if `output` does not depend on the tape's input.
+    pub fn backward(&self, output: Var) -> (Grad, Grad) {
+        // in the Wirtinger calculus, for w = w(u, conj(u)) we have
+        //
+        // dL/du = dL/dw * dw/du + dL/dconjw * conj(dw/dconju)
+        // dL/dconju = dL/dw * dw/dconju + dL/dconjw * conj(dw/du)
+        //
+        // so every node carries the pair of adjoints (dL/dw, dL/dconjw), seeded with (1, 0)
+        let seed = &self.values[output.0];
+        let mut adjoints: Vec<Option<(T, T)>> = vec![None; output.0 + 1];
+        adjoints[output.0] = Some((seed.one(), seed.zero()));
+
+        let mut grad: Option<(Grad, Grad)> = None;
+
+        for i in (0..=output.0).rev() {
+            let (a, b) = match adjoints[i].take() {
+                Some(adjoint) => adjoint,
+                None => continue,
+            };
+
+            match &self.nodes[i] {
+                Node::Constant => {}
+                Node::Leaf(dwdx, dwdconjx) => {
+                    let dx = dwdx
+                        .clone()
+                        .mul(a.clone())
+                        .add(dwdconjx.conj().mul(b.clone()));
+                    let dconjx = dwdconjx.clone().mul(a).add(dwdx.conj().mul(b));
+
+                    grad = Some(match grad {
+                        Some((g, gconj)) => (g.add(dx), gconj.add(dconjx)),
+                        None => (dx, dconjx),
+                    });
+                }
+                Node::Vjp(pullback) => {
+                    let (dx, dconjx) = pullback(&a, &b);
+
+                    grad = Some(match grad {
+                        Some((g, gconj)) => (g.add(dx), gconj.add(dconjx)),
+                        None => (dx, dconjx),
+                    });
+                }
+                Node::Unary(u) => {
+                    accumulate(&mut adjoints, u, &a, &b);
+                }
+                Node::Binary(u, v) => {
+                    accumulate(&mut adjoints, u, &a, &b);
+                    accumulate(&mut adjoints, v, &a, &b);
+                }
+            }
+        }
+
+        grad.unwrap_or_else(|| {
+            let zero = (self.zero_grad)(seed);
+            (zero.clone(), zero)
+        })
+    }
+}
+
+// add the contribution of a node with adjoints (a, b) to one of its parents
+fn accumulate<T>(adjoints: &mut [Option<(T, T)>], partial: &Partial<T>, a: &T, b: &T)
+where
+    T: Clone + InstZero + InstOne + Conjugate<Output = T>,
+{
+    let (u, dwdu, dwdconju) = partial;
+    let du = a
+        .clone()
+        .mul(dwdu.clone())
+        .add(b.clone().mul(dwdconju.conj()));
+    let dconju = a
+        .clone()
+        .mul(dwdconju.clone())
+        .add(b.clone().mul(dwdu.conj()));
+
+    adjoints[*u] = Some(match adjoints[*u].take() {
+        Some((au, bu)) => (au.add(du), bu.add(dconju)),
+        None => (du, dconju),
+    });
+}
+
+pub trait ReverseDiffable<StaticArgs>: Diffable<StaticArgs>
+where
+    <Self as Diffable<StaticArgs>>::Input: GradientType<<Self as Diffable<StaticArgs>>::Output>,
+{
+    /// Evaluate the function for a given input and static arguments, recording every operation
+    /// on the tape.
+    /// Returns the variable holding `f(x, static_args)`
+    #[allow(clippy::type_complexity)]
+    fn eval_reverse<'a>(
+        &'a self,
+        x: &'a <Self as Diffable<StaticArgs>>::Input,
+        tape: &mut Tape<
+            'a,
+            <<Self as Diffable<StaticArgs>>::Input as GradientType<
+                <Self as Diffable<StaticArgs>>::Output,
+            >>::GradientType,
+            <Self as Diffable<StaticArgs>>::Output,
+        >,
+        static_args: &'a StaticArgs,
+    ) -> Var;
+
+    /// Evaluate the function and its gradient in reverse mode, using a new tape.
+    /// Returns the same as `AutoDiffable::eval_grad`
+    fn eval_grad_reverse<Grad>(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad)
+    where
+        <Self as Diffable<StaticArgs>>::Input:
+            GradientZero<<Self as Diffable<StaticArgs>>::Output, GradientType = Grad>,
+        <Self as Diffable<StaticArgs>>::Output:
+            Clone + InstZero + InstOne + Conjugate<Output = <Self as Diffable<StaticArgs>>::Output>,
+        Grad: Clone
+            + Add<Grad, Output = Grad>
+            + Mul<<Self as Diffable<StaticArgs>>::Output, Output = Grad>
+            + Conjugate<Output = Grad>,
+    {
+        let mut tape = Tape::new(x);
+        let output = self.eval_reverse(x, &mut tape, static_args);
+        let (grad, _) = tape.backward(output);
+
+        (tape.values.swap_remove(output.0), grad)
+    }
+
+    /// Evaluate the function and its gradient wrt the conjugate of the input in reverse mode,
+    /// using a new tape.
+    /// Returns the same as `AutoDiffable::eval_conj_grad`
+    fn eval_conj_grad_reverse<Grad>(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> (<Self as Diffable<StaticArgs>>::Output, Grad)
+    where
+        <Self as Diffable<StaticArgs>>::Input:
+            GradientZero<<Self as Diffable<StaticArgs>>::Output, GradientType = Grad>,
+        <Self as Diffable<StaticArgs>>::Output:
+            Clone + InstZero + InstOne + Conjugate<Output = <Self as Diffable<StaticArgs>>::Output>,
+        Grad: Clone
+            + Add<Grad, Output = Grad>
+            + Mul<<Self as Diffable<StaticArgs>>::Output, Output = Grad>
+            + Conjugate<Output = Grad>,
+    {
+        let mut tape = Tape::new(x);
+        let output = self.eval_reverse(x, &mut tape, static_args);
+        let (_, conj_grad) = tape.backward(output);
+
+        (tape.values.swap_remove(output.0), conj_grad)
+    }
+}
@@ -0,0 +1,377 @@
+use crate::autodiffable::{AutoDiffable, Diffable, ForwardDiffable};
+use crate::gradienttype::GradientType;
+use crate::graphviz::{DotAnnotate, DotGraph, ToDot};
+use crate::simplify::{DynExpr, DynExprScalar, ToDynExpr};
+use crate::symbolic::{Expr, Symbolic};
+use crate::tryautodiff::{AutoDiffError, CheckValue, TryAutoDiffable};
+use std::cell::{Cell, RefCell, RefMut};
+use std::rc::Rc;
+
+use crate as autodiff;
+use autodiff_derive::*;
+
+// the address and type of the input and static args of an evaluation
+type EvaluationKey = (*const (), &'static str, *const (), &'static str);
+
+fn evaluation_key<Input, StaticArgs>(x: &Input, static_args: &StaticArgs) -> EvaluationKey {
+    (
+        x as *const Input as *const (),
+        std::any::type_name::<Input>(),
+        static_args as *const StaticArgs as *const (),
+        std::any::type_name::<StaticArgs>(),
+    )
+}
+
+// the outermost evaluation in progress on this thread, numbered so that shared functions can tell
+// it apart from earlier ones. its input and static args outlive it, so their addresses identify
+// them until it returns
+#[derive(Clone, Copy)]
+struct Evaluation {
+    generation: u64,
+    depth: usize,
+    key: Option<EvaluationKey>,
+}
+
+thread_local! {
+    static EVALUATION: Cell<Evaluation> = const {
+        Cell::new(Evaluation {
+            generation: 0,
+            depth: 0,
+            key: None,
+        })
+    };
+}
+
+// leaves the evaluation when dropped, also when it panics
+struct EvaluationGuard;
+
+impl Drop for EvaluationGuard {
+    fn drop(&mut self) {
+        EVALUATION.with(|e| {
+            let mut evaluation = e.get();
+            evaluation.depth -= 1;
+            if evaluation.depth == 0 {
+                evaluation.key = None;
+            }
+            e.set(evaluation);
+        });
+    }
+}
+
+/// Run an evaluation of a function at `x` and `static_args`. The outermost one on the stack starts
+/// a new evaluation, within which shared functions reuse their results at `x` and `static_args`.
+pub(crate) fn evaluate<Input, StaticArgs, R>(
+    x: &Input,
+    static_args: &StaticArgs,
+    f: impl FnOnce() -> R,
+) -> R {
+    EVALUATION.with(|e| {
+        let mut evaluation = e.get();
+        if evaluation.depth == 0 {
+            evaluation.generation += 1;
+            evaluation.key = Some(evaluation_key(x, static_args));
+        }
+        evaluation.depth += 1;
+        e.set(evaluation);
+    });
+    let _guard = EvaluationGuard;
+    f()
+}
+
+// the generation of the evaluation in progress if `x` and `static_args` are its input and static
+// args. shared functions used at any other point, e.g. as the outer function of a composition, are
+// not cached, since those points are temporaries whose addresses may be reused
+fn generation_at<Input, StaticArgs>(x: &Input, static_args: &StaticArgs) -> Option<u64> {
+    let evaluation = EVALUATION.with(|e| e.get());
+    (evaluation.key == Some(evaluation_key(x, static_args))).then_some(evaluation.generation)
+}
+
+// the results of a shared function at the input of the evaluation in progress
+struct SharedCache<Output, Grad> {
+    generation: Option<u64>,
+    value: Option<Output>,
+    grad: Option<Grad>,
+    conj_grad: Option<Grad>,
+}
+
+#[allow(clippy::type_complexity)]
+struct SharedNode<StaticArgs, T>
+where
+    T: Diffable<StaticArgs>,
+    T::Input: GradientType<T::Output>,
+{
+    f: T,
+    cache: RefCell<SharedCache<T::Output, <T::Input as GradientType<T::Output>>::GradientType>>,
+}
+
+/// A function shared by several parts of an expression, e.g. `p * p + p` for `p = f.share()`.
+/// Its value and gradients are cached during a single evaluation, e.g. one call to `eval_grad`, so
+/// it is evaluated once for all its uses at the input of that evaluation rather than once per
+/// copy, and expressions shaped like a DAG cost as much as the DAG rather than its expansion into
+/// a tree. Nothing is reused across evaluations, nor at other inputs, e.g. as the outer function
+/// of a composition.
+///
+/// Clones share the function and its cache. Forward mode is not cached, since it also depends on
+/// the direction `dx`.
+#[derive(FuncCompose)]
+pub struct ADShared<StaticArgs, T>(Rc<SharedNode<StaticArgs, T>>)
+where
+    T: Diffable<StaticArgs>,
+    T::Input: GradientType<T::Output>;
+
+impl<StaticArgs, T> ADShared<StaticArgs, T>
+where
+    T: Diffable<StaticArgs>,
+    T::Input: GradientType<T::Output>,
+{
+    pub fn new(f: T) -> Self {
+        ADShared(Rc::new(SharedNode {
+            f,
+            cache: RefCell::new(SharedCache {
+                generation: None,
+                value: None,
+                grad: None,
+                conj_grad: None,
+            }),
+        }))
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, T> SharedNode<StaticArgs, T>
+where
+    T: Diffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+{
+    // the cache, emptied first if it holds the results of an earlier evaluation, or None if `x`
+    // is not the input of the evaluation in progress. it must not be held while evaluating the
+    // function, which may borrow the caches of other shared functions
+    fn cache_at(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Option<RefMut<'_, SharedCache<Output, Grad>>> {
+        let generation = generation_at(x, static_args)?;
+        let mut cache = self.cache.borrow_mut();
+        if cache.generation != Some(generation) {
+            *cache = SharedCache {
+                generation: Some(generation),
+                value: None,
+                grad: None,
+                conj_grad: None,
+            };
+        }
+        Some(cache)
+    }
+}
+
+impl<StaticArgs, T> Clone for ADShared<StaticArgs, T>
+where
+    T: Diffable<StaticArgs>,
+    T::Input: GradientType<T::Output>,
+{
+    fn clone(&self) -> Self {
+        ADShared(self.0.clone())
+    }
+}
+
+impl<StaticArgs, T> std::fmt::Debug for ADShared<StaticArgs, T>
+where
+    T: Diffable<StaticArgs> + std::fmt::Debug,
+    T::Input: GradientType<T::Output>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ADShared").field(&self.0.f).finish()
+    }
+}
+
+impl<StaticArgs, T> Diffable<StaticArgs> for ADShared<StaticArgs, T>
+where
+    T: Diffable<StaticArgs>,
+    T::Input: GradientType<T::Output>,
+{
+    type Input = T::Input;
+    type Output = T::Output;
+}
+
+impl<StaticArgs, T> Symbolic for ADShared<StaticArgs, T>
+where
+    T: Diffable<StaticArgs> + Symbolic,
+    T::Input: GradientType<T::Output>,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        self.0.f.symbolic(x)
+    }
+}
+
+// a shared function is drawn once, with an edge to every function using it
+impl<StaticArgs, T> ToDot<StaticArgs> for ADShared<StaticArgs, T>
+where
+    T: ToDot<StaticArgs>,
+    T::Input: GradientType<T::Output>,
+    Self: DotAnnotate<StaticArgs, Input = T::Input>,
+{
+    fn dot_node(&self, graph: &mut DotGraph, at: Option<(&T::Input, &StaticArgs)>) -> usize {
+        let key = Rc::as_ptr(&self.0) as *const () as usize;
+        graph.node_once(key, self.annotate_at(at), |graph| {
+            self.0.f.dot_node(graph, at)
+        })
+    }
+}
+
+// a shared function is kept as an opaque function, so that its uses still share its cache
+impl<StaticArgs, Input, T> ToDynExpr<StaticArgs, Input> for ADShared<StaticArgs, T>
+where
+    T: Diffable<StaticArgs, Input = Input, Output = Input>,
+    Input: DynExprScalar,
+    // ensure the shared function can be boxed as an opaque function
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Input>
+        + ForwardDiffable<StaticArgs, Input = Input, Output = Input>
+        + Symbolic
+        + 'static,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, Input> {
+        DynExpr::apply(self.clone(), DynExpr::X)
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad, T> AutoDiffable<StaticArgs> for ADShared<StaticArgs, T>
+where
+    T: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    // ensure the cached results can be returned to every use
+    Output: Clone,
+    Grad: Clone,
+{
+    fn eval(&self, x: &Input, static_args: &StaticArgs) -> Output {
+        if let Some(SharedCache { value: Some(f), .. }) = self.0.cache_at(x, static_args).as_deref()
+        {
+            return f.clone();
+        }
+
+        let f = self.0.f.eval(x, static_args);
+        if let Some(mut cache) = self.0.cache_at(x, static_args) {
+            cache.value = Some(f.clone());
+        }
+        f
+    }
+
+    fn eval_grad(&self, x: &Input, static_args: &StaticArgs) -> (Output, Grad) {
+        if let Some(SharedCache {
+            value: Some(f),
+            grad: Some(df),
+            ..
+        }) = self.0.cache_at(x, static_args).as_deref()
+        {
+            return (f.clone(), df.clone());
+        }
+
+        let (f, df) = self.0.f.eval_grad(x, static_args);
+        if let Some(mut cache) = self.0.cache_at(x, static_args) {
+            cache.value = Some(f.clone());
+            cache.grad = Some(df.clone());
+        }
+        (f, df)
+    }
+
+    fn eval_conj_grad(&self, x: &Input, static_args: &StaticArgs) -> (Output, Grad) {
+        if let Some(SharedCache {
+            value: Some(f),
+            conj_grad: Some(df),
+            ..
+        }) = self.0.cache_at(x, static_args).as_deref()
+        {
+            return (f.clone(), df.clone());
+        }
+
+        let (f, df) = self.0.f.eval_conj_grad(x, static_args);
+        if let Some(mut cache) = self.0.cache_at(x, static_args) {
+            cache.value = Some(f.clone());
+            cache.conj_grad = Some(df.clone());
+        }
+        (f, df)
+    }
+
+    fn eval_all(&self, x: &Input, static_args: &StaticArgs) -> (Output, Grad, Grad) {
+        if let Some(SharedCache {
+            value: Some(f),
+            grad: Some(df),
+            conj_grad: Some(dfdconjz),
+            ..
+        }) = self.0.cache_at(x, static_args).as_deref()
+        {
+            return (f.clone(), df.clone(), dfdconjz.clone());
+        }
+
+        let (f, df, dfdconjz) = self.0.f.eval_all(x, static_args);
+        if let Some(mut cache) = self.0.cache_at(x, static_args) {
+            cache.value = Some(f.clone());
+            cache.grad = Some(df.clone());
+            cache.conj_grad = Some(dfdconjz.clone());
+        }
+        (f, df, dfdconjz)
+    }
+}
+
+// checked evaluation bypasses the cache, whose results were not checked
+impl<StaticArgs, Input, Output, Grad, T> TryAutoDiffable<StaticArgs> for ADShared<StaticArgs, T>
+where
+    T: TryAutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    Input: GradientType<Output, GradientType = Grad>,
+    Self: AutoDiffable<StaticArgs, Input = Input, Output = Output>,
+    // ensure the results of the shared function can be checked
+    Output: CheckValue,
+    Grad: CheckValue,
+{
+    fn try_eval(&self, x: &Input, static_args: &StaticArgs) -> Result<Output, AutoDiffError> {
+        self.0.f.try_eval(x, static_args)
+    }
+
+    fn try_eval_all(
+        &self,
+        x: &Input,
+        static_args: &StaticArgs,
+    ) -> Result<(Output, Grad, Grad), AutoDiffError> {
+        self.0.f.try_eval_all(x, static_args)
+    }
+}
+
+impl<StaticArgs, T> ForwardDiffable<StaticArgs> for ADShared<StaticArgs, T>
+where
+    T: ForwardDiffable<StaticArgs>,
+    T::Input: GradientType<T::Output>,
+{
+    fn eval_forward(&self, x: &T::Input, static_args: &StaticArgs) -> T::Output {
+        self.0.f.eval_forward(x, static_args)
+    }
+
+    fn eval_forward_grad(
+        &self,
+        x: &T::Input,
+        dx: &T::Input,
+        static_args: &StaticArgs,
+    ) -> (T::Output, T::Output) {
+        self.0.f.eval_forward_grad(x, dx, static_args)
+    }
+
+    fn forward_grad(&self, x: &T::Input, dx: &T::Input, static_args: &StaticArgs) -> T::Output {
+        self.0.f.forward_grad(x, dx, static_args)
+    }
+
+    fn eval_forward_conj_grad(
+        &self,
+        x: &T::Input,
+        dx: &T::Input,
+        static_args: &StaticArgs,
+    ) -> (T::Output, T::Output) {
+        self.0.f.eval_forward_conj_grad(x, dx, static_args)
+    }
+
+    fn forward_conj_grad(
+        &self,
+        x: &T::Input,
+        dx: &T::Input,
+        static_args: &StaticArgs,
+    ) -> T::Output {
+        self.0.f.forward_conj_grad(x, dx, static_args)
+    }
+}
@@ -0,0 +1,613 @@
+use crate::autodiffable::{AutoDiffable, Diffable, ForwardDiffable};
+use crate::dynautodiff::DynDiff;
+use crate::funcs::Polynomial;
+use crate::gradienttype::GradientType;
+use crate::graphviz::type_label;
+use crate::symbolic::{Expr, Symbolic, SymbolicConstant};
+use crate::traits::{Conjugate, InstOne, InstZero, PossiblyComplex};
+use crate::tryautodiff::{CheckValue, TryAutoDiffable};
+use num::complex::Complex;
+use num::traits::{one, zero, One, Pow, Zero};
+use std::ops::{Add, Div, Neg, Sub};
+
+use crate as autodiff;
+use autodiff_derive::*;
+
+// the highest degree of the polynomials made by merging polynomials and integer powers, above
+// which they are left as they are
+const MAX_DEGREE: usize = 16;
+
+/// Scalars which a `DynExpr` can be a function of
+pub trait DynExprScalar:
+    Clone
+    + PartialEq
+    + std::fmt::Debug
+    + Zero
+    + One
+    + InstZero
+    + InstOne
+    + Sub<Self, Output = Self>
+    + Div<Self, Output = Self>
+    + Neg<Output = Self>
+    + Pow<Self, Output = Self>
+    + Conjugate<Output = Self>
+    + PossiblyComplex
+    + SymbolicConstant
+    + GradientType<Self, GradientType = Self>
+    + 'static
+{
+    /// The scalar as a small non-negative integer, if it is one, for the powers which can be
+    /// merged into polynomials
+    fn as_power(&self) -> Option<usize>;
+
+    /// The constant `c` of a constant operation as a scalar, e.g. the value of a named constant
+    fn from_constant<C>(c: &C) -> Self
+    where
+        Self: Add<C, Output = Self>,
+        C: Clone,
+    {
+        zero::<Self>().add(c.clone())
+    }
+}
+
+macro_rules! impl_dyn_expr_scalar {
+    ($($t:ty),*) => ($(
+        impl DynExprScalar for $t {
+            fn as_power(&self) -> Option<usize> {
+                match *self >= 0.0 && self.fract() == 0.0 && *self <= MAX_DEGREE as $t {
+                    true => Some(*self as usize),
+                    false => None,
+                }
+            }
+        }
+
+        impl DynExprScalar for Complex<$t> {
+            fn as_power(&self) -> Option<usize> {
+                match self.im == 0.0 {
+                    true => self.re.as_power(),
+                    false => None,
+                }
+            }
+        }
+    )*)
+}
+
+impl_dyn_expr_scalar!(f32, f64);
+
+/// A function of a scalar `x` as a tree of its operations, which can be simplified with
+/// `DynExpr::simplify`. Expressions are built from functions by `ToDynExpr`, e.g. with
+/// `AutoDiff::simplify`.
+///
+/// Operations which are not simplified, e.g. `sin`, are kept as opaque functions applied to a
+/// `DynExpr`, so that their arguments are still simplified.
+#[derive(FuncCompose, SimpleForwardDiffable, Debug)]
+pub enum DynExpr<StaticArgs, T>
+where
+    T: DynExprScalar,
+{
+    /// The input x
+    X,
+    Const(T),
+    Neg(Box<Self>),
+    Add(Box<Self>, Box<Self>),
+    Sub(Box<Self>, Box<Self>),
+    Mul(Box<Self>, Box<Self>),
+    Div(Box<Self>, Box<Self>),
+    /// f^p for a constant p
+    Pow(Box<Self>, T),
+    Conj(Box<Self>),
+    /// c_0 + c_1 * f + c_2 * f^2 + ..., with at least one coefficient
+    Poly(Vec<T>, Box<Self>),
+    /// An opaque function g applied to f, where the expression of g(x) is used to print it
+    Func(Expr, DynDiff<StaticArgs, T, T>, Box<Self>),
+}
+
+use DynExpr::*;
+
+impl<StaticArgs, T> Clone for DynExpr<StaticArgs, T>
+where
+    T: DynExprScalar,
+{
+    fn clone(&self) -> Self {
+        match self {
+            X => X,
+            Const(c) => Const(c.clone()),
+            Neg(f) => Neg(f.clone()),
+            Add(f, g) => Add(f.clone(), g.clone()),
+            Sub(f, g) => Sub(f.clone(), g.clone()),
+            Mul(f, g) => Mul(f.clone(), g.clone()),
+            Div(f, g) => Div(f.clone(), g.clone()),
+            Pow(f, p) => Pow(f.clone(), p.clone()),
+            Conj(f) => Conj(f.clone()),
+            Poly(c, f) => Poly(c.clone(), f.clone()),
+            Func(name, g, f) => Func(name.clone(), g.clone(), f.clone()),
+        }
+    }
+}
+
+impl<StaticArgs, T> DynExpr<StaticArgs, T>
+where
+    T: DynExprScalar,
+{
+    /// The opaque function `g` applied to `f`, printed as the expression of `g`
+    pub fn apply<G>(g: G, f: Self) -> Self
+    where
+        G: AutoDiffable<StaticArgs, Input = T, Output = T>
+            + ForwardDiffable<StaticArgs, Input = T, Output = T>
+            + Symbolic
+            + Clone
+            + 'static,
+    {
+        Func(g.symbolic(&Expr::var("x")), DynDiff::new(g), Box::new(f))
+    }
+
+    /// The opaque function `g` of x, printed by its type name, for functions without a
+    /// symbolic form
+    pub fn opaque<G>(g: G) -> Self
+    where
+        G: AutoDiffable<StaticArgs, Input = T, Output = T>
+            + ForwardDiffable<StaticArgs, Input = T, Output = T>
+            + Clone
+            + 'static,
+    {
+        let name = Expr::call(type_label::<G>(), vec![Expr::var("x")]);
+        Func(name, DynDiff::new(g), Box::new(X))
+    }
+
+    /// The composition self(inner(x)), i.e. the expression with every x replaced by `inner`
+    pub fn substitute(&self, inner: &Self) -> Self {
+        let sub = |f: &Self| Box::new(f.substitute(inner));
+        match self {
+            X => inner.clone(),
+            Const(c) => Const(c.clone()),
+            Neg(f) => Neg(sub(f)),
+            Add(f, g) => Add(sub(f), sub(g)),
+            Sub(f, g) => Sub(sub(f), sub(g)),
+            Mul(f, g) => Mul(sub(f), sub(g)),
+            Div(f, g) => Div(sub(f), sub(g)),
+            Pow(f, p) => Pow(sub(f), p.clone()),
+            Conj(f) => Conj(sub(f)),
+            Poly(c, f) => Poly(c.clone(), sub(f)),
+            Func(name, g, f) => Func(name.clone(), g.clone(), sub(f)),
+        }
+    }
+
+    /// The equivalent expression with identities removed (e.g. f * 1, f + 0, -(-f) and conj(f)
+    /// for real scalars), constants folded, and polynomials, integer powers and sums and products
+    /// of the same expression merged, e.g. 2 * (x + 1) + x * x becomes 2 + 2 * x + x^2.
+    ///
+    /// Opaque functions are not evaluated even at constant arguments, since they may depend on
+    /// the static args.
+    pub fn simplify(self) -> Self {
+        // each operation is rebuilt from its simplified operands by a constructor that only
+        // has to simplify that one operation
+        match self {
+            X | Const(_) => self,
+            Neg(f) => Self::neg(f.simplify()),
+            Add(f, g) => Self::add(f.simplify(), g.simplify()),
+            Sub(f, g) => Self::sub(f.simplify(), g.simplify()),
+            Mul(f, g) => Self::mul(f.simplify(), g.simplify()),
+            Div(f, g) => Self::div(f.simplify(), g.simplify()),
+            Pow(f, p) => Self::pow(f.simplify(), p),
+            Conj(f) => Self::conj(f.simplify()),
+            Poly(c, f) => Self::polynomial(c, f.simplify()),
+            Func(name, g, f) => Func(name, g, Box::new(f.simplify())),
+        }
+    }
+
+    // whether the expressions are the same, where opaque functions are never the same since
+    // they can't be compared
+    fn same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (X, X) => true,
+            (Const(a), Const(b)) => a == b,
+            (Neg(f), Neg(g)) | (Conj(f), Conj(g)) => f.same(g),
+            (Add(f1, g1), Add(f2, g2))
+            | (Sub(f1, g1), Sub(f2, g2))
+            | (Mul(f1, g1), Mul(f2, g2))
+            | (Div(f1, g1), Div(f2, g2)) => f1.same(f2) && g1.same(g2),
+            (Pow(f, p), Pow(g, q)) => p == q && f.same(g),
+            (Poly(a, f), Poly(b, g)) => a == b && f.same(g),
+            _ => false,
+        }
+    }
+
+    // the expression as a polynomial c of a base expression, e.g. 2 * f^2 is [0, 0, 2] of f.
+    // constants have no base, since they are polynomials of anything
+    fn polynomial_parts(&self) -> Option<(Vec<T>, &Self)> {
+        match self {
+            Const(_) => None,
+            Poly(c, f) => Some((c.clone(), f)),
+            Pow(f, p) => p.as_power().map(|n| (monomial(n), &**f)),
+            Mul(f, c) => match &**c {
+                Const(c) => f.polynomial_parts().map(|(a, f)| (scale(&a, c), f)),
+                _ => Some((monomial(1), self)),
+            },
+            Add(f, c) => match &**c {
+                Const(c) => f
+                    .polynomial_parts()
+                    .map(|(a, f)| (add_coeffs(a, vec![c.clone()]), f)),
+                _ => Some((monomial(1), self)),
+            },
+            Neg(f) => f
+                .polynomial_parts()
+                .map(|(a, f)| (a.into_iter().map(T::neg).collect(), f)),
+            _ => Some((monomial(1), self)),
+        }
+    }
+
+    // the polynomials of the same base of f and g, if they have one
+    fn common_base(f: &Self, g: &Self) -> Option<(Vec<T>, Vec<T>, Self)> {
+        let (a, f) = f.polynomial_parts()?;
+        let (b, g) = g.polynomial_parts()?;
+        match f.same(g) {
+            true => Some((a, b, f.clone())),
+            false => None,
+        }
+    }
+
+    fn neg(f: Self) -> Self {
+        match f {
+            Const(c) => Const(c.neg()),
+            Neg(f) => *f,
+            Mul(f, c) if matches!(*c, Const(_)) => Self::mul(*f, Self::neg(*c)),
+            Poly(c, f) => Self::polynomial(c.into_iter().map(T::neg).collect(), *f),
+            f => Neg(Box::new(f)),
+        }
+    }
+
+    fn add(f: Self, g: Self) -> Self {
+        match (f, g) {
+            (Const(a), Const(b)) => Const(a.add(b)),
+            (f, Const(b)) if b == zero() => f,
+            (Const(a), g) if a == zero() => g,
+            // constants are kept on the right, where they can be merged
+            (Const(a), g) => Self::add(g, Const(a)),
+            (Add(f, a), Const(b)) if matches!(*a, Const(_)) => {
+                Self::add(*f, Self::add(*a, Const(b)))
+            }
+            (Poly(mut c, f), Const(b)) => {
+                c[0] = c[0].clone().add(b);
+                Self::polynomial(c, *f)
+            }
+            (f, Neg(g)) => Self::sub(f, *g),
+            (f, g) => match Self::common_base(&f, &g) {
+                Some((a, b, base)) => Self::polynomial(add_coeffs(a, b), base),
+                None => Add(Box::new(f), Box::new(g)),
+            },
+        }
+    }
+
+    fn sub(f: Self, g: Self) -> Self {
+        match (f, g) {
+            (Const(a), Const(b)) => Const(a.sub(b)),
+            (f, Const(b)) => Self::add(f, Const(b.neg())),
+            (Const(a), g) if a == zero() => Self::neg(g),
+            (f, Neg(g)) => Self::add(f, *g),
+            (f, g) => match Self::common_base(&f, &g) {
+                Some((a, b, base)) => {
+                    Self::polynomial(add_coeffs(a, b.into_iter().map(T::neg).collect()), base)
+                }
+                None => Sub(Box::new(f), Box::new(g)),
+            },
+        }
+    }
+
+    fn mul(f: Self, g: Self) -> Self {
+        match (f, g) {
+            (Const(a), Const(b)) => Const(a.mul(b)),
+            (Const(a), _) | (_, Const(a)) if a == zero() => Const(a),
+            (f, Const(b)) if b == one() => f,
+            (Const(a), g) if a == one() => g,
+            // constants are kept on the right, where they can be merged
+            (Const(a), g) => Self::mul(g, Const(a)),
+            (Mul(f, a), Const(b)) if matches!(*a, Const(_)) => {
+                Self::mul(*f, Self::mul(*a, Const(b)))
+            }
+            (Neg(f), Const(b)) => Self::mul(*f, Const(b.neg())),
+            (Poly(c, f), Const(b)) => Self::polynomial(scale(&c, &b), *f),
+            (f, g) => match Self::common_base(&f, &g) {
+                Some((a, b, base)) if a.len() + b.len() - 2 <= MAX_DEGREE => {
+                    Self::polynomial(mul_coeffs(&a, &b), base)
+                }
+                _ => Mul(Box::new(f), Box::new(g)),
+            },
+        }
+    }
+
+    fn div(f: Self, g: Self) -> Self {
+        match (f, g) {
+            (Const(a), Const(b)) => Const(a.div(b)),
+            (f, Const(b)) if b == one() => f,
+            (f, Const(b)) => Self::mul(f, Const(one::<T>().div(b))),
+            (f, g) => Div(Box::new(f), Box::new(g)),
+        }
+    }
+
+    fn pow(f: Self, p: T) -> Self {
+        if p == zero() {
+            return Const(one());
+        }
+        if p == one() {
+            return f;
+        }
+
+        match (f, p.as_power()) {
+            (Const(c), _) => Const(c.pow(p)),
+            // (f^q)^n = f^(q n) for integers q and n, which for other q only holds where f^q is
+            // defined and away from the branch cuts of complex powers
+            (Pow(f, q), Some(_)) if q.as_power().is_some() => Self::pow(*f, q.mul(p)),
+            // integer powers are merged with polynomials of f, or kept as f^n
+            (f, Some(n)) => Self::polynomial(monomial(n), f),
+            (f, _) => Pow(Box::new(f), p),
+        }
+    }
+
+    fn conj(f: Self) -> Self {
+        if T::is_always_real() {
+            return f;
+        }
+
+        match f {
+            Const(c) => Const(c.conj()),
+            Conj(f) => *f,
+            f => Conj(Box::new(f)),
+        }
+    }
+
+    // the polynomial c of f, written as the simplest of a constant, a scaled and shifted f, a
+    // scaled power of f, or a polynomial, where polynomials of polynomials (or of scaled and
+    // shifted expressions) are merged
+    fn polynomial(mut c: Vec<T>, f: Self) -> Self {
+        while c.len() > 1 && c[c.len() - 1] == zero() {
+            c.pop();
+        }
+        let degree = c.len() - 1;
+
+        if let Const(x) = &f {
+            return Const(eval_poly(&c, x).0);
+        }
+        let composed = match f.polynomial_parts() {
+            Some((d, g)) if !std::ptr::eq(g, &f) && degree * (d.len() - 1) <= MAX_DEGREE => {
+                Some((compose_coeffs(&c, &d), g.clone()))
+            }
+            _ => None,
+        };
+        if let Some((c, g)) = composed {
+            return Self::polynomial(c, g);
+        }
+
+        match degree {
+            0 => Const(c.swap_remove(0)),
+            1 => {
+                let c0 = c[0].clone();
+                Self::add(Self::mul(f, Const(c.swap_remove(1))), Const(c0))
+            }
+            // the power with the same leading term, if it has no lower order terms
+            _ if c[..degree].iter().all(|ci| *ci == zero()) => {
+                let p = (0..degree).fold(zero::<T>(), |p, _| p.add(one()));
+                Self::mul(Pow(Box::new(f), p), Const(c.swap_remove(degree)))
+            }
+            _ => Poly(c, Box::new(f)),
+        }
+    }
+}
+
+// the coefficients of x^n
+fn monomial<T: DynExprScalar>(n: usize) -> Vec<T> {
+    let mut c = vec![zero(); n + 1];
+    c[n] = one();
+    c
+}
+
+fn scale<T: DynExprScalar>(a: &[T], s: &T) -> Vec<T> {
+    a.iter().map(|a| a.clone().mul(s.clone())).collect()
+}
+
+fn add_coeffs<T: DynExprScalar>(mut a: Vec<T>, mut b: Vec<T>) -> Vec<T> {
+    if a.len() < b.len() {
+        std::mem::swap(&mut a, &mut b);
+    }
+    for (i, bi) in b.into_iter().enumerate() {
+        a[i] = a[i].clone().add(bi);
+    }
+    a
+}
+
+fn mul_coeffs<T: DynExprScalar>(a: &[T], b: &[T]) -> Vec<T> {
+    let mut c: Vec<T> = vec![zero(); a.len() + b.len() - 1];
+    for (i, ai) in a.iter().enumerate() {
+        for (j, bj) in b.iter().enumerate() {
+            c[i + j] = c[i + j].clone().add(ai.clone().mul(bj.clone()));
+        }
+    }
+    c
+}
+
+// the coefficients of a(b(x)), by Horner's method
+fn compose_coeffs<T: DynExprScalar>(a: &[T], b: &[T]) -> Vec<T> {
+    a.iter().rev().fold(vec![zero()], |c, ai| {
+        add_coeffs(mul_coeffs(&c, b), vec![ai.clone()])
+    })
+}
+
+// the value and derivative of the polynomial c at x, by Horner's method
+fn eval_poly<T: DynExprScalar>(c: &[T], x: &T) -> (T, T) {
+    c.iter().rev().fold((zero(), zero()), |(p, dp), ci| {
+        (
+            p.clone().mul(x.clone()).add(ci.clone()),
+            dp.mul(x.clone()).add(p),
+        )
+    })
+}
+
+impl<StaticArgs, T> Diffable<StaticArgs> for DynExpr<StaticArgs, T>
+where
+    T: DynExprScalar,
+{
+    type Input = T;
+    type Output = T;
+}
+
+impl<StaticArgs, T> Symbolic for DynExpr<StaticArgs, T>
+where
+    T: DynExprScalar,
+{
+    fn symbolic(&self, x: &Expr) -> Expr {
+        let sym = |f: &Self| Box::new(f.symbolic(x));
+        match self {
+            X => x.clone(),
+            Const(c) => c.symbolic_constant(),
+            Neg(f) => Expr::Neg(sym(f)),
+            Add(f, g) => Expr::Add(sym(f), sym(g)),
+            Sub(f, g) => Expr::Sub(sym(f), sym(g)),
+            Mul(f, g) => Expr::Mul(sym(f), sym(g)),
+            Div(f, g) => Expr::Div(sym(f), sym(g)),
+            Pow(f, p) => Expr::Pow(sym(f), Box::new(p.symbolic_constant())),
+            Conj(f) => Expr::call("conj", vec![f.symbolic(x)]),
+            Poly(c, f) => Polynomial::<StaticArgs, T, T>::new(c.clone()).symbolic(&f.symbolic(x)),
+            Func(name, _, f) => name.substitute("x", &f.symbolic(x)),
+        }
+    }
+}
+
+impl<StaticArgs, T> std::fmt::Display for DynExpr<StaticArgs, T>
+where
+    T: DynExprScalar,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbolic(&Expr::var("x")))
+    }
+}
+
+impl<StaticArgs, T> AutoDiffable<StaticArgs> for DynExpr<StaticArgs, T>
+where
+    T: DynExprScalar,
+{
+    fn eval(&self, x: &T, static_args: &StaticArgs) -> T {
+        match self {
+            X => x.clone(),
+            Const(c) => c.clone(),
+            Neg(f) => f.eval(x, static_args).neg(),
+            Add(f, g) => f.eval(x, static_args).add(g.eval(x, static_args)),
+            Sub(f, g) => f.eval(x, static_args).sub(g.eval(x, static_args)),
+            Mul(f, g) => f.eval(x, static_args).mul(g.eval(x, static_args)),
+            Div(f, g) => f.eval(x, static_args).div(g.eval(x, static_args)),
+            Pow(f, p) => f.eval(x, static_args).pow(p.clone()),
+            Conj(f) => f.eval(x, static_args).conj(),
+            Poly(c, f) => eval_poly(c, &f.eval(x, static_args)).0,
+            Func(_, g, f) => g.eval(&f.eval(x, static_args), static_args),
+        }
+    }
+
+    fn eval_grad(&self, x: &T, static_args: &StaticArgs) -> (T, T) {
+        let (f, df, _) = self.eval_all(x, static_args);
+        (f, df)
+    }
+
+    fn eval_conj_grad(&self, x: &T, static_args: &StaticArgs) -> (T, T) {
+        let (f, _, dfdconjz) = self.eval_all(x, static_args);
+        (f, dfdconjz)
+    }
+
+    fn eval_all(&self, x: &T, static_args: &StaticArgs) -> (T, T, T) {
+        match self {
+            X => (x.clone(), one(), zero()),
+            Const(c) => (c.clone(), zero(), zero()),
+            Neg(f) => {
+                let (f, df, dfdconjz) = f.eval_all(x, static_args);
+                (f.neg(), df.neg(), dfdconjz.neg())
+            }
+            Add(f, g) => {
+                let (f, df, dfdconjz) = f.eval_all(x, static_args);
+                let (g, dg, dgdconjz) = g.eval_all(x, static_args);
+                (f.add(g), df.add(dg), dfdconjz.add(dgdconjz))
+            }
+            Sub(f, g) => {
+                let (f, df, dfdconjz) = f.eval_all(x, static_args);
+                let (g, dg, dgdconjz) = g.eval_all(x, static_args);
+                (f.sub(g), df.sub(dg), dfdconjz.sub(dgdconjz))
+            }
+            Mul(f, g) => {
+                // d(fg) = df * g + f * dg
+                let (f, df, dfdconjz) = f.eval_all(x, static_args);
+                let (g, dg, dgdconjz) = g.eval_all(x, static_args);
+                (
+                    f.clone().mul(g.clone()),
+                    df.mul(g.clone()).add(f.clone().mul(dg)),
+                    dfdconjz.mul(g).add(f.mul(dgdconjz)),
+                )
+            }
+            Div(f, g) => {
+                // d(f/g) = (df - f/g * dg) / g
+                let (f, df, dfdconjz) = f.eval_all(x, static_args);
+                let (g, dg, dgdconjz) = g.eval_all(x, static_args);
+                let q = f.div(g.clone());
+                (
+                    q.clone(),
+                    df.sub(q.clone().mul(dg)).div(g.clone()),
+                    dfdconjz.sub(q.mul(dgdconjz)).div(g),
+                )
+            }
+            Pow(f, p) => {
+                // d(f^p) = p * f^(p - 1) * df
+                let (f, df, dfdconjz) = f.eval_all(x, static_args);
+                let dgdf = p.clone().mul(f.clone().pow(p.clone().sub(one())));
+                (f.pow(p.clone()), df.mul(dgdf.clone()), dfdconjz.mul(dgdf))
+            }
+            Conj(f) => {
+                // Wirtinger derivatives dconj(f)/dz = conj(df/dconjz) and
+                // dconj(f)/dconjz = conj(df/dz)
+                let (f, df, dfdconjz) = f.eval_all(x, static_args);
+                (f.conj(), dfdconjz.conj(), df.conj())
+            }
+            Poly(c, f) => {
+                let (f, df, dfdconjz) = f.eval_all(x, static_args);
+                let (p, dpdf) = eval_poly(c, &f);
+                (p, df.mul(dpdf.clone()), dfdconjz.mul(dpdf))
+            }
+            Func(_, g, f) => {
+                let (f, df, dfdconjz) = f.eval_all(x, static_args);
+                let (g, dgdf, dgdconjf) = g.eval_all(&f, static_args);
+
+                if T::is_always_real() {
+                    // dg(f)/dz = dg/df * df/dz
+                    return (g, df.mul(dgdf.clone()), dfdconjz.mul(dgdf));
+                }
+
+                // dg(f)/dz = dg/df * df/dz + dg/dconjf * conj(df/dconjz)
+                // dg(f)/dconjz = dg/df * df/dconjz + dg/dconjf * conj(df/dz)
+                (
+                    g,
+                    df.clone()
+                        .mul(dgdf.clone())
+                        .add(dfdconjz.conj().mul(dgdconjf.clone())),
+                    dfdconjz.mul(dgdf).add(df.conj().mul(dgdconjf)),
+                )
+            }
+        }
+    }
+}
+
+// the simplified expression is checked as a whole, since its nodes aren't those of the original
+impl<StaticArgs, T> TryAutoDiffable<StaticArgs> for DynExpr<StaticArgs, T> where
+    T: DynExprScalar + CheckValue
+{
+}
+
+/// Functions of a scalar which can be written as a `DynExpr` to be simplified
+pub trait ToDynExpr<StaticArgs, T>
+where
+    T: DynExprScalar,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T>;
+}
+
+impl<StaticArgs, T> ToDynExpr<StaticArgs, T> for DynExpr<StaticArgs, T>
+where
+    T: DynExprScalar,
+{
+    fn to_dyn_expr(&self) -> DynExpr<StaticArgs, T> {
+        self.clone()
+    }
+}
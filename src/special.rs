@@ -0,0 +1,251 @@
+// numerical implementations of the special functions used by the Erf, Erfc, Gamma, Lgamma, Digamma
+// and Trigamma traits. Everything is computed in f64, f32 implementations just cast.
+
+use std::f64::consts::PI;
+
+// 2/sqrt(pi)
+const FRAC_2_SQRT_PI: f64 = std::f64::consts::FRAC_2_SQRT_PI;
+
+// erf(x) is computed with the series below this point, and as 1 - erfc(x) above it
+const ERF_SERIES_THRESHOLD: f64 = 2.5;
+
+// erfc(x) is computed with the continued fraction above this point, and as 1 - erf(x) below it,
+// where erfc(x) is still large enough for the subtraction not to lose relative precision
+const ERFC_CF_THRESHOLD: f64 = 0.5;
+
+// erf(x) for x >= 0 via the series
+// erf(x) = 2/sqrt(pi) * exp(-x^2) * sum_n 2^n x^(2n+1) / (1 * 3 * ... * (2n+1))
+// which has only positive terms, so it does not suffer from cancellation
+fn erf_series(x: f64) -> f64 {
+    let x2 = x * x;
+    let mut term = x;
+    let mut sum = x;
+    let mut n = 0.0;
+    while term > sum * f64::EPSILON {
+        n += 1.0;
+        term *= 2.0 * x2 / (2.0 * n + 1.0);
+        sum += term;
+    }
+    FRAC_2_SQRT_PI * (-x2).exp() * sum
+}
+
+// erfc(x) for x >= ERFC_CF_THRESHOLD via the continued fraction
+// erfc(x) = exp(-x^2)/sqrt(pi) * 1/(x + (1/2)/(x + 1/(x + (3/2)/(x + 2/(x + ...)))))
+// evaluated with the modified Lentz algorithm
+fn erfc_continued_fraction(x: f64) -> f64 {
+    let tiny = 1e-300;
+    let mut f = x;
+    let mut c = x;
+    let mut d = 0.0;
+    let mut n = 0.0;
+    loop {
+        n += 0.5;
+        d = x + n * d;
+        d = if d == 0.0 { 1.0 / tiny } else { 1.0 / d };
+        c = x + n / c;
+        if c == 0.0 {
+            c = tiny;
+        }
+        let delta = c * d;
+        f *= delta;
+        if (delta - 1.0).abs() < f64::EPSILON || n > 500.0 {
+            break;
+        }
+    }
+    0.5 * FRAC_2_SQRT_PI * (-x * x).exp() / f
+}
+
+pub fn erf(x: f64) -> f64 {
+    if x.is_nan() {
+        x
+    } else if x < 0.0 {
+        -erf(-x)
+    } else if x < ERF_SERIES_THRESHOLD {
+        erf_series(x)
+    } else {
+        1.0 - erfc_continued_fraction(x)
+    }
+}
+
+pub fn erfc(x: f64) -> f64 {
+    if x.is_nan() {
+        x
+    } else if x < 0.0 {
+        2.0 - erfc(-x)
+    } else if x < ERFC_CF_THRESHOLD {
+        1.0 - erf_series(x)
+    } else {
+        erfc_continued_fraction(x)
+    }
+}
+
+// Lanczos approximation with g = 7 and 9 coefficients, accurate to about 1e-15
+const LANCZOS_G: f64 = 7.0;
+const LANCZOS_COEFFICIENTS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+// ln(gamma(x)) for x >= 0.5 using the Lanczos approximation
+fn lanczos_ln_gamma(x: f64) -> f64 {
+    let x = x - 1.0;
+    let mut a = LANCZOS_COEFFICIENTS[0];
+    for (i, c) in LANCZOS_COEFFICIENTS.iter().enumerate().skip(1) {
+        a += c / (x + i as f64);
+    }
+    let t = x + LANCZOS_G + 0.5;
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + a.ln()
+}
+
+pub fn gamma(x: f64) -> f64 {
+    if x == 0.0 {
+        // the pole at zero, with the sign of the zero
+        return 1.0 / x;
+    }
+    if x < 0.0 && x == x.floor() {
+        // the poles at the negative integers, approached from either side with either sign
+        return f64::NAN;
+    }
+    if x < 0.5 {
+        // reflection formula gamma(x) gamma(1 - x) = pi / sin(pi x)
+        PI / ((PI * x).sin() * gamma(1.0 - x))
+    } else {
+        lanczos_ln_gamma(x).exp()
+    }
+}
+
+// ln|gamma(x)|
+pub fn lgamma(x: f64) -> f64 {
+    if x <= 0.0 && x == x.floor() {
+        // |gamma(x)| diverges at the non-positive integers
+        return f64::INFINITY;
+    }
+    if x < 0.5 {
+        // reflection formula gamma(x) gamma(1 - x) = pi / sin(pi x)
+        (PI / (PI * x).sin().abs()).ln() - lgamma(1.0 - x)
+    } else {
+        lanczos_ln_gamma(x)
+    }
+}
+
+pub fn digamma(x: f64) -> f64 {
+    if x <= 0.0 && x == x.floor() {
+        // poles at the non-positive integers
+        return f64::NAN;
+    }
+    if x < 0.5 {
+        // reflection formula digamma(1 - x) - digamma(x) = pi / tan(pi x)
+        return digamma(1.0 - x) - PI / (PI * x).tan();
+    }
+
+    // shift x up with digamma(x) = digamma(x + 1) - 1/x, then use the asymptotic series
+    // digamma(x) ~ ln(x) - 1/(2x) - 1/(12x^2) + 1/(120x^4) - 1/(252x^6) + 1/(240x^8) - 1/(132x^10)
+    let mut x = x;
+    let mut result = 0.0;
+    while x < 10.0 {
+        result -= 1.0 / x;
+        x += 1.0;
+    }
+    let inv2 = 1.0 / (x * x);
+    result + x.ln()
+        - 0.5 / x
+        - inv2
+            * (1.0 / 12.0
+                - inv2
+                    * (1.0 / 120.0
+                        - inv2 * (1.0 / 252.0 - inv2 * (1.0 / 240.0 - inv2 * (1.0 / 132.0)))))
+}
+
+pub fn trigamma(x: f64) -> f64 {
+    if x <= 0.0 && x == x.floor() {
+        // poles at the non-positive integers
+        return f64::NAN;
+    }
+    if x < 0.5 {
+        // reflection formula trigamma(1 - x) + trigamma(x) = pi^2 / sin^2(pi x)
+        let s = (PI * x).sin();
+        return PI * PI / (s * s) - trigamma(1.0 - x);
+    }
+
+    // shift x up with trigamma(x) = trigamma(x + 1) + 1/x^2, then use the asymptotic series
+    // trigamma(x) ~ 1/x + 1/(2x^2) + 1/(6x^3) - 1/(30x^5) + 1/(42x^7) - 1/(30x^9) + 5/(66x^11)
+    let mut x = x;
+    let mut result = 0.0;
+    while x < 10.0 {
+        result += 1.0 / (x * x);
+        x += 1.0;
+    }
+    let inv = 1.0 / x;
+    let inv2 = inv * inv;
+    result
+        + inv
+        + 0.5 * inv2
+        + inv
+            * inv2
+            * (1.0 / 6.0
+                - inv2
+                    * (1.0 / 30.0
+                        - inv2 * (1.0 / 42.0 - inv2 * (1.0 / 30.0 - inv2 * (5.0 / 66.0)))))
+}
+
+#[test]
+fn test_special() {
+    fn assert_close(left: f64, right: f64) {
+        assert!(
+            (left - right).abs() <= 1e-13 * right.abs().max(1.0),
+            "assertion failed: `(left ~= right)`\n  left: `{:?}`,\n right: `{:?}`",
+            left,
+            right
+        );
+    }
+
+    // reference values from Abramowitz and Stegun / mpmath
+    assert_eq!(erf(0.0), 0.0);
+    assert_close(erf(0.5), 0.520_499_877_813_046_5);
+    assert_close(erf(1.0), 0.842_700_792_949_714_9);
+    assert_close(erf(-2.0), -0.995_322_265_018_952_7);
+    assert_close(erf(3.0), 0.999_977_909_503_001_4);
+    assert_close(erfc(0.5), 0.479_500_122_186_953_5);
+    assert_close(erfc(3.0), 2.209_049_699_858_544e-5);
+    assert_close(erfc(6.0), 2.151_973_671_249_891_3e-17);
+    assert_close(erfc(-1.0), 1.842_700_792_949_715);
+    // erfc keeps its relative precision where it is much smaller than erf
+    for (x, expected) in [
+        (1.0, 0.157_299_207_050_285_13),
+        (2.0, 4.677_734_981_047_266e-3),
+    ] {
+        assert!((erfc(x) - expected).abs() <= 1e-14 * expected);
+    }
+
+    assert_close(gamma(5.0), 24.0);
+    assert_close(gamma(0.5), PI.sqrt());
+    assert_close(gamma(-1.5), 4.0 * PI.sqrt() / 3.0);
+    assert_close(lgamma(100.0), 359.134_205_369_575_4);
+    assert_close(lgamma(-0.5), (2.0 * PI.sqrt()).ln());
+    // poles at the non-positive integers
+    assert_eq!(gamma(0.0), f64::INFINITY);
+    assert_eq!(gamma(-0.0), f64::NEG_INFINITY);
+    assert!(gamma(-1.0).is_nan());
+    assert!(gamma(-4.0).is_nan());
+    assert_eq!(lgamma(0.0), f64::INFINITY);
+    assert_eq!(lgamma(-3.0), f64::INFINITY);
+
+    // digamma(1) = -euler_gamma, digamma(1/2) = -euler_gamma - 2 ln(2)
+    let euler_gamma = 0.577_215_664_901_532_9;
+    assert_close(digamma(1.0), -euler_gamma);
+    assert_close(digamma(0.5), -euler_gamma - 2.0 * 2.0_f64.ln());
+    assert_close(digamma(-0.5), -euler_gamma - 2.0 * 2.0_f64.ln() + 2.0);
+    assert!(digamma(-1.0).is_nan());
+
+    // trigamma(1) = pi^2/6, trigamma(1/2) = pi^2/2
+    assert_close(trigamma(1.0), PI * PI / 6.0);
+    assert_close(trigamma(0.5), PI * PI / 2.0);
+    assert_close(trigamma(-0.5), PI * PI / 2.0 + 4.0);
+}
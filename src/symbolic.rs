@@ -0,0 +1,403 @@
+use crate::adops::*;
+use crate::funcs::{Identity, Monomial, Polynomial};
+use crate::traits::{InstOne, InstZero};
+use num::complex::Complex;
+use num::traits::Pow;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// A symbolic math expression, used to print functions in infix notation (with `Display`) or as
+/// LaTeX (with `Expr::to_latex`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// A variable, e.g. the input `x`
+    Var(String),
+    /// A constant, given by its name or its value
+    Const(String),
+    Neg(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+    Pow(Box<Expr>, Box<Expr>),
+    /// A function applied to its arguments, e.g. `sin(x)` or `log(x, 2)`
+    Call(String, Vec<Expr>),
+}
+
+impl Expr {
+    pub fn var(name: &str) -> Self {
+        Expr::Var(name.to_string())
+    }
+
+    pub fn call(name: &str, args: Vec<Expr>) -> Self {
+        Expr::Call(name.to_string(), args)
+    }
+
+    /// The expression with every occurrence of the variable `name` replaced by `e`
+    pub fn substitute(&self, name: &str, e: &Expr) -> Expr {
+        let sub = |a: &Expr| Box::new(a.substitute(name, e));
+        match self {
+            Expr::Var(v) if v == name => e.clone(),
+            Expr::Var(..) | Expr::Const(..) => self.clone(),
+            Expr::Neg(a) => Expr::Neg(sub(a)),
+            Expr::Add(a, b) => Expr::Add(sub(a), sub(b)),
+            Expr::Sub(a, b) => Expr::Sub(sub(a), sub(b)),
+            Expr::Mul(a, b) => Expr::Mul(sub(a), sub(b)),
+            Expr::Div(a, b) => Expr::Div(sub(a), sub(b)),
+            Expr::Pow(a, b) => Expr::Pow(sub(a), sub(b)),
+            Expr::Call(f, args) => Expr::Call(
+                f.clone(),
+                args.iter().map(|a| a.substitute(name, e)).collect(),
+            ),
+        }
+    }
+
+    /// The binding strength of the outermost operation, where operands of a weaker operation need
+    /// parentheses
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Add(..) | Expr::Sub(..) => 1,
+            Expr::Mul(..) | Expr::Div(..) => 2,
+            Expr::Neg(..) => 3,
+            Expr::Pow(..) => 4,
+            // negative and complex constants behave like negations and sums
+            Expr::Const(c) if c.starts_with('-') => 3,
+            Expr::Const(c) if c.chars().skip(1).any(|c| "+- ".contains(c)) => 1,
+            Expr::Var(..) | Expr::Const(..) | Expr::Call(..) => 5,
+        }
+    }
+
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>, min_precedence: u8) -> fmt::Result {
+        if self.precedence() < min_precedence {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+
+    fn latex_operand(&self, min_precedence: u8) -> String {
+        if self.precedence() < min_precedence {
+            format!("\\left({}\\right)", self.to_latex())
+        } else {
+            self.to_latex()
+        }
+    }
+
+    /// Renders the expression as LaTeX math, e.g. `\frac{\sin\left(x\right)}{x^{2}}`
+    pub fn to_latex(&self) -> String {
+        match self {
+            Expr::Var(v) | Expr::Const(v) => v.clone(),
+            Expr::Neg(a) => format!("-{}", a.latex_operand(3)),
+            Expr::Add(a, b) => format!("{} + {}", a.latex_operand(1), b.latex_operand(1)),
+            Expr::Sub(a, b) => format!("{} - {}", a.latex_operand(1), b.latex_operand(2)),
+            Expr::Mul(a, b) => format!("{} \\cdot {}", a.latex_operand(2), b.latex_operand(2)),
+            Expr::Div(a, b) => format!("\\frac{{{}}}{{{}}}", a.to_latex(), b.to_latex()),
+            Expr::Pow(a, b) => format!("{}^{{{}}}", a.latex_operand(5), b.to_latex()),
+            Expr::Call(name, args) => {
+                let args: Vec<String> = args.iter().map(Expr::to_latex).collect();
+                let arg = |i: usize| args.get(i).cloned().unwrap_or_default();
+                match name.as_str() {
+                    "exp" => format!("e^{{{}}}", arg(0)),
+                    "abs" => format!("\\left|{}\\right|", arg(0)),
+                    "abs_sqr" => format!("\\left|{}\\right|^{{2}}", arg(0)),
+                    "conj" => format!("\\overline{{{}}}", arg(0)),
+                    "log" => format!("\\log_{{{}}}\\left({}\\right)", arg(1), arg(0)),
+                    "log2" => format!("\\log_{{2}}\\left({}\\right)", arg(0)),
+                    "log10" => format!("\\log_{{10}}\\left({}\\right)", arg(0)),
+                    _ => format!("{}\\left({}\\right)", latex_function(name), args.join(", ")),
+                }
+            }
+        }
+    }
+}
+
+// the LaTeX name of a function applied with parentheses
+fn latex_function(name: &str) -> String {
+    match name {
+        "sin" | "cos" | "tan" | "sinh" | "cosh" | "tanh" | "ln" | "min" | "max" => {
+            format!("\\{}", name)
+        }
+        "asin" | "acos" | "atan" => format!("\\arc{}", &name[1..]),
+        "signum" => "\\operatorname{sgn}".to_string(),
+        "gamma" => "\\Gamma".to_string(),
+        "lgamma" => "\\ln\\Gamma".to_string(),
+        "digamma" => "\\psi".to_string(),
+        "sigmoid" => "\\sigma".to_string(),
+        _ => format!("\\operatorname{{{}}}", name.replace('_', "\\_")),
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Var(v) | Expr::Const(v) => write!(f, "{}", v),
+            Expr::Neg(a) => {
+                write!(f, "-")?;
+                a.fmt_operand(f, 3)
+            }
+            Expr::Add(a, b) => {
+                a.fmt_operand(f, 1)?;
+                write!(f, " + ")?;
+                b.fmt_operand(f, 1)
+            }
+            Expr::Sub(a, b) => {
+                a.fmt_operand(f, 1)?;
+                write!(f, " - ")?;
+                b.fmt_operand(f, 2)
+            }
+            Expr::Mul(a, b) => {
+                a.fmt_operand(f, 2)?;
+                write!(f, " * ")?;
+                b.fmt_operand(f, 2)
+            }
+            Expr::Div(a, b) => {
+                a.fmt_operand(f, 2)?;
+                write!(f, " / ")?;
+                b.fmt_operand(f, 3)
+            }
+            Expr::Pow(a, b) => {
+                // a^b^c = a^(b^c)
+                a.fmt_operand(f, 5)?;
+                write!(f, "^")?;
+                b.fmt_operand(f, 4)
+            }
+            Expr::Call(name, args) => {
+                write!(f, "{}(", name)?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Functions that can be written as a symbolic expression of their input
+pub trait Symbolic {
+    /// The expression of this function applied to the input `x`, where `x` is the expression of
+    /// the input, e.g. `Expr::var("x")`, or the expression of an inner function for compositions
+    fn symbolic(&self, x: &Expr) -> Expr;
+
+    /// The function of `x` as LaTeX math
+    fn to_latex(&self) -> String {
+        self.symbolic(&Expr::var("x")).to_latex()
+    }
+}
+
+/// Constants of the constant operations (e.g. `ADConstantMul`) that can be written symbolically,
+/// either by their value or, when wrapped in `Named`, by their name
+pub trait SymbolicConstant {
+    fn symbolic_constant(&self) -> Expr;
+}
+
+macro_rules! impl_symbolic_constant {
+    ($($t:ty),*) => ($(
+        impl SymbolicConstant for $t {
+            fn symbolic_constant(&self) -> Expr {
+                Expr::Const(self.to_string())
+            }
+        }
+    )*)
+}
+
+impl_symbolic_constant!(
+    f32,
+    f64,
+    i8,
+    i16,
+    i32,
+    i64,
+    u8,
+    u16,
+    u32,
+    u64,
+    isize,
+    usize,
+    Complex<f32>,
+    Complex<f64>
+);
+
+/// A constant with a symbolic name, which is printed instead of its value, e.g.
+/// `f * Named::new("k", 2.5)` prints as `f * k` and evaluates as `f * 2.5`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Named<T> {
+    pub name: String,
+    pub value: T,
+}
+
+impl<T> Named<T> {
+    pub fn new(name: &str, value: T) -> Self {
+        Named {
+            name: name.to_string(),
+            value,
+        }
+    }
+}
+
+impl<T> SymbolicConstant for Named<T> {
+    fn symbolic_constant(&self) -> Expr {
+        Expr::Const(self.name.clone())
+    }
+}
+
+// arithmetic between named constants names the result after the expression, e.g. for the
+// exponent p - 1 of the derivative of f^p
+macro_rules! impl_named_binop {
+    ($($trait:ident, $method:ident, $variant:ident);* $(;)?) => ($(
+        impl<T: $trait<T, Output = T>> $trait<Named<T>> for Named<T> {
+            type Output = Named<T>;
+
+            fn $method(self, other: Named<T>) -> Named<T> {
+                let name = Expr::$variant(
+                    Box::new(self.symbolic_constant()),
+                    Box::new(other.symbolic_constant()),
+                );
+                Named {
+                    name: format!("({})", name),
+                    value: self.value.$method(other.value),
+                }
+            }
+        }
+    )*)
+}
+
+impl_named_binop!(Add, add, Add; Sub, sub, Sub; Mul, mul, Mul; Div, div, Div);
+
+impl<T: InstZero> InstZero for Named<T> {
+    fn zero(&self) -> Self {
+        Named {
+            name: "0".to_string(),
+            value: self.value.zero(),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+}
+
+impl<T: InstOne> InstOne for Named<T> {
+    fn one(&self) -> Self {
+        Named {
+            name: "1".to_string(),
+            value: self.value.one(),
+        }
+    }
+}
+
+// scalars act on named constants through their values, so that named constants can be used in
+// the constant operations
+macro_rules! impl_named_scalar_ops {
+    ($($t:ty),*) => ($(
+        impl Add<Named<$t>> for $t {
+            type Output = $t;
+            fn add(self, other: Named<$t>) -> $t {
+                self + other.value
+            }
+        }
+
+        impl Sub<Named<$t>> for $t {
+            type Output = $t;
+            fn sub(self, other: Named<$t>) -> $t {
+                self - other.value
+            }
+        }
+
+        impl Mul<Named<$t>> for $t {
+            type Output = $t;
+            fn mul(self, other: Named<$t>) -> $t {
+                self * other.value
+            }
+        }
+
+        impl Div<Named<$t>> for $t {
+            type Output = $t;
+            fn div(self, other: Named<$t>) -> $t {
+                self / other.value
+            }
+        }
+
+        impl Pow<Named<$t>> for $t {
+            type Output = $t;
+            fn pow(self, other: Named<$t>) -> $t {
+                self.pow(other.value)
+            }
+        }
+    )*)
+}
+
+impl_named_scalar_ops!(f32, f64, Complex<f32>, Complex<f64>);
+
+// Display for the nodes, in infix notation of the input `x`
+macro_rules! impl_display {
+    ($($name:ident<$($param:ident),+>),* $(,)?) => ($(
+        impl<$($param),+> fmt::Display for $name<$($param),+>
+        where
+            Self: Symbolic,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.symbolic(&Expr::var("x")))
+            }
+        }
+    )*)
+}
+
+impl_display!(
+    Identity<S, I>,
+    Polynomial<S, I, O>,
+    Monomial<S, I, P>,
+    ADCoerce<A, I, O>,
+    ADAppendStaticArgs<A, S>,
+    ADPrependStaticArgs<A, S>,
+    ADAdd<A, B>,
+    ADSub<A, B>,
+    ADMul<A, B>,
+    ADDiv<A, B>,
+    ADNeg<A>,
+    ADCompose<A, B>,
+    ADConstantAdd<A, B>,
+    ADConstantSub<A, B>,
+    ADConstantMul<A, B>,
+    ADConstantDiv<A, B>,
+    ADConstantPow<A, B>,
+    ADAbs<A>,
+    ADAbsSqr<A>,
+    ADSignum<A>,
+    ADConjugate<A>,
+    ADLog<A, B>,
+    ADPow<A, B>,
+    ADExp<A>,
+    ADLn<A>,
+    ADLog2<A>,
+    ADLog10<A>,
+    ADSin<A>,
+    ADCos<A>,
+    ADTan<A>,
+    ADAsin<A>,
+    ADAcos<A>,
+    ADAtan<A>,
+    ADSinh<A>,
+    ADCosh<A>,
+    ADTanh<A>,
+    ADAsinh<A>,
+    ADAcosh<A>,
+    ADAtanh<A>,
+    ADAtan2<A, B>,
+    ADHypot<A, B>,
+    ADMin<A, B>,
+    ADMax<A, B>,
+    ADErf<A>,
+    ADErfc<A>,
+    ADGamma<A>,
+    ADLgamma<A>,
+    ADDigamma<A>,
+    ADRelu<A>,
+    ADLeakyRelu<A, B>,
+    ADElu<A, B>,
+    ADSigmoid<A>,
+    ADSoftplus<A>,
+    ADGelu<A>,
+);
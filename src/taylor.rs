@@ -0,0 +1,788 @@
+use crate::diffable::Diffable;
+use crate::traits::{
+    Acos, Acosh, Asin, Asinh, Atan, Atanh, Cos, Cosh, Erf, Erfc, Exp, Gelu, InstOne, InstZero, Ln,
+    Log10, Log2, NormCdf, Sigmoid, Sin, Sinh, Softplus, Sqrt, Tan, Tanh,
+};
+use num::traits::{FloatConst, FromPrimitive, Pow, ToPrimitive};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A power series a_0 + a_1 t + ... + a_(K-1) t^(K-1) truncated after K coefficients.
+///
+/// `TaylorDiffable::eval_taylor_series` propagates a series through a function, so that
+/// passing `Taylor::variable(x0)`, i.e. x0 + t, gives the Taylor coefficients of f(x0 + t), the
+/// kth of which is the kth derivative of f at x0 divided by k!. Every operation computes the
+/// coefficients of its result from the recurrence of its power series, so the cost of any
+/// expression is quadratic in K rather than exponential as with nested forward mode.
+///
+/// A series has at least its constant term, so using one with K = 0 is a compile error.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Taylor<T, const K: usize>(pub [T; K]);
+
+/// The operations on the coefficients of a `Taylor` series needed to propagate it through
+/// arithmetic
+pub trait TaylorCoefficient:
+    Clone
+    + InstZero
+    + InstOne
+    + FromPrimitive
+    + Sub<Self, Output = Self>
+    + Div<Self, Output = Self>
+    + Neg<Output = Self>
+{
+}
+
+impl<T> TaylorCoefficient for T where
+    T: Clone
+        + InstZero
+        + InstOne
+        + FromPrimitive
+        + Sub<T, Output = T>
+        + Div<T, Output = T>
+        + Neg<Output = T>
+{
+}
+
+// the integer k as a coefficient
+fn int<T: FromPrimitive>(k: usize) -> T {
+    T::from_usize(k).expect("the order of a Taylor series must be representable")
+}
+
+// the coefficients computed in order, which are always exactly K
+fn collect<T, const K: usize>(coeffs: Vec<T>) -> [T; K] {
+    match coeffs.try_into() {
+        Ok(coeffs) => coeffs,
+        Err(_) => unreachable!("a Taylor series has exactly K coefficients"),
+    }
+}
+
+// sum_(j=0..=k) a_j b_(k-j), the kth coefficient of the product of two series
+fn cauchy<T: TaylorCoefficient>(a: &[T], b: &[T], k: usize) -> T {
+    (1..=k).fold(a[0].clone().mul(b[k].clone()), |acc, j| {
+        acc.add(a[j].clone().mul(b[k - j].clone()))
+    })
+}
+
+// the coefficients of u given u_0 and u' = w a', where w_(k-1) is computed from
+// u_0, ..., u_(k-1), using
+//
+// u_k = 1/k sum_(j=1..=k) j a_j w_(k-j)
+fn integrate<T: TaylorCoefficient, const K: usize>(
+    a: &[T; K],
+    u0: T,
+    mut w: impl FnMut(&[T]) -> T,
+) -> [T; K] {
+    let mut u = Vec::with_capacity(K);
+    let mut ws: Vec<T> = Vec::with_capacity(K);
+    if K > 0 {
+        u.push(u0);
+    }
+    for k in 1..K {
+        ws.push(w(&u));
+        let uk = (2..=k).fold(a[1].clone().mul(ws[k - 1].clone()), |acc, j| {
+            acc.add(int::<T>(j).mul(a[j].clone()).mul(ws[k - j].clone()))
+        });
+        u.push(uk.div(int(k)));
+    }
+    collect(u)
+}
+
+// the coefficients of u given u_0 and u' = w a' for a series w that does not depend on u
+fn integrate_series<T: TaylorCoefficient, const K: usize>(a: &[T; K], u0: T, w: &[T; K]) -> [T; K] {
+    integrate(a, u0, |u| w[u.len() - 1].clone())
+}
+
+impl<T, const K: usize> Taylor<T, K> {
+    pub fn new(coeffs: [T; K]) -> Self {
+        Taylor(coeffs)
+    }
+
+    // the constant term a_0, which every series needs, so that K = 0 is rejected at compile time
+    // wherever it is used
+    fn a0(&self) -> &T {
+        const { assert!(K > 0, "a Taylor series needs at least one coefficient") };
+        &self.0[0]
+    }
+
+    /// The coefficients a_0, ..., a_(K-1)
+    pub fn coefficients(&self) -> &[T; K] {
+        &self.0
+    }
+
+    /// Applies f to every coefficient
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Taylor<U, K> {
+        Taylor(self.0.map(f))
+    }
+
+    /// self * b for a constant b
+    pub fn mul_constant<B: Clone, U>(self, b: B) -> Taylor<U, K>
+    where
+        T: Mul<B, Output = U>,
+    {
+        self.map(|a| a.mul(b.clone()))
+    }
+
+    /// self / b for a constant b
+    pub fn div_constant<B: Clone, U>(self, b: B) -> Taylor<U, K>
+    where
+        T: Div<B, Output = U>,
+    {
+        self.map(|a| a.div(b.clone()))
+    }
+}
+
+impl<T: TaylorCoefficient, const K: usize> Taylor<T, K> {
+    /// The series c + 0 t + ... of a constant
+    pub fn constant(c: T) -> Self {
+        let zero = c.zero();
+        Taylor(std::array::from_fn(|k| {
+            if k == 0 {
+                c.clone()
+            } else {
+                zero.clone()
+            }
+        }))
+    }
+
+    /// The series x0 + t of the variable expanded around x0
+    pub fn variable(x0: T) -> Self {
+        let (zero, one) = (x0.zero(), x0.one());
+        Taylor(std::array::from_fn(|k| match k {
+            0 => x0.clone(),
+            1 => one.clone(),
+            _ => zero.clone(),
+        }))
+    }
+
+    /// The derivatives k! a_k of the function the series was expanded from
+    pub fn derivatives(&self) -> [T; K] {
+        let mut factorial = self.a0().one();
+        std::array::from_fn(|k| {
+            if k > 1 {
+                factorial = factorial.clone().mul(int::<T>(k));
+            }
+            self.0[k].clone().mul(factorial.clone())
+        })
+    }
+
+    /// self + b for a constant b
+    pub fn add_constant<B>(mut self, b: B) -> Self
+    where
+        T: Add<B, Output = T>,
+    {
+        if K > 0 {
+            self.0[0] = self.0[0].clone().add(b);
+        }
+        self
+    }
+
+    /// self - b for a constant b
+    pub fn sub_constant<B>(mut self, b: B) -> Self
+    where
+        T: Sub<B, Output = T>,
+    {
+        if K > 0 {
+            self.0[0] = self.0[0].clone().sub(b);
+        }
+        self
+    }
+
+    // sin and cos of the series, which are computed together as
+    //
+    // s_k = 1/k sum_(j=1..=k) j a_j c_(k-j)
+    // c_k = -1/k sum_(j=1..=k) j a_j s_(k-j)
+    //
+    // or with +c_k for sinh and cosh
+    fn sin_cos(&self, s0: T, c0: T, hyperbolic: bool) -> (Self, Self) {
+        let a = &self.0;
+        let mut s = Vec::with_capacity(K);
+        let mut c = Vec::with_capacity(K);
+        if K > 0 {
+            s.push(s0);
+            c.push(c0);
+        }
+        for k in 1..K {
+            let (sk, ck) = (2..=k).fold(
+                (
+                    a[1].clone().mul(c[k - 1].clone()),
+                    a[1].clone().mul(s[k - 1].clone()),
+                ),
+                |(sk, ck), j| {
+                    let ja = int::<T>(j).mul(a[j].clone());
+                    (
+                        sk.add(ja.clone().mul(c[k - j].clone())),
+                        ck.add(ja.mul(s[k - j].clone())),
+                    )
+                },
+            );
+            s.push(sk.div(int(k)));
+            c.push(if hyperbolic {
+                ck.div(int(k))
+            } else {
+                ck.div(int(k)).neg()
+            });
+        }
+        (Taylor(collect(s)), Taylor(collect(c)))
+    }
+
+    // 1 + sign * self^2
+    fn one_plus_square(&self, negate: bool) -> Self {
+        let sq = self.clone().mul(self.clone());
+        let one = self.a0().one();
+        if negate {
+            sq.neg().add_constant(one)
+        } else {
+            sq.add_constant(one)
+        }
+    }
+
+    fn recip(self) -> Self {
+        Taylor::constant(self.a0().one()).div(self)
+    }
+}
+
+impl<T: TaylorCoefficient, const K: usize> Add<Taylor<T, K>> for Taylor<T, K> {
+    type Output = Taylor<T, K>;
+
+    fn add(self, rhs: Taylor<T, K>) -> Self::Output {
+        let mut rhs = rhs.0.into_iter();
+        // both iterators have exactly K elements
+        self.map(|a| a.add(rhs.next().unwrap()))
+    }
+}
+
+impl<T: TaylorCoefficient, const K: usize> Sub<Taylor<T, K>> for Taylor<T, K> {
+    type Output = Taylor<T, K>;
+
+    fn sub(self, rhs: Taylor<T, K>) -> Self::Output {
+        let mut rhs = rhs.0.into_iter();
+        // both iterators have exactly K elements
+        self.map(|a| a.sub(rhs.next().unwrap()))
+    }
+}
+
+impl<T: TaylorCoefficient, const K: usize> Neg for Taylor<T, K> {
+    type Output = Taylor<T, K>;
+
+    fn neg(self) -> Self::Output {
+        self.map(|a| a.neg())
+    }
+}
+
+impl<T: TaylorCoefficient, const K: usize> Mul<Taylor<T, K>> for Taylor<T, K> {
+    type Output = Taylor<T, K>;
+
+    fn mul(self, rhs: Taylor<T, K>) -> Self::Output {
+        Taylor(std::array::from_fn(|k| cauchy(&self.0, &rhs.0, k)))
+    }
+}
+
+impl<T: TaylorCoefficient, const K: usize> Div<Taylor<T, K>> for Taylor<T, K> {
+    type Output = Taylor<T, K>;
+
+    // q = a / b is the solution of q b = a, so that
+    //
+    // q_k = (a_k - sum_(j=0..k) q_j b_(k-j)) / b_0
+    fn div(self, rhs: Taylor<T, K>) -> Self::Output {
+        let (a, b) = (&self.0, &rhs.0);
+        let mut q: Vec<T> = Vec::with_capacity(K);
+        for k in 0..K {
+            let qk = (0..k).fold(a[k].clone(), |acc, j| {
+                acc.sub(q[j].clone().mul(b[k - j].clone()))
+            });
+            q.push(qk.div(b[0].clone()));
+        }
+        Taylor(collect(q))
+    }
+}
+
+impl<T: TaylorCoefficient, const K: usize> InstZero for Taylor<T, K> {
+    fn zero(&self) -> Self {
+        self.clone().map(|a| a.zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(|a| a.is_zero())
+    }
+}
+
+impl<T: TaylorCoefficient, const K: usize> InstOne for Taylor<T, K> {
+    fn one(&self) -> Self {
+        Taylor::constant(self.a0().one())
+    }
+}
+
+// the recurrence of u = a^p from a u' = p a' u,
+//
+// u_k = 1/(k a_0) sum_(j=1..=k) (p j - (k - j)) a_j u_(k-j)
+//
+// which, like the derivative of x^p, requires a_0 != 0
+fn pow_recurrence<T, P, const K: usize>(a: &[T; K], p: &P) -> [T; K]
+where
+    T: TaylorCoefficient + Pow<P, Output = T> + Mul<P, Output = T>,
+    P: Clone,
+{
+    let mut u = Vec::with_capacity(K);
+    if K > 0 {
+        u.push(a[0].clone().pow(p.clone()));
+    }
+    for k in 1..K {
+        let uk = (1..=k).fold(a[0].zero(), |acc, j| {
+            let coeff = int::<T>(j).mul(p.clone()).sub(int(k - j));
+            acc.add(coeff.mul(a[j].clone()).mul(u[k - j].clone()))
+        });
+        u.push(uk.div(int::<T>(k).mul(a[0].clone())));
+    }
+    collect(u)
+}
+
+/// a^p for a constant p.
+///
+/// Non-negative integer powers are computed by repeated squaring, so they hold for any a_0.
+/// Other powers use the recurrence of u = a^p from a u' = p a' u, which divides by a_0, so for
+/// a = t^m b with b_0 != 0 they are computed as t^(m p) b^p when m p is a non-negative integer.
+/// Otherwise the coefficients below t^(m p) are zero and those above it diverge, like the
+/// derivatives of x^p at zero.
+impl<T, P, const K: usize> Pow<P> for Taylor<T, K>
+where
+    T: TaylorCoefficient + Pow<P, Output = T> + Mul<P, Output = T> + PartialEq,
+    P: Clone + ToPrimitive,
+{
+    type Output = Taylor<T, K>;
+
+    fn pow(self, p: P) -> Self::Output {
+        let p_real = p.to_f64().filter(|p| p.is_finite());
+
+        // a^n for an integer n >= 0
+        if let Some(n) = p_real.filter(|p| *p >= 0.0 && p.fract() == 0.0) {
+            let (mut n, mut square) = (n as u64, self.clone());
+            let mut u = self.one();
+            while n > 0 {
+                if n % 2 == 1 {
+                    u = u.mul(square.clone());
+                }
+                n /= 2;
+                if n > 0 {
+                    square = square.clone().mul(square);
+                }
+            }
+            return u;
+        }
+
+        let a = &self.0;
+        let m = match a.iter().position(|a| !a.is_zero()) {
+            Some(0) => return Taylor(pow_recurrence(a, &p)),
+            Some(m) => m,
+            // 0^p, which is a constant
+            None => return Taylor::constant(self.a0().clone().pow(p)),
+        };
+
+        // the order m p of the leading term t^(m p) of a^p
+        let order = p_real.map(|p| m as f64 * p);
+        match order.filter(|q| *q >= 0.0 && q.fract() == 0.0) {
+            Some(q) => {
+                // shift b = a / t^m back up by t^(m p)
+                let b: [T; K] =
+                    std::array::from_fn(|j| a.get(j + m).cloned().unwrap_or_else(|| a[0].zero()));
+                let b_pow = pow_recurrence(&b, &p);
+                let q = q as usize;
+                Taylor(std::array::from_fn(|k| match k.checked_sub(q) {
+                    Some(j) => b_pow[j].clone(),
+                    None => a[0].zero(),
+                }))
+            }
+            None => {
+                let zero = a[0].zero();
+                let inf = zero.one().div(zero.clone());
+                Taylor(std::array::from_fn(|k| match order {
+                    Some(q) if (k as f64) < q => zero.clone(),
+                    _ => inf.clone(),
+                }))
+            }
+        }
+    }
+}
+
+impl<T, const K: usize> Exp for Taylor<T, K>
+where
+    T: TaylorCoefficient + Exp<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn exp(self) -> Self::Output {
+        // (e^a)' = e^a a'
+        let u0 = self.a0().clone().exp();
+        Taylor(integrate(&self.0, u0, |u| u[u.len() - 1].clone()))
+    }
+}
+
+impl<T, const K: usize> Ln for Taylor<T, K>
+where
+    T: TaylorCoefficient + Ln<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn ln(self) -> Self::Output {
+        // ln(a)' = a' / a
+        let w = self.clone().recip();
+        let u0 = self.a0().clone().ln();
+        Taylor(integrate_series(&self.0, u0, &w.0))
+    }
+}
+
+impl<T, const K: usize> Log2 for Taylor<T, K>
+where
+    T: TaylorCoefficient + Ln<Output = T> + Log2<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn log2(self) -> Self::Output {
+        let ln2 = int::<T>(2).ln();
+        let mut u = self.clone().ln().div_constant(ln2);
+        if K > 0 {
+            u.0[0] = self.a0().clone().log2();
+        }
+        u
+    }
+}
+
+impl<T, const K: usize> Log10 for Taylor<T, K>
+where
+    T: TaylorCoefficient + Ln<Output = T> + Log10<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn log10(self) -> Self::Output {
+        let ln10 = int::<T>(10).ln();
+        let mut u = self.clone().ln().div_constant(ln10);
+        if K > 0 {
+            u.0[0] = self.a0().clone().log10();
+        }
+        u
+    }
+}
+
+impl<T, const K: usize> Sqrt for Taylor<T, K>
+where
+    T: TaylorCoefficient + Sqrt<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    // s^2 = a, so that s_k = (a_k - sum_(j=1..k) s_j s_(k-j)) / (2 s_0)
+    fn sqrt(self) -> Self::Output {
+        let a = &self.0;
+        let mut s: Vec<T> = Vec::with_capacity(K);
+        if K > 0 {
+            s.push(a[0].clone().sqrt());
+        }
+        for k in 1..K {
+            let sk = (1..k).fold(a[k].clone(), |acc, j| {
+                acc.sub(s[j].clone().mul(s[k - j].clone()))
+            });
+            s.push(sk.div(int::<T>(2).mul(s[0].clone())));
+        }
+        Taylor(collect(s))
+    }
+}
+
+impl<T, const K: usize> Sin for Taylor<T, K>
+where
+    T: TaylorCoefficient + Sin<Output = T> + Cos<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn sin(self) -> Self::Output {
+        let a0 = self.a0().clone();
+        self.sin_cos(a0.clone().sin(), a0.cos(), false).0
+    }
+}
+
+impl<T, const K: usize> Cos for Taylor<T, K>
+where
+    T: TaylorCoefficient + Sin<Output = T> + Cos<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn cos(self) -> Self::Output {
+        let a0 = self.a0().clone();
+        self.sin_cos(a0.clone().sin(), a0.cos(), false).1
+    }
+}
+
+impl<T, const K: usize> Tan for Taylor<T, K>
+where
+    T: TaylorCoefficient + Tan<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn tan(self) -> Self::Output {
+        // tan(a)' = (1 + tan(a)^2) a', where the 1 only contributes to w_0
+        let u0 = self.a0().clone().tan();
+        Taylor(integrate(&self.0, u0, |u| match u.len() {
+            1 => u[0].one().add(cauchy(u, u, 0)),
+            k => cauchy(u, u, k - 1),
+        }))
+    }
+}
+
+impl<T, const K: usize> Asin for Taylor<T, K>
+where
+    T: TaylorCoefficient + Asin<Output = T> + Sqrt<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn asin(self) -> Self::Output {
+        // asin(a)' = a' / sqrt(1 - a^2)
+        let w = self.one_plus_square(true).sqrt().recip();
+        let u0 = self.a0().clone().asin();
+        Taylor(integrate_series(&self.0, u0, &w.0))
+    }
+}
+
+impl<T, const K: usize> Acos for Taylor<T, K>
+where
+    T: TaylorCoefficient + Acos<Output = T> + Sqrt<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn acos(self) -> Self::Output {
+        // acos(a)' = -a' / sqrt(1 - a^2)
+        let w = self.one_plus_square(true).sqrt().recip().neg();
+        let u0 = self.a0().clone().acos();
+        Taylor(integrate_series(&self.0, u0, &w.0))
+    }
+}
+
+impl<T, const K: usize> Atan for Taylor<T, K>
+where
+    T: TaylorCoefficient + Atan<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn atan(self) -> Self::Output {
+        // atan(a)' = a' / (1 + a^2)
+        let w = self.one_plus_square(false).recip();
+        let u0 = self.a0().clone().atan();
+        Taylor(integrate_series(&self.0, u0, &w.0))
+    }
+}
+
+impl<T, const K: usize> Sinh for Taylor<T, K>
+where
+    T: TaylorCoefficient + Sinh<Output = T> + Cosh<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn sinh(self) -> Self::Output {
+        let a0 = self.a0().clone();
+        self.sin_cos(a0.clone().sinh(), a0.cosh(), true).0
+    }
+}
+
+impl<T, const K: usize> Cosh for Taylor<T, K>
+where
+    T: TaylorCoefficient + Sinh<Output = T> + Cosh<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn cosh(self) -> Self::Output {
+        let a0 = self.a0().clone();
+        self.sin_cos(a0.clone().sinh(), a0.cosh(), true).1
+    }
+}
+
+impl<T, const K: usize> Tanh for Taylor<T, K>
+where
+    T: TaylorCoefficient + Tanh<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn tanh(self) -> Self::Output {
+        // tanh(a)' = (1 - tanh(a)^2) a', where the 1 only contributes to w_0
+        let u0 = self.a0().clone().tanh();
+        Taylor(integrate(&self.0, u0, |u| match u.len() {
+            1 => u[0].one().sub(cauchy(u, u, 0)),
+            k => cauchy(u, u, k - 1).neg(),
+        }))
+    }
+}
+
+impl<T, const K: usize> Asinh for Taylor<T, K>
+where
+    T: TaylorCoefficient + Asinh<Output = T> + Sqrt<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn asinh(self) -> Self::Output {
+        // asinh(a)' = a' / sqrt(1 + a^2)
+        let w = self.one_plus_square(false).sqrt().recip();
+        let u0 = self.a0().clone().asinh();
+        Taylor(integrate_series(&self.0, u0, &w.0))
+    }
+}
+
+impl<T, const K: usize> Acosh for Taylor<T, K>
+where
+    T: TaylorCoefficient + Acosh<Output = T> + Sqrt<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn acosh(self) -> Self::Output {
+        // acosh(a)' = a' / (sqrt(a - 1) sqrt(a + 1)), which is also the principal branch for
+        // complex a
+        let one = self.a0().one();
+        let w = self
+            .clone()
+            .sub_constant(one.clone())
+            .sqrt()
+            .mul(self.clone().add_constant(one).sqrt())
+            .recip();
+        let u0 = self.a0().clone().acosh();
+        Taylor(integrate_series(&self.0, u0, &w.0))
+    }
+}
+
+impl<T, const K: usize> Atanh for Taylor<T, K>
+where
+    T: TaylorCoefficient + Atanh<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn atanh(self) -> Self::Output {
+        // atanh(a)' = a' / (1 - a^2)
+        let w = self.one_plus_square(true).recip();
+        let u0 = self.a0().clone().atanh();
+        Taylor(integrate_series(&self.0, u0, &w.0))
+    }
+}
+
+impl<T, const K: usize> Erf for Taylor<T, K>
+where
+    T: TaylorCoefficient + Erf<Output = T> + Exp<Output = T> + FloatConst,
+{
+    type Output = Taylor<T, K>;
+
+    fn erf(self) -> Self::Output {
+        // erf(a)' = 2/sqrt(pi) e^(-a^2) a'
+        let w = self
+            .clone()
+            .mul(self.clone())
+            .neg()
+            .exp()
+            .mul_constant(T::FRAC_2_SQRT_PI());
+        let u0 = self.a0().clone().erf();
+        Taylor(integrate_series(&self.0, u0, &w.0))
+    }
+}
+
+impl<T, const K: usize> Erfc for Taylor<T, K>
+where
+    T: TaylorCoefficient + Erfc<Output = T> + Exp<Output = T> + FloatConst,
+{
+    type Output = Taylor<T, K>;
+
+    fn erfc(self) -> Self::Output {
+        // erfc(a)' = -2/sqrt(pi) e^(-a^2) a'
+        let w = self
+            .clone()
+            .mul(self.clone())
+            .neg()
+            .exp()
+            .mul_constant(T::FRAC_2_SQRT_PI().neg());
+        let u0 = self.a0().clone().erfc();
+        Taylor(integrate_series(&self.0, u0, &w.0))
+    }
+}
+
+impl<T, const K: usize> Sigmoid for Taylor<T, K>
+where
+    T: TaylorCoefficient + Sigmoid<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn sigmoid(self) -> Self::Output {
+        // sigmoid(a)' = sigmoid(a) (1 - sigmoid(a)) a', where 1 - u = sigmoid(-a_0) - (u - u_0),
+        // so that the constant term does not round to 0 for large a_0
+        let u0 = self.a0().clone().sigmoid();
+        let v0 = self.a0().clone().neg().sigmoid();
+        Taylor(integrate(&self.0, u0, |u| {
+            let k = u.len() - 1;
+            let uk_v0 = u[k].clone().mul(v0.clone());
+            match k {
+                0 => uk_v0,
+                _ => uk_v0.sub(cauchy(&u[..k], &u[1..], k - 1)),
+            }
+        }))
+    }
+}
+
+impl<T, const K: usize> Softplus for Taylor<T, K>
+where
+    T: TaylorCoefficient + Softplus<Output = T> + Sigmoid<Output = T>,
+{
+    type Output = Taylor<T, K>;
+
+    fn softplus(self) -> Self::Output {
+        // softplus(a)' = sigmoid(a) a'
+        let w = self.clone().sigmoid();
+        let u0 = self.a0().clone().softplus();
+        Taylor(integrate_series(&self.0, u0, &w.0))
+    }
+}
+
+impl<T, const K: usize> Gelu for Taylor<T, K>
+where
+    T: TaylorCoefficient + Gelu<Output = T> + NormCdf<Output = T> + Exp<Output = T> + FloatConst,
+{
+    type Output = Taylor<T, K>;
+
+    fn gelu(self) -> Self::Output {
+        // gelu(a) = a Phi(a), where Phi(a)' = e^(-a^2/2) / sqrt(2 pi) a'
+        let w = self
+            .clone()
+            .mul(self.clone())
+            .div_constant(int::<T>(2))
+            .neg()
+            .exp()
+            .mul_constant(T::FRAC_1_SQRT_2().mul(T::FRAC_2_SQRT_PI()).div(int(2)));
+        let phi0 = self.a0().clone().norm_cdf();
+        let phi = Taylor(integrate_series(&self.0, phi0, &w.0));
+        let mut u = self.clone().mul(phi);
+        if K > 0 {
+            u.0[0] = self.a0().clone().gelu();
+        }
+        u
+    }
+}
+
+/// Functions whose Taylor series can be propagated through them, using the recurrences of the
+/// power series of each elementary operation. The series has K coefficients, so that
+/// derivatives up to order K - 1 are computed in a single pass.
+pub trait TaylorDiffable<StaticArgs, const K: usize>: Diffable<StaticArgs> {
+    /// Propagates the series x through the function, returning the series of f(x)
+    fn eval_taylor_series(
+        &self,
+        x: &Taylor<<Self as Diffable<StaticArgs>>::Input, K>,
+        static_args: &StaticArgs,
+    ) -> Taylor<<Self as Diffable<StaticArgs>>::Output, K>;
+
+    /// The first K Taylor coefficients of f(x + t) about t = 0, i.e. the kth derivative of f at
+    /// x divided by k!, with coefficients beyond `order` set to zero
+    fn eval_taylor(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        order: usize,
+        static_args: &StaticArgs,
+    ) -> [<Self as Diffable<StaticArgs>>::Output; K]
+    where
+        <Self as Diffable<StaticArgs>>::Input: TaylorCoefficient,
+        <Self as Diffable<StaticArgs>>::Output: InstZero,
+    {
+        let mut coeffs = self
+            .eval_taylor_series(&Taylor::variable(x.clone()), static_args)
+            .0;
+        for c in coeffs.iter_mut().skip(order + 1) {
+            c.set_zero();
+        }
+        coeffs
+    }
+}
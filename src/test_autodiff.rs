@@ -1,13 +1,29 @@
 use crate::autodiff::AutoDiff;
 use crate::autodiffable::*;
+use crate::autotuple::AutoTuple;
 use crate::compose::*;
+use crate::dual::*;
+use crate::dynautodiff::*;
+use crate::finitediff::*;
 use crate::func_traits::*;
 use crate::funcs::*;
+use crate::graphviz::*;
+use crate::reverse::*;
+use crate::symbolic::*;
+use crate::taylor::*;
+use crate::tryautodiff::*;
 use num::complex::Complex;
 use num::traits::Pow;
 use std::ops::Deref;
 
+use crate as autodiff;
+use autodiff_derive::*;
+
+// the value, gradient, conjugate gradient and forward gradient of a complex function
+type ComplexDerivatives = (Complex<f64>, Complex<f64>, Complex<f64>, Complex<f64>);
+
 #[test]
+#[allow(clippy::clone_on_copy)]
 fn test_all_ops() {
     // test all supported unary operations on p(x) = 1 + 2x + 3x^2
     // test all supported binary operations on p(x) and q(x) = x^5
@@ -565,3 +581,1735 @@ fn test_all_ops() {
         p.clone().compose(q.clone()).grad(&z, &())
     );
 }
+
+// assert that two real or complex values agree up to a relative tolerance
+fn assert_close<T: Into<Complex<f64>>>(left: T, right: T) {
+    let (left, right): (Complex<f64>, Complex<f64>) = (left.into(), right.into());
+    assert!(
+        (left - right).norm() <= 1e-12 * right.norm().max(1.0),
+        "assertion failed: `(left ~= right)`\n  left: `{:?}`,\n right: `{:?}`",
+        left,
+        right
+    );
+}
+
+#[test]
+fn test_exp_log() {
+    // test exp and logs of p(x) = 1 + 2x + 3x^2 and of f(z) = z * conj(z) + z
+
+    let x = 2.0_f64;
+    let dx = 0.5_f64;
+
+    let p = AutoDiff::new(Polynomial::new(vec![1.0, 2.0, 3.0]));
+    let (p_x, dp_x): (f64, f64) = p.eval_forward_grad(&x, &dx, &());
+    let dp_dx: f64 = p.grad(&x, &());
+
+    // exp
+    let (e_x, de_x) = p.clone().exp().eval_forward_grad(&x, &dx, &());
+    assert_close(e_x, p_x.exp());
+    assert_close(de_x, p_x.exp() * dp_x);
+    assert_close(p.clone().exp().grad(&x, &()), p_x.exp() * dp_dx);
+    assert_eq!(p.clone().exp().conj_grad(&x, &()), 0.0);
+
+    // ln
+    let (l_x, dl_x) = p.clone().ln().eval_forward_grad(&x, &dx, &());
+    assert_close(l_x, p_x.ln());
+    assert_close(dl_x, dp_x / p_x);
+    assert_close(p.clone().ln().grad(&x, &()), dp_dx / p_x);
+    assert_eq!(p.clone().ln().conj_grad(&x, &()), 0.0);
+
+    // log2, log10 and log with a constant base
+    let (l2_x, dl2_x) = p.clone().log2().eval_forward_grad(&x, &dx, &());
+    assert_close(l2_x, p_x.log2());
+    assert_close(dl2_x, dp_x / (p_x * 2.0_f64.ln()));
+    assert_close(p.clone().log2().grad(&x, &()), dp_dx / (p_x * 2.0_f64.ln()));
+
+    let (l10_x, dl10_x) = p.clone().log10().eval_forward_grad(&x, &dx, &());
+    assert_close(l10_x, p_x.log10());
+    assert_close(dl10_x, dp_x / (p_x * 10.0_f64.ln()));
+    assert_close(
+        p.clone().log10().grad(&x, &()),
+        dp_dx / (p_x * 10.0_f64.ln()),
+    );
+
+    let (l3_x, dl3_x) = p.clone().log(3.0).eval_forward_grad(&x, &dx, &());
+    assert_close(l3_x, p_x.log(3.0));
+    assert_close(dl3_x, dp_x / (p_x * 3.0_f64.ln()));
+    assert_close(
+        p.clone().log(3.0).grad(&x, &()),
+        dp_dx / (p_x * 3.0_f64.ln()),
+    );
+
+    // complex, with a non-holomorphic inner function so that the conj grads are nonzero
+    let z = Complex::new(0.5_f64, -0.25);
+    let dz = Complex::new(0.5_f64, 0.75);
+
+    let i = AutoDiff::new(Identity::new());
+    let f = i.abs_sqr() + i;
+
+    let f_z: Complex<f64> = z * z.conj() + z;
+    let df_dz: Complex<f64> = z.conj() + 1.0;
+    let df_dconjz: Complex<f64> = z;
+    assert_close(f.eval(&z, &()), f_z);
+    assert_close(f.grad(&z, &()), df_dz);
+    assert_close(f.conj_grad(&z, &()), df_dconjz);
+
+    // exp
+    let e = f.exp();
+    let (e_z, de_z) = e.eval_forward_grad(&z, &dz, &());
+    assert_close(e_z, f_z.exp());
+    assert_close(de_z, f_z.exp() * df_dz * dz);
+    assert_close(e.grad(&z, &()), f_z.exp() * df_dz);
+    assert_close(e.conj_grad(&z, &()), f_z.exp() * df_dconjz);
+    assert_close(
+        e.forward_conj_grad(&z, &dz, &()),
+        f_z.exp() * df_dconjz * dz.conj(),
+    );
+
+    // ln
+    let l = f.ln();
+    let (l_z, dl_z) = l.eval_forward_grad(&z, &dz, &());
+    assert_close(l_z, f_z.ln());
+    assert_close(dl_z, df_dz * dz / f_z);
+    assert_close(l.grad(&z, &()), df_dz / f_z);
+    assert_close(l.conj_grad(&z, &()), df_dconjz / f_z);
+
+    // log10 and log with a complex base
+    let l10 = f.log10();
+    assert_close(l10.eval(&z, &()), f_z.log10());
+    assert_close(l10.grad(&z, &()), df_dz / (f_z * 10.0_f64.ln()));
+    assert_close(l10.conj_grad(&z, &()), df_dconjz / (f_z * 10.0_f64.ln()));
+
+    let b = Complex::new(2.0_f64, 1.0);
+    let lb = f.log(b);
+    assert_close(lb.eval(&z, &()), f_z.ln() / b.ln());
+    assert_close(lb.grad(&z, &()), df_dz / (f_z * b.ln()));
+    assert_close(lb.conj_grad(&z, &()), df_dconjz / (f_z * b.ln()));
+}
+
+#[test]
+fn test_trig() {
+    // test trig functions of p(x) = 0.1 + 0.2x + 0.3x^2 and of f(z) = z * conj(z) + z
+
+    let x = 0.5_f64;
+    let dx = 0.5_f64;
+
+    let p = AutoDiff::new(Polynomial::new(vec![0.1, 0.2, 0.3]));
+    let (p_x, dp_x): (f64, f64) = p.eval_forward_grad(&x, &dx, &());
+    let dp_dx: f64 = p.grad(&x, &());
+
+    let check_real = |f: (f64, f64), df_dx: f64, g: f64, dg_df: f64| {
+        assert_close(f.0, g);
+        assert_close(f.1, dg_df * dp_x);
+        assert_close(df_dx, dg_df * dp_dx);
+    };
+
+    let s = p.clone().sin();
+    check_real(
+        s.eval_forward_grad(&x, &dx, &()),
+        s.grad(&x, &()),
+        p_x.sin(),
+        p_x.cos(),
+    );
+    let c = p.clone().cos();
+    check_real(
+        c.eval_forward_grad(&x, &dx, &()),
+        c.grad(&x, &()),
+        p_x.cos(),
+        -p_x.sin(),
+    );
+    let t = p.clone().tan();
+    check_real(
+        t.eval_forward_grad(&x, &dx, &()),
+        t.grad(&x, &()),
+        p_x.tan(),
+        1.0 / p_x.cos().powi(2),
+    );
+    let as_ = p.clone().asin();
+    check_real(
+        as_.eval_forward_grad(&x, &dx, &()),
+        as_.grad(&x, &()),
+        p_x.asin(),
+        1.0 / (1.0 - p_x * p_x).sqrt(),
+    );
+    let ac = p.clone().acos();
+    check_real(
+        ac.eval_forward_grad(&x, &dx, &()),
+        ac.grad(&x, &()),
+        p_x.acos(),
+        -1.0 / (1.0 - p_x * p_x).sqrt(),
+    );
+    let at = p.clone().atan();
+    check_real(
+        at.eval_forward_grad(&x, &dx, &()),
+        at.grad(&x, &()),
+        p_x.atan(),
+        1.0 / (1.0 + p_x * p_x),
+    );
+    assert_eq!(p.clone().sin().conj_grad(&x, &()), 0.0);
+
+    // complex, with a non-holomorphic inner function so that the conj grads are nonzero
+    let z = Complex::new(0.3_f64, -0.2);
+    let dz = Complex::new(0.5_f64, 0.75);
+
+    let i = AutoDiff::new(Identity::new());
+    let f = i.abs_sqr() + i;
+
+    let f_z: Complex<f64> = z * z.conj() + z;
+    let df_dz: Complex<f64> = z.conj() + 1.0;
+    let df_dconjz: Complex<f64> = z;
+
+    let one = Complex::new(1.0_f64, 0.0);
+    let expected: [(Complex<f64>, Complex<f64>); 6] = [
+        (f_z.sin(), f_z.cos()),
+        (f_z.cos(), -f_z.sin()),
+        (f_z.tan(), one / f_z.cos().powi(2)),
+        (f_z.asin(), one / (one - f_z * f_z).sqrt()),
+        (f_z.acos(), -one / (one - f_z * f_z).sqrt()),
+        (f_z.atan(), one / (one + f_z * f_z)),
+    ];
+    let results: [ComplexDerivatives; 6] = [
+        (
+            f.sin().eval(&z, &()),
+            f.sin().grad(&z, &()),
+            f.sin().conj_grad(&z, &()),
+            f.sin().forward_grad(&z, &dz, &()),
+        ),
+        (
+            f.cos().eval(&z, &()),
+            f.cos().grad(&z, &()),
+            f.cos().conj_grad(&z, &()),
+            f.cos().forward_grad(&z, &dz, &()),
+        ),
+        (
+            f.tan().eval(&z, &()),
+            f.tan().grad(&z, &()),
+            f.tan().conj_grad(&z, &()),
+            f.tan().forward_grad(&z, &dz, &()),
+        ),
+        (
+            f.asin().eval(&z, &()),
+            f.asin().grad(&z, &()),
+            f.asin().conj_grad(&z, &()),
+            f.asin().forward_grad(&z, &dz, &()),
+        ),
+        (
+            f.acos().eval(&z, &()),
+            f.acos().grad(&z, &()),
+            f.acos().conj_grad(&z, &()),
+            f.acos().forward_grad(&z, &dz, &()),
+        ),
+        (
+            f.atan().eval(&z, &()),
+            f.atan().grad(&z, &()),
+            f.atan().conj_grad(&z, &()),
+            f.atan().forward_grad(&z, &dz, &()),
+        ),
+    ];
+
+    for ((g, dg_df), (g_z, dg_dz, dg_dconjz, dg_z)) in expected.into_iter().zip(results) {
+        assert_close(g_z, g);
+        assert_close(dg_dz, dg_df * df_dz);
+        assert_close(dg_dconjz, dg_df * df_dconjz);
+        assert_close(dg_z, dg_df * df_dz * dz);
+    }
+}
+
+#[test]
+fn test_hyperbolic() {
+    // test hyperbolic functions of p(x) = 0.1 + 0.2x + 0.3x^2 and of f(z) = z * conj(z) + z
+
+    let x = 0.5_f64;
+    let dx = 0.5_f64;
+
+    let p = AutoDiff::new(Polynomial::new(vec![0.1, 0.2, 0.3]));
+    let (p_x, dp_x): (f64, f64) = p.eval_forward_grad(&x, &dx, &());
+    let dp_dx: f64 = p.grad(&x, &());
+
+    let check_real = |f: (f64, f64), df_dx: f64, g: f64, dg_df: f64| {
+        assert_close(f.0, g);
+        assert_close(f.1, dg_df * dp_x);
+        assert_close(df_dx, dg_df * dp_dx);
+    };
+
+    let s = p.clone().sinh();
+    check_real(
+        s.eval_forward_grad(&x, &dx, &()),
+        s.grad(&x, &()),
+        p_x.sinh(),
+        p_x.cosh(),
+    );
+    let c = p.clone().cosh();
+    check_real(
+        c.eval_forward_grad(&x, &dx, &()),
+        c.grad(&x, &()),
+        p_x.cosh(),
+        p_x.sinh(),
+    );
+    let t = p.clone().tanh();
+    check_real(
+        t.eval_forward_grad(&x, &dx, &()),
+        t.grad(&x, &()),
+        p_x.tanh(),
+        1.0 / p_x.cosh().powi(2),
+    );
+    let as_ = p.clone().asinh();
+    check_real(
+        as_.eval_forward_grad(&x, &dx, &()),
+        as_.grad(&x, &()),
+        p_x.asinh(),
+        1.0 / (p_x * p_x + 1.0).sqrt(),
+    );
+    let at = p.clone().atanh();
+    check_real(
+        at.eval_forward_grad(&x, &dx, &()),
+        at.grad(&x, &()),
+        p_x.atanh(),
+        1.0 / (1.0 - p_x * p_x),
+    );
+    assert_eq!(p.clone().tanh().conj_grad(&x, &()), 0.0);
+
+    // acosh is only defined for real values >= 1
+    let q = p.clone() + 1.0;
+    let (q_x, dq_x): (f64, f64) = q.eval_forward_grad(&x, &dx, &());
+    let (ac_x, dac_x) = q.clone().acosh().eval_forward_grad(&x, &dx, &());
+    assert_close(ac_x, q_x.acosh());
+    assert_close(dac_x, dq_x / (q_x * q_x - 1.0).sqrt());
+    assert_close(
+        q.clone().acosh().grad(&x, &()),
+        dp_dx / (q_x * q_x - 1.0).sqrt(),
+    );
+
+    // complex, with a non-holomorphic inner function so that the conj grads are nonzero
+    let z = Complex::new(0.3_f64, -0.2);
+    let dz = Complex::new(0.5_f64, 0.75);
+
+    let i = AutoDiff::new(Identity::new());
+    let f = i.abs_sqr() + i;
+
+    let f_z: Complex<f64> = z * z.conj() + z;
+    let df_dz: Complex<f64> = z.conj() + 1.0;
+    let df_dconjz: Complex<f64> = z;
+
+    let one = Complex::new(1.0_f64, 0.0);
+    let expected: [(Complex<f64>, Complex<f64>); 6] = [
+        (f_z.sinh(), f_z.cosh()),
+        (f_z.cosh(), f_z.sinh()),
+        (f_z.tanh(), one / f_z.cosh().powi(2)),
+        (f_z.asinh(), one / (f_z * f_z + one).sqrt()),
+        (f_z.acosh(), one / ((f_z - one).sqrt() * (f_z + one).sqrt())),
+        (f_z.atanh(), one / (one - f_z * f_z)),
+    ];
+    let results: [ComplexDerivatives; 6] = [
+        (
+            f.sinh().eval(&z, &()),
+            f.sinh().grad(&z, &()),
+            f.sinh().conj_grad(&z, &()),
+            f.sinh().forward_grad(&z, &dz, &()),
+        ),
+        (
+            f.cosh().eval(&z, &()),
+            f.cosh().grad(&z, &()),
+            f.cosh().conj_grad(&z, &()),
+            f.cosh().forward_grad(&z, &dz, &()),
+        ),
+        (
+            f.tanh().eval(&z, &()),
+            f.tanh().grad(&z, &()),
+            f.tanh().conj_grad(&z, &()),
+            f.tanh().forward_grad(&z, &dz, &()),
+        ),
+        (
+            f.asinh().eval(&z, &()),
+            f.asinh().grad(&z, &()),
+            f.asinh().conj_grad(&z, &()),
+            f.asinh().forward_grad(&z, &dz, &()),
+        ),
+        (
+            f.acosh().eval(&z, &()),
+            f.acosh().grad(&z, &()),
+            f.acosh().conj_grad(&z, &()),
+            f.acosh().forward_grad(&z, &dz, &()),
+        ),
+        (
+            f.atanh().eval(&z, &()),
+            f.atanh().grad(&z, &()),
+            f.atanh().conj_grad(&z, &()),
+            f.atanh().forward_grad(&z, &dz, &()),
+        ),
+    ];
+
+    for ((g, dg_df), (g_z, dg_dz, dg_dconjz, dg_z)) in expected.into_iter().zip(results) {
+        assert_close(g_z, g);
+        assert_close(dg_dz, dg_df * df_dz);
+        assert_close(dg_dconjz, dg_df * df_dconjz);
+        assert_close(dg_z, dg_df * df_dz * dz);
+    }
+
+    // a holomorphic function of a holomorphic function has zero conj grad
+    let g = AutoDiff::new(Identity::new()).tanh().sinh();
+    assert_eq!(g.conj_grad(&z, &()), Complex::new(0.0, 0.0));
+    assert_close(g.grad(&z, &()), z.tanh().cosh() / z.cosh().powi(2));
+}
+
+#[test]
+fn test_pow() {
+    // test f^g for f(x) = x and g(x) = x, and for f(x) = 1 + 2x + 3x^2 and g(x) = x^5
+
+    let x = 1.5_f64;
+    let dx = 0.5_f64;
+
+    let i = AutoDiff::new(Identity::new());
+    let x_pow_x = i.pow(i);
+
+    let (xx_x, dxx_x): (f64, f64) = x_pow_x.eval_forward_grad(&x, &dx, &());
+    assert_close(xx_x, x.powf(x));
+    assert_close(dxx_x, x.powf(x) * (x.ln() + 1.0) * dx);
+    assert_close(x_pow_x.grad(&x, &()), x.powf(x) * (x.ln() + 1.0));
+    assert_eq!(x_pow_x.conj_grad(&x, &()), 0.0);
+
+    let p = AutoDiff::new(Polynomial::new(vec![1.0, 2.0, 3.0]));
+    let q = AutoDiff::new(Monomial::<(), f64, f64>::new(5.0));
+    let p_x: f64 = p.eval(&x, &());
+    let dp_dx: f64 = p.grad(&x, &());
+    let q_x: f64 = q.eval(&x, &());
+    let dq_dx: f64 = q.grad(&x, &());
+
+    let p_pow_q = p.pow(q);
+    let expected: f64 = q_x * p_x.powf(q_x - 1.0) * dp_dx + p_x.powf(q_x) * p_x.ln() * dq_dx;
+    assert_close(p_pow_q.eval(&x, &()), p_x.powf(q_x));
+    assert_close(p_pow_q.grad(&x, &()), expected);
+    assert_close(p_pow_q.forward_grad(&x, &dx, &()), expected * dx);
+
+    // complex, with a non-holomorphic exponent so that the conj grads are nonzero
+    let z = Complex::new(0.5_f64, -0.25);
+    let dz = Complex::new(0.5_f64, 0.75);
+
+    let iz = AutoDiff::new(Identity::new());
+    let z_pow_z = iz.pow(iz);
+    let zz: Complex<f64> = z.powc(z);
+    assert_close(z_pow_z.eval(&z, &()), zz);
+    assert_close(z_pow_z.grad(&z, &()), zz * (z.ln() + 1.0));
+    assert_close(z_pow_z.forward_grad(&z, &dz, &()), zz * (z.ln() + 1.0) * dz);
+    assert_eq!(z_pow_z.conj_grad(&z, &()), Complex::new(0.0, 0.0));
+
+    // z^|z|^2
+    let z_pow_abs_sqr = iz.pow(iz.abs_sqr());
+    let g_z: Complex<f64> = z.norm_sqr().into();
+    let zg: Complex<f64> = z.powc(g_z);
+    assert_close(z_pow_abs_sqr.eval(&z, &()), zg);
+    assert_close(
+        z_pow_abs_sqr.grad(&z, &()),
+        g_z * z.powc(g_z - 1.0) + zg * z.ln() * z.conj(),
+    );
+    assert_close(z_pow_abs_sqr.conj_grad(&z, &()), zg * z.ln() * z);
+    assert_close(
+        z_pow_abs_sqr.forward_conj_grad(&z, &dz, &()),
+        zg * z.ln() * z * dz.conj(),
+    );
+
+    // x^2 with a constant exponent, where ln(x) is -inf at x = 0 and NaN at x = -1.5
+    let x_sqr = i.pow(AutoDiff::new(Polynomial::new(vec![2.0])));
+    for (x, expected) in [(0.0_f64, 0.0), (-1.5, -3.0)] {
+        assert_eq!(x_sqr.eval_grad(&x, &()), (x * x, expected));
+        assert_eq!(x_sqr.eval_all(&x, &()), (x * x, expected, 0.0));
+        assert_eq!(
+            x_sqr.eval_forward_grad(&x, &dx, &()),
+            (x * x, expected * dx)
+        );
+        assert_eq!(x_sqr.eval_vjp(&x, &dx, &()), (x * x, expected * dx));
+        assert_eq!(x_sqr.eval_grad_reverse(&x, &()), (x * x, expected));
+        assert_eq!(x_sqr.eval_taylor(&x, 2, &()), [x * x, 2.0 * x, 1.0]);
+    }
+
+    // the exponent term vanishes with f^g = 0, as for x^(1 + x) at x = 0
+    let p = AutoDiff::new(Polynomial::new(vec![1.0, 1.0]));
+    let x_pow_p = i.pow(p);
+    assert_eq!(x_pow_p.eval_grad(&0.0, &()), (0.0, 1.0));
+    assert_eq!(x_pow_p.eval_grad_reverse(&0.0, &()), (0.0, 1.0));
+    assert_eq!(x_pow_p.eval_vjp(&0.0, &1.0, &()), (0.0, 1.0));
+}
+
+#[test]
+fn test_binary_funcs() {
+    // test atan2, hypot, min and max of p(x) = 1 + 2x + 3x^2 and q(x) = x^5
+
+    let x = 0.5_f64;
+    let dx = 0.5_f64;
+
+    let p = AutoDiff::new(Polynomial::new(vec![1.0, 2.0, 3.0]));
+    let q = AutoDiff::new(Monomial::<(), f64, f64>::new(5.0));
+    let (p_x, dp_dx): (f64, f64) = p.eval_grad(&x, &());
+    let (q_x, dq_dx): (f64, f64) = q.eval_grad(&x, &());
+
+    // atan2
+    let a = p.clone().atan2(q);
+    let da_dx = (q_x * dp_dx - p_x * dq_dx) / (p_x * p_x + q_x * q_x);
+    assert_close(a.eval(&x, &()), p_x.atan2(q_x));
+    assert_close(a.grad(&x, &()), da_dx);
+    assert_close(a.forward_grad(&x, &dx, &()), da_dx * dx);
+    assert_eq!(a.conj_grad(&x, &()), 0.0);
+
+    // hypot
+    let h = p.clone().hypot(q);
+    let dh_dx = (p_x * dp_dx + q_x * dq_dx) / p_x.hypot(q_x);
+    assert_close(h.eval(&x, &()), p_x.hypot(q_x));
+    assert_close(h.grad(&x, &()), dh_dx);
+    assert_close(h.forward_grad(&x, &dx, &()), dh_dx * dx);
+
+    // min and max route the gradient to the active operand
+    // p(0.5) = 2.75 > q(0.5) = 0.03125
+    let mn = p.clone().min(q);
+    let mx = p.clone().max(q);
+    assert_eq!(mn.eval_grad(&x, &()), (q_x, dq_dx));
+    assert_eq!(mx.eval_grad(&x, &()), (p_x, dp_dx));
+    assert_eq!(mn.eval_forward_grad(&x, &dx, &()), (q_x, dq_dx * dx));
+    assert_eq!(mx.eval_forward_grad(&x, &dx, &()), (p_x, dp_dx * dx));
+
+    // ties route the gradient to the left operand
+    let i = AutoDiff::new(Identity::new());
+    let c = AutoDiff::new(Polynomial::new(vec![1.0]));
+    let x = 1.0_f64;
+    assert_eq!(i.min(c.clone()).eval_grad(&x, &()), (1.0, 1.0));
+    assert_eq!(c.clone().min(i).eval_grad(&x, &()), (1.0, 0.0));
+    assert_eq!(i.max(c.clone()).eval_grad(&x, &()), (1.0, 1.0));
+    assert_eq!(c.max(i).eval_grad(&x, &()), (1.0, 0.0));
+}
+
+#[test]
+fn test_special_funcs() {
+    // test special functions of p(x) = 0.1 + 0.2x + 0.3x^2, checking the derivatives against
+    // central differences
+
+    let x = 0.5_f64;
+    let dx = 0.5_f64;
+    let h = 1e-6_f64;
+
+    let p = AutoDiff::new(Polynomial::new(vec![0.1, 0.2, 0.3]));
+    let (p_x, dp_dx): (f64, f64) = p.eval_grad(&x, &());
+
+    fn check<F: AutoDiffable<(), Input = f64, Output = f64> + ForwardDiffable<()>>(
+        f: &F,
+        x: f64,
+        dx: f64,
+        h: f64,
+        expected: f64,
+    ) {
+        let numerical = (f.eval(&(x + h), &()) - f.eval(&(x - h), &())) / (2.0 * h);
+        let (f_x, df_dx) = f.eval_grad(&x, &());
+        assert_close(f_x, expected);
+        assert!(
+            (df_dx - numerical).abs() < 1e-8,
+            "{} != {}",
+            df_dx,
+            numerical
+        );
+        assert_close(f.forward_grad(&x, &dx, &()), df_dx * dx);
+        assert_eq!(f.conj_grad(&x, &()), 0.0);
+    }
+
+    check(&p.clone().erf(), x, dx, h, Erf::erf(p_x));
+    check(&p.clone().erfc(), x, dx, h, Erfc::erfc(p_x));
+    check(&p.clone().gamma(), x, dx, h, Gamma::gamma(p_x));
+    check(&p.clone().lgamma(), x, dx, h, Lgamma::lgamma(p_x));
+    check(&p.clone().digamma(), x, dx, h, Digamma::digamma(p_x));
+
+    // closed forms of the derivatives
+    let frac_2_sqrt_pi = std::f64::consts::FRAC_2_SQRT_PI;
+    assert_close(
+        p.clone().erf().grad(&x, &()),
+        frac_2_sqrt_pi * (-p_x * p_x).exp() * dp_dx,
+    );
+    assert_close(
+        p.clone().lgamma().grad(&x, &()),
+        Digamma::digamma(p_x) * dp_dx,
+    );
+    assert_close(
+        p.clone().digamma().grad(&x, &()),
+        Trigamma::trigamma(p_x) * dp_dx,
+    );
+
+    // f32 inputs
+    let x32 = 1.5_f32;
+    let i = AutoDiff::new(Identity::new());
+    let (lg, dlg): (f32, f32) = i.lgamma().eval_grad(&x32, &());
+    assert!((lg - Lgamma::lgamma(x32)).abs() < 1e-6);
+    assert!((dlg - Digamma::digamma(x32)).abs() < 1e-6);
+}
+
+#[test]
+fn test_activations() {
+    // test the activation functions on p(x) = 1 + 2x + 3x^2 for x on both sides of the kink
+
+    let p = AutoDiff::new(Polynomial::new(vec![-1.0, 2.0, 3.0]));
+    let dx = 0.5_f64;
+
+    for x in [-0.5_f64, 0.5] {
+        let (p_x, dp_dx): (f64, f64) = p.eval_grad(&x, &());
+        let step = if p_x > 0.0 { 1.0 } else { 0.0 };
+
+        let relu = p.clone().relu();
+        assert_eq!(relu.eval_grad(&x, &()), (p_x.max(0.0), step * dp_dx));
+        assert_eq!(relu.forward_grad(&x, &dx, &()), step * dp_dx * dx);
+
+        let leaky = p.clone().leaky_relu(0.01);
+        let leaky_slope = if p_x > 0.0 { 1.0 } else { 0.01 };
+        assert_close(leaky.eval(&x, &()), leaky_slope * p_x);
+        assert_close(leaky.grad(&x, &()), leaky_slope * dp_dx);
+
+        let sigmoid = p.clone().sigmoid();
+        let s = 1.0 / (1.0 + (-p_x).exp());
+        assert_close(sigmoid.eval(&x, &()), s);
+        assert_close(sigmoid.grad(&x, &()), s * (1.0 - s) * dp_dx);
+
+        let softplus = p.clone().softplus();
+        assert_close(softplus.eval(&x, &()), (1.0 + p_x.exp()).ln());
+        assert_close(softplus.grad(&x, &()), s * dp_dx);
+
+        let gelu = p.clone().gelu();
+        let phi = 0.5 * (1.0 + Erf::erf(p_x / 2.0_f64.sqrt()));
+        let dphi = (-0.5 * p_x * p_x).exp() / (2.0 * std::f64::consts::PI).sqrt();
+        assert_close(gelu.eval(&x, &()), p_x * phi);
+        assert_close(gelu.grad(&x, &()), (phi + p_x * dphi) * dp_dx);
+
+        let elu = p.clone().elu(1.5);
+        let (elu_x, delu_x) = if p_x > 0.0 {
+            (p_x, 1.0)
+        } else {
+            (1.5 * (p_x.exp() - 1.0), 1.5 * p_x.exp())
+        };
+        assert_close(elu.eval(&x, &()), elu_x);
+        assert_close(elu.grad(&x, &()), delu_x * dp_dx);
+    }
+
+    // numerical stability for large inputs
+    let i = AutoDiff::new(Identity::new());
+    assert_eq!(i.softplus().eval_grad(&1000.0_f64, &()), (1000.0, 1.0));
+    assert_eq!(i.softplus().eval_grad(&-1000.0_f64, &()), (0.0, 0.0));
+    assert_eq!(i.sigmoid().eval_grad(&-1000.0_f64, &()), (0.0, 0.0));
+    assert_eq!(i.sigmoid().eval_grad(&1000.0_f64, &()), (1.0, 0.0));
+    assert_eq!(i.elu(1.0).eval_grad(&1000.0_f64, &()), (1000.0, 1.0));
+    assert_eq!(i.gelu().eval_grad(&-1000.0_f64, &()), (-0.0, 0.0));
+    assert_close(i.softplus().eval(&-40.0_f64, &()), (-40.0_f64).exp());
+
+    // the tails of the derivatives of sigmoid are not lost to 1 - sigmoid(x) rounding to 0, and
+    // are compared relative to their own tiny size
+    let x = 40.0_f64;
+    let e = (-x).exp();
+    let dsigmoid = e / ((1.0 + e) * (1.0 + e));
+    let d2sigmoid = -dsigmoid * (1.0 - e) / (1.0 + e);
+    let assert_tail = |left: f64, right: f64| assert!((left - right).abs() <= 1e-12 * right.abs());
+    assert_tail(i.sigmoid().grad(&x, &()), dsigmoid);
+    assert_tail(i.sigmoid().hessian(&x, &()), d2sigmoid);
+    assert_tail(i.softplus().hessian(&x, &()), dsigmoid);
+    let [s0, s1, s2]: [f64; 3] = i.sigmoid().eval_taylor(&x, 2, &());
+    assert_eq!(s0, 1.0);
+    assert_tail(s1, dsigmoid);
+    assert_tail(s2, d2sigmoid / 2.0);
+}
+
+#[test]
+fn test_reverse() {
+    // reverse mode on the tape should agree with the forward composition of eval_grad
+    let x = 0.7_f64;
+
+    let i = AutoDiff::new(Identity::new());
+    let p = AutoDiff::new(Polynomial::new(vec![-1.0, 2.0, 3.0]));
+    let q = AutoDiff::new(Monomial::<(), f64, f64>::new(3.0));
+
+    let f = (p.clone() * i.sin() + q / i.exp() - p.clone().compose(q).tanh())
+        .max(i * 2.0)
+        .abs()
+        .pow(1.5)
+        - (i.atan2(p.clone()) + 1.0).ln()
+        + i.pow(i).sigmoid();
+
+    let (f_x, df_dx) = f.eval_grad(&x, &());
+    let (fr_x, dfr_dx) = f.eval_grad_reverse(&x, &());
+    assert_eq!(f_x, fr_x);
+    assert_close(df_dx, dfr_dx);
+    assert_eq!(f.eval_conj_grad_reverse(&x, &()), (f_x, 0.0));
+
+    // the same function can be recorded more than once, and each node keeps its own value
+    let mut tape = Tape::new(&x);
+    let px = p.eval_reverse(&x, &mut tape, &());
+    let qx = q.eval_reverse(&x, &mut tape, &());
+    assert_eq!(tape.len(), 2);
+    assert_eq!(*tape.value(px), p.eval(&x, &()));
+    assert_eq!(tape.backward(qx), (q.grad(&x, &()), 0.0));
+
+    // an output that does not depend on the input has a zero gradient
+    let c = tape.constant(3.0);
+    assert_eq!(tape.backward(c), (0.0, 0.0));
+    let c_sqr = tape.unary(9.0, (c, 6.0, 0.0));
+    assert_eq!(tape.backward(c_sqr), (0.0, 0.0));
+
+    // complex functions, including non-holomorphic ones, need both adjoints
+    let z = Complex::<f64>::new(2.0, -3.0);
+    let iz = AutoDiff::new(Identity::new());
+    let g = (iz * iz.clone().conj() + iz.abs_sqr().ln() * iz.exp()) / (iz.signum() - 0.5)
+        + iz.abs().compose(iz.sin());
+
+    let (g_z, dg_dz) = g.eval_grad(&z, &());
+    let dg_dconjz = g.conj_grad(&z, &());
+    let mut tape = Tape::new(&z);
+    let gz = g.eval_reverse(&z, &mut tape, &());
+    let (dgr_dz, dgr_dconjz) = tape.backward(gz);
+    assert_eq!(*tape.value(gz), g_z);
+    assert_close(dg_dz, dgr_dz);
+    assert_close(dg_dconjz, dgr_dconjz);
+}
+
+#[test]
+fn test_vjp() {
+    // the vector-Jacobian product of a scalar function is the cotangent times the gradient
+    let x = 0.7_f64;
+    let c = -1.3_f64;
+
+    let i = AutoDiff::new(Identity::new());
+    let p = AutoDiff::new(Polynomial::new(vec![-1.0, 2.0, 3.0]));
+    let q = AutoDiff::new(Monomial::<(), f64, f64>::new(3.0));
+
+    let f = (p.clone() * i.sin() + q / i.exp() - p.clone().compose(q).tanh())
+        .max(i * 2.0)
+        .abs()
+        .pow(1.5)
+        - (i.atan2(p.clone()) + 1.0).ln()
+        + i.pow(i).sigmoid();
+
+    let (f_x, df_dx) = f.eval_grad(&x, &());
+    let (fv_x, vjp) = f.eval_vjp(&x, &c, &());
+    assert_eq!(f_x, fv_x);
+    assert_eq!(f.eval_primal(&x, &()), f_x);
+    assert_close(c * df_dx, vjp);
+    assert_close(c * f.conj_grad(&x, &()), f.conj_vjp(&x, &c, &()));
+
+    // complex functions, including non-holomorphic ones
+    let z = Complex::<f64>::new(2.0, -3.0);
+    let cz = Complex::<f64>::new(0.5, 1.5);
+    let iz = AutoDiff::new(Identity::new());
+    let g = (iz * iz.clone().conj() + iz.abs_sqr().ln() * iz.exp()) / (iz.signum() - 0.5)
+        + iz.abs().compose(iz.sin());
+
+    let (g_z, dg_dz) = g.eval_grad(&z, &());
+    let (gv_z, vjp) = g.eval_vjp(&z, &cz, &());
+    assert_eq!(g_z, gv_z);
+    assert_close(cz * dg_dz, vjp);
+    assert_close(cz * g.conj_grad(&z, &()), g.conj_vjp(&z, &cz, &()));
+}
+
+type XY = AutoTuple<(f64, f64)>;
+
+// f(x, y) = x^2 y, a scalar function of an AutoTuple whose Hessian is the nested AutoTuple of its
+// rows
+#[derive(Debug, Clone, Copy, FuncCompose)]
+struct XSqrY;
+
+impl Diffable<()> for XSqrY {
+    type Input = XY;
+    type Output = AutoTuple<(f64,)>;
+}
+
+impl AutoDiffable<()> for XSqrY {
+    fn eval_grad(&self, xy: &XY, _: &()) -> (AutoTuple<(f64,)>, XY) {
+        let (x, y) = (xy.0 .0, xy.0 .1);
+        (
+            AutoTuple::new((x * x * y,)),
+            AutoTuple::new((2.0 * x * y, x * x)),
+        )
+    }
+
+    fn eval_conj_grad(&self, xy: &XY, _: &()) -> (AutoTuple<(f64,)>, XY) {
+        (self.eval(xy, &()), AutoTuple::new((0.0, 0.0)))
+    }
+}
+
+impl HessianDiffable<()> for XSqrY {
+    fn eval_hessian(&self, xy: &XY, _: &()) -> (AutoTuple<(f64,)>, XY, AutoTuple<(XY, XY)>) {
+        let (x, y) = (xy.0 .0, xy.0 .1);
+        let (f, df) = self.eval_grad(xy, &());
+        let d2f = AutoTuple::new((
+            AutoTuple::new((2.0 * y, 2.0 * x)),
+            AutoTuple::new((2.0 * x, 0.0)),
+        ));
+        (f, df, d2f)
+    }
+}
+
+#[test]
+fn test_hessian() {
+    // the Hessian should agree with the central difference of the gradient
+    let x = 0.7_f64;
+    let h = 1e-5_f64;
+
+    let i = AutoDiff::new(Identity::new());
+    let p = AutoDiff::new(Polynomial::new(vec![-1.0, 2.0, 3.0]));
+    let q = AutoDiff::new(Monomial::<(), f64, f64>::new(3.0));
+
+    let f = (p.clone() * i.sin() + q / i.exp() - p.clone().compose(q).tanh()).pow(3.0)
+        - (i.atan() * 2.0 + 1.0).ln()
+        + i.sigmoid() * p.clone().gelu()
+        - i.cosh().pow(0.5);
+
+    let (f_x, df_dx, d2f_dx2) = f.eval_hessian(&x, &());
+    let (fg_x, dfg_dx) = f.eval_grad(&x, &());
+    assert_eq!(f_x, fg_x);
+    assert_close(df_dx, dfg_dx);
+
+    let fd = (f.grad(&(x + h), &()) - f.grad(&(x - h), &())) / (2.0 * h);
+    assert!((d2f_dx2 - fd).abs() <= 1e-6 * fd.abs().max(1.0));
+
+    // the gradient as its own function has the Hessian as its gradient
+    let df = f.clone().grad_fn();
+    assert_eq!(df.eval_grad(&x, &()), (df_dx, d2f_dx2));
+
+    // holomorphic complex functions
+    let z = Complex::<f64>::new(0.5, -0.3);
+    let hz = Complex::<f64>::new(h, 0.0);
+    let iz = AutoDiff::new(Identity::new());
+    let g = (iz * iz.exp() + iz.sin() / (iz + 2.0)).pow(2.0) - iz.tanh();
+
+    let d2g_dz2 = g.hessian(&z, &());
+    let fd = (g.grad(&(z + hz), &()) - g.grad(&(z - hz), &())) / (2.0 * hz);
+    assert!((d2g_dz2 - fd).norm() <= 1e-6 * fd.norm().max(1.0));
+
+    // AutoTuple inputs, where each row of the Hessian is the central difference of the gradient
+    // along one element of the input
+    let xy = AutoTuple::new((0.7_f64, -1.2_f64));
+    let u = AutoDiff::new(XSqrY);
+    let w = u * u + u;
+    let (w_xy, dw_dxy, d2w_dxy2) = w.eval_hessian(&xy, &());
+    assert_eq!((w_xy, dw_dxy), w.eval_grad(&xy, &()));
+    let rows = [
+        (AutoTuple::new((h, 0.0)), d2w_dxy2.0 .0),
+        (AutoTuple::new((0.0, h)), d2w_dxy2.0 .1),
+    ];
+    for (dxy, row) in rows {
+        let (plus, minus) = (w.grad(&(xy + dxy), &()), w.grad(&(xy - dxy), &()));
+        let fd = [
+            (plus.0 .0 - minus.0 .0) / (2.0 * h),
+            (plus.0 .1 - minus.0 .1) / (2.0 * h),
+        ];
+        assert!((row.0 .0 - fd[0]).abs() <= 1e-6 * fd[0].abs().max(1.0));
+        assert!((row.0 .1 - fd[1]).abs() <= 1e-6 * fd[1].abs().max(1.0));
+    }
+
+    // monomials at zero, where the powers with negative exponents diverge
+    let m = |p: f64| AutoDiff::new(Monomial::<(), f64, f64>::new(p));
+    assert_eq!(m(1.0).eval_hessian(&0.0, &()), (0.0, 1.0, 0.0));
+    assert_eq!(m(2.0).eval_hessian(&0.0, &()), (0.0, 0.0, 2.0));
+    assert_eq!(m(3.0).eval_hessian(&0.0, &()), (0.0, 0.0, 0.0));
+    assert_eq!(m(0.0).eval_hessian(&0.0, &()), (1.0, 0.0, 0.0));
+    assert_eq!(m(1.5).eval_hessian(&0.0, &()).0, 0.0);
+    assert_eq!(m(1.5).eval_hessian(&0.0, &()).1, 0.0);
+}
+
+#[test]
+fn test_hvp() {
+    // the Hessian-vector product should agree with the Hessian times v
+    let x = 0.7_f64;
+    let v = -1.3_f64;
+
+    let i = AutoDiff::new(Identity::new());
+    let p = AutoDiff::new(Polynomial::new(vec![-1.0, 2.0, 3.0]));
+    let q = AutoDiff::new(Monomial::<(), f64, f64>::new(3.0));
+
+    let f = (p.clone() * i.sin() + q / i.exp() - p.clone().compose(q).tanh()).pow(3.0)
+        - (i.atan() * 2.0 + 1.0).ln()
+        + i.sigmoid() * p.clone().gelu()
+        - i.cosh().pow(0.5);
+
+    let (f_x, df_dx, d2f_dx2) = f.eval_hessian(&x, &());
+    assert_close(f.hvp(&x, &v, &()), d2f_dx2 * v);
+
+    // with a cotangent c whose derivative along v is dc, the results are the vjp and its derivative
+    let (c, dc) = (0.5_f64, 2.0_f64);
+    let (fv_x, vjp, dvjp) = f.eval_hvp(&x, &v, &c, &dc, &());
+    assert_eq!(fv_x, f_x);
+    assert_close(vjp, c * df_dx);
+    assert_close(dvjp, dc * df_dx + c * d2f_dx2 * v);
+
+    // the forward gradient of the gradient is the Hessian-vector product
+    assert_close(f.clone().grad_fn().forward_grad(&x, &v, &()), d2f_dx2 * v);
+
+    // holomorphic complex functions
+    let z = Complex::<f64>::new(0.5, -0.3);
+    let vz = Complex::<f64>::new(-0.2, 1.1);
+    let iz = AutoDiff::new(Identity::new());
+    let g = (iz * iz.exp() + iz.sin() / (iz + 2.0)).pow(2.0) - iz.tanh();
+
+    assert_close(g.hvp(&z, &vz, &()), g.hessian(&z, &()) * vz);
+
+    // monomials at zero, where the powers with negative exponents diverge
+    let m = |p: f64| AutoDiff::new(Monomial::<(), f64, f64>::new(p));
+    assert_eq!(m(1.0).hvp(&0.0, &v, &()), 0.0);
+    assert_eq!(m(1.0).eval_hvp(&0.0, &v, &c, &dc, &()), (0.0, c, dc));
+    assert_eq!(m(2.0).hvp(&0.0, &v, &()), 2.0 * v);
+}
+
+// the first coefficients of the Taylor series of f should be f, f' and f''/2
+fn assert_taylor_hessian<F>(f: &F, x: f64)
+where
+    F: TaylorDiffable<(), 4, Input = f64, Output = f64>
+        + HessianDiffable<(), Input = f64, Output = f64>,
+{
+    let [a0, a1, a2, a3] = f.eval_taylor(&x, 3, &());
+    let (f_x, df_dx, d2f_dx2) = f.eval_hessian(&x, &());
+    assert_close(a0, f_x);
+    assert_close(a1, df_dx);
+    assert_close(a2 * 2.0, d2f_dx2);
+
+    // and f'''/6, compared against central differences of the Hessian
+    let eps = 1e-5;
+    let d3f_dx3 =
+        (f.eval_hessian(&(x + eps), &()).2 - f.eval_hessian(&(x - eps), &()).2) / (2.0 * eps);
+    assert!(
+        (a3 * 6.0 - d3f_dx3).abs() <= 1e-5 * d3f_dx3.abs().max(1.0),
+        "{} != {}",
+        a3 * 6.0,
+        d3f_dx3
+    );
+}
+
+#[test]
+fn test_taylor() {
+    let i = AutoDiff::new(Identity::new());
+
+    // e^(x0 + t) = e^x0 sum t^k / k!
+    let x0 = 0.5_f64;
+    let exp: [f64; 6] = i.exp().eval_taylor(&x0, 5, &());
+    let mut factorial = 1.0;
+    for (k, a) in exp.into_iter().enumerate() {
+        factorial *= (k.max(1)) as f64;
+        assert_close(a, x0.exp() / factorial);
+    }
+
+    // sin(t) = t - t^3/6 + t^5/120
+    let sin: [f64; 6] = i.sin().eval_taylor(&0.0, 5, &());
+    for (a, b) in sin
+        .into_iter()
+        .zip([0.0, 1.0, 0.0, -1.0 / 6.0, 0.0, 1.0 / 120.0])
+    {
+        assert_close(a, b);
+    }
+
+    // 1/(1 - t) = sum t^k, both as a power and as a quotient
+    let geometric = (-i + 1.0).pow(-1.0);
+    let quotient = i.exp() / (i.exp() - i.exp() * i);
+    let geometric_coeffs: [f64; 5] = geometric.eval_taylor(&0.0, 4, &());
+    let quotient_coeffs: [f64; 5] = quotient.eval_taylor(&0.0, 4, &());
+    for (a, b) in geometric_coeffs.into_iter().zip(quotient_coeffs) {
+        assert_close(a, 1.0);
+        assert_close(b, 1.0);
+    }
+
+    // coefficients beyond the order are zero
+    let truncated: [f64; 5] = geometric.eval_taylor(&0.0, 2, &());
+    assert_eq!(truncated, [1.0, 1.0, 1.0, 0.0, 0.0]);
+
+    // powers at zero, where the recurrence of a^p would divide by a_0 = 0
+    let m = |p: f64| AutoDiff::new(Monomial::<(), f64, f64>::new(p));
+    assert_eq!(m(2.0).eval_taylor(&0.0, 3, &()), [0.0, 0.0, 1.0, 0.0]);
+    assert_eq!(m(3.0).eval_taylor(&0.0, 3, &()), [0.0, 0.0, 0.0, 1.0]);
+    assert_eq!(m(0.0).eval_taylor(&0.0, 3, &()), [1.0, 0.0, 0.0, 0.0]);
+    assert_eq!(
+        (i * i).pow(0.5).eval_taylor(&0.0, 3, &()),
+        [0.0, 1.0, 0.0, 0.0]
+    );
+    assert_eq!(
+        (i * i + i * i * i).pow(1.5).eval_taylor(&0.0, 3, &()),
+        [0.0, 0.0, 0.0, 1.0]
+    );
+    let [a0, a1, a2, a3]: [f64; 4] = m(1.5).eval_taylor(&0.0, 3, &());
+    assert_eq!((a0, a1), (0.0, 0.0));
+    assert!(a2.is_infinite() && a3.is_infinite());
+    let reciprocal: [f64; 4] = m(-1.0).eval_taylor(&0.0, 3, &());
+    assert!(reciprocal.iter().all(|a| a.is_infinite()));
+
+    // the Taylor coefficients agree with the Hessian for every supported operation
+    let p = AutoDiff::new(Polynomial::new(vec![-1.0, 2.0, 3.0]));
+    let q = AutoDiff::new(Monomial::<(), f64, f64>::new(3.0));
+    let x = 0.7_f64;
+
+    let f = (p.clone() * i.sin() + q / i.exp() - p.clone().compose(q).tanh()).pow(3.0)
+        - (i.atan() * 2.0 + 1.0).ln()
+        + i.sigmoid() * p.clone().gelu()
+        - i.cosh().pow(0.5);
+    assert_taylor_hessian(&f, x);
+
+    assert_taylor_hessian(&(i.tan() + i.cos() * i.sinh()), x);
+    assert_taylor_hessian(&(i.asin() - i.acos() / 3.0), x);
+    assert_taylor_hessian(&(i.asinh() * i.atanh()), x);
+    assert_taylor_hessian(&((i + 1.0).acosh() - 2.0), x);
+    assert_taylor_hessian(&(i.erf() + i.erfc() * i), x);
+    assert_taylor_hessian(&(i.softplus() - i.log2() + i.log10()), x);
+
+    // f^g is propagated as e^(g * ln(f))
+    let pow: [f64; 4] = (q + i).pow(i.sin()).eval_taylor(&x, 3, &());
+    let exp_ln = (i.sin() * (q + i).ln()).exp();
+    assert_taylor_hessian(&exp_ln, x);
+    let exp_ln_coeffs: [f64; 4] = exp_ln.eval_taylor(&x, 3, &());
+    for (a, b) in pow.into_iter().zip(exp_ln_coeffs) {
+        assert_close(a, b);
+    }
+
+    // the series of a holomorphic complex function gives its complex derivatives
+    let z = Complex::<f64>::new(0.5, -0.3);
+    let iz = AutoDiff::new(Identity::new());
+    let g = (iz * iz.exp() + iz.sin() / (iz + 2.0)).pow(2.0) - iz.tanh();
+
+    let [g_z, dg_dz, d2g_dz2_2]: [Complex<f64>; 3] = g.eval_taylor(&z, 2, &());
+    let (g_z_h, dg_dz_h, d2g_dz2) = g.eval_hessian(&z, &());
+    assert_close(g_z, g_z_h);
+    assert_close(dg_dz, dg_dz_h);
+    assert_close(d2g_dz2_2 * 2.0, d2g_dz2);
+}
+
+// a function written generically over Float, which cannot be built as an AutoDiff expression
+fn generic_float<F: num::Float>(x: F, y: F) -> F {
+    let two = F::from(2.0).unwrap();
+    let mut acc = x.sin() * y.exp() + x.powi(3) / (y + two);
+    for _ in 0..3 {
+        acc = if acc > F::zero() {
+            acc.sqrt() + x.hypot(y).ln()
+        } else {
+            acc.abs().powf(y) - x.atan2(y)
+        };
+    }
+    acc.tanh().max(x.cosh().recip())
+}
+
+fn generic_expr<F: num::Float>(x: F) -> F {
+    (x.sin() * x.exp() + x.powf(F::from(3.0).unwrap())).ln()
+}
+
+#[test]
+fn test_dual() {
+    let (x, y) = (0.7_f64, -0.4_f64);
+
+    // the tangent of f(x + ε) is f'(x), compared against central differences
+    let eps = 1e-6;
+    let dfdx = (generic_float(x + eps, y) - generic_float(x - eps, y)) / (2.0 * eps);
+    let dfdy = (generic_float(x, y + eps) - generic_float(x, y - eps)) / (2.0 * eps);
+
+    let f = generic_float(Dual::variable(x), Dual::constant(y));
+    assert_eq!(f.value, generic_float(x, y));
+    assert!((f.tangent - dfdx).abs() < 1e-8);
+
+    // DualN gives the whole gradient in one evaluation
+    let [xn, yn] = DualN::variables([x, y]);
+    let f = generic_float(xn, yn);
+    assert_eq!(f.value, generic_float(x, y));
+    assert!((f.tangent[0] - dfdx).abs() < 1e-8);
+    assert!((f.tangent[1] - dfdy).abs() < 1e-8);
+
+    // agrees with AutoDiff on an expression both can evaluate
+    let i = AutoDiff::new(Identity::new());
+    let g = (i.sin() * i.exp() + i.pow(3.0)).ln();
+    let gd = generic_expr(Dual::variable(x));
+    assert_close(gd.value, g.eval(&x, &()));
+    assert_close(gd.tangent, g.grad(&x, &()));
+
+    // as the input of Polynomial and Monomial, the tangent of the output is p'(x)
+    let p = AutoDiff::new(Polynomial::new(vec![
+        Dual::constant(3.0),
+        Dual::constant(2.0),
+        Dual::constant(1.0),
+    ]));
+    let px = p.eval(&Dual::variable(2.0), &());
+    assert_eq!((px.value, px.tangent), (11.0, 6.0));
+
+    // and the gradient of the dual input carries the second derivative in its tangent
+    let (_, dp) = p.eval_grad(&Dual::variable(2.0), &());
+    assert_eq!((dp.value, dp.tangent), (6.0, 2.0));
+
+    let q = AutoDiff::new(Monomial::<(), Dual<f64>, f64>::new(3.0));
+    let qx = q.eval(&Dual::variable(2.0), &());
+    assert_eq!((qx.value, qx.tangent), (8.0, 12.0));
+    let (_, dq) = q.eval_grad(&Dual::variable(2.0), &());
+    assert_eq!((dq.value, dq.tangent), (12.0, 12.0));
+}
+
+#[test]
+fn test_finite_diff() {
+    let x = 0.7_f64;
+    let i = AutoDiff::new(Identity::new());
+
+    // a black box whose exact derivative is (e^x sin(x))' = e^x (sin(x) + cos(x))
+    let black_box = |x: &f64, _: &()| x.exp() * x.sin();
+    let exact = x.exp() * (x.sin() + x.cos());
+
+    let central = AutoDiff::new(ADFiniteDiff::new(black_box));
+    let (f_x, df_dx) = central.eval_grad(&x, &());
+    assert_eq!(f_x, black_box(&x, &()));
+    assert!((df_dx - exact).abs() < 1e-9);
+    assert_eq!(central.conj_grad(&x, &()), 0.0);
+
+    let richardson = AutoDiff::new(ADFiniteDiff::new(black_box).with_richardson());
+    assert!((richardson.grad(&x, &()) - exact).abs() < 1e-11);
+
+    let coarse = AutoDiff::new(ADFiniteDiff::new(black_box).with_step(1e-2));
+    assert!((coarse.grad(&x, &()) - exact).abs() > 1e-6);
+
+    // the default step is relative to large inputs, so x + h does not round away most of h
+    let large = 1e8_f64;
+    let x_ln_x = |x: &f64, _: &()| x * x.ln();
+    let exact_large = large.ln() + 1.0;
+    let central_large = AutoDiff::new(ADFiniteDiff::new(x_ln_x)).grad(&large, &());
+    let richardson_large = AutoDiff::new(ADFiniteDiff::new(x_ln_x).with_richardson());
+    assert!((central_large - exact_large).abs() < 1e-8 * exact_large);
+    assert!((richardson_large.grad(&large, &()) - exact_large).abs() < 1e-8 * exact_large);
+    let z_large = Complex::new(large, -large);
+    let z_sqr = AutoDiff::new(ADFiniteDiff::new(|z: &Complex<f64>, _: &()| z * z));
+    assert!((z_sqr.grad(&z_large, &()) - 2.0 * z_large).norm() < 1e-8 * z_large.norm());
+
+    // complex-step differentiation has no cancellation, so it is exact to rounding
+    let complex_step = AutoDiff::new(ADFiniteDiff::complex_step(|z: &Complex<f64>, _: &()| {
+        z.exp() * z.sin()
+    }));
+    assert_eq!(complex_step.eval(&x, &()), f_x);
+    assert_close(complex_step.grad(&x, &()), exact);
+
+    // the nodes compose with exact derivatives on either side
+    let p = AutoDiff::new(Polynomial::new(vec![1.0, 2.0, 3.0]));
+    let inner = central.compose(p.clone()) * i.cos();
+    let outer = p.clone().compose(complex_step) + i;
+    let y = 1.0 + 2.0 * x + 3.0 * x * x;
+    let dinner =
+        y.exp() * (y.sin() + y.cos()) * (2.0 + 6.0 * x) * x.cos() - y.exp() * y.sin() * x.sin();
+    assert!((inner.grad(&x, &()) - dinner).abs() < 1e-8);
+    assert_close(outer.grad(&x, &()), (2.0 + 6.0 * f_x) * exact + 1.0);
+
+    // complex inputs give both Wirtinger derivatives
+    let z = Complex::new(0.5, -0.3);
+    let holomorphic = AutoDiff::new(ADFiniteDiff::new(|z: &Complex<f64>, _: &()| z * z.sin()));
+    let (dh_dz, dh_dconjz) = (holomorphic.grad(&z, &()), holomorphic.conj_grad(&z, &()));
+    assert!((dh_dz - (z.sin() + z * z.cos())).norm() < 1e-9);
+    assert!(dh_dconjz.norm() < 1e-9);
+
+    let abs_sqr = AutoDiff::new(ADFiniteDiff::new(|z: &Complex<f64>, _: &()| z * z.conj()));
+    assert!((abs_sqr.grad(&z, &()) - z.conj()).norm() < 1e-9);
+    assert!((abs_sqr.conj_grad(&z, &()) - z).norm() < 1e-9);
+
+    // AutoTuple inputs differentiate each output element wrt the matching input element
+    let xy = AutoTuple::new((0.7_f64, -1.2_f64));
+    let pairwise = AutoDiff::new(ADFiniteDiff::new(|xy: &AutoTuple<(f64, f64)>, _: &()| {
+        AutoTuple::new((xy.0 .0 * xy.0 .1, xy.0 .1.exp()))
+    }));
+    let d_pairwise = pairwise.grad(&xy, &());
+    assert!((d_pairwise.0 .0 - -1.2).abs() < 1e-9);
+    assert!((d_pairwise.0 .1 - (-1.2_f64).exp()).abs() < 1e-9);
+
+    // and a scalar output gives its gradient wrt every input element
+    let sum_sqr = AutoDiff::new(ADFiniteDiff::new(|xy: &AutoTuple<(f64, f64)>, _: &()| {
+        AutoTuple::new((xy.0 .0 * xy.0 .0 + xy.0 .1 * xy.0 .1,))
+    }));
+    let d_sum_sqr = sum_sqr.grad(&xy, &());
+    assert!((d_sum_sqr.0 .0 - 1.4).abs() < 1e-9);
+    assert!((d_sum_sqr.0 .1 - -2.4).abs() < 1e-9);
+}
+
+// z^2 with a forward mode that drops the imaginary part of dz, which is correct along dz = 1
+#[derive(Debug, Clone, Copy)]
+struct RealForwardSqr;
+
+impl Diffable<()> for RealForwardSqr {
+    type Input = Complex<f64>;
+    type Output = Complex<f64>;
+}
+
+impl AutoDiffable<()> for RealForwardSqr {
+    fn eval_grad(&self, z: &Complex<f64>, _: &()) -> (Complex<f64>, Complex<f64>) {
+        (z * z, 2.0 * z)
+    }
+
+    fn eval_conj_grad(&self, z: &Complex<f64>, _: &()) -> (Complex<f64>, Complex<f64>) {
+        (z * z, Complex::new(0.0, 0.0))
+    }
+}
+
+impl ForwardDiffable<()> for RealForwardSqr {
+    fn eval_forward_grad(
+        &self,
+        z: &Complex<f64>,
+        dz: &Complex<f64>,
+        _: &(),
+    ) -> (Complex<f64>, Complex<f64>) {
+        (z * z, 2.0 * z * dz.re)
+    }
+
+    fn eval_forward_conj_grad(
+        &self,
+        z: &Complex<f64>,
+        _: &Complex<f64>,
+        _: &(),
+    ) -> (Complex<f64>, Complex<f64>) {
+        (z * z, Complex::new(0.0, 0.0))
+    }
+}
+
+#[test]
+fn test_check_grad() {
+    // real
+    let x = 0.7_f64;
+    let i = AutoDiff::new(Identity::new());
+    let p = AutoDiff::new(Polynomial::new(vec![3.0, 2.0, 1.0, -0.5]));
+    let check = check_grad(&(p.clone() * i.sin()), &x, &(), 1e-8);
+    assert!(check.is_ok(), "{}", check);
+
+    // complex, with nonzero conj grads
+    let z = Complex::new(0.5_f64, -0.25);
+    let i_complex = AutoDiff::new(Identity::new());
+    let f = i_complex.abs_sqr() * i_complex.exp() + i_complex;
+    let check = check_grad(&f, &z, &(), 1e-8);
+    assert!(check.is_ok(), "{}", check);
+
+    // AutoTuple, differentiating each output element wrt the matching input element
+    let xy = AutoTuple::new((0.7_f64, -1.2_f64));
+    let pairwise = FnDiff::new(
+        |xy: &AutoTuple<(f64, f64)>, _: &()| AutoTuple::new((xy.0 .0 * xy.0 .1, xy.0 .1.exp())),
+        |xy: &AutoTuple<(f64, f64)>, _: &()| AutoTuple::new((xy.0 .1, xy.0 .1.exp())),
+    );
+    let check = check_grad(&pairwise, &xy, &(), 1e-8);
+    assert!(check.is_ok(), "{}", check);
+
+    // a wrong derivative is reported in grad and in the forward mode gradient, but not in the
+    // conj grad
+    let wrong = FnDiff::new(|x: &f64, _: &()| x.sin(), |x: &f64, _: &()| x.sin());
+    let check = check_grad(&wrong, &x, &(), 1e-8);
+    assert!(!check.is_ok());
+    let kinds: Vec<GradCheckKind> = check.mismatches.iter().map(|m| m.kind).collect();
+    assert_eq!(kinds, vec![GradCheckKind::Grad, GradCheckKind::ForwardGrad]);
+    assert_eq!(check.mismatches[0].index, 0);
+    assert_close(check.mismatches[0].computed, x.sin().into());
+    assert!((check.mismatches[0].numerical.re - x.cos()).abs() < 1e-8);
+
+    // forward mode is checked along every direction, here dz = 1 and dz = i
+    let check = check_grad(&RealForwardSqr, &z, &(), 1e-8);
+    let kinds: Vec<GradCheckKind> = check.mismatches.iter().map(|m| m.kind).collect();
+    assert_eq!(kinds, vec![GradCheckKind::ForwardGrad]);
+    assert_eq!(check.mismatches[0].index, 1);
+    assert_eq!(check.mismatches[0].computed, Complex::new(0.0, 0.0));
+    assert!((check.mismatches[0].numerical - 2.0 * z * Complex::i()).norm() < 1e-8);
+}
+
+#[test]
+fn test_check_wirtinger() {
+    let z = Complex::new(0.5_f64, -0.25);
+    let i = AutoDiff::new(Identity::new());
+    let p = AutoDiff::new(Polynomial::new(vec![
+        Complex::new(1.0, 0.5),
+        Complex::new(2.0, 0.0),
+        Complex::new(0.0, -1.0),
+    ]));
+
+    macro_rules! assert_wirtinger {
+        ($f:expr, $tol:expr, $holomorphic:expr) => {
+            let check = check_wirtinger(&$f, &z, &(), $tol, $holomorphic);
+            assert!(check.is_ok(), "{}: {}", stringify!($f), check);
+        };
+    }
+
+    // holomorphic functions, including compositions
+    assert_wirtinger!(i.exp() * i.sin(), 1e-8, true);
+    assert_wirtinger!(p.clone().compose(i.exp()), 1e-8, true);
+
+    // the non-holomorphic branches of abs, abs_sqr and signum, also composed on both sides
+    assert_wirtinger!(i.abs(), 1e-7, false);
+    assert_wirtinger!(i.abs_sqr(), 1e-7, false);
+    assert_wirtinger!(i.signum(), 1e-7, false);
+    assert_wirtinger!(p.clone().abs(), 1e-7, false);
+    assert_wirtinger!(p.clone().compose(i.abs_sqr() + i), 1e-7, false);
+    assert_wirtinger!(i.signum().compose(p.clone()), 1e-7, false);
+    assert_wirtinger!(i.abs_sqr().exp(), 1e-7, false);
+
+    // a non-holomorphic function claimed to be holomorphic is flagged
+    let check = check_wirtinger(&i.abs_sqr(), &z, &(), 1e-8, true);
+    let kinds: Vec<GradCheckKind> = check.mismatches.iter().map(|m| m.kind).collect();
+    assert_eq!(kinds, vec![GradCheckKind::Holomorphic]);
+    assert_close(check.mismatches[0].computed, z);
+
+    // and so is a missing conjugate gradient; the differential still agrees, since it is only
+    // compared with the computed gradients rather than with finite differences
+    let missing = FnDiff::new(
+        |z: &Complex<f64>, _: &()| z * z.conj(),
+        |z: &Complex<f64>, _: &()| z.conj(),
+    );
+    let check = check_wirtinger(&missing, &z, &(), 1e-8, false);
+    let kinds: Vec<GradCheckKind> = check.mismatches.iter().map(|m| m.kind).collect();
+    assert_eq!(kinds, vec![GradCheckKind::ConjGrad]);
+    let with_conj = FnDiff::with_conj_grad(
+        |z: &Complex<f64>, _: &()| z * z.conj(),
+        |z: &Complex<f64>, _: &()| z.conj(),
+        |z: &Complex<f64>, _: &()| *z,
+    );
+    let check = check_wirtinger(&with_conj, &z, &(), 1e-8, false);
+    assert!(check.is_ok(), "{}", check);
+}
+
+#[test]
+fn test_dyn_autodiff() {
+    let x = 0.7_f64;
+    let dx = 2.0_f64;
+    let i = AutoDiff::new(Identity::new());
+    let p = AutoDiff::new(Polynomial::new(vec![1.0, 2.0, 3.0]));
+
+    // functions of different types stored together
+    let fs: Vec<DynAutoDiff<(), f64, f64>> = vec![
+        i.sin().boxed(),
+        (p.clone() * i.exp()).boxed(),
+        p.clone().compose(i.cos()).boxed(),
+    ];
+    let expected = [
+        (x.sin(), x.cos()),
+        (
+            (1.0 + 2.0 * x + 3.0 * x * x) * x.exp(),
+            (3.0 + 8.0 * x + 3.0 * x * x) * x.exp(),
+        ),
+        (
+            1.0 + 2.0 * x.cos() + 3.0 * x.cos() * x.cos(),
+            -(2.0 + 6.0 * x.cos()) * x.sin(),
+        ),
+    ];
+    for (f, (f_x, df_dx)) in fs.iter().zip(expected) {
+        let (g_x, dg_dx) = f.eval_grad(&x, &());
+        assert_close(g_x, f_x);
+        assert_close(dg_dx, df_dx);
+        let (g_x, dg_x) = f.eval_forward_grad(&x, &dx, &());
+        assert_close(g_x, f_x);
+        assert_close(dg_x, df_dx * dx);
+    }
+
+    // chosen at runtime
+    let pick = |name: &str| -> DynAutoDiff<(), f64, f64> {
+        match name {
+            "tanh" => i.tanh().boxed(),
+            "softplus" => i.softplus().boxed(),
+            _ => i.boxed(),
+        }
+    };
+    assert_eq!(pick("tanh").eval(&x, &()), x.tanh());
+    assert_eq!(pick("identity").grad(&x, &()), 1.0);
+
+    // and combined with the AutoDiff operators, including with each other
+    let g = (fs[0].clone() * fs[0].clone() + 1.0).compose(fs[2].clone()) - pick("softplus");
+    let y = fs[2].eval(&x, &());
+    let dy_dx = fs[2].grad(&x, &());
+    let (g_x, dg_dx) = g.eval_grad(&x, &());
+    assert_close(g_x, y.sin() * y.sin() + 1.0 - x.exp().ln_1p());
+    assert_close(
+        dg_dx,
+        2.0 * y.sin() * y.cos() * dy_dx - 1.0 / (1.0 + (-x).exp()),
+    );
+    let h: DynAutoDiff<(), f64, f64> = g.boxed();
+    assert_eq!(h.clone().eval(&x, &()), g_x);
+
+    // the conjugate gradients of complex functions are kept
+    let z = Complex::new(0.5_f64, -0.25);
+    let i_complex = AutoDiff::new(Identity::new());
+    let f: DynAutoDiff<(), Complex<f64>, Complex<f64>> = (i_complex.abs_sqr() + i_complex).boxed();
+    assert_close(f.grad(&z, &()), z.conj() + 1.0);
+    assert_close(f.conj_grad(&z, &()), z);
+    let check = check_wirtinger(&f, &z, &(), 1e-8, false);
+    assert!(check.is_ok(), "{}", check);
+}
+
+// a custom node, whose name is given by its Symbolic impl and type name in graphs
+#[derive(Debug, Clone, Copy, SimpleForwardDiffable, FuncCompose)]
+struct Cube;
+
+impl Diffable<()> for Cube {
+    type Input = f64;
+    type Output = f64;
+}
+
+impl AutoDiffable<()> for Cube {
+    fn eval_grad(&self, x: &f64, _: &()) -> (f64, f64) {
+        (x * x * x, 3.0 * x * x)
+    }
+
+    fn eval_conj_grad(&self, x: &f64, _: &()) -> (f64, f64) {
+        (x * x * x, 0.0)
+    }
+}
+
+impl Symbolic for Cube {
+    fn symbolic(&self, x: &Expr) -> Expr {
+        Expr::call("cube", vec![x.clone()])
+    }
+}
+
+impl ToDot<()> for Cube {}
+
+#[test]
+fn test_symbolic() {
+    let i = AutoDiff::new(Identity::<(), f64>::new());
+    let p = AutoDiff::new(Polynomial::<(), f64, f64>::new(vec![1.0, 0.0, -3.0, 1.0]));
+
+    assert_eq!(i.to_string(), "x");
+    assert_eq!(p.to_string(), "1 - 3 * x^2 + x^3");
+    assert_eq!(
+        (i.sin() * i.exp() + i.cos() / i).to_string(),
+        "sin(x) * exp(x) + cos(x) / x"
+    );
+    assert_eq!(
+        ((i + i) * (i - i) / (i * i)).to_string(),
+        "(x + x) * (x - x) / (x * x)"
+    );
+    assert_eq!((i - (i - i)).to_string(), "x - (x - x)");
+    assert_eq!((-(i + 1.0)).to_string(), "-(x + 1)");
+    assert_eq!(
+        (i.pow(i).pow(i) * 2.0 - 0.5).to_string(),
+        "(x^x)^x * 2 - 0.5"
+    );
+    assert_eq!(i.pow(i.pow(i)).to_string(), "x^x^x");
+    assert_eq!(i.pow(-1.0).to_string(), "x^(-1)");
+    assert_eq!((i * -2.0).to_string(), "x * -2");
+    assert_eq!(
+        i.log(2.0).atan2(i.max(i.relu())).to_string(),
+        "atan2(log(x, 2), max(x, relu(x)))"
+    );
+    assert_eq!(i.leaky_relu(0.1).to_string(), "leaky_relu(x, 0.1)");
+
+    // compositions substitute the inner function for the input of the outer one
+    let q = p.clone().compose(i.sin() + 1.0);
+    assert_eq!(q.to_string(), "1 - 3 * (sin(x) + 1)^2 + (sin(x) + 1)^3");
+    assert_eq!(
+        i.exp().compose(p.clone()).to_string(),
+        "exp(1 - 3 * x^2 + x^3)"
+    );
+    assert_eq!(p.symbolic(&Expr::var("t")).to_string(), "1 - 3 * t^2 + t^3");
+
+    // negative coefficients are subtracted, and a leading one is negated
+    let r: AutoDiff<(), _> =
+        AutoDiff::new(Polynomial::<(), f64, f64>::new(vec![0.0, -1.0, 2.0, -1.0]));
+    assert_eq!(r.to_string(), "-x + 2 * x^2 - x^3");
+    let r = AutoDiff::new(Polynomial::<(), f64, f64>::new(vec![-2.5, 0.0, -4.0]));
+    assert_eq!(r.to_string(), "-2.5 - 4 * x^2");
+    assert_eq!(r.compose(i - 1.0).to_string(), "-2.5 - 4 * (x - 1)^2");
+
+    // custom nodes and named constants
+    let cube = AutoDiff::new(Cube);
+    let k = Named::new("k", 2.5);
+    let f = (cube * k.clone() + i).compose(i / Named::new("\\lambda", 0.5));
+    assert_eq!(f.to_string(), "cube(x / \\lambda) * k + x / \\lambda");
+    assert_eq!(f.eval(&1.0, &()), 8.0 * 2.5 + 2.0);
+    assert_eq!(f.grad(&1.0, &()), (3.0 * 4.0 * 2.5 + 1.0) * 2.0);
+    let g = i.pow(k.clone());
+    assert_eq!(g.to_string(), "x^k");
+    assert_close(g.grad(&2.0, &()), 2.5 * 2.0_f64.powf(1.5));
+
+    // LaTeX
+    assert_eq!(p.to_latex(), "1 - 3 \\cdot x^{2} + x^{3}");
+    assert_eq!(
+        (i.sin() / (i.exp() + 1.0)).to_latex(),
+        "\\frac{\\sin\\left(x\\right)}{e^{x} + 1}"
+    );
+    assert_eq!(
+        (i.abs().pow(i + 1.0) * k).to_latex(),
+        "\\left|x\\right|^{x + 1} \\cdot k"
+    );
+    assert_eq!(
+        (i.log(2.0) - i.asinh().sigmoid().ln()).to_latex(),
+        "\\log_{2}\\left(x\\right) - \\ln\\left(\\sigma\\left(\\operatorname{asinh}\\left(x\\right)\\right)\\right)"
+    );
+    let z = AutoDiff::new(Identity::<(), Complex<f64>>::new());
+    assert_eq!(
+        (z.conj() * Complex::new(1.0, -2.0) + z.abs_sqr()).to_latex(),
+        "\\overline{x} \\cdot \\left(1-2i\\right) + \\left|x\\right|^{2}"
+    );
+    assert_eq!(
+        (z.conj() * Complex::new(1.0, -2.0)).to_string(),
+        "conj(x) * (1-2i)"
+    );
+}
+
+#[test]
+fn test_to_dot() {
+    let i = AutoDiff::new(Identity::<(), f64>::new());
+
+    let f = i.sin() * 2.0;
+    assert_eq!(
+        f.to_dot(),
+        "digraph {
+    n0 [label=\"x\"];
+    n1 [label=\"sin\"];
+    n2 [label=\"2\", shape=box];
+    n3 [label=\"*\"];
+    n0 -> n1;
+    n1 -> n3;
+    n2 -> n3;
+}"
+    );
+
+    // every node is annotated with its value and gradient
+    let dot = f.to_dot_at(&0.0, &());
+    assert!(dot.contains("n0 [label=\"x\\nvalue = 0.0\\ngrad = 1.0\"];"));
+    assert!(dot.contains("n1 [label=\"sin\\nvalue = 0.0\\ngrad = 1.0\"];"));
+    assert!(dot.contains("n3 [label=\"*\\nvalue = 0.0\\ngrad = 2.0\"];"));
+
+    // custom functions are labelled by their type, and the operands of non commutative functions
+    // by their position. the outer function of a composition is annotated at the inner value
+    let cube = AutoDiff::new(Cube);
+    let g = cube.compose(i - Named::new("k", 1.0));
+    let dot = g.to_dot_at(&3.0, &());
+    assert!(dot.contains("n1 [label=\"k\", shape=box];"));
+    assert!(dot.contains("n0 -> n2 [label=\"lhs\"];"));
+    assert!(dot.contains("n1 -> n2 [label=\"rhs\"];"));
+    assert!(dot.contains("n3 [label=\"Cube\\nvalue = 8.0\\ngrad = 12.0\"];"));
+    assert!(dot.contains("n3 -> n4 [label=\"outer\"];"));
+    assert!(dot.contains("n2 -> n4 [label=\"inner\"];"));
+    assert!(dot.contains("n4 [label=\"compose\\nvalue = 8.0\\ngrad = 12.0\"];"));
+
+    // static args used by part of an expression
+    let h = i.append_static_args::<f64>() * AutoDiff::new(Cube).append_static_args::<f64>();
+    let dot = h.to_dot_at(&2.0, &((), 0.5));
+    assert!(dot.contains("n1 [label=\"append_static_args\\nvalue = 2.0\\ngrad = 1.0\"];"));
+    assert!(dot.contains("n0 -> n1 [label=\"static_args.0\"];"));
+    assert!(dot.contains("n4 [label=\"*\\nvalue = 16.0\\ngrad = 32.0\"];"));
+}
+
+// the identity on complex numbers, which counts how often its gradients are evaluated
+#[derive(Debug, Clone, SimpleForwardDiffable, FuncCompose)]
+struct Counted(std::rc::Rc<std::cell::Cell<usize>>);
+
+impl Diffable<()> for Counted {
+    type Input = Complex<f64>;
+    type Output = Complex<f64>;
+}
+
+impl AutoDiffable<()> for Counted {
+    fn eval_grad(&self, x: &Complex<f64>, _: &()) -> (Complex<f64>, Complex<f64>) {
+        self.0.set(self.0.get() + 1);
+        (*x, Complex::new(1.0, 0.0))
+    }
+
+    fn eval_conj_grad(&self, x: &Complex<f64>, _: &()) -> (Complex<f64>, Complex<f64>) {
+        self.0.set(self.0.get() + 1);
+        (*x, Complex::new(0.0, 0.0))
+    }
+}
+
+impl ToDot<()> for Counted {}
+
+#[test]
+fn test_eval_all() {
+    let z = Complex::new(0.5_f64, -0.25);
+    let i = AutoDiff::new(Identity::new());
+
+    // eval_all agrees with eval, grad and conj_grad, including non-holomorphic compositions
+    let f = (i.abs_sqr() * i.sin() / (i.exp() + 2.0)).compose(i.conj() - i * i);
+    let (f_z, df_dz, df_dconjz) = f.eval_all(&z, &());
+    assert_close(f_z, f.eval(&z, &()));
+    assert_close(df_dz, f.grad(&z, &()));
+    assert_close(df_dconjz, f.conj_grad(&z, &()));
+    let check = check_wirtinger(&f, &z, &(), 1e-7, false);
+    assert!(check.is_ok(), "{}", check);
+
+    // every node of a deep composition of complex functions is evaluated a fixed number of
+    // times, rather than a number exponential in the depth
+    let count = std::rc::Rc::new(std::cell::Cell::new(0));
+    let c = AutoDiff::new(Counted(count.clone()));
+    let g = c
+        .clone()
+        .compose(c.clone().compose(c.clone().compose(c.clone())));
+    let g = g.clone().compose(g.clone().compose(g));
+    let (g_z, dg_dz) = g.eval_grad(&z, &());
+    assert_close(g_z, z);
+    assert_close(dg_dz, Complex::new(1.0, 0.0));
+    assert_eq!(count.get(), 2 * 12);
+
+    // the same holds for nodes which need both derivatives of their operand
+    count.set(0);
+    let h = c.clone().abs_sqr().abs_sqr().abs_sqr().abs_sqr();
+    let h = h.abs_sqr().abs_sqr().abs_sqr().abs_sqr();
+    // |w|^256 on the unit circle, so that the value and gradient stay of order 1
+    let w = Complex::new(0.6, 0.8);
+    let (h_w, dh_dw) = h.eval_grad(&w, &());
+    assert_close(h_w, Complex::new(1.0, 0.0));
+    assert_close(dh_dw, w.conj() * 128.0);
+    assert_eq!(count.get(), 2);
+
+    count.set(0);
+    let h = (c.clone().abs() * c.clone().conj())
+        .pow(c.clone().signum())
+        .ln();
+    h.eval_all(&z, &());
+    assert_eq!(count.get(), 2 * 3);
+    let check = check_wirtinger(&h, &z, &(), 1e-7, false);
+    assert!(check.is_ok(), "{}", check);
+}
+
+#[test]
+fn test_share() {
+    let z = Complex::new(0.5_f64, -0.25);
+    let count = std::rc::Rc::new(std::cell::Cell::new(0));
+    let c = AutoDiff::new(Counted(count.clone()));
+
+    // each copy of an unshared function is evaluated
+    let f = c.clone() * c.clone() + c.clone();
+    f.eval_grad(&z, &());
+    assert_eq!(count.get(), 3);
+
+    // a shared function is evaluated once for all its uses within a single evaluation, and
+    // nothing is reused by the next one
+    count.set(0);
+    let p = c.clone().share();
+    let f = p.clone() * p.clone() + p.clone();
+    let (f_z, df_dz) = f.eval_grad(&z, &());
+    assert_close(f_z, z * z + z);
+    assert_close(df_dz, z * 2.0 + 1.0);
+    assert_eq!(count.get(), 1);
+    f.eval_grad(&z, &());
+    assert_eq!(count.get(), 2);
+    assert_close(f.eval(&z, &()), z * z + z);
+    assert_eq!(count.get(), 3);
+
+    // eval_all evaluates both gradients of the shared function once, Counted computing each one
+    // separately
+    let (f_z, _, df_dconjz) = f.eval_all(&z, &());
+    assert_close(f_z, z * z + z);
+    assert_close(df_dconjz, Complex::new(0.0, 0.0));
+    assert_eq!(count.get(), 5);
+    let w = Complex::new(-1.0, 2.0);
+    assert_close(f.eval_grad(&w, &()).1, w * 2.0 + 1.0);
+    assert_eq!(count.get(), 6);
+
+    // inputs which compare equal are still distinct evaluations, e.g. either side of the branch
+    // cut of ln
+    let i = AutoDiff::new(Identity::<(), Complex<f64>>::new());
+    let l = i.ln().share();
+    let (above, below) = (Complex::new(-1.0, 0.0), Complex::new(-1.0, -0.0));
+    assert_eq!(above, below);
+    assert_close(l.eval(&above, &()), Complex::new(0.0, std::f64::consts::PI));
+    assert_close(
+        l.eval(&below, &()),
+        Complex::new(0.0, -std::f64::consts::PI),
+    );
+    assert_close(l.eval(&below, &()), i.ln().eval(&below, &()));
+
+    // a shared function used at different inputs, as the inner and outer function of a
+    // composition, is still evaluated at each of them
+    let g = p.clone().compose(p.clone() * 2.0);
+    let (g_z, dg_dz) = g.eval_grad(&z, &());
+    assert_close(g_z, z * 2.0);
+    assert_close(dg_dz, Complex::new(2.0, 0.0));
+
+    // and is drawn once
+    assert_eq!(
+        (p.clone() * p.clone() + p).to_dot(),
+        "digraph {
+    n0 [label=\"Counted\"];
+    n1 [label=\"*\"];
+    n2 [label=\"+\"];
+    n0 -> n1;
+    n0 -> n1;
+    n1 -> n2;
+    n0 -> n2;
+}"
+    );
+}
+
+#[test]
+fn test_simplify() {
+    let x = 0.7_f64;
+    let i = AutoDiff::new(Identity::<(), f64>::new());
+
+    macro_rules! assert_simplified {
+        ($f:expr, $at:expr, $expected:expr) => {
+            let f = $f;
+            let s = f.simplify();
+            assert_eq!(s.to_string(), $expected);
+            let (f_x, df_dx) = f.eval_grad(&$at, &());
+            let (s_x, ds_dx) = s.eval_grad(&$at, &());
+            assert_close(s_x, f_x);
+            assert_close(ds_dx, df_dx);
+        };
+    }
+
+    // identities are removed, including the conjugate of a real function
+    assert_simplified!(-(-(i * 1.0 + 0.0)) / 1.0, x, "x");
+    assert_eq!(i.conj().simplify().to_string(), "x");
+
+    // constants are folded, and polynomials, integer powers and sums and products of the same
+    // expression are merged
+    let p = AutoDiff::new(Polynomial::new(vec![1.0, 2.0]));
+    assert_simplified!(
+        p.clone().compose(i * i) * 3.0 + i.pow(2.0) - (i + 1.0) * (i - 1.0),
+        x,
+        "4 + 6 * x^2"
+    );
+    assert_simplified!(
+        AutoDiff::new(Monomial::new(3.0)).compose(p.clone() + i),
+        x,
+        "1 + 9 * x + 27 * x^2 + 27 * x^3"
+    );
+
+    // the arguments of functions which are not simplified are
+    assert_simplified!(
+        (i * 2.0 + 1.0).sin() * (3.0 - 1.0) + i.exp() * 0.0,
+        x,
+        "sin(x * 2 + 1) * 2"
+    );
+
+    // non-integer powers of powers are kept, since they differ on the branch cuts
+    assert_simplified!(i.pow(0.5).pow(2.0), x, "(x^0.5)^2");
+
+    // conjugates are kept for complex functions, and the simplified function has the same
+    // Wirtinger derivatives
+    let z = Complex::new(0.5_f64, -0.25);
+    let i = AutoDiff::new(Identity::<(), Complex<f64>>::new());
+    let f = i.conj().conj() * i.conj() * Complex::new(2.0, 0.0) + i.abs();
+    assert_simplified!(f, z, "x * conj(x) * (2+0i) + abs(x)");
+    assert_close(f.simplify().conj_grad(&z, &()), f.conj_grad(&z, &()));
+    let check = check_wirtinger(&f.simplify(), &z, &(), 1e-7, false);
+    assert!(check.is_ok(), "{}", check);
+}
+
+#[test]
+fn test_try_autodiff() {
+    let i = AutoDiff::new(Identity::<(), f64>::new());
+
+    // functions which can be evaluated have the same results as with AutoDiffable
+    let f = (i * 2.0).sin() / (i + 1.0);
+    assert_eq!(f.try_eval(&0.5, &()), Ok(f.eval(&0.5, &())));
+    assert_eq!(f.try_eval_grad(&0.5, &()), Ok(f.eval_grad(&0.5, &())));
+
+    // division by zero is a domain error at the division, with the path to it from the root
+    let g = i.exp() + i / (i - 1.0);
+    let err = g.try_eval(&1.0, &()).unwrap_err();
+    assert_eq!(
+        err,
+        AutoDiffError::DomainError {
+            path: vec!["ADAdd.1".to_string(), "ADDiv".to_string()],
+            message: "division by zero".to_string(),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "domain error at ADAdd.1 > ADDiv: division by zero"
+    );
+    assert_eq!(g.try_eval_all(&1.0, &()), Err(err));
+
+    // so is a fractional power of a negative number, which is NaN rather than an error otherwise
+    let h = (i - 2.0).pow(0.5_f64).compose(i * 3.0);
+    assert!(h.eval(&0.5, &()).is_nan());
+    assert_eq!(
+        h.try_eval_grad(&0.5, &()).unwrap_err().path(),
+        ["ADCompose.0", "ADConstantPow"]
+    );
+
+    // infinite values and gradients are not finite, and only the latter stop try_eval_grad
+    assert!(matches!(
+        i.ln().try_eval(&0.0, &()),
+        Err(AutoDiffError::NonFinite { .. })
+    ));
+    assert_eq!(i.pow(0.5_f64).try_eval(&0.0, &()), Ok(0.0));
+    assert_eq!(
+        i.pow(0.5_f64).try_eval_grad(&0.0, &()),
+        Err(AutoDiffError::non_finite(
+            "ADConstantPow",
+            "the gradient is not finite"
+        ))
+    );
+}
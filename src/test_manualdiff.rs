@@ -1,5 +1,6 @@
 use crate::autodiff::AutoDiff;
 use crate::autodiffable::*;
+use crate::autotuple::AutoTuple;
 use crate::compose::*;
 use crate::diffable::Diffable;
 use crate::forward::ForwardMul;
@@ -243,3 +244,118 @@ fn test_manual() {
     // TODO: use the compiletest_rs crate to ensure that this doesn't compile
     // let f4 = *f / *f;
 }
+
+#[test]
+fn test_manual_hessian() {
+    // f(x, y) = x^2 y, whose Hessian wrt (x, y) is a nested AutoTuple
+    type XY = AutoTuple<(f64, f64)>;
+    type Hess = AutoTuple<(XY, XY)>;
+
+    #[derive(Debug, Clone, Copy)]
+    struct X2Y;
+
+    impl Diffable<()> for X2Y {
+        type Input = XY;
+        type Output = AutoTuple<(f64,)>;
+    }
+
+    impl HessianDiffable<()> for X2Y {
+        fn eval_hessian(&self, x: &XY, _: &()) -> (AutoTuple<(f64,)>, XY, Hess) {
+            let (x, y) = (x.0 .0, x.0 .1);
+            (
+                AutoTuple::new((x * x * y,)),
+                AutoTuple::new((2.0 * x * y, x * x)),
+                AutoTuple::new((
+                    AutoTuple::new((2.0 * y, 2.0 * x)),
+                    AutoTuple::new((2.0 * x, 0.0)),
+                )),
+            )
+        }
+    }
+
+    impl ForwardDiffable<()> for X2Y {
+        fn eval_forward_grad(
+            &self,
+            x: &XY,
+            dx: &XY,
+            _: &(),
+        ) -> (AutoTuple<(f64,)>, AutoTuple<(f64,)>) {
+            let (x, y) = (x.0 .0, x.0 .1);
+            (
+                AutoTuple::new((x * x * y,)),
+                AutoTuple::new((2.0 * x * y * dx.0 .0 + x * x * dx.0 .1,)),
+            )
+        }
+        fn eval_forward_conj_grad(
+            &self,
+            x: &XY,
+            _: &XY,
+            _: &(),
+        ) -> (AutoTuple<(f64,)>, AutoTuple<(f64,)>) {
+            (self.eval_forward(x, &()), AutoTuple::new((0.0,)))
+        }
+    }
+
+    impl HvpDiffable<()> for X2Y {
+        fn eval_hvp(
+            &self,
+            x: &XY,
+            v: &XY,
+            cotangent: &AutoTuple<(f64,)>,
+            dcotangent: &AutoTuple<(f64,)>,
+            _: &(),
+        ) -> (AutoTuple<(f64,)>, XY, XY) {
+            let (x, y) = (x.0 .0, x.0 .1);
+            let (v0, v1) = (v.0 .0, v.0 .1);
+            let (c, dc) = (cotangent.0 .0, dcotangent.0 .0);
+            (
+                AutoTuple::new((x * x * y,)),
+                AutoTuple::new((c * 2.0 * x * y, c * x * x)),
+                AutoTuple::new((
+                    dc * 2.0 * x * y + c * (2.0 * y * v0 + 2.0 * x * v1),
+                    dc * x * x + c * 2.0 * x * v0,
+                )),
+            )
+        }
+    }
+
+    let xy = AutoTuple::new((3.0, 2.0));
+    let f = AutoDiff::new(X2Y);
+
+    let (f_xy, df_dxy, d2f_dxy2) = f.eval_hessian(&xy, &());
+    assert_eq!(f_xy, AutoTuple::new((18.0,)));
+    assert_eq!(df_dxy, AutoTuple::new((12.0, 9.0)));
+    assert_eq!(
+        d2f_dxy2,
+        AutoTuple::new((AutoTuple::new((4.0, 6.0)), AutoTuple::new((6.0, 0.0))))
+    );
+
+    // the Hessian is linear
+    let g = (f + f * 2.0 - AutoTuple::new((1.0,))) / 2.0;
+    assert_eq!(
+        g.eval_hessian(&xy, &()),
+        (
+            AutoTuple::new((26.5,)),
+            AutoTuple::new((18.0, 13.5)),
+            AutoTuple::new((AutoTuple::new((6.0, 9.0)), AutoTuple::new((9.0, 0.0))))
+        )
+    );
+
+    // and the product rule adds the outer products of the gradients, f^2 = x^4 y^2
+    assert_eq!(
+        (f * f).eval_hessian(&xy, &()),
+        (
+            AutoTuple::new((324.0,)),
+            AutoTuple::new((432.0, 324.0)),
+            AutoTuple::new((
+                AutoTuple::new((432.0, 432.0)),
+                AutoTuple::new((432.0, 162.0))
+            ))
+        )
+    );
+
+    // the Hessian-vector product is the Hessian times v without forming the Hessian
+    let v = AutoTuple::new((1.0, -1.0));
+    assert_eq!(f.hvp(&xy, &v, &()), AutoTuple::new((-2.0, 6.0)));
+    assert_eq!(g.hvp(&xy, &v, &()), AutoTuple::new((-3.0, 9.0)));
+}
@@ -1,6 +1,8 @@
 use crate::gradienttype::GradientType;
+use crate::special;
 use num::complex::Complex;
 use num::rational::Ratio;
+use num::traits::FloatConst;
 use num::{Float, Integer, Num, One, Zero};
 use std::num::Wrapping;
 use std::ops::{Add, Mul, Neg};
@@ -57,6 +59,13 @@ where
     fn grad_identity(&self) -> <Self as GradientType<Self>>::GradientType;
 }
 
+pub trait GradientZero<O>: GradientType<O> {
+    /// Returns the zero gradient for a function with input Self and output `O`,
+    /// given the output so that gradients with the shape of both can be built
+    /// without evaluating the gradient itself
+    fn grad_zero(&self, output: &O) -> <Self as GradientType<O>>::GradientType;
+}
+
 pub trait PossiblyComplex {
     fn is_always_real() -> bool;
 }
@@ -86,6 +95,194 @@ pub trait Signum {
     fn signum(self) -> Self::Output;
 }
 
+pub trait Exp {
+    type Output;
+    fn exp(self) -> Self::Output;
+}
+
+pub trait Ln {
+    type Output;
+    fn ln(self) -> Self::Output;
+}
+
+pub trait Log<B> {
+    type Output;
+    fn log(self, base: B) -> Self::Output;
+}
+
+pub trait Log2 {
+    type Output;
+    fn log2(self) -> Self::Output;
+}
+
+pub trait Log10 {
+    type Output;
+    fn log10(self) -> Self::Output;
+}
+
+pub trait Sqrt {
+    type Output;
+    fn sqrt(self) -> Self::Output;
+}
+
+pub trait Sin {
+    type Output;
+    fn sin(self) -> Self::Output;
+}
+
+pub trait Cos {
+    type Output;
+    fn cos(self) -> Self::Output;
+}
+
+pub trait Tan {
+    type Output;
+    fn tan(self) -> Self::Output;
+}
+
+pub trait Asin {
+    type Output;
+    fn asin(self) -> Self::Output;
+}
+
+pub trait Acos {
+    type Output;
+    fn acos(self) -> Self::Output;
+}
+
+pub trait Atan {
+    type Output;
+    fn atan(self) -> Self::Output;
+}
+
+pub trait Sinh {
+    type Output;
+    fn sinh(self) -> Self::Output;
+}
+
+pub trait Cosh {
+    type Output;
+    fn cosh(self) -> Self::Output;
+}
+
+pub trait Tanh {
+    type Output;
+    fn tanh(self) -> Self::Output;
+}
+
+pub trait Asinh {
+    type Output;
+    fn asinh(self) -> Self::Output;
+}
+
+pub trait Acosh {
+    type Output;
+    fn acosh(self) -> Self::Output;
+}
+
+pub trait Atanh {
+    type Output;
+    fn atanh(self) -> Self::Output;
+}
+
+pub trait Atan2<Rhs = Self> {
+    type Output;
+    fn atan2(self, other: Rhs) -> Self::Output;
+}
+
+pub trait Hypot<Rhs = Self> {
+    type Output;
+    fn hypot(self, other: Rhs) -> Self::Output;
+}
+
+pub trait Min<Rhs = Self> {
+    type Output;
+    fn min(self, other: Rhs) -> Self::Output;
+}
+
+pub trait Max<Rhs = Self> {
+    type Output;
+    fn max(self, other: Rhs) -> Self::Output;
+}
+
+pub trait Erf {
+    type Output;
+    fn erf(self) -> Self::Output;
+}
+
+pub trait Erfc {
+    type Output;
+    fn erfc(self) -> Self::Output;
+}
+
+pub trait Gamma {
+    type Output;
+    fn gamma(self) -> Self::Output;
+}
+
+pub trait Lgamma {
+    type Output;
+    fn lgamma(self) -> Self::Output;
+}
+
+pub trait Digamma {
+    type Output;
+    fn digamma(self) -> Self::Output;
+}
+
+pub trait Trigamma {
+    type Output;
+    fn trigamma(self) -> Self::Output;
+}
+
+/// The Heaviside step function, with H(0) = 0
+pub trait Heaviside {
+    type Output;
+    fn heaviside(self) -> Self::Output;
+}
+
+/// The cumulative distribution function of the standard normal distribution
+pub trait NormCdf {
+    type Output;
+    fn norm_cdf(self) -> Self::Output;
+}
+
+/// The probability density function of the standard normal distribution
+pub trait NormPdf {
+    type Output;
+    fn norm_pdf(self) -> Self::Output;
+}
+
+pub trait Relu {
+    type Output;
+    fn relu(self) -> Self::Output;
+}
+
+pub trait LeakyRelu<B> {
+    type Output;
+    fn leaky_relu(self, alpha: B) -> Self::Output;
+}
+
+pub trait Sigmoid {
+    type Output;
+    fn sigmoid(self) -> Self::Output;
+}
+
+pub trait Softplus {
+    type Output;
+    fn softplus(self) -> Self::Output;
+}
+
+pub trait Gelu {
+    type Output;
+    fn gelu(self) -> Self::Output;
+}
+
+pub trait Elu<B> {
+    type Output;
+    fn elu(self, alpha: B) -> Self::Output;
+}
+
 // implementation for InstZero for all the types that implement Zero from num
 // u32, i128, i16, u128, f64, usize, i32, i8, f32, i64, u16, Wrapping<T: Zero>, isize, u8, u64,
 // BigInt, BigUint, Ratio<T: Integer>, Complex<T: Num>
@@ -246,6 +443,55 @@ where
     }
 }
 
+// macro for implementing the zero gradient for primitive types, whose gradient has the type of
+// the output
+macro_rules! impl_grad_zero {
+    ($($t:ty),*) => ($(
+        impl<O> GradientZero<O> for $t
+        where
+            Self: GradientType<O, GradientType = O>,
+            O: InstZero,
+        {
+            fn grad_zero(&self, output: &O) -> O {
+                output.zero()
+            }
+        }
+    )*)
+}
+
+impl_grad_zero!(i64, u128, f32, u16, u32, i16, f64, isize, i32, u8, u64, usize, i128, i8);
+impl_grad_zero!(num::BigInt, num::BigUint);
+
+impl<T, O> GradientZero<O> for Wrapping<T>
+where
+    Self: GradientType<O, GradientType = Wrapping<O>>,
+    O: InstZero,
+{
+    fn grad_zero(&self, output: &O) -> Wrapping<O> {
+        Wrapping(output.zero())
+    }
+}
+
+impl<T, O> GradientZero<O> for Ratio<T>
+where
+    Self: GradientType<O, GradientType = Ratio<O>>,
+    O: Clone + Integer + InstZero,
+{
+    fn grad_zero(&self, output: &O) -> Ratio<O> {
+        Ratio::from_integer(output.zero())
+    }
+}
+
+impl<T, O> GradientZero<O> for Complex<T>
+where
+    Self: GradientType<O, GradientType = O>,
+    O: InstZero,
+{
+    fn grad_zero(&self, output: &O) -> O {
+        output.zero()
+    }
+}
+
 // implementation of Complex traits for all real number types
 macro_rules! impl_complex_traits_real_signed_copy {
     ($t:ty, $pi:expr) => {
@@ -357,6 +603,318 @@ impl_complex_traits_real_unsigned_copy!(u16);
 impl_complex_traits_real_unsigned_copy!(u8);
 impl_complex_traits_real_unsigned_copy!(usize);
 
+// implementation of elementary functions for all real floating point types
+macro_rules! impl_elementary_funcs_real_float {
+    ($t:ty) => {
+        impl Exp for $t {
+            type Output = Self;
+            fn exp(self) -> Self::Output {
+                <$t>::exp(self)
+            }
+        }
+
+        impl Ln for $t {
+            type Output = Self;
+            fn ln(self) -> Self::Output {
+                <$t>::ln(self)
+            }
+        }
+
+        impl Log<$t> for $t {
+            type Output = Self;
+            fn log(self, base: $t) -> Self::Output {
+                <$t>::log(self, base)
+            }
+        }
+
+        impl Log2 for $t {
+            type Output = Self;
+            fn log2(self) -> Self::Output {
+                <$t>::log2(self)
+            }
+        }
+
+        impl Log10 for $t {
+            type Output = Self;
+            fn log10(self) -> Self::Output {
+                <$t>::log10(self)
+            }
+        }
+
+        impl Sqrt for $t {
+            type Output = Self;
+            fn sqrt(self) -> Self::Output {
+                <$t>::sqrt(self)
+            }
+        }
+
+        impl Sin for $t {
+            type Output = Self;
+            fn sin(self) -> Self::Output {
+                <$t>::sin(self)
+            }
+        }
+
+        impl Cos for $t {
+            type Output = Self;
+            fn cos(self) -> Self::Output {
+                <$t>::cos(self)
+            }
+        }
+
+        impl Tan for $t {
+            type Output = Self;
+            fn tan(self) -> Self::Output {
+                <$t>::tan(self)
+            }
+        }
+
+        impl Asin for $t {
+            type Output = Self;
+            fn asin(self) -> Self::Output {
+                <$t>::asin(self)
+            }
+        }
+
+        impl Acos for $t {
+            type Output = Self;
+            fn acos(self) -> Self::Output {
+                <$t>::acos(self)
+            }
+        }
+
+        impl Atan for $t {
+            type Output = Self;
+            fn atan(self) -> Self::Output {
+                <$t>::atan(self)
+            }
+        }
+
+        impl Sinh for $t {
+            type Output = Self;
+            fn sinh(self) -> Self::Output {
+                <$t>::sinh(self)
+            }
+        }
+
+        impl Cosh for $t {
+            type Output = Self;
+            fn cosh(self) -> Self::Output {
+                <$t>::cosh(self)
+            }
+        }
+
+        impl Tanh for $t {
+            type Output = Self;
+            fn tanh(self) -> Self::Output {
+                <$t>::tanh(self)
+            }
+        }
+
+        impl Asinh for $t {
+            type Output = Self;
+            fn asinh(self) -> Self::Output {
+                <$t>::asinh(self)
+            }
+        }
+
+        impl Acosh for $t {
+            type Output = Self;
+            fn acosh(self) -> Self::Output {
+                <$t>::acosh(self)
+            }
+        }
+
+        impl Atanh for $t {
+            type Output = Self;
+            fn atanh(self) -> Self::Output {
+                <$t>::atanh(self)
+            }
+        }
+
+        impl Atan2 for $t {
+            type Output = Self;
+            fn atan2(self, other: $t) -> Self::Output {
+                <$t>::atan2(self, other)
+            }
+        }
+
+        impl Hypot for $t {
+            type Output = Self;
+            fn hypot(self, other: $t) -> Self::Output {
+                <$t>::hypot(self, other)
+            }
+        }
+
+        impl Min for $t {
+            type Output = Self;
+            fn min(self, other: $t) -> Self::Output {
+                <$t>::min(self, other)
+            }
+        }
+
+        impl Max for $t {
+            type Output = Self;
+            fn max(self, other: $t) -> Self::Output {
+                <$t>::max(self, other)
+            }
+        }
+    };
+}
+
+impl_elementary_funcs_real_float!(f32);
+impl_elementary_funcs_real_float!(f64);
+
+// implementation of special functions for all real floating point types, these are computed in f64
+macro_rules! impl_special_funcs_real_float {
+    ($t:ty) => {
+        impl Erf for $t {
+            type Output = Self;
+            fn erf(self) -> Self::Output {
+                special::erf(self as f64) as $t
+            }
+        }
+
+        impl Erfc for $t {
+            type Output = Self;
+            fn erfc(self) -> Self::Output {
+                special::erfc(self as f64) as $t
+            }
+        }
+
+        impl Gamma for $t {
+            type Output = Self;
+            fn gamma(self) -> Self::Output {
+                special::gamma(self as f64) as $t
+            }
+        }
+
+        impl Lgamma for $t {
+            type Output = Self;
+            fn lgamma(self) -> Self::Output {
+                special::lgamma(self as f64) as $t
+            }
+        }
+
+        impl Digamma for $t {
+            type Output = Self;
+            fn digamma(self) -> Self::Output {
+                special::digamma(self as f64) as $t
+            }
+        }
+
+        impl Trigamma for $t {
+            type Output = Self;
+            fn trigamma(self) -> Self::Output {
+                special::trigamma(self as f64) as $t
+            }
+        }
+    };
+}
+
+impl_special_funcs_real_float!(f32);
+impl_special_funcs_real_float!(f64);
+
+// implementation of the activation functions for all real floating point types
+macro_rules! impl_activation_funcs_real_float {
+    ($t:ty) => {
+        impl Heaviside for $t {
+            type Output = Self;
+            fn heaviside(self) -> Self::Output {
+                if self > 0.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+
+        impl NormCdf for $t {
+            type Output = Self;
+            fn norm_cdf(self) -> Self::Output {
+                // use erfc so that the lower tail does not lose precision to cancellation
+                (0.5 * special::erfc(-(self as f64) * std::f64::consts::FRAC_1_SQRT_2)) as $t
+            }
+        }
+
+        impl NormPdf for $t {
+            type Output = Self;
+            fn norm_pdf(self) -> Self::Output {
+                let x = self as f64;
+                // 1/sqrt(2 pi) = 1/(2 sqrt(2)) * 2/sqrt(pi)
+                (0.5 * std::f64::consts::FRAC_1_SQRT_2
+                    * std::f64::consts::FRAC_2_SQRT_PI
+                    * (-0.5 * x * x).exp()) as $t
+            }
+        }
+
+        impl Relu for $t {
+            type Output = Self;
+            fn relu(self) -> Self::Output {
+                if self > 0.0 {
+                    self
+                } else {
+                    0.0
+                }
+            }
+        }
+
+        impl LeakyRelu<$t> for $t {
+            type Output = Self;
+            fn leaky_relu(self, alpha: $t) -> Self::Output {
+                if self > 0.0 {
+                    self
+                } else {
+                    alpha * self
+                }
+            }
+        }
+
+        impl Sigmoid for $t {
+            type Output = Self;
+            fn sigmoid(self) -> Self::Output {
+                // only ever exponentiate a non-positive number to avoid overflow
+                if self >= 0.0 {
+                    1.0 / (1.0 + (-self).exp())
+                } else {
+                    let e = self.exp();
+                    e / (1.0 + e)
+                }
+            }
+        }
+
+        impl Softplus for $t {
+            type Output = Self;
+            fn softplus(self) -> Self::Output {
+                // ln(1 + e^x) = max(x, 0) + ln(1 + e^-|x|), which neither overflows for large x
+                // nor loses precision for very negative x
+                self.max(0.0) + (-self.abs()).exp().ln_1p()
+            }
+        }
+
+        impl Gelu for $t {
+            type Output = Self;
+            fn gelu(self) -> Self::Output {
+                self * self.norm_cdf()
+            }
+        }
+
+        impl Elu<$t> for $t {
+            type Output = Self;
+            fn elu(self, alpha: $t) -> Self::Output {
+                if self > 0.0 {
+                    self
+                } else {
+                    alpha * self.exp_m1()
+                }
+            }
+        }
+    };
+}
+
+impl_activation_funcs_real_float!(f32);
+impl_activation_funcs_real_float!(f64);
+
 impl PossiblyComplex for num::BigInt {
     fn is_always_real() -> bool {
         true
@@ -610,6 +1168,7 @@ where
     T: Clone + Num + Float,
 {
     type Output = Self;
+    #[allow(clippy::clone_on_copy)]
     fn signum(self) -> Self::Output {
         self.clone() / self.abs()
     }
@@ -623,3 +1182,166 @@ where
         Complex::<T>::arg(self).into()
     }
 }
+
+impl<T> Exp for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn exp(self) -> Self::Output {
+        Complex::<T>::exp(self)
+    }
+}
+impl<T> Ln for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn ln(self) -> Self::Output {
+        Complex::<T>::ln(self)
+    }
+}
+impl<T> Log<Complex<T>> for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn log(self, base: Complex<T>) -> Self::Output {
+        Complex::<T>::ln(self) / Complex::<T>::ln(base)
+    }
+}
+impl<T> Log2 for Complex<T>
+where
+    T: Clone + Num + Float + FloatConst,
+{
+    type Output = Self;
+    fn log2(self) -> Self::Output {
+        Complex::<T>::log2(self)
+    }
+}
+impl<T> Log10 for Complex<T>
+where
+    T: Clone + Num + Float + FloatConst,
+{
+    type Output = Self;
+    fn log10(self) -> Self::Output {
+        Complex::<T>::log10(self)
+    }
+}
+impl<T> Sqrt for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn sqrt(self) -> Self::Output {
+        Complex::<T>::sqrt(self)
+    }
+}
+impl<T> Sin for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn sin(self) -> Self::Output {
+        Complex::<T>::sin(self)
+    }
+}
+impl<T> Cos for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn cos(self) -> Self::Output {
+        Complex::<T>::cos(self)
+    }
+}
+impl<T> Tan for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn tan(self) -> Self::Output {
+        Complex::<T>::tan(self)
+    }
+}
+impl<T> Asin for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn asin(self) -> Self::Output {
+        Complex::<T>::asin(self)
+    }
+}
+impl<T> Acos for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn acos(self) -> Self::Output {
+        Complex::<T>::acos(self)
+    }
+}
+impl<T> Atan for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn atan(self) -> Self::Output {
+        Complex::<T>::atan(self)
+    }
+}
+impl<T> Sinh for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn sinh(self) -> Self::Output {
+        Complex::<T>::sinh(self)
+    }
+}
+impl<T> Cosh for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn cosh(self) -> Self::Output {
+        Complex::<T>::cosh(self)
+    }
+}
+impl<T> Tanh for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn tanh(self) -> Self::Output {
+        Complex::<T>::tanh(self)
+    }
+}
+impl<T> Asinh for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn asinh(self) -> Self::Output {
+        Complex::<T>::asinh(self)
+    }
+}
+impl<T> Acosh for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn acosh(self) -> Self::Output {
+        Complex::<T>::acosh(self)
+    }
+}
+impl<T> Atanh for Complex<T>
+where
+    T: Clone + Num + Float,
+{
+    type Output = Self;
+    fn atanh(self) -> Self::Output {
+        Complex::<T>::atanh(self)
+    }
+}
@@ -0,0 +1,404 @@
+use crate::autodiffable::{AutoDiffable, Diffable};
+use crate::gradienttype::GradientType;
+use crate::graphviz::type_label;
+use crate::symbolic::Named;
+use num::complex::Complex;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Why a function could not be evaluated by `TryAutoDiffable`. The path lists the nodes from the
+/// root of the expression down to the one which failed, each with the index of the operand the
+/// error came from, e.g. `["ADAdd.1", "ADDiv"]` for the division in `f + g / h`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AutoDiffError {
+    /// The input of a node is outside of its domain, e.g. a division by zero or a fractional power
+    /// of a negative number, which would otherwise produce NaN
+    DomainError { path: Vec<String>, message: String },
+    /// The operands of a node have incompatible shapes
+    ShapeMismatch {
+        path: Vec<String>,
+        lhs: Vec<usize>,
+        rhs: Vec<usize>,
+    },
+    /// A node produced an infinite value, or a gradient which isn't finite
+    NonFinite { path: Vec<String>, message: String },
+}
+
+impl AutoDiffError {
+    pub fn domain(node: &str, message: &str) -> Self {
+        AutoDiffError::DomainError {
+            path: vec![node.to_string()],
+            message: message.to_string(),
+        }
+    }
+
+    pub fn shape_mismatch(node: &str, lhs: Vec<usize>, rhs: Vec<usize>) -> Self {
+        AutoDiffError::ShapeMismatch {
+            path: vec![node.to_string()],
+            lhs,
+            rhs,
+        }
+    }
+
+    pub fn non_finite(node: &str, message: &str) -> Self {
+        AutoDiffError::NonFinite {
+            path: vec![node.to_string()],
+            message: message.to_string(),
+        }
+    }
+
+    /// The nodes from the root of the expression down to the one which failed
+    pub fn path(&self) -> &[String] {
+        match self {
+            AutoDiffError::DomainError { path, .. }
+            | AutoDiffError::ShapeMismatch { path, .. }
+            | AutoDiffError::NonFinite { path, .. } => path,
+        }
+    }
+
+    /// The error of an operand, seen from the node using it
+    pub fn within(mut self, node: &str, operand: usize) -> Self {
+        match &mut self {
+            AutoDiffError::DomainError { path, .. }
+            | AutoDiffError::ShapeMismatch { path, .. }
+            | AutoDiffError::NonFinite { path, .. } => {
+                path.insert(0, format!("{}.{}", node, operand))
+            }
+        }
+        self
+    }
+}
+
+impl fmt::Display for AutoDiffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self.path().join(" > ");
+        match self {
+            AutoDiffError::DomainError { message, .. } => {
+                write!(f, "domain error at {}: {}", path, message)
+            }
+            AutoDiffError::ShapeMismatch { lhs, rhs, .. } => {
+                write!(f, "shape mismatch at {}: {:?} and {:?}", path, lhs, rhs)
+            }
+            AutoDiffError::NonFinite { message, .. } => {
+                write!(f, "non finite result at {}: {}", path, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AutoDiffError {}
+
+/// Values which can be checked for NaN, infinities and zeros, and which may have a shape
+pub trait CheckValue {
+    fn has_nan(&self) -> bool;
+
+    fn has_infinite(&self) -> bool;
+
+    fn has_zero(&self) -> bool;
+
+    /// The shape of arrays, or `None` for scalars, which match any shape
+    fn value_shape(&self) -> Option<Vec<usize>> {
+        None
+    }
+}
+
+macro_rules! impl_check_value_float {
+    ($($t:ty),*) => {
+        $(
+            impl CheckValue for $t {
+                fn has_nan(&self) -> bool {
+                    self.is_nan()
+                }
+
+                fn has_infinite(&self) -> bool {
+                    self.is_infinite()
+                }
+
+                fn has_zero(&self) -> bool {
+                    *self == 0.0
+                }
+            }
+
+            impl CheckValue for Complex<$t> {
+                fn has_nan(&self) -> bool {
+                    self.is_nan()
+                }
+
+                fn has_infinite(&self) -> bool {
+                    self.is_infinite()
+                }
+
+                fn has_zero(&self) -> bool {
+                    self.re == 0.0 && self.im == 0.0
+                }
+            }
+        )*
+    };
+}
+
+impl_check_value_float!(f32, f64);
+
+macro_rules! impl_check_value_int {
+    ($($t:ty),*) => {
+        $(
+            impl CheckValue for $t {
+                fn has_nan(&self) -> bool {
+                    false
+                }
+
+                fn has_infinite(&self) -> bool {
+                    false
+                }
+
+                fn has_zero(&self) -> bool {
+                    *self == 0
+                }
+            }
+        )*
+    };
+}
+
+impl_check_value_int!(i8, i16, i32, i64, u8, u16, u32, u64, isize, usize);
+
+impl<T: CheckValue> CheckValue for Named<T> {
+    fn has_nan(&self) -> bool {
+        self.value.has_nan()
+    }
+
+    fn has_infinite(&self) -> bool {
+        self.value.has_infinite()
+    }
+
+    fn has_zero(&self) -> bool {
+        self.value.has_zero()
+    }
+
+    fn value_shape(&self) -> Option<Vec<usize>> {
+        self.value.value_shape()
+    }
+}
+
+/// Checks the value of a node whose operands were already checked, so NaN comes from the node
+/// itself being evaluated outside of its domain
+pub fn check_value<T: CheckValue>(node: &str, value: T) -> Result<T, AutoDiffError> {
+    if value.has_nan() {
+        Err(AutoDiffError::domain(node, "the value is NaN"))
+    } else if value.has_infinite() {
+        Err(AutoDiffError::non_finite(node, "the value is infinite"))
+    } else {
+        Ok(value)
+    }
+}
+
+/// Checks the value and both gradients of a node, see `check_value`
+pub fn check_all<T: CheckValue, G: CheckValue>(
+    node: &str,
+    (f, df, dfc): (T, G, G),
+) -> Result<(T, G, G), AutoDiffError> {
+    let f = check_value(node, f)?;
+    if df.has_nan() || df.has_infinite() {
+        return Err(AutoDiffError::non_finite(
+            node,
+            "the gradient is not finite",
+        ));
+    }
+    if dfc.has_nan() || dfc.has_infinite() {
+        return Err(AutoDiffError::non_finite(
+            node,
+            "the conjugate gradient is not finite",
+        ));
+    }
+    Ok((f, df, dfc))
+}
+
+/// Checks that the operands of an elementwise node can be broadcast together. Axes are matched
+/// from the last one, and each pair must be equal or contain a 1.
+pub fn check_shapes<A: CheckValue, B: CheckValue>(
+    node: &str,
+    a: &A,
+    b: &B,
+) -> Result<(), AutoDiffError> {
+    if let (Some(lhs), Some(rhs)) = (a.value_shape(), b.value_shape()) {
+        let compatible = lhs
+            .iter()
+            .rev()
+            .zip(rhs.iter().rev())
+            .all(|(l, r)| l == r || *l == 1 || *r == 1);
+        if !compatible {
+            return Err(AutoDiffError::shape_mismatch(node, lhs, rhs));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the contracted axes of the operands of a tensor contraction exist and have the
+/// same lengths, pairing `lhs_axes[i]` of `a` with `rhs_axes[i]` of `b`
+pub fn check_contraction<A: CheckValue, B: CheckValue>(
+    node: &str,
+    a: &A,
+    b: &B,
+    lhs_axes: &[usize],
+    rhs_axes: &[usize],
+) -> Result<(), AutoDiffError> {
+    if let (Some(lhs), Some(rhs)) = (a.value_shape(), b.value_shape()) {
+        let compatible = lhs_axes
+            .iter()
+            .zip(rhs_axes)
+            .all(|(l, r)| matches!((lhs.get(*l), rhs.get(*r)), (Some(l), Some(r)) if l == r));
+        if !compatible {
+            return Err(AutoDiffError::shape_mismatch(node, lhs, rhs));
+        }
+    }
+    Ok(())
+}
+
+/// Checks the operands of a dot product, which contracts the last axis of `a` with the first axis
+/// of `b`, see `check_contraction`
+pub fn check_dot<A: CheckValue, B: CheckValue>(
+    node: &str,
+    a: &A,
+    b: &B,
+) -> Result<(), AutoDiffError> {
+    let last = a
+        .value_shape()
+        .map_or(0, |shape| shape.len().saturating_sub(1));
+    check_contraction(node, a, b, &[last], &[0])
+}
+
+/// Fallible version of `AutoDiffable`, which returns an `AutoDiffError` instead of NaN, infinities
+/// or panics, with the path to the node which failed.
+///
+/// The default methods evaluate the function and check its results, which is all that can be done
+/// for leaves such as custom functions, so these can implement it with an empty impl. Combinators
+/// check their operands first, so that errors are reported at the node which caused them.
+pub trait TryAutoDiffable<StaticArgs>: AutoDiffable<StaticArgs>
+where
+    <Self as Diffable<StaticArgs>>::Input: GradientType<<Self as Diffable<StaticArgs>>::Output>,
+    <Self as Diffable<StaticArgs>>::Output: CheckValue,
+    <<Self as Diffable<StaticArgs>>::Input as GradientType<
+        <Self as Diffable<StaticArgs>>::Output,
+    >>::GradientType: CheckValue,
+{
+    /// Evaluate the function for a given input and static arguments, or return why it can't be.
+    fn try_eval(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> Result<<Self as Diffable<StaticArgs>>::Output, AutoDiffError> {
+        check_value(type_label::<Self>(), self.eval(x, static_args))
+    }
+
+    /// Evaluate the function and both of its Wirtinger derivatives, see `AutoDiffable::eval_all`,
+    /// or return why they can't be.
+    #[allow(clippy::type_complexity)]
+    fn try_eval_all(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> Result<
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <<Self as Diffable<StaticArgs>>::Input as GradientType<
+                <Self as Diffable<StaticArgs>>::Output,
+            >>::GradientType,
+            <<Self as Diffable<StaticArgs>>::Input as GradientType<
+                <Self as Diffable<StaticArgs>>::Output,
+            >>::GradientType,
+        ),
+        AutoDiffError,
+    > {
+        check_all(type_label::<Self>(), self.eval_all(x, static_args))
+    }
+
+    /// Evaluate the function and its gradient, or return why they can't be.
+    #[allow(clippy::type_complexity)]
+    fn try_eval_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> Result<
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <<Self as Diffable<StaticArgs>>::Input as GradientType<
+                <Self as Diffable<StaticArgs>>::Output,
+            >>::GradientType,
+        ),
+        AutoDiffError,
+    > {
+        self.try_eval_all(x, static_args).map(|(f, df, _)| (f, df))
+    }
+
+    /// Evaluate the function and its gradient wrt the conjugate of the input, or return why they
+    /// can't be.
+    #[allow(clippy::type_complexity)]
+    fn try_eval_conj_grad(
+        &self,
+        x: &<Self as Diffable<StaticArgs>>::Input,
+        static_args: &StaticArgs,
+    ) -> Result<
+        (
+            <Self as Diffable<StaticArgs>>::Output,
+            <<Self as Diffable<StaticArgs>>::Input as GradientType<
+                <Self as Diffable<StaticArgs>>::Output,
+            >>::GradientType,
+        ),
+        AutoDiffError,
+    > {
+        self.try_eval_all(x, static_args)
+            .map(|(f, _, dfdconjz)| (f, dfdconjz))
+    }
+}
+
+/// An operand which was already evaluated, with its value and, if they were needed, its gradients.
+/// Combinators implement `TryAutoDiffable` by checking the results of their operands and then
+/// evaluating themselves with these in place of the operands, so that the formulas of
+/// `AutoDiffable` are not repeated.
+///
+/// Its gradients panic if only its value was given.
+#[derive(Debug, Clone)]
+pub struct ADEvaluated<Input, Output, Grad>(Output, Option<(Grad, Grad)>, PhantomData<Input>);
+
+impl<Input, Output, Grad> ADEvaluated<Input, Output, Grad> {
+    pub fn value(f: Output) -> Self {
+        ADEvaluated(f, None, PhantomData)
+    }
+
+    pub fn all((f, df, dfdconjz): (Output, Grad, Grad)) -> Self {
+        ADEvaluated(f, Some((df, dfdconjz)), PhantomData)
+    }
+
+    fn grads(&self) -> &(Grad, Grad) {
+        self.1
+            .as_ref()
+            .expect("the gradients of the operand were not evaluated")
+    }
+}
+
+impl<StaticArgs, Input, Output, Grad> Diffable<StaticArgs> for ADEvaluated<Input, Output, Grad> {
+    type Input = Input;
+    type Output = Output;
+}
+
+impl<StaticArgs, Input, Output, Grad> AutoDiffable<StaticArgs> for ADEvaluated<Input, Output, Grad>
+where
+    Input: GradientType<Output, GradientType = Grad>,
+    Output: Clone,
+    Grad: Clone,
+{
+    fn eval(&self, _: &Input, _: &StaticArgs) -> Output {
+        self.0.clone()
+    }
+
+    fn eval_grad(&self, _: &Input, _: &StaticArgs) -> (Output, Grad) {
+        (self.0.clone(), self.grads().0.clone())
+    }
+
+    fn eval_conj_grad(&self, _: &Input, _: &StaticArgs) -> (Output, Grad) {
+        (self.0.clone(), self.grads().1.clone())
+    }
+
+    fn eval_all(&self, _: &Input, _: &StaticArgs) -> (Output, Grad, Grad) {
+        let (df, dfdconjz) = self.grads();
+        (self.0.clone(), df.clone(), dfdconjz.clone())
+    }
+}